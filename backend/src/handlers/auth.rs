@@ -115,7 +115,7 @@ pub async fn verify_signature(
     sqlx::query(
         "UPDATE auth_nonces SET used = true, wallet_address = $1 WHERE id = $2"
     )
-    .bind(&req.address.to_lowercase())
+    .bind(req.address.to_lowercase())
     .bind(nonce_record.id)
     .execute(&state.pool)
     .await
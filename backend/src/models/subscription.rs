@@ -3,20 +3,15 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, sqlx::Type, PartialEq)]
 #[sqlx(type_name = "subscription_tier", rename_all = "lowercase")]
 pub enum SubscriptionTier {
+    #[default]
     Free,
     Dashboard,
     Protocol,
 }
 
-impl Default for SubscriptionTier {
-    fn default() -> Self {
-        Self::Free
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
 #[sqlx(type_name = "subscription_status", rename_all = "lowercase")]
 pub enum SubscriptionStatus {
@@ -41,6 +41,7 @@ async fn check_all_alerts(pool: &sqlx::PgPool, email_service: &EmailService) ->
 }
 
 #[derive(Debug, sqlx::FromRow)]
+#[allow(dead_code)]
 struct AlertConfigWithUser {
     id: uuid::Uuid,
     user_id: uuid::Uuid,
@@ -0,0 +1,664 @@
+//! Balancer composable stable pool math.
+//!
+//! A composable stable pool prices its tokens with the StableSwap
+//! invariant and registers its own pool token (BPT) as one of its tokens,
+//! so joins and exits can also be expressed as swaps into or out of BPT.
+//! The free functions port the join and exit formulas of Balancer's
+//! `StableMath` on top of [`stableswap_d`] and [`stableswap_y_d`]; as in the
+//! weighted pool, the part of a deposit or withdrawal beyond a proportional
+//! one is charged the swap fee.
+//!
+//! [`ComposableStablePool`] tracks the balances of the other tokens and the
+//! virtual supply: BPT minted minus the pre-minted BPT the pool still holds.
+//! Token indices on the pool are registered indices, with BPT at
+//! `bpt_index`. Balances are in a common unit (scale by rate providers
+//! first) and the amplification follows the same convention as
+//! [`StableSwapPool`](super::StableSwapPool). Arithmetic is decimal, so
+//! results agree with the contracts to within rounding.
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::amm::ComposableStablePool;
+//! use precision_core::Decimal;
+//!
+//! // BPT registered first, then two stablecoins, with a 0.01% fee
+//! let mut pool = ComposableStablePool::new(
+//!     &[Decimal::from(1_000_000i64), Decimal::from(1_000_000i64)],
+//!     0,
+//!     Decimal::from(200i64),
+//!     Decimal::new(1, 4),
+//!     Decimal::from(2_000_000i64),
+//! )
+//! .unwrap();
+//!
+//! // Buying BPT with token 1 is a single-token join
+//! let bpt = pool.swap_exact_in(1, 0, Decimal::from(1000i64)).unwrap();
+//! assert!(bpt < Decimal::from(1000i64));
+//! assert!(pool.virtual_supply() > Decimal::from(2_000_000i64));
+//! ```
+
+use super::{
+    complement, stableswap_d, stableswap_y, stableswap_y_d, validate_amounts, MAX_AMPLIFICATION,
+    MAX_STABLE_COINS,
+};
+use precision_core::{ArithmeticError, Decimal};
+
+/// BPT minted for exact token amounts in.
+///
+/// Amounts beyond a proportional join are effectively swapped into the
+/// pool and pay the swap fee.
+pub fn stable_bpt_out_given_exact_tokens_in(
+    balances: &[Decimal],
+    amplification: Decimal,
+    amounts_in: &[Decimal],
+    total_supply: Decimal,
+    swap_fee: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    validate_amounts(balances, amounts_in, MAX_STABLE_COINS)?;
+    let sum = total(balances)?;
+    let mut ratios = [Decimal::ZERO; MAX_STABLE_COINS];
+    let mut invariant_ratio_with_fees = Decimal::ZERO;
+    for i in 0..balances.len() {
+        let weight = balances[i].try_div(sum)?;
+        ratios[i] = balances[i].try_add(amounts_in[i])?.try_div(balances[i])?;
+        invariant_ratio_with_fees =
+            invariant_ratio_with_fees.try_add(ratios[i].try_mul(weight)?)?;
+    }
+
+    let mut new_balances = [Decimal::ZERO; MAX_STABLE_COINS];
+    for i in 0..balances.len() {
+        let amount_in = if ratios[i] > invariant_ratio_with_fees {
+            let non_taxable =
+                balances[i].try_mul(invariant_ratio_with_fees.try_sub(Decimal::ONE)?)?;
+            let taxable = amounts_in[i].try_sub(non_taxable)?;
+            non_taxable.try_add(taxable.try_mul(complement(swap_fee))?)?
+        } else {
+            amounts_in[i]
+        };
+        new_balances[i] = balances[i].try_add(amount_in)?;
+    }
+
+    let invariant = stableswap_d(balances, amplification)?;
+    let new_invariant = stableswap_d(&new_balances[..balances.len()], amplification)?;
+    let invariant_ratio = new_invariant.try_div(invariant)?;
+    if invariant_ratio > Decimal::ONE {
+        total_supply.try_mul(invariant_ratio.try_sub(Decimal::ONE)?)
+    } else {
+        Ok(Decimal::ZERO)
+    }
+}
+
+/// Amount of token `index` needed to mint exactly `bpt_out`.
+///
+/// The share of the deposit beyond the token's share of the balances pays
+/// the swap fee.
+pub fn stable_token_in_given_exact_bpt_out(
+    balances: &[Decimal],
+    amplification: Decimal,
+    index: usize,
+    bpt_out: Decimal,
+    total_supply: Decimal,
+    swap_fee: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    if bpt_out.is_negative() || index >= balances.len() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let invariant = stableswap_d(balances, amplification)?;
+    let new_invariant = total_supply
+        .try_add(bpt_out)?
+        .try_div(total_supply)?
+        .try_mul(invariant)?;
+    let new_balance = stableswap_y_d(balances, amplification, index, new_invariant)?;
+    let amount_without_fee = new_balance.try_sub(balances[index])?;
+
+    let weight = balances[index].try_div(total(balances)?)?;
+    let taxable = amount_without_fee.try_mul(complement(weight))?;
+    let non_taxable = amount_without_fee.try_sub(taxable)?;
+    non_taxable.try_add(taxable.try_div(complement(swap_fee))?)
+}
+
+/// Amount of token `index` received for burning exactly `bpt_in`.
+///
+/// The share of the withdrawal beyond the token's share of the balances
+/// pays the swap fee.
+pub fn stable_token_out_given_exact_bpt_in(
+    balances: &[Decimal],
+    amplification: Decimal,
+    index: usize,
+    bpt_in: Decimal,
+    total_supply: Decimal,
+    swap_fee: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    if bpt_in.is_negative() || bpt_in >= total_supply || index >= balances.len() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let invariant = stableswap_d(balances, amplification)?;
+    let new_invariant = total_supply
+        .try_sub(bpt_in)?
+        .try_div(total_supply)?
+        .try_mul(invariant)?;
+    let new_balance = stableswap_y_d(balances, amplification, index, new_invariant)?;
+    let amount_without_fee = balances[index].try_sub(new_balance)?;
+
+    let weight = balances[index].try_div(total(balances)?)?;
+    let taxable = amount_without_fee.try_mul(complement(weight))?;
+    let non_taxable = amount_without_fee.try_sub(taxable)?;
+    non_taxable.try_add(taxable.try_mul(complement(swap_fee))?)
+}
+
+/// BPT burned to withdraw exact token amounts.
+///
+/// Amounts beyond a proportional exit are effectively swapped out of the
+/// pool and pay the swap fee.
+pub fn stable_bpt_in_given_exact_tokens_out(
+    balances: &[Decimal],
+    amplification: Decimal,
+    amounts_out: &[Decimal],
+    total_supply: Decimal,
+    swap_fee: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    validate_amounts(balances, amounts_out, MAX_STABLE_COINS)?;
+    let sum = total(balances)?;
+    let mut ratios = [Decimal::ZERO; MAX_STABLE_COINS];
+    let mut invariant_ratio_without_fees = Decimal::ZERO;
+    for i in 0..balances.len() {
+        let weight = balances[i].try_div(sum)?;
+        ratios[i] = balances[i].try_sub(amounts_out[i])?.try_div(balances[i])?;
+        invariant_ratio_without_fees =
+            invariant_ratio_without_fees.try_add(ratios[i].try_mul(weight)?)?;
+    }
+
+    let mut new_balances = [Decimal::ZERO; MAX_STABLE_COINS];
+    for i in 0..balances.len() {
+        let amount_out = if invariant_ratio_without_fees > ratios[i] {
+            let non_taxable = balances[i].try_mul(complement(invariant_ratio_without_fees))?;
+            let taxable = amounts_out[i].try_sub(non_taxable)?;
+            non_taxable.try_add(taxable.try_div(complement(swap_fee))?)?
+        } else {
+            amounts_out[i]
+        };
+        new_balances[i] = balances[i].try_sub(amount_out)?;
+        if !new_balances[i].is_positive() {
+            return Err(ArithmeticError::Underflow);
+        }
+    }
+
+    let invariant = stableswap_d(balances, amplification)?;
+    let new_invariant = stableswap_d(&new_balances[..balances.len()], amplification)?;
+    total_supply.try_mul(complement(new_invariant.try_div(invariant)?))
+}
+
+/// A Balancer composable stable pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposableStablePool {
+    balances: [Decimal; MAX_STABLE_COINS],
+    tokens: usize,
+    bpt_index: usize,
+    amplification: Decimal,
+    swap_fee: Decimal,
+    virtual_supply: Decimal,
+}
+
+impl ComposableStablePool {
+    /// Creates a pool.
+    ///
+    /// `balances` excludes BPT, which is registered at `bpt_index` among
+    /// the `balances.len() + 1` pool tokens. `swap_fee` is a fraction of
+    /// the input (0.0001 = 1 bp) and `virtual_supply` the BPT held outside
+    /// the pool.
+    ///
+    /// Returns error unless there are 2 to [`MAX_STABLE_COINS`] tokens with
+    /// positive balances, `bpt_index` is at most `balances.len()`,
+    /// 0 < A ≤ [`MAX_AMPLIFICATION`], 0 ≤ fee < 1 and the virtual supply is
+    /// positive.
+    pub fn new(
+        balances: &[Decimal],
+        bpt_index: usize,
+        amplification: Decimal,
+        swap_fee: Decimal,
+        virtual_supply: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        if balances.len() < 2
+            || balances.len() > MAX_STABLE_COINS
+            || balances.iter().any(|b| !b.is_positive())
+            || bpt_index > balances.len()
+            || !amplification.is_positive()
+            || amplification > Decimal::from(MAX_AMPLIFICATION)
+            || swap_fee.is_negative()
+            || swap_fee >= Decimal::ONE
+            || !virtual_supply.is_positive()
+        {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let mut stored = [Decimal::ZERO; MAX_STABLE_COINS];
+        stored[..balances.len()].copy_from_slice(balances);
+        Ok(Self {
+            balances: stored,
+            tokens: balances.len(),
+            bpt_index,
+            amplification,
+            swap_fee,
+            virtual_supply,
+        })
+    }
+
+    /// Balances of the tokens other than BPT, in registration order.
+    pub fn balances(&self) -> &[Decimal] {
+        &self.balances[..self.tokens]
+    }
+
+    /// Registered index of BPT.
+    pub fn bpt_index(&self) -> usize {
+        self.bpt_index
+    }
+
+    /// Amplification coefficient.
+    pub fn amplification(&self) -> Decimal {
+        self.amplification
+    }
+
+    /// Swap fee as a fraction.
+    pub fn swap_fee(&self) -> Decimal {
+        self.swap_fee
+    }
+
+    /// BPT in circulation: total supply less the pool's own BPT balance.
+    pub fn virtual_supply(&self) -> Decimal {
+        self.virtual_supply
+    }
+
+    /// Invariant D of the balances.
+    pub fn invariant(&self) -> Result<Decimal, ArithmeticError> {
+        stableswap_d(self.balances(), self.amplification)
+    }
+
+    /// Value of one BPT in the common unit, D / virtual supply.
+    pub fn rate(&self) -> Result<Decimal, ArithmeticError> {
+        self.invariant()?.try_div(self.virtual_supply)
+    }
+
+    /// Output for an exact input between registered tokens.
+    ///
+    /// Token to token swaps take the fee from the input; swaps into or out
+    /// of BPT are single-token joins and exits.
+    pub fn calc_out_given_in(
+        &self,
+        token_in: usize,
+        token_out: usize,
+        amount_in: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        if amount_in.is_negative() {
+            return Err(ArithmeticError::Underflow);
+        }
+        match self.resolve_pair(token_in, token_out)? {
+            (Some(i), Some(j)) => {
+                let net = amount_in.try_mul(complement(self.swap_fee))?;
+                let x = self.balances[i].try_add(net)?;
+                let y = stableswap_y(self.balances(), self.amplification, i, j, x)?;
+                self.balances[j].try_sub(y)
+            }
+            (Some(i), None) => {
+                let mut amounts = [Decimal::ZERO; MAX_STABLE_COINS];
+                amounts[i] = amount_in;
+                stable_bpt_out_given_exact_tokens_in(
+                    self.balances(),
+                    self.amplification,
+                    &amounts[..self.tokens],
+                    self.virtual_supply,
+                    self.swap_fee,
+                )
+            }
+            (None, Some(j)) => stable_token_out_given_exact_bpt_in(
+                self.balances(),
+                self.amplification,
+                j,
+                amount_in,
+                self.virtual_supply,
+                self.swap_fee,
+            ),
+            (None, None) => Err(ArithmeticError::DivisionByZero),
+        }
+    }
+
+    /// Input required for an exact output between registered tokens.
+    pub fn calc_in_given_out(
+        &self,
+        token_in: usize,
+        token_out: usize,
+        amount_out: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        if amount_out.is_negative() {
+            return Err(ArithmeticError::Underflow);
+        }
+        match self.resolve_pair(token_in, token_out)? {
+            (Some(i), Some(j)) => {
+                let y = self.balances[j].try_sub(amount_out)?;
+                let x = stableswap_y(self.balances(), self.amplification, j, i, y)?;
+                x.try_sub(self.balances[i])?
+                    .try_div(complement(self.swap_fee))
+            }
+            (Some(i), None) => stable_token_in_given_exact_bpt_out(
+                self.balances(),
+                self.amplification,
+                i,
+                amount_out,
+                self.virtual_supply,
+                self.swap_fee,
+            ),
+            (None, Some(j)) => {
+                let mut amounts = [Decimal::ZERO; MAX_STABLE_COINS];
+                amounts[j] = amount_out;
+                stable_bpt_in_given_exact_tokens_out(
+                    self.balances(),
+                    self.amplification,
+                    &amounts[..self.tokens],
+                    self.virtual_supply,
+                    self.swap_fee,
+                )
+            }
+            (None, None) => Err(ArithmeticError::DivisionByZero),
+        }
+    }
+
+    /// Swaps an exact input and returns the output.
+    pub fn swap_exact_in(
+        &mut self,
+        token_in: usize,
+        token_out: usize,
+        amount_in: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        let amount_out = self.calc_out_given_in(token_in, token_out, amount_in)?;
+        self.apply_swap(token_in, token_out, amount_in, amount_out)?;
+        Ok(amount_out)
+    }
+
+    /// Swaps for an exact output and returns the input paid.
+    pub fn swap_exact_out(
+        &mut self,
+        token_in: usize,
+        token_out: usize,
+        amount_out: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        let amount_in = self.calc_in_given_out(token_in, token_out, amount_out)?;
+        self.apply_swap(token_in, token_out, amount_in, amount_out)?;
+        Ok(amount_in)
+    }
+
+    /// Deposits exact amounts of the tokens other than BPT and returns the
+    /// BPT minted.
+    pub fn join_exact_tokens_in(
+        &mut self,
+        amounts_in: &[Decimal],
+    ) -> Result<Decimal, ArithmeticError> {
+        let bpt_out = stable_bpt_out_given_exact_tokens_in(
+            self.balances(),
+            self.amplification,
+            amounts_in,
+            self.virtual_supply,
+            self.swap_fee,
+        )?;
+        let mut balances = self.balances;
+        for (balance, &amount) in balances.iter_mut().zip(amounts_in) {
+            *balance = balance.try_add(amount)?;
+        }
+        self.balances = balances;
+        self.virtual_supply = self.virtual_supply.try_add(bpt_out)?;
+        Ok(bpt_out)
+    }
+
+    /// Withdraws exact amounts of the tokens other than BPT and returns the
+    /// BPT burned.
+    pub fn exit_exact_tokens_out(
+        &mut self,
+        amounts_out: &[Decimal],
+    ) -> Result<Decimal, ArithmeticError> {
+        let bpt_in = stable_bpt_in_given_exact_tokens_out(
+            self.balances(),
+            self.amplification,
+            amounts_out,
+            self.virtual_supply,
+            self.swap_fee,
+        )?;
+        if bpt_in >= self.virtual_supply {
+            return Err(ArithmeticError::Underflow);
+        }
+        let mut balances = self.balances;
+        for (balance, &amount) in balances.iter_mut().zip(amounts_out) {
+            *balance = balance.try_sub(amount)?;
+        }
+        self.balances = balances;
+        self.virtual_supply = self.virtual_supply.try_sub(bpt_in)?;
+        Ok(bpt_in)
+    }
+
+    /// Burns exactly `bpt_in` for the other tokens in proportion to the
+    /// balances, writing the amounts received to `amounts_out`.
+    pub fn exit_proportional(
+        &mut self,
+        bpt_in: Decimal,
+        amounts_out: &mut [Decimal],
+    ) -> Result<(), ArithmeticError> {
+        if bpt_in.is_negative() || bpt_in >= self.virtual_supply || amounts_out.len() != self.tokens
+        {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let ratio = bpt_in.try_div(self.virtual_supply)?;
+        let mut balances = self.balances;
+        for (i, amount) in amounts_out.iter_mut().enumerate() {
+            *amount = self.balances[i].try_mul(ratio)?;
+            balances[i] = balances[i].try_sub(*amount)?;
+        }
+        self.balances = balances;
+        self.virtual_supply = self.virtual_supply.try_sub(bpt_in)?;
+        Ok(())
+    }
+
+    fn apply_swap(
+        &mut self,
+        token_in: usize,
+        token_out: usize,
+        amount_in: Decimal,
+        amount_out: Decimal,
+    ) -> Result<(), ArithmeticError> {
+        let mut balances = self.balances;
+        let mut virtual_supply = self.virtual_supply;
+        match self.resolve_pair(token_in, token_out)? {
+            (Some(i), Some(j)) => {
+                balances[i] = balances[i].try_add(amount_in)?;
+                balances[j] = balances[j].try_sub(amount_out)?;
+            }
+            (Some(i), None) => {
+                balances[i] = balances[i].try_add(amount_in)?;
+                virtual_supply = virtual_supply.try_add(amount_out)?;
+            }
+            (None, Some(j)) => {
+                balances[j] = balances[j].try_sub(amount_out)?;
+                virtual_supply = virtual_supply.try_sub(amount_in)?;
+            }
+            (None, None) => return Err(ArithmeticError::DivisionByZero),
+        }
+        if balances[..self.tokens].iter().any(|b| !b.is_positive()) {
+            return Err(ArithmeticError::Underflow);
+        }
+        self.balances = balances;
+        self.virtual_supply = virtual_supply;
+        Ok(())
+    }
+
+    /// Maps a registered index to a balance index, or `None` for BPT.
+    fn resolve(&self, token: usize) -> Result<Option<usize>, ArithmeticError> {
+        if token > self.tokens {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(match token.cmp(&self.bpt_index) {
+            core::cmp::Ordering::Less => Some(token),
+            core::cmp::Ordering::Equal => None,
+            core::cmp::Ordering::Greater => Some(token - 1),
+        })
+    }
+
+    fn resolve_pair(
+        &self,
+        token_in: usize,
+        token_out: usize,
+    ) -> Result<(Option<usize>, Option<usize>), ArithmeticError> {
+        if token_in == token_out {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok((self.resolve(token_in)?, self.resolve(token_out)?))
+    }
+}
+
+fn total(balances: &[Decimal]) -> Result<Decimal, ArithmeticError> {
+    balances
+        .iter()
+        .try_fold(Decimal::ZERO, |acc, &b| acc.try_add(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn assert_near(actual: Decimal, expected: Decimal, tolerance: &str) {
+        assert!(
+            (actual - expected).abs() <= decimal(tolerance),
+            "{actual} vs {expected}"
+        );
+    }
+
+    /// BPT registered between two of three stablecoins.
+    fn pool(swap_fee: &str) -> ComposableStablePool {
+        ComposableStablePool::new(
+            &[decimal("1000000"), decimal("1200000"), decimal("900000")],
+            1,
+            decimal("200"),
+            decimal(swap_fee),
+            decimal("3000000"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_token_swaps_follow_stableswap() {
+        let pool = pool("0");
+        // Registered 0 and 2 are balances 0 and 1
+        let out = pool.calc_out_given_in(0, 2, decimal("1000")).unwrap();
+        let y = stableswap_y(
+            pool.balances(),
+            pool.amplification(),
+            0,
+            1,
+            decimal("1001000"),
+        )
+        .unwrap();
+        assert_eq!(out, decimal("1200000") - y);
+        let back = pool.calc_in_given_out(0, 2, out).unwrap();
+        assert_near(back, decimal("1000"), "0.000000001");
+
+        // The fee comes off the input
+        let fee = self::pool("0.001");
+        let net = pool.calc_out_given_in(0, 2, decimal("999")).unwrap();
+        assert_eq!(fee.calc_out_given_in(0, 2, decimal("1000")).unwrap(), net);
+    }
+
+    #[test]
+    fn test_bpt_swaps_are_joins_and_exits() {
+        let mut pool = pool("0");
+        let rate = pool.rate().unwrap();
+
+        // Without fees a join and the matching exit round trip
+        let bpt = pool.calc_out_given_in(0, 1, decimal("5000")).unwrap();
+        let amount = pool.calc_in_given_out(0, 1, bpt).unwrap();
+        assert_near(amount, decimal("5000"), "0.000000001");
+        let tokens = pool.calc_out_given_in(1, 3, bpt).unwrap();
+        let bpt_in = pool.calc_in_given_out(1, 3, tokens).unwrap();
+        assert_near(bpt_in, bpt, "0.000000001");
+
+        // Buying BPT mints it into the virtual supply; selling burns it
+        let minted = pool.swap_exact_in(0, 1, decimal("5000")).unwrap();
+        assert_eq!(pool.virtual_supply(), decimal("3000000") + minted);
+        assert_eq!(pool.balances()[0], decimal("1005000"));
+        let received = pool.swap_exact_in(1, 0, minted).unwrap();
+        assert_near(received, decimal("5000"), "0.000000001");
+        assert_near(pool.virtual_supply(), decimal("3000000"), "0.000000001");
+        assert_near(pool.rate().unwrap(), rate, "0.000000000001");
+    }
+
+    #[test]
+    fn test_fees_apply_only_beyond_proportional() {
+        let pool = pool("0.01");
+        let supply = pool.virtual_supply();
+
+        // A proportional join pays no fee
+        let amounts = [decimal("1000"), decimal("1200"), decimal("900")];
+        let bpt = stable_bpt_out_given_exact_tokens_in(
+            pool.balances(),
+            pool.amplification(),
+            &amounts,
+            supply,
+            pool.swap_fee(),
+        )
+        .unwrap();
+        assert_near(bpt, supply / decimal("1000"), "0.000000001");
+
+        // A single-token join and exit each pay part of the fee, so the
+        // round trip loses value and the remaining holders gain
+        let mut pool = pool;
+        let rate = pool.rate().unwrap();
+        let minted = pool.swap_exact_in(2, 1, decimal("10000")).unwrap();
+        let received = pool.swap_exact_in(1, 2, minted).unwrap();
+        assert!(received < decimal("10000"));
+        assert!(pool.rate().unwrap() > rate);
+    }
+
+    #[test]
+    fn test_multi_token_joins_and_exits() {
+        let mut pool = pool("0.001");
+        let bpt = pool
+            .join_exact_tokens_in(&[decimal("2000"), Decimal::ZERO, decimal("500")])
+            .unwrap();
+        assert!(bpt.is_positive());
+        let burned = pool
+            .exit_exact_tokens_out(&[decimal("2000"), Decimal::ZERO, decimal("500")])
+            .unwrap();
+        assert!(burned > bpt);
+
+        let supply = pool.virtual_supply();
+        let balances = [pool.balances()[0], pool.balances()[1]];
+        let mut out = [Decimal::ZERO; 3];
+        pool.exit_proportional(supply / decimal("10"), &mut out)
+            .unwrap();
+        assert_eq!(out[0], balances[0] / decimal("10"));
+        assert_eq!(out[1], balances[1] / decimal("10"));
+        assert_eq!(pool.virtual_supply(), supply - supply / decimal("10"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_pools() {
+        let balances = [decimal("100"), decimal("100")];
+        let new = |balances: &[Decimal], bpt_index, supply: &str| {
+            ComposableStablePool::new(
+                balances,
+                bpt_index,
+                decimal("100"),
+                decimal("0.001"),
+                decimal(supply),
+            )
+        };
+        assert!(new(&balances[..1], 0, "200").is_err());
+        assert!(new(&balances, 3, "200").is_err());
+        assert!(new(&balances, 2, "0").is_err());
+
+        let pool = new(&balances, 2, "200").unwrap();
+        assert!(pool.calc_out_given_in(2, 2, Decimal::ONE).is_err());
+        assert!(pool.calc_out_given_in(0, 3, Decimal::ONE).is_err());
+        assert!(pool.calc_out_given_in(2, 0, decimal("200")).is_err());
+        assert!(pool.calc_in_given_out(0, 1, decimal("100")).is_err());
+    }
+}
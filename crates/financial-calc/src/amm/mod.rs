@@ -8,6 +8,12 @@
 //! - Tick and sqrt price conversions
 //! - Liquidity provision calculations
 //! - Impermanent loss calculations
+//! - Concentrated liquidity pool simulation with tick-crossing swaps
+//! - Bit-exact Q64.96 integer tick and amount math ([`q96`])
+//! - Curve StableSwap pools and the crypto-swap invariant
+//! - Balancer weighted pools
+//! - Balancer composable stable pools with BPT as a pool token
+//! - Multi-pool routing and split-order optimisation
 //!
 //! # Example
 //!
@@ -30,6 +36,33 @@
 
 use precision_core::{ArithmeticError, Decimal};
 
+mod composable;
+mod pool;
+pub mod q96;
+mod router;
+mod stable;
+mod weighted;
+
+pub use composable::{
+    stable_bpt_in_given_exact_tokens_out, stable_bpt_out_given_exact_tokens_in,
+    stable_token_in_given_exact_bpt_out, stable_token_out_given_exact_bpt_in, ComposableStablePool,
+};
+pub use pool::{ConcentratedPool, SwapResult, TickInfo, MAX_INITIALIZED_TICKS};
+pub use router::{
+    find_best_route, optimize_split, quote_route, Hop, Route, RoutePool, RouteQuote, SplitQuote,
+    MAX_ROUTE_HOPS, MAX_ROUTE_SPLITS,
+};
+pub use stable::{
+    cryptoswap_d, cryptoswap_y, stableswap_d, stableswap_y, stableswap_y_d, AmplificationRamp,
+    StableSwapPool, MAX_AMPLIFICATION, MAX_AMPLIFICATION_CHANGE, MAX_STABLE_COINS, MIN_RAMP_TIME,
+};
+pub use weighted::{
+    weighted_bpt_in_given_exact_tokens_out, weighted_bpt_out_given_exact_tokens_in,
+    weighted_in_given_out, weighted_invariant, weighted_out_given_in, weighted_spot_price,
+    weighted_token_in_given_exact_bpt_out, weighted_token_out_given_exact_bpt_in, WeightedPool,
+    MAX_WEIGHTED_TOKENS,
+};
+
 /// Tick spacing for 0.05% fee tier (Uniswap V3 convention).
 pub const TICK_SPACING_LOW: i32 = 10;
 
@@ -46,7 +79,7 @@ pub const MIN_TICK: i32 = -887272;
 pub const MAX_TICK: i32 = 887272;
 
 /// Parameters for a concentrated liquidity position.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct ConcentratedPosition {
     /// Lower tick bound of the position.
     pub tick_lower: i32,
@@ -423,6 +456,32 @@ pub fn calculate_liquidity_burn(
     Ok((amount_0, amount_1))
 }
 
+/// Checks pool balances are positive and amounts are non-negative, one per
+/// balance, for at most `max_tokens` tokens.
+fn validate_amounts(
+    balances: &[Decimal],
+    amounts: &[Decimal],
+    max_tokens: usize,
+) -> Result<(), ArithmeticError> {
+    if balances.len() > max_tokens
+        || amounts.len() != balances.len()
+        || amounts.iter().any(|a| a.is_negative())
+        || balances.iter().any(|b| !b.is_positive())
+    {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    Ok(())
+}
+
+/// 1 − x, floored at zero.
+fn complement(x: Decimal) -> Decimal {
+    if x < Decimal::ONE {
+        Decimal::ONE - x
+    } else {
+        Decimal::ZERO
+    }
+}
+
 fn parse_const(s: &str) -> Decimal {
     s.parse().expect("Invalid constant")
 }
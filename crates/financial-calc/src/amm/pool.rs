@@ -0,0 +1,928 @@
+//! Concentrated liquidity pool with tick-crossing swaps.
+//!
+//! [`ConcentratedPool`] follows Uniswap V3's pool state machine: initialized
+//! ticks carry `liquidity_net`, active liquidity changes as the price
+//! crosses them, and swap fees accrue to `fee_growth_global` per unit of
+//! liquidity. Swaps step from tick to tick exactly as `UniswapV3Pool.swap`
+//! does, for exact-input or exact-output amounts, and stop early at an
+//! optional sqrt price limit. Positions are tracked per (lower, upper)
+//! range, so liquidity can only be removed from a range that holds it.
+//!
+//! Amounts and liquidity are in raw token units and the sqrt price is
+//! √(token1/token0) as a plain decimal (sqrtPriceX96 / 2^96). Arithmetic is
+//! decimal rather than Q64.96 integer, so results agree with on-chain swaps
+//! to within rounding.
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::amm::ConcentratedPool;
+//! use precision_core::Decimal;
+//!
+//! // 0.3% pool at price 1
+//! let mut pool = ConcentratedPool::new(Decimal::ONE, 3000, 60).unwrap();
+//! let liquidity = Decimal::from(1_000_000_000_000_000_000i64);
+//! pool.add_liquidity(-600, 600, liquidity).unwrap();
+//!
+//! // Sell token0 for token1
+//! let result = pool
+//!     .swap_exact_input(true, Decimal::from(10_000_000_000_000_000i64), None)
+//!     .unwrap();
+//! assert!(result.amount_out < result.amount_in);
+//! assert!(pool.tick() < 0);
+//! ```
+
+use super::{
+    calculate_amount_0, calculate_amount_1, tick_to_sqrt_price, ConcentratedPosition, MAX_TICK,
+    MIN_TICK,
+};
+use crate::storage::{Buffer, Fixed, Storage};
+use precision_core::{ArithmeticError, Decimal};
+
+/// Default number of initialized ticks in a pool (for no_std fixed allocation).
+pub const MAX_INITIALIZED_TICKS: usize = 128;
+
+/// Fee denominator: fees are in hundredths of a basis point (pips).
+const FEE_DENOMINATOR: i64 = 1_000_000;
+
+/// State of an initialized tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TickInfo {
+    /// Tick index.
+    pub index: i32,
+    /// Total liquidity of positions using this tick as a bound.
+    pub liquidity_gross: Decimal,
+    /// Liquidity added to the active range when the price crosses this tick
+    /// upwards (removed when crossing downwards).
+    pub liquidity_net: Decimal,
+    /// Token0 fee growth per unit of liquidity on the other side of this
+    /// tick from the current price.
+    pub fee_growth_outside_0: Decimal,
+    /// Token1 fee growth per unit of liquidity on the other side of this
+    /// tick from the current price.
+    pub fee_growth_outside_1: Decimal,
+}
+
+/// Outcome of a swap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapResult {
+    /// Amount paid into the pool, including the fee.
+    pub amount_in: Decimal,
+    /// Amount paid out of the pool.
+    pub amount_out: Decimal,
+    /// Fee charged, in the input token.
+    pub fee_amount: Decimal,
+    /// Sqrt price after the swap.
+    pub sqrt_price: Decimal,
+    /// Tick after the swap.
+    pub tick: i32,
+    /// Active liquidity after the swap.
+    pub liquidity: Decimal,
+    /// Number of initialized ticks crossed.
+    pub ticks_crossed: u32,
+}
+
+/// A Uniswap V3-style concentrated liquidity pool.
+#[derive(Debug, Clone)]
+pub struct ConcentratedPool<S: Storage = Fixed<MAX_INITIALIZED_TICKS>> {
+    sqrt_price: Decimal,
+    tick: i32,
+    liquidity: Decimal,
+    fee: u32,
+    tick_spacing: i32,
+    fee_growth_global_0: Decimal,
+    fee_growth_global_1: Decimal,
+    ticks: S::Buffer<TickInfo>,
+    positions: S::Buffer<ConcentratedPosition>,
+}
+
+impl ConcentratedPool {
+    /// Creates an empty pool with default fixed storage.
+    ///
+    /// `fee` is in hundredths of a basis point (3000 = 0.30%).
+    pub fn new(sqrt_price: Decimal, fee: u32, tick_spacing: i32) -> Result<Self, ArithmeticError> {
+        Self::with_storage(sqrt_price, fee, tick_spacing)
+    }
+}
+
+impl<S: Storage> ConcentratedPool<S> {
+    /// Creates an empty pool with the chosen storage.
+    ///
+    /// Returns error if the price is outside the tick range, the fee is not
+    /// below 100% or the tick spacing is not positive.
+    pub fn with_storage(
+        sqrt_price: Decimal,
+        fee: u32,
+        tick_spacing: i32,
+    ) -> Result<Self, ArithmeticError> {
+        if i64::from(fee) >= FEE_DENOMINATOR || tick_spacing <= 0 {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        if sqrt_price < tick_to_sqrt_price(MIN_TICK)? || sqrt_price >= tick_to_sqrt_price(MAX_TICK)?
+        {
+            return Err(ArithmeticError::Overflow);
+        }
+        Ok(Self {
+            sqrt_price,
+            tick: tick_at_sqrt_price(sqrt_price)?,
+            liquidity: Decimal::ZERO,
+            fee,
+            tick_spacing,
+            fee_growth_global_0: Decimal::ZERO,
+            fee_growth_global_1: Decimal::ZERO,
+            ticks: Default::default(),
+            positions: Default::default(),
+        })
+    }
+
+    /// Adds liquidity between two ticks.
+    ///
+    /// Returns the (token0, token1) amounts the position requires.
+    pub fn add_liquidity(
+        &mut self,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity: Decimal,
+    ) -> Result<(Decimal, Decimal), ArithmeticError> {
+        if liquidity.is_negative() {
+            return Err(ArithmeticError::Underflow);
+        }
+        self.modify_position(tick_lower, tick_upper, liquidity)
+    }
+
+    /// Removes liquidity between two ticks.
+    ///
+    /// Returns the (token0, token1) amounts released, excluding fees, or
+    /// `Underflow` if the position over exactly this range holds less than
+    /// `liquidity`.
+    pub fn remove_liquidity(
+        &mut self,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity: Decimal,
+    ) -> Result<(Decimal, Decimal), ArithmeticError> {
+        if liquidity.is_negative() {
+            return Err(ArithmeticError::Underflow);
+        }
+        let (amount_0, amount_1) = self.modify_position(tick_lower, tick_upper, -liquidity)?;
+        Ok((-amount_0, -amount_1))
+    }
+
+    /// Swaps an exact input amount.
+    ///
+    /// `zero_for_one` sells token0 for token1, moving the price down. The
+    /// swap stops early if the price reaches `sqrt_price_limit`, leaving
+    /// part of the input unspent.
+    pub fn swap_exact_input(
+        &mut self,
+        zero_for_one: bool,
+        amount_in: Decimal,
+        sqrt_price_limit: Option<Decimal>,
+    ) -> Result<SwapResult, ArithmeticError> {
+        if !amount_in.is_positive() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        self.swap(zero_for_one, amount_in, sqrt_price_limit)
+    }
+
+    /// Swaps for an exact output amount.
+    ///
+    /// The swap stops early if the price reaches `sqrt_price_limit`,
+    /// delivering less than requested.
+    pub fn swap_exact_output(
+        &mut self,
+        zero_for_one: bool,
+        amount_out: Decimal,
+        sqrt_price_limit: Option<Decimal>,
+    ) -> Result<SwapResult, ArithmeticError> {
+        if !amount_out.is_positive() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        self.swap(zero_for_one, -amount_out, sqrt_price_limit)
+    }
+
+    /// Fee growth per unit of liquidity between two ticks, for token0 and
+    /// token1.
+    ///
+    /// A position's uncollected fees are its liquidity times the change in
+    /// this value since it was last checkpointed.
+    pub fn fee_growth_inside(
+        &self,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Result<(Decimal, Decimal), ArithmeticError> {
+        let lower = self.tick_info(tick_lower).unwrap_or_default();
+        let upper = self.tick_info(tick_upper).unwrap_or_default();
+        let globals = [self.fee_growth_global_0, self.fee_growth_global_1];
+        let mut inside = [Decimal::ZERO; 2];
+        for (token, growth) in inside.iter_mut().enumerate() {
+            let (lower_outside, upper_outside) = if token == 0 {
+                (lower.fee_growth_outside_0, upper.fee_growth_outside_0)
+            } else {
+                (lower.fee_growth_outside_1, upper.fee_growth_outside_1)
+            };
+            let below = if self.tick >= tick_lower {
+                lower_outside
+            } else {
+                globals[token].try_sub(lower_outside)?
+            };
+            let above = if self.tick < tick_upper {
+                upper_outside
+            } else {
+                globals[token].try_sub(upper_outside)?
+            };
+            *growth = globals[token].try_sub(below)?.try_sub(above)?;
+        }
+        Ok((inside[0], inside[1]))
+    }
+
+    /// Current sqrt price.
+    pub fn sqrt_price(&self) -> Decimal {
+        self.sqrt_price
+    }
+
+    /// Current tick: the greatest tick whose sqrt price is at or below the
+    /// current sqrt price.
+    pub fn tick(&self) -> i32 {
+        self.tick
+    }
+
+    /// Active liquidity.
+    pub fn liquidity(&self) -> Decimal {
+        self.liquidity
+    }
+
+    /// Swap fee in hundredths of a basis point.
+    pub fn fee(&self) -> u32 {
+        self.fee
+    }
+
+    /// Tick spacing.
+    pub fn tick_spacing(&self) -> i32 {
+        self.tick_spacing
+    }
+
+    /// Accumulated (token0, token1) fees per unit of liquidity.
+    pub fn fee_growth_global(&self) -> (Decimal, Decimal) {
+        (self.fee_growth_global_0, self.fee_growth_global_1)
+    }
+
+    /// Initialized ticks in increasing order.
+    pub fn ticks(&self) -> &[TickInfo] {
+        self.ticks.as_slice()
+    }
+
+    /// Open positions ordered by (lower, upper) tick.
+    pub fn positions(&self) -> &[ConcentratedPosition] {
+        self.positions.as_slice()
+    }
+
+    fn tick_info(&self, index: i32) -> Option<TickInfo> {
+        let ticks = self.ticks.as_slice();
+        ticks
+            .binary_search_by_key(&index, |t| t.index)
+            .ok()
+            .map(|i| ticks[i])
+    }
+
+    fn modify_position(
+        &mut self,
+        tick_lower: i32,
+        tick_upper: i32,
+        delta: Decimal,
+    ) -> Result<(Decimal, Decimal), ArithmeticError> {
+        if tick_lower >= tick_upper
+            || tick_lower < MIN_TICK
+            || tick_upper > MAX_TICK
+            || tick_lower % self.tick_spacing != 0
+            || tick_upper % self.tick_spacing != 0
+        {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        // Checked before any tick changes so a rejected update leaves the
+        // pool untouched
+        let key = (tick_lower, tick_upper);
+        let slot = self
+            .positions
+            .as_slice()
+            .binary_search_by_key(&key, |p| (p.tick_lower, p.tick_upper));
+        let held = slot.map_or(Decimal::ZERO, |i| self.positions.as_slice()[i].liquidity);
+        let remaining = held.try_add(delta)?;
+        if remaining.is_negative() {
+            return Err(ArithmeticError::Underflow);
+        }
+        if slot.is_err() && self.positions.capacity() == Some(self.positions.len()) {
+            return Err(ArithmeticError::Overflow);
+        }
+        let new_ticks = if delta.is_positive() {
+            [tick_lower, tick_upper]
+                .iter()
+                .filter(|&&index| self.tick_info(index).is_none())
+                .count()
+        } else {
+            0
+        };
+        if let Some(capacity) = self.ticks.capacity() {
+            if self.ticks.len() + new_ticks > capacity {
+                return Err(ArithmeticError::Overflow);
+            }
+        }
+        self.update_tick(tick_lower, delta, false)?;
+        self.update_tick(tick_upper, delta, true)?;
+        match slot {
+            Ok(i) if remaining.is_zero() => {
+                self.positions.remove(i);
+            }
+            Ok(i) => self.positions.as_mut_slice()[i].liquidity = remaining,
+            Err(i) if remaining.is_positive() => self.positions.insert(
+                i,
+                ConcentratedPosition {
+                    tick_lower,
+                    tick_upper,
+                    liquidity: remaining,
+                },
+            )?,
+            Err(_) => {}
+        }
+
+        let sqrt_lower = tick_to_sqrt_price(tick_lower)?;
+        let sqrt_upper = tick_to_sqrt_price(tick_upper)?;
+        if self.tick < tick_lower {
+            Ok((
+                calculate_amount_0(sqrt_lower, sqrt_upper, delta)?,
+                Decimal::ZERO,
+            ))
+        } else if self.tick < tick_upper {
+            self.liquidity = self.liquidity.try_add(delta)?;
+            Ok((
+                calculate_amount_0(self.sqrt_price, sqrt_upper, delta)?,
+                calculate_amount_1(sqrt_lower, self.sqrt_price, delta)?,
+            ))
+        } else {
+            Ok((
+                Decimal::ZERO,
+                calculate_amount_1(sqrt_lower, sqrt_upper, delta)?,
+            ))
+        }
+    }
+
+    /// Applies a liquidity change to a tick, initializing or clearing it.
+    fn update_tick(
+        &mut self,
+        index: i32,
+        delta: Decimal,
+        upper: bool,
+    ) -> Result<(), ArithmeticError> {
+        let net_delta = if upper { -delta } else { delta };
+        let position = self
+            .ticks
+            .as_slice()
+            .binary_search_by_key(&index, |t| t.index);
+        match position {
+            Ok(i) => {
+                let tick = &mut self.ticks.as_mut_slice()[i];
+                tick.liquidity_gross = tick.liquidity_gross.try_add(delta)?;
+                tick.liquidity_net = tick.liquidity_net.try_add(net_delta)?;
+                if tick.liquidity_gross.is_zero() {
+                    self.ticks.remove(i);
+                }
+                Ok(())
+            }
+            Err(i) => {
+                if !delta.is_positive() {
+                    return Err(ArithmeticError::Underflow);
+                }
+                // By convention all growth so far happened below the tick
+                let (outside_0, outside_1) = if index <= self.tick {
+                    (self.fee_growth_global_0, self.fee_growth_global_1)
+                } else {
+                    (Decimal::ZERO, Decimal::ZERO)
+                };
+                self.ticks.insert(
+                    i,
+                    TickInfo {
+                        index,
+                        liquidity_gross: delta,
+                        liquidity_net: net_delta,
+                        fee_growth_outside_0: outside_0,
+                        fee_growth_outside_1: outside_1,
+                    },
+                )
+            }
+        }
+    }
+
+    /// Swap loop; positive `amount_specified` is exact input, negative is
+    /// exact output.
+    fn swap(
+        &mut self,
+        zero_for_one: bool,
+        amount_specified: Decimal,
+        sqrt_price_limit: Option<Decimal>,
+    ) -> Result<SwapResult, ArithmeticError> {
+        let min_sqrt = tick_to_sqrt_price(MIN_TICK)?;
+        let max_sqrt = tick_to_sqrt_price(MAX_TICK)?;
+        let limit = match sqrt_price_limit {
+            Some(limit) => limit,
+            None if zero_for_one => min_sqrt,
+            None => max_sqrt,
+        };
+        let valid = if zero_for_one {
+            limit < self.sqrt_price && limit >= min_sqrt
+        } else {
+            limit > self.sqrt_price && limit <= max_sqrt
+        };
+        if !valid {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+
+        let exact_input = amount_specified.is_positive();
+        let mut remaining = amount_specified;
+        let mut result = SwapResult {
+            amount_in: Decimal::ZERO,
+            amount_out: Decimal::ZERO,
+            fee_amount: Decimal::ZERO,
+            sqrt_price: self.sqrt_price,
+            tick: self.tick,
+            liquidity: self.liquidity,
+            ticks_crossed: 0,
+        };
+
+        while !remaining.is_zero() && self.sqrt_price != limit {
+            let start = self.sqrt_price;
+            let (next_index, initialized) = self.next_initialized_tick(zero_for_one);
+            let sqrt_next = tick_to_sqrt_price(next_index)?;
+            let target = if zero_for_one {
+                sqrt_next.max(limit)
+            } else {
+                sqrt_next.min(limit)
+            };
+
+            let step = swap_step(start, target, self.liquidity, remaining, self.fee)?;
+            self.sqrt_price = step.sqrt_price;
+            let paid = step.amount_in.try_add(step.fee_amount)?;
+            remaining = if exact_input {
+                remaining.try_sub(paid)?
+            } else {
+                remaining.try_add(step.amount_out)?
+            };
+            result.amount_in = result.amount_in.try_add(paid)?;
+            result.amount_out = result.amount_out.try_add(step.amount_out)?;
+            result.fee_amount = result.fee_amount.try_add(step.fee_amount)?;
+
+            if self.liquidity.is_positive() {
+                let growth = step.fee_amount.try_div(self.liquidity)?;
+                if zero_for_one {
+                    self.fee_growth_global_0 = self.fee_growth_global_0.try_add(growth)?;
+                } else {
+                    self.fee_growth_global_1 = self.fee_growth_global_1.try_add(growth)?;
+                }
+            }
+
+            if self.sqrt_price == sqrt_next {
+                if initialized {
+                    let net = self.cross(next_index)?;
+                    let net = if zero_for_one { -net } else { net };
+                    self.liquidity = self.liquidity.try_add(net)?;
+                    result.ticks_crossed += 1;
+                }
+                self.tick = if zero_for_one {
+                    next_index - 1
+                } else {
+                    next_index
+                };
+            } else if self.sqrt_price != start {
+                self.tick = tick_at_sqrt_price(self.sqrt_price)?;
+            }
+        }
+
+        result.sqrt_price = self.sqrt_price;
+        result.tick = self.tick;
+        result.liquidity = self.liquidity;
+        Ok(result)
+    }
+
+    /// Next initialized tick in the swap direction, or the tick range bound
+    /// if there is none.
+    fn next_initialized_tick(&self, zero_for_one: bool) -> (i32, bool) {
+        let ticks = self.ticks.as_slice();
+        if zero_for_one {
+            let i = ticks.partition_point(|t| t.index <= self.tick);
+            match i.checked_sub(1) {
+                Some(j) => (ticks[j].index, true),
+                None => (MIN_TICK, false),
+            }
+        } else {
+            let i = ticks.partition_point(|t| t.index <= self.tick);
+            match ticks.get(i) {
+                Some(t) => (t.index, true),
+                None => (MAX_TICK, false),
+            }
+        }
+    }
+
+    /// Flips a tick's fee growth outside and returns its liquidity net.
+    fn cross(&mut self, index: i32) -> Result<Decimal, ArithmeticError> {
+        let (global_0, global_1) = (self.fee_growth_global_0, self.fee_growth_global_1);
+        let slice = self.ticks.as_mut_slice();
+        let i = slice
+            .binary_search_by_key(&index, |t| t.index)
+            .map_err(|_| ArithmeticError::DivisionByZero)?;
+        let tick = &mut slice[i];
+        tick.fee_growth_outside_0 = global_0.try_sub(tick.fee_growth_outside_0)?;
+        tick.fee_growth_outside_1 = global_1.try_sub(tick.fee_growth_outside_1)?;
+        Ok(tick.liquidity_net)
+    }
+}
+
+/// Result of a single swap step within one tick range.
+struct SwapStep {
+    sqrt_price: Decimal,
+    amount_in: Decimal,
+    amount_out: Decimal,
+    fee_amount: Decimal,
+}
+
+/// Moves the price from `current` towards `target` within a range of
+/// constant liquidity (Uniswap's `SwapMath.computeSwapStep`).
+fn swap_step(
+    current: Decimal,
+    target: Decimal,
+    liquidity: Decimal,
+    remaining: Decimal,
+    fee: u32,
+) -> Result<SwapStep, ArithmeticError> {
+    let zero_for_one = current >= target;
+    let exact_input = !remaining.is_negative();
+    let denominator = Decimal::from(FEE_DENOMINATOR);
+    let fee = Decimal::from(i64::from(fee));
+
+    // Amounts to move between two prices, ordered as (in, out)
+    let amounts = |a: Decimal, b: Decimal| -> Result<(Decimal, Decimal), ArithmeticError> {
+        let (low, high) = if a < b { (a, b) } else { (b, a) };
+        let amount_0 = calculate_amount_0(low, high, liquidity)?;
+        let amount_1 = calculate_amount_1(low, high, liquidity)?;
+        Ok(if zero_for_one {
+            (amount_0, amount_1)
+        } else {
+            (amount_1, amount_0)
+        })
+    };
+
+    let (to_target_in, to_target_out) = amounts(current, target)?;
+    let next = if exact_input {
+        let less_fee = remaining
+            .try_mul(denominator.try_sub(fee)?)?
+            .try_div(denominator)?;
+        if less_fee >= to_target_in {
+            target
+        } else {
+            next_sqrt_price(current, liquidity, less_fee, zero_for_one, true)?
+        }
+    } else if remaining.abs() >= to_target_out {
+        target
+    } else {
+        next_sqrt_price(current, liquidity, remaining.abs(), zero_for_one, false)?
+    };
+
+    let (amount_in, mut amount_out) = if next == target {
+        (to_target_in, to_target_out)
+    } else {
+        amounts(current, next)?
+    };
+    // A step that stops short of the target consumes the whole remainder;
+    // taking it exactly keeps decimal rounding from leaving dust behind
+    if !exact_input && (next != target || amount_out > remaining.abs()) {
+        amount_out = remaining.abs();
+    }
+    let fee_amount = if exact_input && next != target {
+        remaining.try_sub(amount_in)?
+    } else {
+        amount_in.try_mul(fee)?.try_div(denominator.try_sub(fee)?)?
+    };
+    Ok(SwapStep {
+        sqrt_price: next,
+        amount_in,
+        amount_out,
+        fee_amount,
+    })
+}
+
+/// Sqrt price after adding (`input`) or removing an amount of one token.
+///
+/// Token0 moves the price as √P' = L√P / (L ± Δx√P); token1 as
+/// √P' = √P ± Δy / L.
+fn next_sqrt_price(
+    sqrt_price: Decimal,
+    liquidity: Decimal,
+    amount: Decimal,
+    zero_for_one: bool,
+    input: bool,
+) -> Result<Decimal, ArithmeticError> {
+    if !liquidity.is_positive() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    // Token0 is the input when selling zero for one, the output otherwise
+    if zero_for_one == input {
+        let product = amount.try_mul(sqrt_price)?;
+        let denominator = if input {
+            liquidity.try_add(product)?
+        } else {
+            liquidity.try_sub(product)?
+        };
+        if !denominator.is_positive() {
+            return Err(ArithmeticError::Underflow);
+        }
+        liquidity.try_mul(sqrt_price)?.try_div(denominator)
+    } else {
+        let shift = amount.try_div(liquidity)?;
+        if input {
+            sqrt_price.try_add(shift)
+        } else {
+            sqrt_price.try_sub(shift)
+        }
+    }
+}
+
+/// Greatest tick with sqrt price at or below `sqrt_price`.
+fn tick_at_sqrt_price(sqrt_price: Decimal) -> Result<i32, ArithmeticError> {
+    let mut tick = super::sqrt_price_to_tick(sqrt_price)?.clamp(MIN_TICK, MAX_TICK);
+    while tick > MIN_TICK && tick_to_sqrt_price(tick)? > sqrt_price {
+        tick -= 1;
+    }
+    while tick < MAX_TICK && tick_to_sqrt_price(tick + 1)? <= sqrt_price {
+        tick += 1;
+    }
+    Ok(tick)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn pool() -> ConcentratedPool {
+        let mut pool = ConcentratedPool::new(Decimal::ONE, 3000, 60).unwrap();
+        pool.add_liquidity(-600, 600, decimal("1000000000000000000"))
+            .unwrap();
+        pool.add_liquidity(-1200, -60, decimal("500000000000000000"))
+            .unwrap();
+        pool.add_liquidity(120, 1800, decimal("2000000000000000000"))
+            .unwrap();
+        pool
+    }
+
+    /// sqrtPriceX96 / 2^96, split in two since 2^96 exceeds `Decimal::MAX`
+    fn from_x96(sqrt_price_x96: &str) -> Decimal {
+        let x: u128 = sqrt_price_x96.parse().unwrap();
+        let half = Decimal::from(1u64 << 48);
+        let high = Decimal::from((x >> 48) as u64);
+        let low = Decimal::from((x & ((1 << 48) - 1)) as u64);
+        (high + low / half) / half
+    }
+
+    fn assert_close(actual: Decimal, expected: &str) {
+        let expected = decimal(expected);
+        assert!(
+            (actual - expected).abs() <= decimal("10"),
+            "{actual} vs {expected}"
+        );
+    }
+
+    fn assert_swap(result: &SwapResult, expected: [&str; 4], tick: i32, crossed: u32) {
+        assert_close(result.amount_in, expected[0]);
+        assert_close(result.amount_out, expected[1]);
+        assert_close(result.fee_amount, expected[2]);
+        let sqrt = from_x96(expected[3]);
+        let diff = (result.sqrt_price - sqrt).abs();
+        assert!(diff < decimal("0.00000000000000001"), "{diff}");
+        assert_eq!(result.tick, tick);
+        assert_eq!(result.ticks_crossed, crossed);
+    }
+
+    // Expected values come from Uniswap V3's integer swap math
+    // (TickMath, SqrtPriceMath, SwapMath) on the same pool.
+
+    #[test]
+    fn test_liquidity_amounts() {
+        let mut pool = ConcentratedPool::new(Decimal::ONE, 3000, 60).unwrap();
+        let (a0, a1) = pool
+            .add_liquidity(-600, 600, decimal("1000000000000000000"))
+            .unwrap();
+        assert_close(a0, "29553010879137170");
+        assert_close(a1, "29553010879137170");
+        let (a0, a1) = pool
+            .add_liquidity(120, 1800, decimal("2000000000000000000"))
+            .unwrap();
+        assert_close(a0, "160165929085669781");
+        assert_eq!(a1, Decimal::ZERO);
+        assert_eq!(pool.liquidity(), decimal("1000000000000000000"));
+        assert_eq!(pool.ticks().len(), 4);
+
+        assert!(pool.add_liquidity(-61, 600, Decimal::ONE).is_err());
+        assert!(pool.add_liquidity(600, -600, Decimal::ONE).is_err());
+        assert!(pool
+            .remove_liquidity(-600, 600, decimal("2000000000000000000"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_exact_input_crosses_ticks() {
+        let mut pool = pool();
+        let result = pool
+            .swap_exact_input(true, decimal("40000000000000000"), None)
+            .unwrap();
+        assert_swap(
+            &result,
+            [
+                "40000000000000000",
+                "38773493729309123",
+                "120000000000001",
+                "77101088582898393738362994973",
+            ],
+            -545,
+            1,
+        );
+        assert_eq!(result.liquidity, decimal("1500000000000000000"));
+
+        let mut pool = self::pool();
+        let result = pool
+            .swap_exact_input(false, decimal("150000000000000000"), None)
+            .unwrap();
+        assert_swap(
+            &result,
+            [
+                "150000000000000000",
+                "140653194123970282",
+                "450000000000002",
+                "84422855238840127231609830155",
+            ],
+            1270,
+            2,
+        );
+    }
+
+    #[test]
+    fn test_exact_output_crosses_ticks() {
+        let mut pool = pool();
+        pool.swap_exact_input(true, decimal("40000000000000000"), None)
+            .unwrap();
+
+        let result = pool
+            .swap_exact_output(false, decimal("120000000000000000"), None)
+            .unwrap();
+        assert_swap(
+            &result,
+            [
+                "122105922681945549",
+                "120000000000000000",
+                "366317768045838",
+                "81785195656890644962049667635",
+            ],
+            635,
+            3,
+        );
+        assert_eq!(result.liquidity, decimal("2000000000000000000"));
+
+        let result = pool
+            .swap_exact_output(true, decimal("50000000000000000"), None)
+            .unwrap();
+        assert_swap(
+            &result,
+            [
+                "47890638692495037",
+                "50000000000000000",
+                "143671916077486",
+                "80416626671474743116225719921",
+            ],
+            297,
+            1,
+        );
+    }
+
+    #[test]
+    fn test_price_limit_stops_swap() {
+        let mut pool = pool();
+        let limit = tick_to_sqrt_price(-900).unwrap();
+        let result = pool
+            .swap_exact_input(true, decimal("1000000000000000000"), Some(limit))
+            .unwrap();
+        assert_swap(
+            &result,
+            [
+                "52119924370439097",
+                "50055517057949422",
+                "156359773111319",
+                "75742094262060239185556691107",
+            ],
+            -900,
+            2,
+        );
+        assert_eq!(pool.sqrt_price(), limit);
+
+        // Limit on the wrong side of the price
+        assert!(pool
+            .swap_exact_input(true, Decimal::ONE, Some(Decimal::ONE))
+            .is_err());
+    }
+
+    #[test]
+    fn test_fee_growth_accrues_to_active_range() {
+        let mut pool = pool();
+        pool.swap_exact_input(true, decimal("40000000000000000"), None)
+            .unwrap();
+        let (global_0, global_1) = pool.fee_growth_global();
+        assert!(global_0.is_positive());
+        assert_eq!(global_1, Decimal::ZERO);
+
+        // Fees earned inside [-600, 600] by 1e18 liquidity, which was
+        // active for the whole swap: all of it except the share paid
+        // while [-1200, -60] was also active
+        let (inside_0, _) = pool.fee_growth_inside(-600, 600).unwrap();
+        let fees = inside_0 * decimal("1000000000000000000");
+        assert!(fees.is_positive() && fees < decimal("120000000000001"));
+
+        // Nothing was earned above the price
+        let (above, _) = pool.fee_growth_inside(120, 1800).unwrap();
+        assert_eq!(above, Decimal::ZERO);
+
+        // Round trip leaves each range's growth consistent
+        let (lower_0, _) = pool.fee_growth_inside(-1200, -60).unwrap();
+        let total = fees + lower_0 * decimal("500000000000000000");
+        assert!((total - decimal("120000000000001")).abs() < decimal("10"));
+    }
+
+    #[test]
+    fn test_remove_liquidity_clears_ticks() {
+        let mut pool = pool();
+        let (a0, a1) = pool
+            .remove_liquidity(-600, 600, decimal("1000000000000000000"))
+            .unwrap();
+        assert_close(a0, "29553010879137170");
+        assert_close(a1, "29553010879137170");
+        assert_eq!(pool.liquidity(), Decimal::ZERO);
+        assert_eq!(pool.ticks().len(), 4);
+        assert!(pool.ticks().iter().all(|t| t.index != 600));
+        assert_eq!(pool.positions().len(), 2);
+    }
+
+    #[test]
+    fn test_remove_liquidity_requires_position() {
+        let mut pool = pool();
+        let ticks = [pool.ticks()[0], pool.ticks()[5]];
+
+        // Both bounds are initialized by other positions, but nothing was
+        // ever added over [-1200, 1800]
+        assert_eq!(
+            pool.remove_liquidity(-1200, 1800, Decimal::ONE),
+            Err(ArithmeticError::Underflow)
+        );
+        assert_eq!(
+            pool.remove_liquidity(-600, 600, decimal("1000000000000000001")),
+            Err(ArithmeticError::Underflow)
+        );
+        assert_eq!([pool.ticks()[0], pool.ticks()[5]], ticks);
+        assert_eq!(pool.liquidity(), decimal("1000000000000000000"));
+
+        // Partial removals draw the position down to zero
+        pool.remove_liquidity(-600, 600, decimal("400000000000000000"))
+            .unwrap();
+        assert_eq!(
+            pool.positions()[1],
+            ConcentratedPosition {
+                tick_lower: -600,
+                tick_upper: 600,
+                liquidity: decimal("600000000000000000"),
+            }
+        );
+        pool.remove_liquidity(-600, 600, decimal("600000000000000000"))
+            .unwrap();
+        assert!(pool.positions().iter().all(|p| p.tick_lower != -600));
+    }
+
+    #[test]
+    fn test_full_tick_storage_rejects_new_ticks() {
+        let mut pool = ConcentratedPool::<Fixed<4>>::with_storage(Decimal::ONE, 3000, 60).unwrap();
+        pool.add_liquidity(-120, 60, decimal("1000")).unwrap();
+        pool.add_liquidity(-120, 120, decimal("1000")).unwrap();
+        let ticks = [pool.ticks()[0], pool.ticks()[1], pool.ticks()[2]];
+        let positions = [pool.positions()[0], pool.positions()[1]];
+
+        // One free slot cannot hold both new ticks
+        assert_eq!(
+            pool.add_liquidity(180, 240, decimal("1000")),
+            Err(ArithmeticError::Overflow)
+        );
+        assert_eq!(pool.ticks(), ticks);
+        assert_eq!(pool.positions(), positions);
+        assert_eq!(pool.liquidity(), decimal("2000"));
+
+        // A range reusing an initialized tick still fits
+        pool.add_liquidity(60, 240, decimal("1000")).unwrap();
+        assert_eq!(pool.ticks().len(), 4);
+    }
+}
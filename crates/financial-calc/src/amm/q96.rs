@@ -0,0 +1,744 @@
+//! Bit-exact Uniswap V3 fixed-point math.
+//!
+//! [`tick_to_sqrt_price`](super::tick_to_sqrt_price) and the other `amm`
+//! functions compute in [`Decimal`], so they differ from on-chain results
+//! in the last digits. This module reproduces the integer libraries
+//! instead: `TickMath.getSqrtRatioAtTick` / `getTickAtSqrtRatio`,
+//! `SqrtPriceMath.getAmount0Delta` / `getAmount1Delta` and
+//! `FullMath.mulDiv` / `mulDivRoundingUp`, returning exactly what the
+//! contracts return.
+//!
+//! Sqrt prices are Q64.96 fixed-point numbers (`sqrtPriceX96`) held in a
+//! [`U256`]; liquidity is a `u128`. Use [`q96_to_decimal`] and
+//! [`U256::to_decimal`] to display results.
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::amm::q96::{get_amount_0_delta, get_sqrt_ratio_at_tick, q96_to_decimal};
+//! use financial_calc::amm::q96::U256;
+//!
+//! let lower = get_sqrt_ratio_at_tick(-600).unwrap();
+//! let upper = get_sqrt_ratio_at_tick(600).unwrap();
+//! let liquidity = 1_000_000_000_000_000_000u128;
+//!
+//! // Deposits round up, withdrawals round down
+//! let deposit = get_amount_0_delta(lower, upper, liquidity, true).unwrap();
+//! let withdrawal = get_amount_0_delta(lower, upper, liquidity, false).unwrap();
+//! assert_eq!(deposit, withdrawal.checked_add(U256::ONE).unwrap());
+//!
+//! let price = q96_to_decimal(upper).unwrap();
+//! assert!(price > precision_core::Decimal::ONE);
+//! ```
+
+use super::{MAX_TICK, MIN_TICK};
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Shl, Shr};
+use core::str::FromStr;
+use precision_core::{ArithmeticError, Decimal, ParseError};
+
+/// Smallest sqrt price, `getSqrtRatioAtTick(MIN_TICK)`.
+pub const MIN_SQRT_RATIO: U256 = U256::from_words(0, 4_295_128_739);
+
+/// Largest sqrt price, `getSqrtRatioAtTick(MAX_TICK)`.
+pub const MAX_SQRT_RATIO: U256 =
+    U256::from_words(4_294_805_859, 0xefd1_fc6a_5064_8849_5d95_1d52_6398_8d26);
+
+/// 2^96, the Q64.96 scaling factor.
+pub const Q96: U256 = U256::from_words(0, 1 << 96);
+
+/// 256-bit unsigned integer, matching Solidity's `uint256`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct U256([u64; 4]);
+
+impl U256 {
+    /// Zero.
+    pub const ZERO: Self = Self([0; 4]);
+
+    /// One.
+    pub const ONE: Self = Self([1, 0, 0, 0]);
+
+    /// 2^256 − 1.
+    pub const MAX: Self = Self([u64::MAX; 4]);
+
+    /// Creates a value from its high and low 128 bits.
+    pub const fn from_words(high: u128, low: u128) -> Self {
+        Self([
+            low as u64,
+            (low >> 64) as u64,
+            high as u64,
+            (high >> 64) as u64,
+        ])
+    }
+
+    /// High 128 bits.
+    pub const fn high_u128(self) -> u128 {
+        (self.0[3] as u128) << 64 | self.0[2] as u128
+    }
+
+    /// Low 128 bits.
+    pub const fn low_u128(self) -> u128 {
+        (self.0[1] as u128) << 64 | self.0[0] as u128
+    }
+
+    /// Returns true if the value is zero.
+    pub fn is_zero(self) -> bool {
+        self == Self::ZERO
+    }
+
+    /// Number of significant bits.
+    pub fn bits(self) -> u32 {
+        for i in (0..4).rev() {
+            if self.0[i] != 0 {
+                return 64 * i as u32 + 64 - self.0[i].leading_zeros();
+            }
+        }
+        0
+    }
+
+    /// Addition, returning `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let (sum, carry) = self.overflowing_add(other);
+        (!carry).then_some(sum)
+    }
+
+    /// Subtraction, returning `None` on underflow.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        let (difference, borrow) = self.overflowing_sub(other);
+        (!borrow).then_some(difference)
+    }
+
+    /// Multiplication, returning `None` on overflow.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let product = full_mul(self, other);
+        product[4..]
+            .iter()
+            .all(|&limb| limb == 0)
+            .then(|| low_half(product))
+    }
+
+    /// Division rounding down, returning `None` when dividing by zero.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+        let mut wide = [0u64; 8];
+        wide[..4].copy_from_slice(&self.0);
+        let (quotient, _) = div_rem_wide(wide, other);
+        Some(low_half(quotient))
+    }
+
+    /// Converts an integer amount to a [`Decimal`].
+    ///
+    /// Returns error if the value exceeds `Decimal::MAX` (2^96 − 1).
+    pub fn to_decimal(self) -> Result<Decimal, ArithmeticError> {
+        if self.bits() > 96 {
+            return Err(ArithmeticError::Overflow);
+        }
+        Ok(Decimal::from(self.low_u128()))
+    }
+
+    fn overflowing_add(self, other: Self) -> (Self, bool) {
+        let mut result = [0u64; 4];
+        let mut carry = false;
+        for (i, limb) in result.iter_mut().enumerate() {
+            let (sum, c1) = self.0[i].overflowing_add(other.0[i]);
+            let (sum, c2) = sum.overflowing_add(u64::from(carry));
+            *limb = sum;
+            carry = c1 || c2;
+        }
+        (Self(result), carry)
+    }
+
+    fn overflowing_sub(self, other: Self) -> (Self, bool) {
+        let mut result = [0u64; 4];
+        let mut borrow = false;
+        for (i, limb) in result.iter_mut().enumerate() {
+            let (difference, b1) = self.0[i].overflowing_sub(other.0[i]);
+            let (difference, b2) = difference.overflowing_sub(u64::from(borrow));
+            *limb = difference;
+            borrow = b1 || b2;
+        }
+        (Self(result), borrow)
+    }
+
+    /// Remainder after division by 10, with the quotient.
+    fn div_rem_10(self) -> (Self, u64) {
+        let mut quotient = [0u64; 4];
+        let mut remainder = 0u128;
+        for i in (0..4).rev() {
+            let current = remainder << 64 | u128::from(self.0[i]);
+            quotient[i] = (current / 10) as u64;
+            remainder = current % 10;
+        }
+        (Self(quotient), remainder as u64)
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(value: u128) -> Self {
+        Self::from_words(0, value)
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(value: u64) -> Self {
+        Self([value, 0, 0, 0])
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Shl<u32> for U256 {
+    type Output = Self;
+
+    /// Shifts left, discarding bits shifted past 2^255 as Solidity does.
+    fn shl(self, bits: u32) -> Self {
+        if bits >= 256 {
+            return Self::ZERO;
+        }
+        let (words, bits) = ((bits / 64) as usize, bits % 64);
+        let mut result = [0u64; 4];
+        for (i, limb) in result.iter_mut().enumerate().skip(words) {
+            *limb = self.0[i - words] << bits;
+            if bits > 0 && i > words {
+                *limb |= self.0[i - words - 1] >> (64 - bits);
+            }
+        }
+        Self(result)
+    }
+}
+
+impl Shr<u32> for U256 {
+    type Output = Self;
+
+    fn shr(self, bits: u32) -> Self {
+        if bits >= 256 {
+            return Self::ZERO;
+        }
+        let (words, bits) = ((bits / 64) as usize, bits % 64);
+        let mut result = [0u64; 4];
+        for (i, limb) in result.iter_mut().enumerate().take(4 - words) {
+            *limb = self.0[i + words] >> bits;
+            if bits > 0 && i + words + 1 < 4 {
+                *limb |= self.0[i + words + 1] << (64 - bits);
+            }
+        }
+        Self(result)
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 2^256 has 78 decimal digits
+        let mut digits = [0u8; 78];
+        let mut start = digits.len();
+        let mut value = *self;
+        loop {
+            let (quotient, digit) = value.div_rem_10();
+            start -= 1;
+            digits[start] = b'0' + digit as u8;
+            value = quotient;
+            if value.is_zero() {
+                break;
+            }
+        }
+        let text = core::str::from_utf8(&digits[start..]).map_err(|_| fmt::Error)?;
+        f.pad_integral(true, "", text)
+    }
+}
+
+impl FromStr for U256 {
+    type Err = ParseError;
+
+    /// Parses a decimal integer.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+        let ten = Self::from(10u64);
+        s.bytes().try_fold(Self::ZERO, |acc, byte| {
+            if !byte.is_ascii_digit() {
+                return Err(ParseError::InvalidCharacter);
+            }
+            acc.checked_mul(ten)
+                .and_then(|v| v.checked_add(Self::from(u64::from(byte - b'0'))))
+                .ok_or(ParseError::OutOfRange)
+        })
+    }
+}
+
+/// Converts a Q64.96 value such as `sqrtPriceX96` to a [`Decimal`].
+///
+/// Exact up to `Decimal`'s 28 significant digits. Returns error if the
+/// integer part exceeds `Decimal::MAX`.
+pub fn q96_to_decimal(value: U256) -> Result<Decimal, ArithmeticError> {
+    let integer = (value >> 96).to_decimal()?;
+    let fraction = value.low_u128() & ((1 << 96) - 1);
+    let half = Decimal::from(1u64 << 48);
+    let high = Decimal::from((fraction >> 48) as u64);
+    let low = Decimal::from((fraction & ((1 << 48) - 1)) as u64);
+    let fraction = high.try_add(low.try_div(half)?)?.try_div(half)?;
+    integer.try_add(fraction)
+}
+
+/// `FullMath.mulDiv`: ⌊a × b / denominator⌋ with a 512-bit intermediate.
+///
+/// Returns error if the denominator is zero or the result overflows 256
+/// bits.
+pub fn mul_div(a: U256, b: U256, denominator: U256) -> Result<U256, ArithmeticError> {
+    mul_div_rem(a, b, denominator).map(|(quotient, _)| quotient)
+}
+
+/// `FullMath.mulDivRoundingUp`: ⌈a × b / denominator⌉.
+///
+/// Returns error if the denominator is zero or the result overflows 256
+/// bits.
+pub fn mul_div_rounding_up(a: U256, b: U256, denominator: U256) -> Result<U256, ArithmeticError> {
+    let (quotient, remainder) = mul_div_rem(a, b, denominator)?;
+    if remainder.is_zero() {
+        Ok(quotient)
+    } else {
+        quotient
+            .checked_add(U256::ONE)
+            .ok_or(ArithmeticError::Overflow)
+    }
+}
+
+/// `TickMath.getSqrtRatioAtTick`: sqrt(1.0001^tick) × 2^96, rounded up.
+///
+/// Returns error if the tick is outside [`MIN_TICK`, `MAX_TICK`].
+pub fn get_sqrt_ratio_at_tick(tick: i32) -> Result<U256, ArithmeticError> {
+    // 2^128 / sqrt(1.0001)^(2^i) for i = 0..19
+    const RATIOS: [u128; 20] = [
+        0xfffc_b933_bd6f_ad37_aa2d_162d_1a59_4001,
+        0xfff9_7272_373d_4132_59a4_6990_580e_213a,
+        0xfff2_e50f_5f65_6932_ef12_357c_f3c7_fdcc,
+        0xffe5_caca_7e10_e4e6_1c36_24ea_a094_1cd0,
+        0xffcb_9843_d60f_6159_c9db_5883_5c92_6644,
+        0xff97_3b41_fa98_c081_472e_6896_dfb2_54c0,
+        0xff2e_a164_66c9_6a38_43ec_78b3_26b5_2861,
+        0xfe5d_ee04_6a99_a2a8_11c4_61f1_969c_3053,
+        0xfcbe_86c7_900a_88ae_dcff_c83b_479a_a3a4,
+        0xf987_a725_3ac4_1317_6f2b_074c_f781_5e54,
+        0xf339_2b08_22b7_0005_940c_7a39_8e4b_70f3,
+        0xe715_9475_a2c2_9b74_43b2_9c7f_a6e8_89d9,
+        0xd097_f3bd_fd20_22b8_845a_d8f7_92aa_5825,
+        0xa9f7_4646_2d87_0fdf_8a65_dc1f_90e0_61e5,
+        0x70d8_69a1_56d2_a1b8_90bb_3df6_2baf_32f7,
+        0x31be_135f_97d0_8fd9_8123_1505_542f_cfa6,
+        0x09aa_508b_5b7a_84e1_c677_de54_f3e9_9bc9,
+        0x005d_6af8_dedb_8119_6699_c329_225e_e604,
+        0x0000_2216_e584_f5fa_1ea9_2604_1bed_fe98,
+        0x0000_0000_048a_1703_91f7_dc42_444e_8fa2,
+    ];
+
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return Err(ArithmeticError::Overflow);
+    }
+    let abs_tick = tick.unsigned_abs();
+    let mut ratio = if abs_tick & 1 != 0 {
+        U256::from(RATIOS[0])
+    } else {
+        U256::from_words(1, 0)
+    };
+    for (i, &factor) in RATIOS.iter().enumerate().skip(1) {
+        if abs_tick & (1 << i) != 0 {
+            // Both factors are below 2^129, so the product fits
+            ratio = low_half(full_mul(ratio, U256::from(factor))) >> 128;
+        }
+    }
+    if tick > 0 {
+        ratio = U256::MAX
+            .checked_div(ratio)
+            .ok_or(ArithmeticError::DivisionByZero)?;
+    }
+
+    // Q128.128 to Q64.96, rounding up
+    let round = u64::from(ratio.0[0] & 0xffff_ffff != 0);
+    (ratio >> 32)
+        .checked_add(U256::from(round))
+        .ok_or(ArithmeticError::Overflow)
+}
+
+/// `TickMath.getTickAtSqrtRatio`: the greatest tick whose sqrt ratio is at
+/// or below `sqrt_price_x96`.
+///
+/// Returns error unless `MIN_SQRT_RATIO <= sqrt_price_x96 < MAX_SQRT_RATIO`.
+pub fn get_tick_at_sqrt_ratio(sqrt_price_x96: U256) -> Result<i32, ArithmeticError> {
+    // log_sqrt(1.0001)(2) as Q128.128, and the error bounds of the
+    // approximation below
+    const LOG_SQRT_10001: u128 = 255_738_958_999_603_826_347_141;
+    const TICK_LOW_OFFSET: u128 = 3_402_992_956_809_132_418_596_140_100_660_247_210;
+    const TICK_HIGH_OFFSET: u128 = 291_339_464_771_989_622_907_027_621_153_398_088_495;
+
+    if sqrt_price_x96 < MIN_SQRT_RATIO || sqrt_price_x96 >= MAX_SQRT_RATIO {
+        return Err(ArithmeticError::Overflow);
+    }
+    let ratio = sqrt_price_x96 << 32;
+    let msb = ratio.bits() - 1;
+    let mut r = if msb >= 128 {
+        ratio >> (msb - 127)
+    } else {
+        ratio << (127 - msb)
+    };
+
+    // Integer part of log2 from the msb, then 14 fractional bits by
+    // repeated squaring
+    let mut log_2 = (i128::from(msb) - 128) << 64;
+    for bit in (50..64).rev() {
+        r = low_half(full_mul(r, r)) >> 127;
+        let f = (r >> 128).0[0] as u32;
+        log_2 |= i128::from(f) << bit;
+        r = r >> f;
+    }
+
+    // Signed Q128.128 log, held as sign and magnitude
+    let negative = log_2 < 0;
+    let log_sqrt = low_half(full_mul(
+        U256::from(log_2.unsigned_abs()),
+        U256::from(LOG_SQRT_10001),
+    ));
+    let tick_low = floor_shr_128(signed_add(
+        negative,
+        log_sqrt,
+        true,
+        U256::from(TICK_LOW_OFFSET),
+    ));
+    let tick_high = floor_shr_128(signed_add(
+        negative,
+        log_sqrt,
+        false,
+        U256::from(TICK_HIGH_OFFSET),
+    ));
+
+    if tick_low == tick_high || get_sqrt_ratio_at_tick(tick_high)? > sqrt_price_x96 {
+        Ok(tick_low)
+    } else {
+        Ok(tick_high)
+    }
+}
+
+/// `SqrtPriceMath.getAmount0Delta`: token0 between two sqrt prices,
+/// L × 2^96 × (√b − √a) / (√b × √a).
+///
+/// Round up for amounts paid into the pool and down for amounts paid out.
+/// Returns error if either price is zero.
+pub fn get_amount_0_delta(
+    sqrt_ratio_a: U256,
+    sqrt_ratio_b: U256,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<U256, ArithmeticError> {
+    let (lower, upper) = ordered(sqrt_ratio_a, sqrt_ratio_b);
+    if lower.is_zero() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let numerator_1 = U256::from(liquidity) << 96;
+    let numerator_2 = upper.checked_sub(lower).ok_or(ArithmeticError::Underflow)?;
+    if round_up {
+        let scaled = mul_div_rounding_up(numerator_1, numerator_2, upper)?;
+        let (quotient, remainder) = mul_div_rem(scaled, U256::ONE, lower)?;
+        quotient
+            .checked_add(U256::from(u64::from(!remainder.is_zero())))
+            .ok_or(ArithmeticError::Overflow)
+    } else {
+        mul_div(numerator_1, numerator_2, upper)?
+            .checked_div(lower)
+            .ok_or(ArithmeticError::DivisionByZero)
+    }
+}
+
+/// `SqrtPriceMath.getAmount1Delta`: token1 between two sqrt prices,
+/// L × (√b − √a) / 2^96.
+///
+/// Round up for amounts paid into the pool and down for amounts paid out.
+pub fn get_amount_1_delta(
+    sqrt_ratio_a: U256,
+    sqrt_ratio_b: U256,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<U256, ArithmeticError> {
+    let (lower, upper) = ordered(sqrt_ratio_a, sqrt_ratio_b);
+    let difference = upper.checked_sub(lower).ok_or(ArithmeticError::Underflow)?;
+    if round_up {
+        mul_div_rounding_up(U256::from(liquidity), difference, Q96)
+    } else {
+        mul_div(U256::from(liquidity), difference, Q96)
+    }
+}
+
+fn ordered(a: U256, b: U256) -> (U256, U256) {
+    if a > b {
+        (b, a)
+    } else {
+        (a, b)
+    }
+}
+
+/// Full 512-bit product as little-endian limbs.
+fn full_mul(a: U256, b: U256) -> [u64; 8] {
+    let mut result = [0u64; 8];
+    for i in 0..4 {
+        let mut carry = 0u128;
+        for j in 0..4 {
+            let current =
+                u128::from(a.0[i]) * u128::from(b.0[j]) + u128::from(result[i + j]) + carry;
+            result[i + j] = current as u64;
+            carry = current >> 64;
+        }
+        result[i + 4] = carry as u64;
+    }
+    result
+}
+
+fn low_half(wide: [u64; 8]) -> U256 {
+    U256([wide[0], wide[1], wide[2], wide[3]])
+}
+
+/// Long division of a 512-bit numerator, one bit at a time.
+fn div_rem_wide(numerator: [u64; 8], divisor: U256) -> ([u64; 8], U256) {
+    let mut quotient = [0u64; 8];
+    let mut remainder = U256::ZERO;
+    for bit in (0..512).rev() {
+        let carry = remainder.0[3] >> 63 != 0;
+        remainder = remainder << 1;
+        remainder.0[0] |= (numerator[bit / 64] >> (bit % 64)) & 1;
+        // With the carry the true remainder is below 2 × divisor, so one
+        // wrapping subtraction brings it back in range
+        if carry || remainder >= divisor {
+            remainder = remainder.overflowing_sub(divisor).0;
+            quotient[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+    (quotient, remainder)
+}
+
+fn mul_div_rem(a: U256, b: U256, denominator: U256) -> Result<(U256, U256), ArithmeticError> {
+    if denominator.is_zero() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let (quotient, remainder) = div_rem_wide(full_mul(a, b), denominator);
+    if quotient[4..].iter().any(|&limb| limb != 0) {
+        return Err(ArithmeticError::Overflow);
+    }
+    Ok((low_half(quotient), remainder))
+}
+
+/// Adds a signed offset to a sign-magnitude value.
+fn signed_add(negative: bool, magnitude: U256, subtract: bool, offset: U256) -> (bool, U256) {
+    if negative == subtract {
+        // Same sign: magnitudes add
+        (negative, magnitude.overflowing_add(offset).0)
+    } else if magnitude >= offset {
+        (negative, magnitude.overflowing_sub(offset).0)
+    } else {
+        (!negative, offset.overflowing_sub(magnitude).0)
+    }
+}
+
+/// Arithmetic shift right by 128 (floor division), as `int24(x >> 128)`.
+fn floor_shr_128((negative, magnitude): (bool, U256)) -> i32 {
+    if negative {
+        let rounded = magnitude.overflowing_add(U256::from(u128::MAX)).0 >> 128;
+        -(rounded.low_u128() as i32)
+    } else {
+        (magnitude >> 128).low_u128() as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u256(s: &str) -> U256 {
+        s.parse().unwrap()
+    }
+
+    /// Formats into a stack buffer, since tests run without alloc.
+    fn display(value: U256, buffer: &mut [u8; 80]) -> &str {
+        struct Writer<'a>(&'a mut [u8], usize);
+        impl fmt::Write for Writer<'_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                let end = self.1 + s.len();
+                self.0
+                    .get_mut(self.1..end)
+                    .ok_or(fmt::Error)?
+                    .copy_from_slice(s.as_bytes());
+                self.1 = end;
+                Ok(())
+            }
+        }
+        let mut writer = Writer(buffer, 0);
+        fmt::write(&mut writer, format_args!("{value}")).unwrap();
+        let len = writer.1;
+        core::str::from_utf8(&buffer[..len]).unwrap()
+    }
+
+    // Reference values from Uniswap V3's TickMath, SqrtPriceMath and
+    // FullMath
+
+    #[test]
+    fn test_sqrt_ratio_at_tick() {
+        let known = [
+            (MIN_TICK, "4295128739"),
+            (-500000, "1101692437043807371"),
+            (-50000, "6504256538020985011912221507"),
+            (-50, "79030349367926598376800521322"),
+            (-1, "79224201403219477170569942574"),
+            (0, "79228162514264337593543950336"),
+            (1, "79232123823359799118286999568"),
+            (50, "79426470787362580746886972461"),
+            (1000, "83290069058676223003182343270"),
+            (150000, "143194173941309278083010301478497"),
+            (500000, "5697689776495288729098254600827762987878"),
+            (738203, "847134979253254120489401328389043031315994541"),
+            (
+                MAX_TICK,
+                "1461446703485210103287273052203988822378723970342",
+            ),
+        ];
+        for (tick, expected) in known {
+            assert_eq!(
+                get_sqrt_ratio_at_tick(tick).unwrap(),
+                u256(expected),
+                "tick {tick}"
+            );
+        }
+        assert_eq!(get_sqrt_ratio_at_tick(MIN_TICK).unwrap(), MIN_SQRT_RATIO);
+        assert_eq!(get_sqrt_ratio_at_tick(MAX_TICK).unwrap(), MAX_SQRT_RATIO);
+        assert!(get_sqrt_ratio_at_tick(MAX_TICK + 1).is_err());
+        assert!(get_sqrt_ratio_at_tick(MIN_TICK - 1).is_err());
+    }
+
+    #[test]
+    fn test_tick_at_sqrt_ratio() {
+        let known = [
+            ("4295128739", MIN_TICK),
+            ("4295128740", MIN_TICK),
+            ("79030349367926598376800521321", -51),
+            ("79228162514264337593543950335", -1),
+            ("79228162514264337593543950336", 0),
+            ("79228162514264337593543950337", 0),
+            ("79426470787362580746886972461", 50),
+            ("1329227995784915872903807060280344576", 332727),
+            ("10000000000000000000000000000000000000000", 511251),
+            ("1461446703485210103287273052203988822378723970341", 887271),
+        ];
+        for (sqrt_price, tick) in known {
+            assert_eq!(
+                get_tick_at_sqrt_ratio(u256(sqrt_price)).unwrap(),
+                tick,
+                "{sqrt_price}"
+            );
+        }
+        for tick in [-887000, -123457, -60, -7, 3, 60, 99999, 887000] {
+            let sqrt_price = get_sqrt_ratio_at_tick(tick).unwrap();
+            assert_eq!(get_tick_at_sqrt_ratio(sqrt_price).unwrap(), tick);
+            let below = sqrt_price.checked_sub(U256::ONE).unwrap();
+            assert_eq!(get_tick_at_sqrt_ratio(below).unwrap(), tick - 1);
+        }
+        assert!(get_tick_at_sqrt_ratio(MAX_SQRT_RATIO).is_err());
+        assert!(get_tick_at_sqrt_ratio(u256("4295128738")).is_err());
+    }
+
+    #[test]
+    fn test_amount_deltas_round_as_flagged() {
+        let lower = get_sqrt_ratio_at_tick(-600).unwrap();
+        let upper = get_sqrt_ratio_at_tick(600).unwrap();
+        let liquidity = 1_000_000_000_000_000_000;
+        for (round_up, expected) in [(true, "60005999255049927"), (false, "60005999255049926")] {
+            let expected = u256(expected);
+            assert_eq!(
+                get_amount_0_delta(lower, upper, liquidity, round_up).unwrap(),
+                expected
+            );
+            assert_eq!(
+                get_amount_1_delta(upper, lower, liquidity, round_up).unwrap(),
+                expected
+            );
+        }
+
+        let max = u128::MAX;
+        assert_eq!(
+            get_amount_0_delta(MIN_SQRT_RATIO, MAX_SQRT_RATIO, max, true).unwrap(),
+            u256("6276865795046577716716727052920969657919881535178523893768")
+        );
+        assert_eq!(
+            get_amount_1_delta(MIN_SQRT_RATIO, MAX_SQRT_RATIO, max, false).unwrap(),
+            u256("6276865796315986613307619852238232712829278890652951511957")
+        );
+        assert!(get_amount_0_delta(U256::ZERO, Q96, 1, true).is_err());
+    }
+
+    #[test]
+    fn test_mul_div() {
+        let half = U256::MAX >> 1;
+        let three = U256::from(3u64);
+        let seven = U256::from(7u64);
+        assert_eq!(mul_div(U256::MAX, U256::MAX, U256::MAX).unwrap(), U256::MAX);
+        // Exact division: rounding up changes nothing
+        let exact =
+            u256("24812590550853470447908068216147408825700710999780120865598053715981384922843");
+        assert_eq!(mul_div(half, three, seven).unwrap(), exact);
+        assert_eq!(mul_div_rounding_up(half, three, seven).unwrap(), exact);
+
+        let a = U256::ONE << 200;
+        let b = U256::ONE << 100;
+        let d = U256::from((1u64 << 60) + 1);
+        let floor =
+            u256("1766847064778384328050801959877029658798364865510225646253752634782515200");
+        assert_eq!(mul_div(a, b, d).unwrap(), floor);
+        assert_eq!(
+            mul_div_rounding_up(a, b, d).unwrap(),
+            floor.checked_add(U256::ONE).unwrap()
+        );
+
+        assert_eq!(
+            mul_div(U256::MAX, U256::MAX, U256::MAX >> 1),
+            Err(ArithmeticError::Overflow)
+        );
+        assert_eq!(
+            mul_div(U256::ONE, U256::ONE, U256::ZERO),
+            Err(ArithmeticError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_u256_parse_display_and_decimal() {
+        let text = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(u256(text), U256::MAX);
+        let mut buffer = [0u8; 80];
+        assert_eq!(display(U256::MAX, &mut buffer), text);
+        assert_eq!(display(U256::ZERO, &mut buffer), "0");
+        assert_eq!("".parse::<U256>(), Err(ParseError::Empty));
+        assert_eq!("12a".parse::<U256>(), Err(ParseError::InvalidCharacter));
+        assert_eq!(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+                .parse::<U256>(),
+            Err(ParseError::OutOfRange)
+        );
+
+        assert_eq!(q96_to_decimal(Q96).unwrap(), Decimal::ONE);
+        let sqrt_price = q96_to_decimal(get_sqrt_ratio_at_tick(600).unwrap()).unwrap();
+        let decimal = super::super::tick_to_sqrt_price(600).unwrap();
+        assert!((sqrt_price - decimal).abs() < Decimal::new(1, 20));
+
+        assert_eq!(
+            U256::from(1_000_000u64).to_decimal().unwrap(),
+            Decimal::from(1_000_000i64)
+        );
+        assert!(MAX_SQRT_RATIO.to_decimal().is_err());
+    }
+}
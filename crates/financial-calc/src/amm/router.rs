@@ -0,0 +1,820 @@
+//! Swap routing across heterogeneous pools.
+//!
+//! Pools are described by [`RoutePool`], which borrows the pool models of
+//! this module and names their tokens with caller-chosen `u32` ids. The
+//! router quotes without mutating any pool.
+//!
+//! - [`find_best_route`] searches every path of up to [`MAX_ROUTE_HOPS`]
+//!   pools and returns the one with the largest output.
+//! - [`optimize_split`] spreads an order over up to [`MAX_ROUTE_SPLITS`]
+//!   routes that share no pool, sizing each leg so the marginal rates of
+//!   all used routes are equal.
+//!
+//! Price impact is measured against the marginal rate at zero size with
+//! fees included, so it excludes fees like
+//! [`calculate_price_impact`](super::calculate_price_impact).
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::amm::{find_best_route, optimize_split, RoutePool};
+//! use precision_core::Decimal;
+//!
+//! const USDC: u32 = 0;
+//! const WETH: u32 = 1;
+//!
+//! let pools = [
+//!     RoutePool::constant_product(
+//!         [USDC, WETH],
+//!         [Decimal::from(2_000_000i64), Decimal::from(1_000i64)],
+//!         Decimal::from(30i64),
+//!     ),
+//!     RoutePool::constant_product(
+//!         [USDC, WETH],
+//!         [Decimal::from(6_000_000i64), Decimal::from(3_000i64)],
+//!         Decimal::from(5i64),
+//!     ),
+//! ];
+//!
+//! let amount = Decimal::from(100_000i64);
+//! let best = find_best_route(&pools, USDC, WETH, amount).unwrap();
+//! let split = optimize_split(&pools, USDC, WETH, amount).unwrap();
+//! assert_eq!(split.legs().len(), 2);
+//! assert!(split.amount_out() > best.amount_out);
+//! ```
+
+use precision_core::{ArithmeticError, Decimal};
+
+use super::{
+    calculate_swap_output, ConcentratedPool, StableSwapPool, WeightedPool, MAX_INITIALIZED_TICKS,
+};
+use crate::storage::{Fixed, Storage};
+
+/// Maximum number of pools in a route.
+pub const MAX_ROUTE_HOPS: usize = 3;
+
+/// Maximum number of routes an order is split across.
+pub const MAX_ROUTE_SPLITS: usize = 4;
+
+/// Fee denominator of concentrated pools (pips).
+const PIPS: i64 = 1_000_000;
+
+/// Iteration cap for the split bisections.
+const MAX_ITERATIONS: u32 = 64;
+
+/// Relative tolerance of leg sizes.
+fn tolerance() -> Decimal {
+    Decimal::new(1, 12)
+}
+
+/// Finite-difference step for marginal rates, relative to the order size.
+fn marginal_step() -> Decimal {
+    Decimal::new(1, 8)
+}
+
+/// A pool the router can trade through.
+#[derive(Debug, Clone, Copy)]
+pub enum RoutePool<'a, S: Storage = Fixed<MAX_INITIALIZED_TICKS>> {
+    /// Constant-product pool priced with
+    /// [`calculate_swap_output`](super::calculate_swap_output).
+    ConstantProduct {
+        /// Token ids.
+        tokens: [u32; 2],
+        /// Reserves, in token order.
+        reserves: [Decimal; 2],
+        /// Fee in basis points.
+        fee_bps: Decimal,
+    },
+    /// Concentrated liquidity pool.
+    Concentrated {
+        /// Ids of token0 and token1.
+        tokens: [u32; 2],
+        /// Pool state.
+        pool: &'a ConcentratedPool<S>,
+    },
+    /// StableSwap pool.
+    StableSwap {
+        /// Token id of each coin.
+        tokens: &'a [u32],
+        /// Pool state.
+        pool: &'a StableSwapPool,
+    },
+    /// Weighted pool.
+    Weighted {
+        /// Token id of each pool token.
+        tokens: &'a [u32],
+        /// Pool state.
+        pool: &'a WeightedPool,
+    },
+}
+
+impl<'a> RoutePool<'a> {
+    /// Creates a constant-product pool.
+    pub fn constant_product(tokens: [u32; 2], reserves: [Decimal; 2], fee_bps: Decimal) -> Self {
+        Self::ConstantProduct {
+            tokens,
+            reserves,
+            fee_bps,
+        }
+    }
+}
+
+impl<'a, S: Storage + Clone> RoutePool<'a, S> {
+    /// Token ids of the pool.
+    pub fn tokens(&self) -> &[u32] {
+        match self {
+            Self::ConstantProduct { tokens, .. } | Self::Concentrated { tokens, .. } => tokens,
+            Self::StableSwap { tokens, .. } | Self::Weighted { tokens, .. } => tokens,
+        }
+    }
+
+    /// Output for swapping `amount_in` of one token for another, after fees.
+    ///
+    /// Returns error if the pool does not hold both tokens, and `Overflow`
+    /// if a concentrated pool runs out of liquidity before filling the
+    /// input.
+    pub fn quote(
+        &self,
+        token_in: u32,
+        token_out: u32,
+        amount_in: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        let (i, j) = self.indices(token_in, token_out)?;
+        match self {
+            Self::ConstantProduct {
+                reserves, fee_bps, ..
+            } => calculate_swap_output(reserves[i], reserves[j], amount_in, *fee_bps),
+            Self::Concentrated { pool, .. } => {
+                let mut pool = (*pool).clone();
+                let result = pool.swap_exact_input(i == 0, amount_in, None)?;
+                if result.amount_in < amount_in {
+                    return Err(ArithmeticError::Overflow);
+                }
+                Ok(result.amount_out)
+            }
+            Self::StableSwap { pool, .. } => pool.get_dy(i, j, amount_in),
+            Self::Weighted { pool, .. } => pool.calc_out_given_in(i, j, amount_in),
+        }
+    }
+
+    /// Marginal output per unit of input at zero size, after fees.
+    pub fn spot_rate(&self, token_in: u32, token_out: u32) -> Result<Decimal, ArithmeticError> {
+        let (i, j) = self.indices(token_in, token_out)?;
+        match self {
+            Self::ConstantProduct {
+                reserves, fee_bps, ..
+            } => {
+                let bps_base = Decimal::from(10000i64);
+                let fee_factor = bps_base.try_sub(*fee_bps)?.try_div(bps_base)?;
+                reserves[j].try_div(reserves[i])?.try_mul(fee_factor)
+            }
+            Self::Concentrated { pool, .. } => {
+                if pool.liquidity().is_zero() {
+                    return Ok(Decimal::ZERO);
+                }
+                let price = pool.sqrt_price().try_mul(pool.sqrt_price())?;
+                let rate = if i == 0 {
+                    price
+                } else {
+                    Decimal::ONE.try_div(price)?
+                };
+                let fee = Decimal::from(i64::from(pool.fee())).try_div(Decimal::from(PIPS))?;
+                rate.try_mul(Decimal::ONE.try_sub(fee)?)
+            }
+            Self::StableSwap { pool, .. } => {
+                let dx = pool.balances()[i].try_mul(Decimal::new(1, 9))?;
+                pool.get_dy(i, j, dx)?.try_div(dx)
+            }
+            Self::Weighted { pool, .. } => Decimal::ONE.try_div(pool.spot_price(i, j)?),
+        }
+    }
+
+    fn indices(&self, token_in: u32, token_out: u32) -> Result<(usize, usize), ArithmeticError> {
+        let tokens = self.tokens();
+        let expected = match self {
+            Self::ConstantProduct { .. } | Self::Concentrated { .. } => 2,
+            Self::StableSwap { pool, .. } => pool.balances().len(),
+            Self::Weighted { pool, .. } => pool.balances().len(),
+        };
+        let i = tokens.iter().position(|&t| t == token_in);
+        let j = tokens.iter().position(|&t| t == token_out);
+        match (i, j) {
+            (Some(i), Some(j)) if i != j && tokens.len() == expected => Ok((i, j)),
+            _ => Err(ArithmeticError::DivisionByZero),
+        }
+    }
+}
+
+/// One swap of a route.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Hop {
+    /// Index of the pool in the router's pool slice.
+    pub pool: usize,
+    /// Token sold.
+    pub token_in: u32,
+    /// Token bought.
+    pub token_out: u32,
+}
+
+/// A path of up to [`MAX_ROUTE_HOPS`] swaps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Route {
+    hops: [Hop; MAX_ROUTE_HOPS],
+    len: usize,
+}
+
+impl Route {
+    /// Creates a route from its hops.
+    ///
+    /// Returns error unless there are 1 to [`MAX_ROUTE_HOPS`] hops, each
+    /// hop sells the token the previous one bought and no pool is used
+    /// twice.
+    pub fn new(hops: &[Hop]) -> Result<Self, ArithmeticError> {
+        if hops.is_empty() || hops.len() > MAX_ROUTE_HOPS {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        for (k, hop) in hops.iter().enumerate() {
+            let chained = k == 0 || hops[k - 1].token_out == hop.token_in;
+            if !chained || hops[..k].iter().any(|h| h.pool == hop.pool) {
+                return Err(ArithmeticError::DivisionByZero);
+            }
+        }
+        let mut route = Self::default();
+        route.hops[..hops.len()].copy_from_slice(hops);
+        route.len = hops.len();
+        Ok(route)
+    }
+
+    /// Swaps in order.
+    pub fn hops(&self) -> &[Hop] {
+        &self.hops[..self.len]
+    }
+
+    fn uses_pool(&self, pool: usize) -> bool {
+        self.hops().iter().any(|hop| hop.pool == pool)
+    }
+
+    fn push(&mut self, hop: Hop) {
+        self.hops[self.len] = hop;
+        self.len += 1;
+    }
+}
+
+/// Quote for trading through one route.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RouteQuote {
+    /// Route taken.
+    pub route: Route,
+    /// Input amount.
+    pub amount_in: Decimal,
+    /// Expected output after fees.
+    pub amount_out: Decimal,
+    /// Shortfall against the zero-size marginal rate (0.01 = 1%).
+    pub price_impact: Decimal,
+}
+
+/// Quote for an order split across routes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitQuote {
+    legs: [RouteQuote; MAX_ROUTE_SPLITS],
+    len: usize,
+    amount_in: Decimal,
+    amount_out: Decimal,
+    price_impact: Decimal,
+}
+
+impl SplitQuote {
+    /// Legs with a non-zero allocation.
+    pub fn legs(&self) -> &[RouteQuote] {
+        &self.legs[..self.len]
+    }
+
+    /// Total input.
+    pub fn amount_in(&self) -> Decimal {
+        self.amount_in
+    }
+
+    /// Total expected output after fees.
+    pub fn amount_out(&self) -> Decimal {
+        self.amount_out
+    }
+
+    /// Shortfall against the best zero-size marginal rate of the legs.
+    pub fn price_impact(&self) -> Decimal {
+        self.price_impact
+    }
+}
+
+/// Quotes `amount_in` through a route.
+///
+/// Returns error if a hop does not match its pool.
+pub fn quote_route<S: Storage + Clone>(
+    pools: &[RoutePool<'_, S>],
+    route: &Route,
+    amount_in: Decimal,
+) -> Result<RouteQuote, ArithmeticError> {
+    let amount_out = route_output(pools, route, amount_in)?;
+    let spot = route_spot_rate(pools, route)?;
+    Ok(RouteQuote {
+        route: *route,
+        amount_in,
+        amount_out,
+        price_impact: price_impact(amount_in, amount_out, spot)?,
+    })
+}
+
+/// Finds the route of up to [`MAX_ROUTE_HOPS`] pools with the largest
+/// output for `amount_in`.
+///
+/// Paths whose pools cannot absorb the input are skipped. Returns error if
+/// no path connects the tokens.
+pub fn find_best_route<S: Storage + Clone>(
+    pools: &[RoutePool<'_, S>],
+    token_in: u32,
+    token_out: u32,
+    amount_in: Decimal,
+) -> Result<RouteQuote, ArithmeticError> {
+    if !amount_in.is_positive() || token_in == token_out {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let mut best: Option<(Route, Decimal)> = None;
+    search(
+        pools,
+        token_in,
+        token_out,
+        &mut Route::default(),
+        &mut |route| {
+            if let Ok(out) = route_output(pools, route, amount_in) {
+                if best.map_or(true, |(_, best_out)| out > best_out) {
+                    best = Some((*route, out));
+                }
+            }
+        },
+    );
+    let (route, _) = best.ok_or(ArithmeticError::DivisionByZero)?;
+    quote_route(pools, &route, amount_in)
+}
+
+/// Splits `amount_in` across routes to maximise total output.
+///
+/// Up to [`MAX_ROUTE_SPLITS`] routes sharing no pool are taken in order of
+/// marginal rate, so their outputs are independent. Leg sizes are then
+/// solved so every used route ends at the same marginal rate; routes whose
+/// zero-size rate is below it get nothing. The best single route is
+/// returned instead if it does better.
+///
+/// Returns error if no path connects the tokens, and `Overflow` if the
+/// routes cannot absorb the order.
+pub fn optimize_split<S: Storage + Clone>(
+    pools: &[RoutePool<'_, S>],
+    token_in: u32,
+    token_out: u32,
+    amount_in: Decimal,
+) -> Result<SplitQuote, ArithmeticError> {
+    let best = find_best_route(pools, token_in, token_out, amount_in);
+
+    let mut routes = [Route::default(); MAX_ROUTE_SPLITS];
+    let mut rates = [Decimal::ZERO; MAX_ROUTE_SPLITS];
+    let mut count = 0;
+    while count < MAX_ROUTE_SPLITS {
+        let selected = &routes[..count];
+        let mut candidate: Option<(Route, Decimal)> = None;
+        search(
+            pools,
+            token_in,
+            token_out,
+            &mut Route::default(),
+            &mut |route| {
+                if selected
+                    .iter()
+                    .any(|r| route.hops().iter().any(|hop| r.uses_pool(hop.pool)))
+                {
+                    return;
+                }
+                if let Ok(rate) = route_spot_rate(pools, route) {
+                    if rate.is_positive() && candidate.map_or(true, |(_, best)| rate > best) {
+                        candidate = Some((*route, rate));
+                    }
+                }
+            },
+        );
+        match candidate {
+            Some((route, rate)) => {
+                routes[count] = route;
+                rates[count] = rate;
+                count += 1;
+            }
+            None => break,
+        }
+    }
+
+    let split = if count > 1 {
+        split_across(pools, &routes[..count], &rates[..count], amount_in)
+    } else {
+        Err(ArithmeticError::DivisionByZero)
+    };
+    match (split, best) {
+        (Ok(split), Ok(best)) if split.amount_out >= best.amount_out => Ok(split),
+        (Ok(split), Err(_)) => Ok(split),
+        (_, Ok(best)) => {
+            let mut legs = [RouteQuote::default(); MAX_ROUTE_SPLITS];
+            legs[0] = best;
+            Ok(SplitQuote {
+                legs,
+                len: 1,
+                amount_in,
+                amount_out: best.amount_out,
+                price_impact: best.price_impact,
+            })
+        }
+        (Err(e), Err(_)) => Err(e),
+    }
+}
+
+/// Equalises marginal rates by bisecting on the common rate λ: each
+/// route's leg is the size at which its marginal rate falls to λ, and λ
+/// is lowered until the legs add up to the order.
+fn split_across<S: Storage + Clone>(
+    pools: &[RoutePool<'_, S>],
+    routes: &[Route],
+    rates: &[Decimal],
+    amount_in: Decimal,
+) -> Result<SplitQuote, ArithmeticError> {
+    let step = amount_in.try_mul(marginal_step())?;
+    let tolerance = amount_in.try_mul(tolerance())?;
+    let mut sizes = [Decimal::ZERO; MAX_ROUTE_SPLITS];
+
+    let mut low = Decimal::ZERO;
+    let mut high = rates.iter().fold(Decimal::ZERO, |acc, &r| acc.max(r));
+    for _ in 0..MAX_ITERATIONS {
+        let lambda = low.try_add(high)?.try_div(Decimal::from(2i64))?;
+        let mut total = Decimal::ZERO;
+        for (size, route) in sizes.iter_mut().zip(routes) {
+            *size = leg_size(pools, route, lambda, amount_in, step, tolerance)?;
+            total = total.try_add(*size)?;
+        }
+        if total > amount_in {
+            low = lambda;
+        } else {
+            high = lambda;
+        }
+        if total.try_sub(amount_in)?.abs() <= tolerance || high.try_sub(low)?.is_zero() {
+            break;
+        }
+    }
+
+    // Leave no input unallocated: the rounding residual goes to the
+    // largest leg.
+    let sizes = &mut sizes[..routes.len()];
+    let total = sizes
+        .iter()
+        .try_fold(Decimal::ZERO, |acc, &size| acc.try_add(size))?;
+    let mut largest = 0;
+    for (k, size) in sizes.iter().enumerate() {
+        if *size > sizes[largest] {
+            largest = k;
+        }
+    }
+    sizes[largest] = sizes[largest].try_add(amount_in.try_sub(total)?)?;
+
+    let mut legs = [RouteQuote::default(); MAX_ROUTE_SPLITS];
+    let mut len = 0;
+    let mut amount_out = Decimal::ZERO;
+    let mut best_rate = Decimal::ZERO;
+    for (k, &size) in sizes.iter().enumerate() {
+        if !size.is_positive() {
+            continue;
+        }
+        legs[len] = quote_route(pools, &routes[k], size)?;
+        amount_out = amount_out.try_add(legs[len].amount_out)?;
+        best_rate = best_rate.max(rates[k]);
+        len += 1;
+    }
+    Ok(SplitQuote {
+        legs,
+        len,
+        amount_in,
+        amount_out,
+        price_impact: price_impact(amount_in, amount_out, best_rate)?,
+    })
+}
+
+/// Largest input in [0, `max`] at which the route's marginal rate is
+/// still above `lambda`.
+fn leg_size<S: Storage + Clone>(
+    pools: &[RoutePool<'_, S>],
+    route: &Route,
+    lambda: Decimal,
+    max: Decimal,
+    step: Decimal,
+    tolerance: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let above = |size: Decimal| -> Result<bool, ArithmeticError> {
+        Ok(marginal_rate(pools, route, size, step)? > lambda)
+    };
+    if !above(Decimal::ZERO)? {
+        return Ok(Decimal::ZERO);
+    }
+    if above(max)? {
+        return Ok(max);
+    }
+    let mut low = Decimal::ZERO;
+    let mut high = max;
+    for _ in 0..MAX_ITERATIONS {
+        if high.try_sub(low)? <= tolerance {
+            break;
+        }
+        let mid = low.try_add(high)?.try_div(Decimal::from(2i64))?;
+        if above(mid)? {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
+
+/// Forward-difference marginal rate at `size`. Sizes the route cannot
+/// absorb have a marginal rate of zero.
+fn marginal_rate<S: Storage + Clone>(
+    pools: &[RoutePool<'_, S>],
+    route: &Route,
+    size: Decimal,
+    step: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let base = if size.is_zero() {
+        Decimal::ZERO
+    } else {
+        match route_output(pools, route, size) {
+            Ok(out) => out,
+            Err(_) => return Ok(Decimal::ZERO),
+        }
+    };
+    match route_output(pools, route, size.try_add(step)?) {
+        Ok(out) => out.try_sub(base)?.try_div(step),
+        Err(_) => Ok(Decimal::ZERO),
+    }
+}
+
+fn route_output<S: Storage + Clone>(
+    pools: &[RoutePool<'_, S>],
+    route: &Route,
+    amount_in: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    route.hops().iter().try_fold(amount_in, |amount, hop| {
+        pools
+            .get(hop.pool)
+            .ok_or(ArithmeticError::DivisionByZero)?
+            .quote(hop.token_in, hop.token_out, amount)
+    })
+}
+
+fn route_spot_rate<S: Storage + Clone>(
+    pools: &[RoutePool<'_, S>],
+    route: &Route,
+) -> Result<Decimal, ArithmeticError> {
+    route.hops().iter().try_fold(Decimal::ONE, |rate, hop| {
+        let pool = pools.get(hop.pool).ok_or(ArithmeticError::DivisionByZero)?;
+        rate.try_mul(pool.spot_rate(hop.token_in, hop.token_out)?)
+    })
+}
+
+/// 1 − (amount_out / amount_in) / spot_rate, floored at zero.
+fn price_impact(
+    amount_in: Decimal,
+    amount_out: Decimal,
+    spot_rate: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let effective = amount_out.try_div(amount_in)?;
+    let impact = Decimal::ONE.try_sub(effective.try_div(spot_rate)?)?;
+    Ok(impact.max(Decimal::ZERO))
+}
+
+/// Calls `visit` with every simple path from `token` to `target` that
+/// extends `route` by at most [`MAX_ROUTE_HOPS`] hops in total.
+fn search<S: Storage + Clone, F: FnMut(&Route)>(
+    pools: &[RoutePool<'_, S>],
+    token: u32,
+    target: u32,
+    route: &mut Route,
+    visit: &mut F,
+) {
+    if route.len == MAX_ROUTE_HOPS {
+        return;
+    }
+    for (index, pool) in pools.iter().enumerate() {
+        let tokens = pool.tokens();
+        if route.uses_pool(index) || !tokens.contains(&token) {
+            continue;
+        }
+        for &next in tokens {
+            let revisits = next == token || route.hops().iter().any(|hop| hop.token_in == next);
+            if revisits {
+                continue;
+            }
+            let mut extended = *route;
+            extended.push(Hop {
+                pool: index,
+                token_in: token,
+                token_out: next,
+            });
+            if next == target {
+                visit(&extended);
+            } else {
+                search(pools, next, target, &mut extended, visit);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amm::{calculate_liquidity_from_amounts, tick_to_sqrt_price};
+
+    const USDC: u32 = 0;
+    const USDT: u32 = 1;
+    const DAI: u32 = 2;
+    const WETH: u32 = 3;
+
+    fn cp<'a>(tokens: [u32; 2], reserves: [i64; 2], fee_bps: i64) -> RoutePool<'a> {
+        RoutePool::constant_product(
+            tokens,
+            [Decimal::from(reserves[0]), Decimal::from(reserves[1])],
+            Decimal::from(fee_bps),
+        )
+    }
+
+    #[test]
+    fn test_best_route_prefers_multi_hop_when_deeper() {
+        let pools = [
+            // Shallow direct pool
+            cp([USDC, WETH], [200_000, 100], 30),
+            // Deep USDC/DAI and DAI/WETH pools
+            cp([USDC, DAI], [50_000_000, 50_000_000], 5),
+            cp([DAI, WETH], [20_000_000, 10_000], 30),
+        ];
+        let amount = Decimal::from(50_000i64);
+        let best = find_best_route(&pools, USDC, WETH, amount).unwrap();
+        assert_eq!(best.route.hops().len(), 2);
+        assert_eq!(best.route.hops()[0].pool, 1);
+        assert_eq!(best.route.hops()[1].token_in, DAI);
+
+        let direct = Route::new(&[Hop {
+            pool: 0,
+            token_in: USDC,
+            token_out: WETH,
+        }])
+        .unwrap();
+        let direct = quote_route(&pools, &direct, amount).unwrap();
+        assert!(best.amount_out > direct.amount_out);
+        assert!(direct.price_impact > best.price_impact);
+
+        // Small orders take the direct pool's single fee
+        let small = find_best_route(&pools, USDC, WETH, Decimal::from(10i64)).unwrap();
+        assert_eq!(small.route.hops().len(), 1);
+
+        assert!(find_best_route(&pools, USDC, USDT, amount).is_err());
+    }
+
+    #[test]
+    fn test_split_equalises_marginal_rates() {
+        let pools = [
+            cp([USDC, WETH], [2_000_000, 1_000], 0),
+            cp([USDC, WETH], [6_000_000, 3_000], 0),
+        ];
+        // Identical prices without fees: the optimum splits in proportion
+        // to depth
+        let amount = Decimal::from(100_000i64);
+        let split = optimize_split(&pools, USDC, WETH, amount).unwrap();
+        let legs = split.legs();
+        assert_eq!(legs.len(), 2);
+        let (shallow, deep) = if legs[0].route.hops()[0].pool == 0 {
+            (legs[0], legs[1])
+        } else {
+            (legs[1], legs[0])
+        };
+        assert!((shallow.amount_in - Decimal::from(25_000i64)).abs() < Decimal::new(1, 3));
+        assert!((deep.amount_in - Decimal::from(75_000i64)).abs() < Decimal::new(1, 3));
+        assert_eq!(shallow.amount_in + deep.amount_in, amount);
+
+        // Same as one pool with the combined depth
+        let combined = calculate_swap_output(
+            Decimal::from(8_000_000i64),
+            Decimal::from(4_000i64),
+            amount,
+            Decimal::ZERO,
+        )
+        .unwrap();
+        assert!((split.amount_out() - combined).abs() < Decimal::new(1, 9));
+        assert!(split.price_impact() > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_split_skips_expensive_route() {
+        let pools = [
+            cp([USDC, WETH], [2_000_000, 1_000], 30),
+            // Quoted 10% worse: unused until the first pool moves that far
+            cp([USDC, WETH], [2_200_000, 1_000], 30),
+        ];
+        let split = optimize_split(&pools, USDC, WETH, Decimal::from(1_000i64)).unwrap();
+        assert_eq!(split.legs().len(), 1);
+        assert_eq!(split.legs()[0].route.hops()[0].pool, 0);
+    }
+
+    #[test]
+    fn test_split_across_pool_types() {
+        let stable = StableSwapPool::new(
+            &[
+                Decimal::from(10_000_000i64),
+                Decimal::from(10_000_000i64),
+                Decimal::from(10_000_000i64),
+            ],
+            Decimal::from(200i64),
+            Decimal::new(4, 4),
+            Decimal::from(30_000_000i64),
+        )
+        .unwrap();
+        let weighted = WeightedPool::new(
+            &[Decimal::from(1_000_000i64), Decimal::from(1_000_000i64)],
+            &[Decimal::new(5, 1), Decimal::new(5, 1)],
+            Decimal::new(3, 3),
+            Decimal::from(1_000_000i64),
+        )
+        .unwrap();
+        let mut concentrated = ConcentratedPool::new(Decimal::ONE, 500, 10).unwrap();
+        let (lower, upper) = (
+            tick_to_sqrt_price(-1000).unwrap(),
+            tick_to_sqrt_price(1000).unwrap(),
+        );
+        let liquidity = calculate_liquidity_from_amounts(
+            Decimal::ONE,
+            lower,
+            upper,
+            Decimal::from(1_000_000i64),
+            Decimal::from(1_000_000i64),
+        )
+        .unwrap();
+        concentrated.add_liquidity(-1000, 1000, liquidity).unwrap();
+
+        let stable_tokens = [USDC, USDT, DAI];
+        let pair = [USDC, USDT];
+        let pools = [
+            RoutePool::StableSwap {
+                tokens: &stable_tokens,
+                pool: &stable,
+            },
+            RoutePool::Weighted {
+                tokens: &pair,
+                pool: &weighted,
+            },
+            RoutePool::Concentrated {
+                tokens: pair,
+                pool: &concentrated,
+            },
+        ];
+
+        let amount = Decimal::from(2_000_000i64);
+        let best = find_best_route(&pools, USDC, USDT, amount).unwrap();
+        let split = optimize_split(&pools, USDC, USDT, amount).unwrap();
+        assert!(split.legs().len() >= 2);
+        assert!(split.amount_out() > best.amount_out);
+        let total = split
+            .legs()
+            .iter()
+            .fold(Decimal::ZERO, |acc, leg| acc + leg.amount_in);
+        assert_eq!(total, amount);
+
+        // Marginal rates of the used legs meet at one level
+        let step = amount * marginal_step();
+        let mut rates = [Decimal::ZERO; MAX_ROUTE_SPLITS];
+        for (rate, leg) in rates.iter_mut().zip(split.legs()) {
+            *rate = marginal_rate(&pools, &leg.route, leg.amount_in, step).unwrap();
+        }
+        let rates = &rates[..split.legs().len()];
+        let max = rates.iter().fold(Decimal::ZERO, |acc, &r| acc.max(r));
+        let min = rates.iter().fold(max, |acc, &r| acc.min(r));
+        assert!((max - min) / max < Decimal::new(1, 6));
+    }
+
+    #[test]
+    fn test_route_validation() {
+        let hop = Hop {
+            pool: 0,
+            token_in: USDC,
+            token_out: WETH,
+        };
+        assert!(Route::new(&[]).is_err());
+        assert!(Route::new(&[hop, hop]).is_err());
+        let broken = Hop {
+            pool: 1,
+            token_in: DAI,
+            token_out: USDC,
+        };
+        assert!(Route::new(&[hop, broken]).is_err());
+
+        let pools = [cp([USDC, WETH], [1_000, 1_000], 30)];
+        let wrong = Route::new(&[broken]).unwrap();
+        assert!(quote_route(&pools, &wrong, Decimal::ONE).is_err());
+    }
+}
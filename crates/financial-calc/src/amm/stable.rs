@@ -0,0 +1,914 @@
+//! Curve StableSwap and crypto-swap invariants.
+//!
+//! [`StableSwapPool`] reproduces Curve's n-coin StableSwap pool: the
+//! invariant D by Newton iteration, `get_y`, swaps with fees, LP token
+//! estimates, single-sided withdrawals, virtual price and linear
+//! amplification ramps. [`cryptoswap_d`] and [`cryptoswap_y`] solve the
+//! crypto-swap (Curve v2) invariant.
+//!
+//! Balances are in a common unit: multiply by each coin's rate first when
+//! decimals or redemption rates differ. The amplification coefficient
+//! follows Curve's contracts, where `Ann = A * n`.
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::amm::StableSwapPool;
+//! use precision_core::Decimal;
+//!
+//! let balances = [Decimal::from(1_000_000i64), Decimal::from(1_200_000i64)];
+//! let pool = StableSwapPool::new(
+//!     &balances,
+//!     Decimal::from(200i64),
+//!     Decimal::new(4, 4), // 0.04% fee
+//!     Decimal::from(2_200_000i64),
+//! )
+//! .unwrap();
+//!
+//! // Near the peg a swap pays out almost one for one
+//! let out = pool.get_dy(0, 1, Decimal::from(1000i64)).unwrap();
+//! assert!((out - Decimal::from(1000i64)).abs() < Decimal::ONE);
+//! ```
+
+use precision_core::{ArithmeticError, Decimal};
+
+/// Maximum number of coins in a StableSwap pool.
+pub const MAX_STABLE_COINS: usize = 8;
+
+/// Maximum amplification coefficient.
+pub const MAX_AMPLIFICATION: i64 = 1_000_000;
+
+/// Maximum factor by which a ramp may change the amplification.
+pub const MAX_AMPLIFICATION_CHANGE: i64 = 10;
+
+/// Minimum duration of an amplification ramp in seconds.
+pub const MIN_RAMP_TIME: u64 = 86_400;
+
+/// Iteration cap for the invariant solvers, as in the contracts.
+const MAX_ITERATIONS: u32 = 255;
+
+/// Relative change below which an iteration has converged.
+fn convergence() -> Decimal {
+    Decimal::new(1, 24)
+}
+
+/// Linear change of the amplification coefficient over time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AmplificationRamp {
+    /// Amplification at the start of the ramp.
+    pub initial: Decimal,
+    /// Amplification at the end of the ramp.
+    pub future: Decimal,
+    /// Ramp start, in seconds.
+    pub initial_time: u64,
+    /// Ramp end, in seconds.
+    pub future_time: u64,
+}
+
+impl AmplificationRamp {
+    /// A constant amplification coefficient.
+    pub fn constant(amplification: Decimal) -> Self {
+        Self {
+            initial: amplification,
+            future: amplification,
+            initial_time: 0,
+            future_time: 0,
+        }
+    }
+
+    /// Amplification at a timestamp, interpolated linearly during the ramp.
+    pub fn at(&self, timestamp: u64) -> Result<Decimal, ArithmeticError> {
+        if timestamp >= self.future_time {
+            return Ok(self.future);
+        }
+        if timestamp <= self.initial_time {
+            return Ok(self.initial);
+        }
+        let elapsed = Decimal::from(timestamp - self.initial_time);
+        let duration = Decimal::from(self.future_time - self.initial_time);
+        let change = self
+            .future
+            .try_sub(self.initial)?
+            .try_mul(elapsed)?
+            .try_div(duration)?;
+        self.initial.try_add(change)
+    }
+}
+
+/// A Curve StableSwap pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StableSwapPool {
+    balances: [Decimal; MAX_STABLE_COINS],
+    coins: usize,
+    fee: Decimal,
+    total_supply: Decimal,
+    ramp: AmplificationRamp,
+    timestamp: u64,
+}
+
+impl StableSwapPool {
+    /// Creates a pool.
+    ///
+    /// `fee` is a fraction of the output (0.0004 = 4 bps) and
+    /// `total_supply` the outstanding LP tokens.
+    ///
+    /// Returns error unless there are 2 to [`MAX_STABLE_COINS`] coins with
+    /// positive balances, 0 < A ≤ [`MAX_AMPLIFICATION`] and 0 ≤ fee < 1.
+    pub fn new(
+        balances: &[Decimal],
+        amplification: Decimal,
+        fee: Decimal,
+        total_supply: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        if balances.len() < 2
+            || balances.len() > MAX_STABLE_COINS
+            || balances.iter().any(|b| !b.is_positive())
+            || !amplification.is_positive()
+            || amplification > Decimal::from(MAX_AMPLIFICATION)
+            || fee.is_negative()
+            || fee >= Decimal::ONE
+            || total_supply.is_negative()
+        {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let mut stored = [Decimal::ZERO; MAX_STABLE_COINS];
+        stored[..balances.len()].copy_from_slice(balances);
+        Ok(Self {
+            balances: stored,
+            coins: balances.len(),
+            fee,
+            total_supply,
+            ramp: AmplificationRamp::constant(amplification),
+            timestamp: 0,
+        })
+    }
+
+    /// Coin balances.
+    pub fn balances(&self) -> &[Decimal] {
+        &self.balances[..self.coins]
+    }
+
+    /// Swap fee as a fraction.
+    pub fn fee(&self) -> Decimal {
+        self.fee
+    }
+
+    /// Outstanding LP tokens.
+    pub fn total_supply(&self) -> Decimal {
+        self.total_supply
+    }
+
+    /// Current amplification ramp.
+    pub fn ramp(&self) -> AmplificationRamp {
+        self.ramp
+    }
+
+    /// Pool clock in seconds, used to evaluate the ramp.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Advances the pool clock.
+    pub fn set_timestamp(&mut self, timestamp: u64) {
+        self.timestamp = timestamp;
+    }
+
+    /// Amplification coefficient at the pool clock.
+    pub fn amplification(&self) -> Result<Decimal, ArithmeticError> {
+        self.ramp.at(self.timestamp)
+    }
+
+    /// Starts ramping the amplification to `future` by `future_time`.
+    ///
+    /// Applies the contracts' limits: the ramp lasts at least
+    /// [`MIN_RAMP_TIME`] and changes A by at most
+    /// [`MAX_AMPLIFICATION_CHANGE`] times.
+    pub fn ramp_amplification(
+        &mut self,
+        future: Decimal,
+        future_time: u64,
+    ) -> Result<(), ArithmeticError> {
+        let current = self.amplification()?;
+        let max_change = Decimal::from(MAX_AMPLIFICATION_CHANGE);
+        if future_time < self.timestamp.saturating_add(MIN_RAMP_TIME)
+            || !future.is_positive()
+            || future > Decimal::from(MAX_AMPLIFICATION)
+            || future > current.try_mul(max_change)?
+            || future.try_mul(max_change)? < current
+        {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        self.ramp = AmplificationRamp {
+            initial: current,
+            future,
+            initial_time: self.timestamp,
+            future_time,
+        };
+        Ok(())
+    }
+
+    /// Freezes the amplification at its current value.
+    pub fn stop_ramp(&mut self) -> Result<(), ArithmeticError> {
+        self.ramp = AmplificationRamp::constant(self.amplification()?);
+        Ok(())
+    }
+
+    /// Invariant D of the current balances.
+    pub fn invariant(&self) -> Result<Decimal, ArithmeticError> {
+        stableswap_d(self.balances(), self.amplification()?)
+    }
+
+    /// Value of one LP token in the common unit, D / supply.
+    pub fn virtual_price(&self) -> Result<Decimal, ArithmeticError> {
+        self.invariant()?.try_div(self.total_supply)
+    }
+
+    /// Output of swapping `dx` of coin `i` for coin `j`, after fees.
+    pub fn get_dy(&self, i: usize, j: usize, dx: Decimal) -> Result<Decimal, ArithmeticError> {
+        self.swap_amounts(i, j, dx).map(|(dy, _)| dy)
+    }
+
+    /// Swaps `dx` of coin `i` for coin `j` and returns the output.
+    ///
+    /// The fee stays in the pool, raising the virtual price.
+    pub fn exchange(
+        &mut self,
+        i: usize,
+        j: usize,
+        dx: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        let (dy, _) = self.swap_amounts(i, j, dx)?;
+        self.balances[i] = self.balances[i].try_add(dx)?;
+        self.balances[j] = self.balances[j].try_sub(dy)?;
+        Ok(dy)
+    }
+
+    /// LP tokens minted by a deposit or burned by a withdrawal, ignoring
+    /// imbalance fees (Curve's `calc_token_amount`).
+    pub fn calc_token_amount(
+        &self,
+        amounts: &[Decimal],
+        is_deposit: bool,
+    ) -> Result<Decimal, ArithmeticError> {
+        let amplification = self.amplification()?;
+        let new_balances = self.shifted(amounts, is_deposit)?;
+        let d0 = stableswap_d(self.balances(), amplification)?;
+        let d1 = stableswap_d(&new_balances[..self.coins], amplification)?;
+        let difference = if is_deposit {
+            d1.try_sub(d0)?
+        } else {
+            d0.try_sub(d1)?
+        };
+        difference.try_mul(self.total_supply)?.try_div(d0)
+    }
+
+    /// Deposits coins and returns the LP tokens minted.
+    ///
+    /// Deposits that move the pool away from its current proportions pay
+    /// the imbalance fee, fee × n / (4(n − 1)), on the excess. The first
+    /// deposit into an empty pool mints D.
+    pub fn add_liquidity(&mut self, amounts: &[Decimal]) -> Result<Decimal, ArithmeticError> {
+        let amplification = self.amplification()?;
+        let new_balances = self.shifted(amounts, true)?;
+        let d1 = stableswap_d(&new_balances[..self.coins], amplification)?;
+        if self.total_supply.is_zero() {
+            self.balances = new_balances;
+            self.total_supply = d1;
+            return Ok(d1);
+        }
+
+        let d0 = stableswap_d(self.balances(), amplification)?;
+        let fee = self.imbalance_fee()?;
+        let mut charged = new_balances;
+        for (k, balance) in charged[..self.coins].iter_mut().enumerate() {
+            let ideal = d1.try_mul(self.balances[k])?.try_div(d0)?;
+            let difference = ideal.try_sub(new_balances[k])?.abs();
+            *balance = balance.try_sub(fee.try_mul(difference)?)?;
+        }
+        let d2 = stableswap_d(&charged[..self.coins], amplification)?;
+        let minted = self.total_supply.try_mul(d2.try_sub(d0)?)?.try_div(d0)?;
+
+        self.balances = new_balances;
+        self.total_supply = self.total_supply.try_add(minted)?;
+        Ok(minted)
+    }
+
+    /// Coin `i` received for burning `token_amount` LP tokens, and the fee
+    /// charged (Curve's `calc_withdraw_one_coin`).
+    pub fn calc_withdraw_one_coin(
+        &self,
+        token_amount: Decimal,
+        i: usize,
+    ) -> Result<(Decimal, Decimal), ArithmeticError> {
+        if i >= self.coins || token_amount.is_negative() || token_amount > self.total_supply {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let amplification = self.amplification()?;
+        let balances = self.balances();
+        let d0 = stableswap_d(balances, amplification)?;
+        let d1 = d0.try_sub(token_amount.try_mul(d0)?.try_div(self.total_supply)?)?;
+        let new_y = stableswap_y_d(balances, amplification, i, d1)?;
+
+        // Fees on the amounts each coin moves away from a proportional
+        // withdrawal
+        let fee = self.imbalance_fee()?;
+        let mut reduced = self.balances;
+        for (j, balance) in reduced[..self.coins].iter_mut().enumerate() {
+            let proportional = balances[j].try_mul(d1)?.try_div(d0)?;
+            let expected = if j == i {
+                proportional.try_sub(new_y)?
+            } else {
+                balances[j].try_sub(proportional)?
+            };
+            *balance = balance.try_sub(fee.try_mul(expected)?)?;
+        }
+        let dy = reduced[i].try_sub(stableswap_y_d(
+            &reduced[..self.coins],
+            amplification,
+            i,
+            d1,
+        )?)?;
+        let dy_without_fee = balances[i].try_sub(new_y)?;
+        Ok((dy, dy_without_fee.try_sub(dy)?))
+    }
+
+    /// Burns `token_amount` LP tokens for coin `i` and returns the amount
+    /// received.
+    pub fn remove_liquidity_one_coin(
+        &mut self,
+        token_amount: Decimal,
+        i: usize,
+    ) -> Result<Decimal, ArithmeticError> {
+        let (dy, _) = self.calc_withdraw_one_coin(token_amount, i)?;
+        self.balances[i] = self.balances[i].try_sub(dy)?;
+        self.total_supply = self.total_supply.try_sub(token_amount)?;
+        Ok(dy)
+    }
+
+    /// Output and fee of a swap.
+    fn swap_amounts(
+        &self,
+        i: usize,
+        j: usize,
+        dx: Decimal,
+    ) -> Result<(Decimal, Decimal), ArithmeticError> {
+        if dx.is_negative() {
+            return Err(ArithmeticError::Underflow);
+        }
+        let x = self
+            .balances()
+            .get(i)
+            .ok_or(ArithmeticError::DivisionByZero)?
+            .try_add(dx)?;
+        let y = stableswap_y(self.balances(), self.amplification()?, i, j, x)?;
+        let dy = self.balances[j].try_sub(y)?;
+        let fee = dy.try_mul(self.fee)?;
+        Ok((dy.try_sub(fee)?, fee))
+    }
+
+    /// Balances after adding or removing amounts.
+    fn shifted(
+        &self,
+        amounts: &[Decimal],
+        add: bool,
+    ) -> Result<[Decimal; MAX_STABLE_COINS], ArithmeticError> {
+        if amounts.len() != self.coins || amounts.iter().any(|a| a.is_negative()) {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let mut balances = self.balances;
+        for (balance, &amount) in balances.iter_mut().zip(amounts) {
+            *balance = if add {
+                balance.try_add(amount)?
+            } else {
+                balance.try_sub(amount)?
+            };
+        }
+        Ok(balances)
+    }
+
+    fn imbalance_fee(&self) -> Result<Decimal, ArithmeticError> {
+        let n = Decimal::from(self.coins as u64);
+        self.fee
+            .try_mul(n)?
+            .try_div(Decimal::from(4i64).try_mul(n.try_sub(Decimal::ONE)?)?)
+    }
+}
+
+/// StableSwap invariant D, solved by Newton's method as in Curve's
+/// `get_D`:
+///
+/// A·nⁿ·Σx + D = A·nⁿ·D + Dⁿ⁺¹ / (nⁿ·Πx)
+///
+/// with `amplification` in the contracts' convention (`Ann = A * n`).
+/// Returns zero for an empty pool.
+pub fn stableswap_d(
+    balances: &[Decimal],
+    amplification: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let n = Decimal::from(balances.len() as u64);
+    let sum = balances
+        .iter()
+        .try_fold(Decimal::ZERO, |acc, &x| acc.try_add(x))?;
+    if sum.is_zero() {
+        return Ok(Decimal::ZERO);
+    }
+    if balances.iter().any(|x| !x.is_positive()) {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let ann = amplification.try_mul(n)?;
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        // D_P = Dⁿ⁺¹ / (nⁿ·Πx), built one coin at a time
+        let mut d_p = d;
+        for &x in balances {
+            d_p = d_p.try_mul(d)?.try_div(x.try_mul(n)?)?;
+        }
+        // (Ann·S + n·D_P)·D / ((Ann − 1)·D + (n + 1)·D_P), divided through
+        // by D to stay within range
+        let numerator = ann.try_mul(sum)?.try_add(d_p.try_mul(n)?)?;
+        let denominator = ann
+            .try_sub(Decimal::ONE)?
+            .try_add(n.try_add(Decimal::ONE)?.try_mul(d_p)?.try_div(d)?)?;
+        let previous = d;
+        d = numerator.try_div(denominator)?;
+        if converged(d, previous)? {
+            return Ok(d);
+        }
+    }
+    Err(ArithmeticError::Overflow)
+}
+
+/// Balance of coin `j` that keeps the invariant when coin `i` has balance
+/// `x` (Curve's `get_y`).
+pub fn stableswap_y(
+    balances: &[Decimal],
+    amplification: Decimal,
+    i: usize,
+    j: usize,
+    x: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let coins = balances.len();
+    if i == j || i >= coins || j >= coins || coins > MAX_STABLE_COINS {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let d = stableswap_d(balances, amplification)?;
+    let mut shifted = [Decimal::ZERO; MAX_STABLE_COINS];
+    shifted[..coins].copy_from_slice(balances);
+    shifted[i] = x;
+    stableswap_y_d(&shifted[..coins], amplification, j, d)
+}
+
+/// Balance of coin `i` that gives invariant `d` with the other balances
+/// fixed (Curve's `get_y_D`).
+pub fn stableswap_y_d(
+    balances: &[Decimal],
+    amplification: Decimal,
+    i: usize,
+    d: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    if i >= balances.len() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let n = Decimal::from(balances.len() as u64);
+    let ann = amplification.try_mul(n)?;
+    let mut c = d;
+    let mut sum = Decimal::ZERO;
+    for (k, &x) in balances.iter().enumerate() {
+        if k == i {
+            continue;
+        }
+        if !x.is_positive() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        sum = sum.try_add(x)?;
+        c = c.try_mul(d)?.try_div(x.try_mul(n)?)?;
+    }
+    c = c.try_mul(d)?.try_div(ann.try_mul(n)?)?;
+    let b = sum.try_add(d.try_div(ann)?)?;
+
+    // y = (y² + c) / (2y + b − D), divided through by y
+    let two = Decimal::from(2i64);
+    let mut y = d;
+    for _ in 0..MAX_ITERATIONS {
+        let previous = y;
+        let numerator = y.try_add(c.try_div(y)?)?;
+        let denominator = two.try_add(b.try_sub(d)?.try_div(y)?)?;
+        y = numerator.try_div(denominator)?;
+        if converged(y, previous)? {
+            return Ok(y);
+        }
+    }
+    Err(ArithmeticError::Overflow)
+}
+
+/// Crypto-swap (Curve v2) invariant D.
+///
+/// Solves K·Dⁿ⁻¹·Σx + Πx = K·Dⁿ + (D/n)ⁿ with K0 = Πx·nⁿ/Dⁿ and
+/// K = A·nⁿ·K0·γ² / (γ + 1 − K0)². `balances` are already scaled by the
+/// pool's price scale. D lies between n times the geometric mean
+/// (constant product) and the sum (constant sum).
+pub fn cryptoswap_d(
+    balances: &[Decimal],
+    amplification: Decimal,
+    gamma: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    validate_cryptoswap(balances, amplification, gamma)?;
+    if balances.iter().any(|x| !x.is_positive()) {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+
+    // The equation is homogeneous, so solve with balances summing to one
+    let sum = balances
+        .iter()
+        .try_fold(Decimal::ZERO, |acc, &x| acc.try_add(x))?;
+    let mut scaled = [Decimal::ZERO; MAX_STABLE_COINS];
+    let mut min = Decimal::MAX;
+    for (s, &x) in scaled.iter_mut().zip(balances) {
+        *s = x.try_div(sum)?;
+        min = min.min(*s);
+    }
+    let scaled = &scaled[..balances.len()];
+    let n = Decimal::from(balances.len() as u64);
+    let d = safeguarded_newton(
+        |d| cryptoswap_equation(scaled, d, amplification, gamma, None),
+        n.try_mul(min)?,
+        Decimal::ONE,
+    )?;
+    d.try_mul(sum)
+}
+
+/// Balance of coin `i` that gives crypto-swap invariant `d` with the other
+/// balances fixed.
+pub fn cryptoswap_y(
+    balances: &[Decimal],
+    amplification: Decimal,
+    gamma: Decimal,
+    d: Decimal,
+    i: usize,
+) -> Result<Decimal, ArithmeticError> {
+    validate_cryptoswap(balances, amplification, gamma)?;
+    if i >= balances.len() || !d.is_positive() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+
+    // Solve with D scaled to one; the residual rises with the balance
+    let mut scaled = [Decimal::ZERO; MAX_STABLE_COINS];
+    for (k, (s, &x)) in scaled.iter_mut().zip(balances).enumerate() {
+        if k != i && !x.is_positive() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        *s = x.try_div(d)?;
+    }
+    let scaled = &mut scaled[..balances.len()];
+    let mut residual = |y: Decimal| {
+        scaled[i] = y;
+        cryptoswap_equation(scaled, Decimal::ONE, amplification, gamma, Some(i))
+    };
+    let mut high = Decimal::ONE;
+    for _ in 0..64 {
+        if residual(high)?.0.is_positive() {
+            break;
+        }
+        high = high.try_mul(Decimal::from(2i64))?;
+    }
+    let y = safeguarded_newton(&mut residual, Decimal::ZERO, high)?;
+    y.try_mul(d)
+}
+
+fn validate_cryptoswap(
+    balances: &[Decimal],
+    amplification: Decimal,
+    gamma: Decimal,
+) -> Result<(), ArithmeticError> {
+    if balances.len() < 2
+        || balances.len() > MAX_STABLE_COINS
+        || !amplification.is_positive()
+        || !gamma.is_positive()
+    {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    Ok(())
+}
+
+/// Crypto-swap residual F and its derivative in D (`wrt` = None) or in
+/// balance `wrt`.
+fn cryptoswap_equation(
+    balances: &[Decimal],
+    d: Decimal,
+    amplification: Decimal,
+    gamma: Decimal,
+    wrt: Option<usize>,
+) -> Result<(Decimal, Decimal), ArithmeticError> {
+    let count = balances.len();
+    let n = Decimal::from(count as u64);
+    let n_pow = n.try_powi(count as i32)?;
+    let ann = amplification.try_mul(n_pow)?;
+    let d_pow_1 = d.try_powi(count as i32 - 1)?;
+    let d_pow = d_pow_1.try_mul(d)?;
+
+    let mut sum = Decimal::ZERO;
+    let mut product = Decimal::ONE;
+    let mut others = Decimal::ONE;
+    for (k, &x) in balances.iter().enumerate() {
+        sum = sum.try_add(x)?;
+        product = product.try_mul(x)?;
+        if Some(k) != wrt {
+            others = others.try_mul(x)?;
+        }
+    }
+
+    let k0 = product.try_mul(n_pow)?.try_div(d_pow)?;
+    let gap = gamma.try_add(Decimal::ONE)?.try_sub(k0)?;
+    let gamma_sq = gamma.try_mul(gamma)?;
+    let k = ann
+        .try_mul(k0)?
+        .try_mul(gamma_sq)?
+        .try_div(gap.try_mul(gap)?)?;
+    // dK/dK0
+    let dk = ann
+        .try_mul(gamma_sq)?
+        .try_mul(gap.try_add(k0.try_add(k0)?)?)?
+        .try_div(gap.try_powi(3)?)?;
+    let mean_pow_1 = d.try_div(n)?.try_powi(count as i32 - 1)?;
+
+    let f = k
+        .try_mul(d_pow_1)?
+        .try_mul(sum)?
+        .try_add(product)?
+        .try_sub(k.try_mul(d_pow)?)?
+        .try_sub(mean_pow_1.try_mul(d.try_div(n)?)?)?;
+
+    let derivative = match wrt {
+        None => {
+            // dK0/dD = −n·K0/D
+            let dk_dd = -dk.try_mul(n.try_mul(k0)?.try_div(d)?)?;
+            let n_minus_1 = n.try_sub(Decimal::ONE)?;
+            dk_dd
+                .try_mul(d_pow_1)?
+                .try_mul(sum)?
+                .try_add(
+                    k.try_mul(n_minus_1)?
+                        .try_mul(d_pow_1)?
+                        .try_mul(sum)?
+                        .try_div(d)?,
+                )?
+                .try_sub(dk_dd.try_mul(d_pow)?)?
+                .try_sub(k.try_mul(n)?.try_mul(d_pow_1)?)?
+                .try_sub(mean_pow_1)?
+        }
+        Some(_) => {
+            // dK0/dx = Π(others)·nⁿ/Dⁿ
+            let dk_dx = dk.try_mul(others.try_mul(n_pow)?.try_div(d_pow)?)?;
+            dk_dx
+                .try_mul(d_pow_1)?
+                .try_mul(sum.try_sub(d)?)?
+                .try_add(k.try_mul(d_pow_1)?)?
+                .try_add(others)?
+        }
+    };
+    Ok((f, derivative))
+}
+
+/// Newton's method kept inside a bracket, bisecting whenever a step would
+/// leave it. `f` returns the value and derivative, and must change sign
+/// between `low` and `high`.
+fn safeguarded_newton<F>(mut f: F, low: Decimal, high: Decimal) -> Result<Decimal, ArithmeticError>
+where
+    F: FnMut(Decimal) -> Result<(Decimal, Decimal), ArithmeticError>,
+{
+    let two = Decimal::from(2i64);
+    let (f_low, _) = f(low)?;
+    if f_low.is_zero() {
+        return Ok(low);
+    }
+    let low_positive = f_low.is_positive();
+    let (mut low, mut high) = (low, high);
+    let mut x = high;
+    for _ in 0..MAX_ITERATIONS {
+        let (value, derivative) = f(x)?;
+        if value.is_zero() {
+            return Ok(x);
+        }
+        if value.is_positive() == low_positive {
+            low = x;
+        } else {
+            high = x;
+        }
+        let (lower, upper) = (low.min(high), low.max(high));
+        let newton = if derivative.is_zero() {
+            None
+        } else {
+            Some(x.try_sub(value.try_div(derivative)?)?)
+        };
+        let next = match newton {
+            Some(next) if next > lower && next < upper => next,
+            _ => low.try_add(high)?.try_div(two)?,
+        };
+        if converged(next, x)? || upper.try_sub(lower)? <= upper.try_mul(convergence())? {
+            return Ok(next);
+        }
+        x = next;
+    }
+    Err(ArithmeticError::Overflow)
+}
+
+fn converged(current: Decimal, previous: Decimal) -> Result<bool, ArithmeticError> {
+    let change = current.try_sub(previous)?.abs();
+    Ok(change <= current.abs().try_mul(convergence())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn assert_close(actual: Decimal, expected: &str) {
+        assert_near(actual, decimal(expected));
+    }
+
+    fn assert_near(actual: Decimal, expected: Decimal) {
+        let tolerance = expected.abs() * decimal("0.000000000001");
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} vs {expected}"
+        );
+    }
+
+    fn pool() -> StableSwapPool {
+        let balances = [
+            Decimal::from(1_000_000i64),
+            Decimal::from(1_200_000i64),
+            Decimal::from(800_000i64),
+        ];
+        StableSwapPool::new(
+            &balances,
+            Decimal::from(200i64),
+            decimal("0.0004"),
+            Decimal::from(2_990_000i64),
+        )
+        .unwrap()
+    }
+
+    // Expected values come from Curve's integer StableSwap math on the
+    // same pool with 18-decimal balances
+
+    #[test]
+    fn test_invariant_and_virtual_price() {
+        let pool = pool();
+        assert_close(pool.invariant().unwrap(), "2999792.760282722013878415");
+        assert_close(pool.virtual_price().unwrap(), "1.003275170663117730");
+
+        // Balanced pools have D equal to the sum
+        let balanced = [Decimal::from(500i64); 3];
+        assert_eq!(
+            stableswap_d(&balanced, Decimal::from(100i64)).unwrap(),
+            Decimal::from(1500i64)
+        );
+        assert_eq!(
+            stableswap_d(&[Decimal::ZERO; 2], Decimal::ONE).unwrap(),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn test_swap_output() {
+        let mut pool = pool();
+        assert_close(
+            pool.get_dy(1, 0, Decimal::from(1000i64)).unwrap(),
+            "998.732569903207834691",
+        );
+        let d_before = pool.invariant().unwrap();
+        let dy = pool.exchange(0, 2, Decimal::from(50_000i64)).unwrap();
+        assert_close(dy, "49897.962468301565947856");
+        assert_close(pool.balances()[2], "750102.037531698434052144");
+        // Fees stay in the pool
+        assert!(pool.invariant().unwrap() > d_before);
+
+        let y = stableswap_y(
+            pool.balances(),
+            Decimal::from(200i64),
+            0,
+            1,
+            pool.balances()[0],
+        )
+        .unwrap();
+        assert_close(y, "1200000");
+        assert!(pool.get_dy(0, 0, Decimal::ONE).is_err());
+        assert!(pool.get_dy(0, 3, Decimal::ONE).is_err());
+    }
+
+    #[test]
+    fn test_liquidity() {
+        let mut pool = pool();
+        let deposit = [
+            Decimal::from(10_000i64),
+            Decimal::ZERO,
+            Decimal::from(5_000i64),
+        ];
+        assert_close(
+            pool.calc_token_amount(&deposit, true).unwrap(),
+            "14956.317089244531787625",
+        );
+        let withdrawal = [Decimal::ZERO, Decimal::from(20_000i64), Decimal::ZERO];
+        assert_close(
+            pool.calc_token_amount(&withdrawal, false).unwrap(),
+            "19916.642907704356328255",
+        );
+
+        let (dy, fee) = pool
+            .calc_withdraw_one_coin(Decimal::from(30_000i64), 2)
+            .unwrap();
+        assert_close(dy, "30052.088389881136599233");
+        assert_close(fee, "6.609168067146296867");
+
+        let minted = pool.add_liquidity(&deposit).unwrap();
+        assert_close(minted, "14954.523856732042418960");
+        assert_eq!(pool.balances()[0], Decimal::from(1_010_000i64));
+        assert!(pool.add_liquidity(&deposit[..2]).is_err());
+
+        let supply = pool.total_supply();
+        pool.remove_liquidity_one_coin(Decimal::from(1000i64), 1)
+            .unwrap();
+        assert_eq!(pool.total_supply(), supply - Decimal::from(1000i64));
+    }
+
+    #[test]
+    fn test_amplification_ramp() {
+        let mut pool = pool();
+        pool.set_timestamp(1_000);
+        assert!(pool
+            .ramp_amplification(Decimal::from(400i64), 1_000 + MIN_RAMP_TIME - 1)
+            .is_err());
+        assert!(pool
+            .ramp_amplification(Decimal::from(2001i64), 1_000 + MIN_RAMP_TIME)
+            .is_err());
+
+        pool.ramp_amplification(Decimal::from(400i64), 1_000 + 2 * MIN_RAMP_TIME)
+            .unwrap();
+        assert_eq!(pool.amplification().unwrap(), Decimal::from(200i64));
+        pool.set_timestamp(1_000 + MIN_RAMP_TIME);
+        assert_eq!(pool.amplification().unwrap(), Decimal::from(300i64));
+
+        // Higher A flattens the curve: better rates for imbalanced swaps
+        let before = pool.get_dy(0, 2, Decimal::from(50_000i64)).unwrap();
+        pool.stop_ramp().unwrap();
+        pool.set_timestamp(1_000 + 10 * MIN_RAMP_TIME);
+        assert_eq!(pool.amplification().unwrap(), Decimal::from(300i64));
+        assert_eq!(pool.get_dy(0, 2, Decimal::from(50_000i64)).unwrap(), before);
+        assert!(before > decimal("49897.962468301565947856"));
+    }
+
+    #[test]
+    fn test_cryptoswap_invariant() {
+        let amplification = Decimal::from(135i64);
+        let gamma = decimal("0.000021");
+
+        // Balanced: D is the sum
+        let balanced = [Decimal::from(1_000_000i64); 3];
+        assert_close(
+            cryptoswap_d(&balanced, amplification, gamma).unwrap(),
+            "3000000",
+        );
+
+        // Imbalanced: between constant product and constant sum
+        let balances = [
+            Decimal::from(1_000_000i64),
+            Decimal::from(1_100_000i64),
+            Decimal::from(900_000i64),
+        ];
+        let d = cryptoswap_d(&balances, amplification, gamma).unwrap();
+        let product = Decimal::from(990_000i64) * Decimal::from(1_000_000_000_000i64);
+        let geometric = product.try_ln().unwrap() / Decimal::from(3i64);
+        let lower = Decimal::from(3i64) * geometric.try_exp().unwrap();
+        assert!(d > lower && d < Decimal::from(3_000_000i64));
+        let (residual, _) = cryptoswap_equation(
+            &[balances[0] / d, balances[1] / d, balances[2] / d],
+            Decimal::ONE,
+            amplification,
+            gamma,
+            None,
+        )
+        .unwrap();
+        assert!(residual.abs() < decimal("0.0000000000000000001"));
+
+        // Solving back for each balance recovers it
+        for i in 0..3 {
+            let y = cryptoswap_y(&balances, amplification, gamma, d, i).unwrap();
+            assert_near(y, balances[i]);
+        }
+
+        // A swap pays out between the constant-sum and constant-product
+        // amounts
+        let mut after = balances;
+        after[0] = after[0] + Decimal::from(10_000i64);
+        let y = cryptoswap_y(&after, amplification, gamma, d, 1).unwrap();
+        let constant_product = balances[0] * balances[1] / after[0];
+        assert!(y > constant_product && y < balances[1] - Decimal::from(10_000i64));
+
+        assert!(cryptoswap_d(&balances, amplification, Decimal::ZERO).is_err());
+    }
+}
@@ -0,0 +1,790 @@
+//! Balancer weighted pool math.
+//!
+//! A weighted pool holds N tokens with normalized weights summing to one and
+//! keeps the invariant V = Π bᵢ^wᵢ. With equal weights and two tokens this
+//! is the constant-product pool of
+//! [`calculate_swap_output`](super::calculate_swap_output).
+//!
+//! The free functions port Balancer's `WeightedMath` and take amounts net
+//! of swap fees where the contracts do; [`WeightedPool`] applies the fee and
+//! tracks balances and BPT supply. Non-integer powers are computed as
+//! exp(y·ln x), so like the contracts each power is widened by a relative
+//! error bound in the direction that favours the pool.
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::amm::WeightedPool;
+//! use precision_core::Decimal;
+//!
+//! // 80/20 pool with a 0.3% fee
+//! let mut pool = WeightedPool::new(
+//!     &[Decimal::from(800i64), Decimal::from(50_000i64)],
+//!     &[Decimal::new(8, 1), Decimal::new(2, 1)],
+//!     Decimal::new(3, 3),
+//!     Decimal::from(10_000i64),
+//! )
+//! .unwrap();
+//!
+//! let out = pool.swap_exact_in(0, 1, Decimal::from(8i64)).unwrap();
+//! assert!(out < Decimal::from(2_000i64));
+//! ```
+
+use super::{complement, validate_amounts};
+use precision_core::{ArithmeticError, Decimal};
+
+/// Maximum number of tokens in a weighted pool.
+pub const MAX_WEIGHTED_TOKENS: usize = 8;
+
+/// Largest swap input or output as a fraction of the token balance (30%).
+fn max_swap_ratio() -> Decimal {
+    Decimal::new(3, 1)
+}
+
+/// Smallest normalized weight (1%).
+fn min_weight() -> Decimal {
+    Decimal::new(1, 2)
+}
+
+/// Relative error bound of a non-integer power.
+fn max_pow_relative_error() -> Decimal {
+    Decimal::new(1, 14)
+}
+
+/// Largest invariant growth from a single-token join (3×).
+const MAX_INVARIANT_RATIO: i64 = 3;
+
+/// Invariant V = Π bᵢ^wᵢ, rounded down.
+pub fn weighted_invariant(
+    balances: &[Decimal],
+    weights: &[Decimal],
+) -> Result<Decimal, ArithmeticError> {
+    if balances.len() != weights.len() || balances.is_empty() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    balances
+        .iter()
+        .zip(weights)
+        .try_fold(Decimal::ONE, |acc, (&balance, &weight)| {
+            acc.try_mul(pow_down(balance, weight)?)
+        })
+}
+
+/// Spot price of the output token in units of the input token,
+/// (b_in / w_in) / (b_out / w_out) / (1 − fee).
+pub fn weighted_spot_price(
+    balance_in: Decimal,
+    weight_in: Decimal,
+    balance_out: Decimal,
+    weight_out: Decimal,
+    swap_fee: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let numerator = balance_in.try_div(weight_in)?;
+    let denominator = balance_out.try_div(weight_out)?;
+    numerator
+        .try_div(denominator)?
+        .try_div(complement(swap_fee))
+}
+
+/// Output for an exact input net of fees,
+/// b_out · (1 − (b_in / (b_in + a_in))^(w_in / w_out)).
+///
+/// Returns error if the input exceeds 30% of the balance.
+pub fn weighted_out_given_in(
+    balance_in: Decimal,
+    weight_in: Decimal,
+    balance_out: Decimal,
+    weight_out: Decimal,
+    amount_in: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    if amount_in.is_negative() || amount_in > balance_in.try_mul(max_swap_ratio())? {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let base = balance_in.try_div(balance_in.try_add(amount_in)?)?;
+    let exponent = weight_in.try_div(weight_out)?;
+    let power = pow_up(base, exponent)?;
+    balance_out.try_mul(complement(power))
+}
+
+/// Input net of fees for an exact output,
+/// b_in · ((b_out / (b_out − a_out))^(w_out / w_in) − 1).
+///
+/// Returns error if the output exceeds 30% of the balance.
+pub fn weighted_in_given_out(
+    balance_in: Decimal,
+    weight_in: Decimal,
+    balance_out: Decimal,
+    weight_out: Decimal,
+    amount_out: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    if amount_out.is_negative() || amount_out > balance_out.try_mul(max_swap_ratio())? {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let base = balance_out.try_div(balance_out.try_sub(amount_out)?)?;
+    let exponent = weight_out.try_div(weight_in)?;
+    let power = pow_up(base, exponent)?;
+    balance_in.try_mul(power.try_sub(Decimal::ONE)?)
+}
+
+/// BPT minted for exact token amounts in.
+///
+/// Amounts beyond a proportional join are effectively swapped into the
+/// pool and pay the swap fee.
+pub fn weighted_bpt_out_given_exact_tokens_in(
+    balances: &[Decimal],
+    weights: &[Decimal],
+    amounts_in: &[Decimal],
+    total_supply: Decimal,
+    swap_fee: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    if weights.len() != balances.len() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    validate_amounts(balances, amounts_in, MAX_WEIGHTED_TOKENS)?;
+    let mut ratios = [Decimal::ZERO; MAX_WEIGHTED_TOKENS];
+    let mut invariant_ratio_with_fees = Decimal::ZERO;
+    for i in 0..balances.len() {
+        ratios[i] = balances[i].try_add(amounts_in[i])?.try_div(balances[i])?;
+        invariant_ratio_with_fees =
+            invariant_ratio_with_fees.try_add(ratios[i].try_mul(weights[i])?)?;
+    }
+
+    let mut invariant_ratio = Decimal::ONE;
+    for i in 0..balances.len() {
+        let amount_in = if ratios[i] > invariant_ratio_with_fees {
+            let non_taxable =
+                balances[i].try_mul(invariant_ratio_with_fees.try_sub(Decimal::ONE)?)?;
+            let taxable = amounts_in[i].try_sub(non_taxable)?;
+            non_taxable.try_add(taxable.try_mul(complement(swap_fee))?)?
+        } else {
+            amounts_in[i]
+        };
+        let balance_ratio = balances[i].try_add(amount_in)?.try_div(balances[i])?;
+        invariant_ratio = invariant_ratio.try_mul(pow_down(balance_ratio, weights[i])?)?;
+    }
+
+    if invariant_ratio > Decimal::ONE {
+        total_supply.try_mul(invariant_ratio.try_sub(Decimal::ONE)?)
+    } else {
+        Ok(Decimal::ZERO)
+    }
+}
+
+/// Amount of one token needed to mint exactly `bpt_out`.
+///
+/// The share of the deposit not matched by the token's weight pays the
+/// swap fee. Returns error if the join would more than triple the
+/// invariant.
+pub fn weighted_token_in_given_exact_bpt_out(
+    balance: Decimal,
+    weight: Decimal,
+    bpt_out: Decimal,
+    total_supply: Decimal,
+    swap_fee: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let invariant_ratio = total_supply.try_add(bpt_out)?.try_div(total_supply)?;
+    if bpt_out.is_negative() || invariant_ratio > Decimal::from(MAX_INVARIANT_RATIO) {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let balance_ratio = pow_up(invariant_ratio, Decimal::ONE.try_div(weight)?)?;
+    let amount_without_fee = balance.try_mul(balance_ratio.try_sub(Decimal::ONE)?)?;
+    let taxable = amount_without_fee.try_mul(complement(weight))?;
+    let non_taxable = amount_without_fee.try_sub(taxable)?;
+    non_taxable.try_add(taxable.try_div(complement(swap_fee))?)
+}
+
+/// Amount of one token received for burning exactly `bpt_in`.
+///
+/// The share of the withdrawal not matched by the token's weight pays the
+/// swap fee. Returns error if the exit would shrink the invariant by more
+/// than 30%.
+pub fn weighted_token_out_given_exact_bpt_in(
+    balance: Decimal,
+    weight: Decimal,
+    bpt_in: Decimal,
+    total_supply: Decimal,
+    swap_fee: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let invariant_ratio = total_supply.try_sub(bpt_in)?.try_div(total_supply)?;
+    if bpt_in.is_negative() || invariant_ratio < complement(max_swap_ratio()) {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let balance_ratio = pow_up(invariant_ratio, Decimal::ONE.try_div(weight)?)?;
+    let amount_without_fee = balance.try_mul(complement(balance_ratio))?;
+    let taxable = amount_without_fee.try_mul(complement(weight))?;
+    let non_taxable = amount_without_fee.try_sub(taxable)?;
+    non_taxable.try_add(taxable.try_mul(complement(swap_fee))?)
+}
+
+/// BPT burned to withdraw exact token amounts.
+///
+/// Amounts beyond a proportional exit are effectively swapped out of the
+/// pool and pay the swap fee.
+pub fn weighted_bpt_in_given_exact_tokens_out(
+    balances: &[Decimal],
+    weights: &[Decimal],
+    amounts_out: &[Decimal],
+    total_supply: Decimal,
+    swap_fee: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    if weights.len() != balances.len() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    validate_amounts(balances, amounts_out, MAX_WEIGHTED_TOKENS)?;
+    let mut ratios = [Decimal::ZERO; MAX_WEIGHTED_TOKENS];
+    let mut invariant_ratio_without_fees = Decimal::ZERO;
+    for i in 0..balances.len() {
+        ratios[i] = balances[i].try_sub(amounts_out[i])?.try_div(balances[i])?;
+        invariant_ratio_without_fees =
+            invariant_ratio_without_fees.try_add(ratios[i].try_mul(weights[i])?)?;
+    }
+
+    let mut invariant_ratio = Decimal::ONE;
+    for i in 0..balances.len() {
+        let amount_out = if invariant_ratio_without_fees > ratios[i] {
+            let non_taxable = balances[i].try_mul(complement(invariant_ratio_without_fees))?;
+            let taxable = amounts_out[i].try_sub(non_taxable)?;
+            non_taxable.try_add(taxable.try_div(complement(swap_fee))?)?
+        } else {
+            amounts_out[i]
+        };
+        let balance_ratio = balances[i].try_sub(amount_out)?.try_div(balances[i])?;
+        if balance_ratio.is_negative() {
+            return Err(ArithmeticError::Underflow);
+        }
+        invariant_ratio = invariant_ratio.try_mul(pow_down(balance_ratio, weights[i])?)?;
+    }
+    total_supply.try_mul(complement(invariant_ratio))
+}
+
+/// A Balancer weighted pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedPool {
+    balances: [Decimal; MAX_WEIGHTED_TOKENS],
+    weights: [Decimal; MAX_WEIGHTED_TOKENS],
+    tokens: usize,
+    swap_fee: Decimal,
+    total_supply: Decimal,
+}
+
+impl WeightedPool {
+    /// Creates a pool.
+    ///
+    /// `swap_fee` is a fraction of the input (0.003 = 0.3%) and
+    /// `total_supply` the outstanding BPT.
+    ///
+    /// Returns error unless there are 2 to [`MAX_WEIGHTED_TOKENS`] tokens
+    /// with positive balances, each weight is at least 1% and the weights
+    /// sum to exactly one, and 0 ≤ fee < 1.
+    pub fn new(
+        balances: &[Decimal],
+        weights: &[Decimal],
+        swap_fee: Decimal,
+        total_supply: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        let tokens = balances.len();
+        if !(2..=MAX_WEIGHTED_TOKENS).contains(&tokens)
+            || weights.len() != tokens
+            || balances.iter().any(|b| !b.is_positive())
+            || weights.iter().any(|&w| w < min_weight())
+            || swap_fee.is_negative()
+            || swap_fee >= Decimal::ONE
+            || !total_supply.is_positive()
+        {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let weight_sum = weights
+            .iter()
+            .try_fold(Decimal::ZERO, |acc, &w| acc.try_add(w))?;
+        if weight_sum != Decimal::ONE {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+
+        let mut pool = Self {
+            balances: [Decimal::ZERO; MAX_WEIGHTED_TOKENS],
+            weights: [Decimal::ZERO; MAX_WEIGHTED_TOKENS],
+            tokens,
+            swap_fee,
+            total_supply,
+        };
+        pool.balances[..tokens].copy_from_slice(balances);
+        pool.weights[..tokens].copy_from_slice(weights);
+        Ok(pool)
+    }
+
+    /// Token balances.
+    pub fn balances(&self) -> &[Decimal] {
+        &self.balances[..self.tokens]
+    }
+
+    /// Normalized weights.
+    pub fn weights(&self) -> &[Decimal] {
+        &self.weights[..self.tokens]
+    }
+
+    /// Swap fee as a fraction.
+    pub fn swap_fee(&self) -> Decimal {
+        self.swap_fee
+    }
+
+    /// Outstanding BPT.
+    pub fn total_supply(&self) -> Decimal {
+        self.total_supply
+    }
+
+    /// Invariant of the current balances.
+    pub fn invariant(&self) -> Result<Decimal, ArithmeticError> {
+        weighted_invariant(self.balances(), self.weights())
+    }
+
+    /// Spot price of token `out` in units of token `in`, including the fee.
+    pub fn spot_price(
+        &self,
+        token_in: usize,
+        token_out: usize,
+    ) -> Result<Decimal, ArithmeticError> {
+        self.check_pair(token_in, token_out)?;
+        weighted_spot_price(
+            self.balances[token_in],
+            self.weights[token_in],
+            self.balances[token_out],
+            self.weights[token_out],
+            self.swap_fee,
+        )
+    }
+
+    /// Output for an exact input, after the swap fee.
+    pub fn calc_out_given_in(
+        &self,
+        token_in: usize,
+        token_out: usize,
+        amount_in: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        self.check_pair(token_in, token_out)?;
+        let net = amount_in.try_mul(complement(self.swap_fee))?;
+        weighted_out_given_in(
+            self.balances[token_in],
+            self.weights[token_in],
+            self.balances[token_out],
+            self.weights[token_out],
+            net,
+        )
+    }
+
+    /// Input, including the swap fee, for an exact output.
+    pub fn calc_in_given_out(
+        &self,
+        token_in: usize,
+        token_out: usize,
+        amount_out: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        self.check_pair(token_in, token_out)?;
+        weighted_in_given_out(
+            self.balances[token_in],
+            self.weights[token_in],
+            self.balances[token_out],
+            self.weights[token_out],
+            amount_out,
+        )?
+        .try_div(complement(self.swap_fee))
+    }
+
+    /// Swaps an exact input and returns the output.
+    pub fn swap_exact_in(
+        &mut self,
+        token_in: usize,
+        token_out: usize,
+        amount_in: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        let amount_out = self.calc_out_given_in(token_in, token_out, amount_in)?;
+        self.apply_swap(token_in, token_out, amount_in, amount_out)?;
+        Ok(amount_out)
+    }
+
+    /// Swaps for an exact output and returns the input paid.
+    pub fn swap_exact_out(
+        &mut self,
+        token_in: usize,
+        token_out: usize,
+        amount_out: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        let amount_in = self.calc_in_given_out(token_in, token_out, amount_out)?;
+        self.apply_swap(token_in, token_out, amount_in, amount_out)?;
+        Ok(amount_in)
+    }
+
+    /// Mints exactly `bpt_out` for tokens in proportion to the balances,
+    /// writing the amounts required to `amounts_in`.
+    pub fn join_proportional(
+        &mut self,
+        bpt_out: Decimal,
+        amounts_in: &mut [Decimal],
+    ) -> Result<(), ArithmeticError> {
+        if bpt_out.is_negative() || amounts_in.len() != self.tokens {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let ratio = bpt_out.try_div(self.total_supply)?;
+        let mut balances = self.balances;
+        for (i, amount) in amounts_in.iter_mut().enumerate() {
+            *amount = self.balances[i].try_mul(ratio)?;
+            balances[i] = balances[i].try_add(*amount)?;
+        }
+        self.balances = balances;
+        self.total_supply = self.total_supply.try_add(bpt_out)?;
+        Ok(())
+    }
+
+    /// Deposits exact token amounts and returns the BPT minted.
+    pub fn join_exact_tokens_in(
+        &mut self,
+        amounts_in: &[Decimal],
+    ) -> Result<Decimal, ArithmeticError> {
+        let bpt_out = weighted_bpt_out_given_exact_tokens_in(
+            self.balances(),
+            self.weights(),
+            amounts_in,
+            self.total_supply,
+            self.swap_fee,
+        )?;
+        let mut balances = self.balances;
+        for (balance, &amount) in balances.iter_mut().zip(amounts_in) {
+            *balance = balance.try_add(amount)?;
+        }
+        self.balances = balances;
+        self.total_supply = self.total_supply.try_add(bpt_out)?;
+        Ok(bpt_out)
+    }
+
+    /// Mints exactly `bpt_out` for a single token and returns the amount
+    /// deposited.
+    pub fn join_single_token(
+        &mut self,
+        token: usize,
+        bpt_out: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        self.check_token(token)?;
+        let amount_in = weighted_token_in_given_exact_bpt_out(
+            self.balances[token],
+            self.weights[token],
+            bpt_out,
+            self.total_supply,
+            self.swap_fee,
+        )?;
+        let balance = self.balances[token].try_add(amount_in)?;
+        self.total_supply = self.total_supply.try_add(bpt_out)?;
+        self.balances[token] = balance;
+        Ok(amount_in)
+    }
+
+    /// Burns exactly `bpt_in` for tokens in proportion to the balances,
+    /// writing the amounts received to `amounts_out`.
+    pub fn exit_proportional(
+        &mut self,
+        bpt_in: Decimal,
+        amounts_out: &mut [Decimal],
+    ) -> Result<(), ArithmeticError> {
+        if bpt_in.is_negative() || bpt_in > self.total_supply || amounts_out.len() != self.tokens {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let ratio = bpt_in.try_div(self.total_supply)?;
+        let mut balances = self.balances;
+        for (i, amount) in amounts_out.iter_mut().enumerate() {
+            *amount = self.balances[i].try_mul(ratio)?;
+            balances[i] = balances[i].try_sub(*amount)?;
+        }
+        self.balances = balances;
+        self.total_supply = self.total_supply.try_sub(bpt_in)?;
+        Ok(())
+    }
+
+    /// Withdraws exact token amounts and returns the BPT burned.
+    pub fn exit_exact_tokens_out(
+        &mut self,
+        amounts_out: &[Decimal],
+    ) -> Result<Decimal, ArithmeticError> {
+        let bpt_in = weighted_bpt_in_given_exact_tokens_out(
+            self.balances(),
+            self.weights(),
+            amounts_out,
+            self.total_supply,
+            self.swap_fee,
+        )?;
+        if bpt_in > self.total_supply {
+            return Err(ArithmeticError::Underflow);
+        }
+        let mut balances = self.balances;
+        for (balance, &amount) in balances.iter_mut().zip(amounts_out) {
+            *balance = balance.try_sub(amount)?;
+        }
+        self.balances = balances;
+        self.total_supply = self.total_supply.try_sub(bpt_in)?;
+        Ok(bpt_in)
+    }
+
+    /// Burns exactly `bpt_in` for a single token and returns the amount
+    /// received.
+    pub fn exit_single_token(
+        &mut self,
+        token: usize,
+        bpt_in: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        self.check_token(token)?;
+        let amount_out = weighted_token_out_given_exact_bpt_in(
+            self.balances[token],
+            self.weights[token],
+            bpt_in,
+            self.total_supply,
+            self.swap_fee,
+        )?;
+        let balance = self.balances[token].try_sub(amount_out)?;
+        self.total_supply = self.total_supply.try_sub(bpt_in)?;
+        self.balances[token] = balance;
+        Ok(amount_out)
+    }
+
+    fn apply_swap(
+        &mut self,
+        token_in: usize,
+        token_out: usize,
+        amount_in: Decimal,
+        amount_out: Decimal,
+    ) -> Result<(), ArithmeticError> {
+        let balance_in = self.balances[token_in].try_add(amount_in)?;
+        let balance_out = self.balances[token_out].try_sub(amount_out)?;
+        self.balances[token_in] = balance_in;
+        self.balances[token_out] = balance_out;
+        Ok(())
+    }
+
+    fn check_token(&self, token: usize) -> Result<(), ArithmeticError> {
+        if token < self.tokens {
+            Ok(())
+        } else {
+            Err(ArithmeticError::DivisionByZero)
+        }
+    }
+
+    fn check_pair(&self, token_in: usize, token_out: usize) -> Result<(), ArithmeticError> {
+        self.check_token(token_in)?;
+        self.check_token(token_out)?;
+        if token_in == token_out {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(())
+    }
+}
+
+/// x^y rounded up: exact for y = 1, 2 and 4, otherwise widened by the
+/// relative error bound.
+fn pow_up(x: Decimal, y: Decimal) -> Result<Decimal, ArithmeticError> {
+    if let Some(exact) = exact_pow(x, y)? {
+        return Ok(exact);
+    }
+    let raw = x.try_pow(y)?;
+    raw.try_add(raw.try_mul(max_pow_relative_error())?)
+}
+
+/// x^y rounded down: exact for y = 1, 2 and 4, otherwise narrowed by the
+/// relative error bound.
+fn pow_down(x: Decimal, y: Decimal) -> Result<Decimal, ArithmeticError> {
+    if let Some(exact) = exact_pow(x, y)? {
+        return Ok(exact);
+    }
+    let raw = x.try_pow(y)?;
+    let error = raw.try_mul(max_pow_relative_error())?;
+    Ok(raw.try_sub(error)?.max(Decimal::ZERO))
+}
+
+fn exact_pow(x: Decimal, y: Decimal) -> Result<Option<Decimal>, ArithmeticError> {
+    if y == Decimal::ONE {
+        Ok(Some(x))
+    } else if y == Decimal::from(2i64) {
+        x.try_mul(x).map(Some)
+    } else if y == Decimal::from(4i64) {
+        let square = x.try_mul(x)?;
+        square.try_mul(square).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    /// Within the power error margins, amplified by 1 − ratio, of the exact
+    /// value.
+    fn assert_close(actual: Decimal, expected: &str) {
+        let expected = decimal(expected);
+        let tolerance = expected.abs() * decimal("0.0000000001");
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} vs {expected}"
+        );
+    }
+
+    fn pool() -> WeightedPool {
+        WeightedPool::new(
+            &[
+                Decimal::from(1_000i64),
+                Decimal::from(2_000_000i64),
+                Decimal::from(500_000i64),
+            ],
+            &[decimal("0.5"), decimal("0.3"), decimal("0.2")],
+            decimal("0.003"),
+            Decimal::from(100_000i64),
+        )
+        .unwrap()
+    }
+
+    // Expected values are the exact formulas evaluated to 60 digits
+
+    #[test]
+    fn test_invariant_and_spot_price() {
+        let pool = pool();
+        assert_close(pool.invariant().unwrap(), "33892.4527733983131207750358");
+        assert_close(
+            pool.spot_price(0, 1).unwrap(),
+            "0.000300902708124373119358074223",
+        );
+        // Equal weights and no fee: the reserve ratio
+        let price = weighted_spot_price(
+            Decimal::from(100i64),
+            decimal("0.5"),
+            Decimal::from(400i64),
+            decimal("0.5"),
+            Decimal::ZERO,
+        )
+        .unwrap();
+        assert_eq!(price, decimal("0.25"));
+    }
+
+    #[test]
+    fn test_swaps() {
+        let mut pool = pool();
+        let quoted = pool.calc_out_given_in(0, 1, Decimal::from(10i64)).unwrap();
+        assert_close(quoted, "32796.8729943266588714193972");
+        // Rounding favours the pool
+        assert!(quoted < decimal("32796.8729943266588714193972"));
+        assert_close(
+            pool.calc_in_given_out(0, 1, Decimal::from(20_000i64))
+                .unwrap(),
+            "6.06661963768239219265838923876",
+        );
+
+        // Fees stay in the pool, so the invariant grows
+        let before = pool.invariant().unwrap();
+        let out = pool.swap_exact_in(0, 1, Decimal::from(10i64)).unwrap();
+        assert_eq!(pool.balances()[1], Decimal::from(2_000_000i64) - out);
+        assert!(pool.invariant().unwrap() > before);
+
+        // Swapping back at the new price costs more than the first leg paid
+        let paid = pool.swap_exact_out(1, 0, Decimal::from(10i64)).unwrap();
+        assert!(paid > out);
+
+        // Equal weights match the constant-product formula
+        let out = weighted_out_given_in(
+            Decimal::from(1_000i64),
+            decimal("0.5"),
+            Decimal::from(1_000i64),
+            decimal("0.5"),
+            Decimal::from(100i64),
+        )
+        .unwrap();
+        assert_close(out, "90.9090909090909090909090909");
+
+        assert!(pool.calc_out_given_in(0, 1, Decimal::from(400i64)).is_err());
+        assert!(pool.calc_out_given_in(1, 1, Decimal::ONE).is_err());
+    }
+
+    #[test]
+    fn test_joins() {
+        let mut pool = pool();
+        let amounts = [Decimal::from(10i64), Decimal::ZERO, Decimal::from(5_000i64)];
+        assert_close(
+            weighted_bpt_out_given_exact_tokens_in(
+                pool.balances(),
+                pool.weights(),
+                &amounts,
+                pool.total_supply(),
+                pool.swap_fee(),
+            )
+            .unwrap(),
+            "698.326400977786399873066077896",
+        );
+
+        let mut copy = pool.clone();
+        assert_close(
+            copy.join_single_token(2, Decimal::from(1_000i64)).unwrap(),
+            "25566.4212988665997993981946",
+        );
+        assert_eq!(copy.total_supply(), Decimal::from(101_000i64));
+
+        let mut required = [Decimal::ZERO; 3];
+        pool.join_proportional(Decimal::from(1_000i64), &mut required)
+            .unwrap();
+        assert_eq!(required[1], Decimal::from(20_000i64));
+        assert_eq!(pool.balances()[0], Decimal::from(1_010i64));
+
+        // A proportional deposit pays no fee
+        let minted = pool
+            .join_exact_tokens_in(&[
+                decimal("10.1"),
+                Decimal::from(20_200i64),
+                Decimal::from(5_050i64),
+            ])
+            .unwrap();
+        assert_close(minted, "1010");
+    }
+
+    #[test]
+    fn test_exits() {
+        let pool = pool();
+        let mut single = pool.clone();
+        assert_close(
+            single
+                .exit_single_token(0, Decimal::from(1_000i64))
+                .unwrap(),
+            "19.87015",
+        );
+        assert!(single
+            .exit_single_token(0, Decimal::from(40_000i64))
+            .is_err());
+
+        let mut exact = pool.clone();
+        let amounts = [Decimal::ZERO, Decimal::from(30_000i64), Decimal::ZERO];
+        let burned = exact.exit_exact_tokens_out(&amounts).unwrap();
+        assert_close(burned, "453.340722128162882301574142308");
+        assert!(burned > decimal("453.340722128162882301574142308"));
+        assert_eq!(exact.balances()[1], Decimal::from(1_970_000i64));
+
+        let mut proportional = pool;
+        let mut received = [Decimal::ZERO; 3];
+        proportional
+            .exit_proportional(Decimal::from(10_000i64), &mut received)
+            .unwrap();
+        assert_eq!(received[2], Decimal::from(50_000i64));
+        assert_eq!(proportional.total_supply(), Decimal::from(90_000i64));
+    }
+
+    #[test]
+    fn test_rejects_invalid_pools() {
+        let balances = [Decimal::ONE, Decimal::ONE];
+        let fee = decimal("0.003");
+        let supply = Decimal::ONE;
+        for weights in [
+            [decimal("0.5"), decimal("0.4")],
+            [decimal("0.995"), decimal("0.005")],
+        ] {
+            assert!(WeightedPool::new(&balances, &weights, fee, supply).is_err());
+        }
+        let weights = [decimal("0.5"), decimal("0.5")];
+        assert!(WeightedPool::new(&balances, &weights, Decimal::ONE, supply).is_err());
+        assert!(WeightedPool::new(&balances[..1], &weights[..1], fee, supply).is_err());
+    }
+}
@@ -83,11 +83,8 @@ impl Date {
         let (y_adj, m_adj) = if m <= 2 { (y - 1, m + 12) } else { (y, m) };
 
         // Julian day number approximation
-        let jdn = d + (153 * (m_adj - 3) + 2) / 5 + 365 * y_adj + y_adj / 4 - y_adj / 100
-            + y_adj / 400
-            - 32045;
-
-        jdn
+        d + (153 * (m_adj - 3) + 2) / 5 + 365 * y_adj + y_adj / 4 - y_adj / 100 + y_adj / 400
+            - 32045
     }
 
     /// Calculates actual days between two dates.
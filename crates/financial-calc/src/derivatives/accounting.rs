@@ -0,0 +1,364 @@
+//! Trade accounting for perpetual positions.
+//!
+//! [`calculate_pnl`] measures price PnL only. A [`PositionLedger`] also
+//! tracks what the position has cost along the way, so net PnL and
+//! breakeven reflect everything the trader has paid:
+//!
+//! - Open/close trading fees and price impact on every fill
+//! - Borrow fees accrued on notional
+//! - Funding paid or received
+//! - PnL realised by partial closes
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::derivatives::{Fill, PositionLedger};
+//! use precision_core::Decimal;
+//!
+//! let fee = Decimal::new(1, 3); // 0.1%
+//! let mut ledger = PositionLedger::open(
+//!     true,
+//!     Decimal::from(10i64),
+//!     Decimal::from(300i64),
+//!     &Fill::new(Decimal::new(15, 1), Decimal::from(2000i64), fee, Decimal::ZERO),
+//! ).unwrap();
+//!
+//! ledger.record_funding(Decimal::from(-2i64)).unwrap();
+//!
+//! // Opening fee 3 and funding 2 are recovered above the entry price
+//! let breakeven = ledger.breakeven_price(fee).unwrap();
+//! assert!(breakeven > Decimal::from(2003i64));
+//! ```
+
+use super::{calculate_pnl, calculate_position_after_fill, PerpPosition};
+use precision_core::{ArithmeticError, Decimal};
+
+/// An executed trade on a position.
+#[derive(Debug, Clone, Copy)]
+pub struct Fill {
+    /// Fill size in base units.
+    pub size: Decimal,
+    /// Execution price.
+    pub price: Decimal,
+    /// Trading fee as decimal of fill notional.
+    pub fee_rate: Decimal,
+    /// Price impact cost in quote currency (negative for a rebate).
+    pub price_impact: Decimal,
+}
+
+impl Fill {
+    /// Creates a fill.
+    pub fn new(size: Decimal, price: Decimal, fee_rate: Decimal, price_impact: Decimal) -> Self {
+        Self {
+            size,
+            price,
+            fee_rate,
+            price_impact,
+        }
+    }
+
+    /// Trading fee charged on the fill.
+    pub fn fee(&self) -> Result<Decimal, ArithmeticError> {
+        self.size.try_mul(self.price)?.try_mul(self.fee_rate)
+    }
+}
+
+/// Running account of a perpetual position's PnL and costs.
+///
+/// Collateral and leverage are carried as given when the position was
+/// opened; only size and entry price change with fills.
+#[derive(Debug, Clone, Copy)]
+pub struct PositionLedger {
+    position: PerpPosition,
+    realized_pnl: Decimal,
+    trading_fees: Decimal,
+    borrow_fees: Decimal,
+    funding_paid: Decimal,
+    price_impact: Decimal,
+}
+
+impl PositionLedger {
+    /// Opens a position with its first fill.
+    ///
+    /// Returns error if the fill size or price is not positive.
+    pub fn open(
+        is_long: bool,
+        leverage: Decimal,
+        collateral: Decimal,
+        fill: &Fill,
+    ) -> Result<Self, ArithmeticError> {
+        let mut ledger = Self {
+            position: PerpPosition {
+                size: Decimal::ZERO,
+                entry_price: Decimal::ZERO,
+                is_long,
+                leverage,
+                collateral,
+            },
+            realized_pnl: Decimal::ZERO,
+            trading_fees: Decimal::ZERO,
+            borrow_fees: Decimal::ZERO,
+            funding_paid: Decimal::ZERO,
+            price_impact: Decimal::ZERO,
+        };
+        ledger.increase(fill)?;
+        Ok(ledger)
+    }
+
+    /// The current position.
+    pub fn position(&self) -> &PerpPosition {
+        &self.position
+    }
+
+    /// Price PnL realised by closes.
+    pub fn realized_pnl(&self) -> Decimal {
+        self.realized_pnl
+    }
+
+    /// Open and close fees paid.
+    pub fn trading_fees(&self) -> Decimal {
+        self.trading_fees
+    }
+
+    /// Borrow fees paid.
+    pub fn borrow_fees(&self) -> Decimal {
+        self.borrow_fees
+    }
+
+    /// Net funding paid (negative if funding was received).
+    pub fn funding_paid(&self) -> Decimal {
+        self.funding_paid
+    }
+
+    /// Net price impact paid.
+    pub fn price_impact(&self) -> Decimal {
+        self.price_impact
+    }
+
+    /// All costs: trading fees, borrow fees, funding and price impact.
+    pub fn total_costs(&self) -> Result<Decimal, ArithmeticError> {
+        self.trading_fees
+            .try_add(self.borrow_fees)?
+            .try_add(self.funding_paid)?
+            .try_add(self.price_impact)
+    }
+
+    /// Adds to the position at a size-weighted entry price.
+    pub fn increase(&mut self, fill: &Fill) -> Result<(), ArithmeticError> {
+        if !fill.size.is_positive() || !fill.price.is_positive() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let outcome = calculate_position_after_fill(
+            self.position.size,
+            self.position.entry_price,
+            fill.size,
+            fill.price,
+        )?;
+        self.position.size = outcome.size;
+        self.position.entry_price = outcome.entry_price;
+        self.record_fill_costs(fill)
+    }
+
+    /// Closes part or all of the position, keeping the entry price.
+    ///
+    /// Returns the price PnL realised, or `Underflow` if the fill is larger
+    /// than the position.
+    pub fn decrease(&mut self, fill: &Fill) -> Result<Decimal, ArithmeticError> {
+        if !fill.size.is_positive() || !fill.price.is_positive() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        if fill.size > self.position.size {
+            return Err(ArithmeticError::Underflow);
+        }
+        let outcome = calculate_position_after_fill(
+            self.signed_size(),
+            self.position.entry_price,
+            if self.position.is_long {
+                -fill.size
+            } else {
+                fill.size
+            },
+            fill.price,
+        )?;
+        self.position.size = outcome.size.abs();
+        self.position.entry_price = outcome.entry_price;
+        self.realized_pnl = self.realized_pnl.try_add(outcome.realized_pnl)?;
+        self.record_fill_costs(fill)?;
+        Ok(outcome.realized_pnl)
+    }
+
+    /// Records a funding payment (positive = received), as returned by
+    /// [`calculate_funding_payment`](super::calculate_funding_payment) or
+    /// [`PositionFunding::settle`](super::PositionFunding::settle).
+    pub fn record_funding(&mut self, payment: Decimal) -> Result<(), ArithmeticError> {
+        self.funding_paid = self.funding_paid.try_sub(payment)?;
+        Ok(())
+    }
+
+    /// Accrues a borrow fee on the position notional.
+    ///
+    /// fee = size × price × rate_per_hour × hours
+    ///
+    /// Returns the fee charged.
+    pub fn accrue_borrow_fee(
+        &mut self,
+        rate_per_hour: Decimal,
+        hours: Decimal,
+        price: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        let fee = self
+            .position
+            .size
+            .try_mul(price)?
+            .try_mul(rate_per_hour)?
+            .try_mul(hours)?;
+        self.borrow_fees = self.borrow_fees.try_add(fee)?;
+        Ok(fee)
+    }
+
+    /// Price PnL of the open size at a price.
+    pub fn unrealized_pnl(&self, price: Decimal) -> Result<Decimal, ArithmeticError> {
+        calculate_pnl(&self.position, price)
+    }
+
+    /// Net PnL if the position were closed at a price: realised and
+    /// unrealised PnL less all costs and the closing fee.
+    pub fn net_pnl(
+        &self,
+        price: Decimal,
+        close_fee_rate: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        let close_fee = self.position.size.try_mul(price)?.try_mul(close_fee_rate)?;
+        self.realized_pnl
+            .try_add(self.unrealized_pnl(price)?)?
+            .try_sub(self.total_costs()?)?
+            .try_sub(close_fee)
+    }
+
+    /// Price at which closing the position gives zero net PnL, including
+    /// realised PnL, all costs so far and the closing fee.
+    ///
+    /// Long: P = (size × entry + costs − realised) / (size × (1 − fee))
+    /// Short: P = (size × entry − costs + realised) / (size × (1 + fee))
+    ///
+    /// Returns error if the position is closed.
+    pub fn breakeven_price(&self, close_fee_rate: Decimal) -> Result<Decimal, ArithmeticError> {
+        let size = self.position.size;
+        let cost_basis = size.try_mul(self.position.entry_price)?;
+        let net_costs = self.total_costs()?.try_sub(self.realized_pnl)?;
+
+        if self.position.is_long {
+            let denominator = size.try_mul(Decimal::ONE.try_sub(close_fee_rate)?)?;
+            cost_basis.try_add(net_costs)?.try_div(denominator)
+        } else {
+            let denominator = size.try_mul(Decimal::ONE.try_add(close_fee_rate)?)?;
+            cost_basis.try_sub(net_costs)?.try_div(denominator)
+        }
+    }
+
+    fn signed_size(&self) -> Decimal {
+        if self.position.is_long {
+            self.position.size
+        } else {
+            -self.position.size
+        }
+    }
+
+    fn record_fill_costs(&mut self, fill: &Fill) -> Result<(), ArithmeticError> {
+        self.trading_fees = self.trading_fees.try_add(fill.fee()?)?;
+        self.price_impact = self.price_impact.try_add(fill.price_impact)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn fill(size: &str, price: &str) -> Fill {
+        Fill::new(
+            decimal(size),
+            decimal(price),
+            decimal("0.001"),
+            Decimal::ZERO,
+        )
+    }
+
+    fn open_long() -> PositionLedger {
+        PositionLedger::open(true, decimal("10"), decimal("300"), &fill("1.5", "2000")).unwrap()
+    }
+
+    #[test]
+    fn test_costs_accumulate() {
+        let mut ledger = open_long();
+        assert_eq!(ledger.trading_fees(), decimal("3"));
+
+        ledger
+            .increase(&Fill::new(
+                decimal("0.5"),
+                decimal("2400"),
+                decimal("0.001"),
+                decimal("1.2"),
+            ))
+            .unwrap();
+        assert_eq!(ledger.position().size, decimal("2"));
+        assert_eq!(ledger.position().entry_price, decimal("2100"));
+
+        ledger.record_funding(decimal("-2")).unwrap();
+        let borrow = ledger
+            .accrue_borrow_fee(decimal("0.0001"), decimal("10"), decimal("2100"))
+            .unwrap();
+        assert_eq!(borrow, decimal("4.2"));
+
+        // 3 + 1.2 fees, 1.2 impact, 2 funding, 4.2 borrow
+        assert_eq!(ledger.total_costs().unwrap(), decimal("11.6"));
+    }
+
+    #[test]
+    fn test_partial_close_realises_pnl() {
+        let mut ledger = open_long();
+        let realized = ledger.decrease(&fill("0.5", "2200")).unwrap();
+        assert_eq!(realized, decimal("100"));
+        assert_eq!(ledger.position().size, decimal("1"));
+        assert_eq!(ledger.position().entry_price, decimal("2000"));
+        assert_eq!(ledger.trading_fees(), decimal("4.1"));
+
+        assert_eq!(
+            ledger.decrease(&fill("2", "2200")).unwrap_err(),
+            ArithmeticError::Underflow
+        );
+    }
+
+    #[test]
+    fn test_breakeven_gives_zero_net_pnl() {
+        let mut ledger = open_long();
+        ledger.record_funding(decimal("-2")).unwrap();
+        ledger.decrease(&fill("0.5", "1990")).unwrap();
+
+        let close_fee = decimal("0.001");
+        let breakeven = ledger.breakeven_price(close_fee).unwrap();
+        let net = ledger.net_pnl(breakeven, close_fee).unwrap();
+        assert!(net.abs() < decimal("0.0000000001"));
+
+        let short =
+            PositionLedger::open(false, decimal("5"), decimal("400"), &fill("1", "2000")).unwrap();
+        let breakeven = short.breakeven_price(close_fee).unwrap();
+        assert!(breakeven < decimal("2000"));
+        assert!(short.net_pnl(breakeven, close_fee).unwrap().abs() < decimal("0.0000000001"));
+    }
+
+    #[test]
+    fn test_breakeven_matches_simple_formula_without_extra_costs() {
+        // With no close fee, breakeven only recovers the opening fee
+        let ledger = open_long();
+        assert_eq!(
+            ledger.breakeven_price(Decimal::ZERO).unwrap(),
+            decimal("2002")
+        );
+    }
+}
@@ -0,0 +1,422 @@
+//! Auto-deleveraging and insurance-fund loss socialisation.
+//!
+//! When a position falls below maintenance margin it is liquidated at the
+//! mark price. Remaining equity goes to the insurance fund; negative equity
+//! (bad debt) is drawn from the fund. Once the fund is exhausted, the
+//! remaining loss is socialised by auto-deleveraging (ADL): the most
+//! profitable, most leveraged positions on the opposite side are closed
+//! against the bankrupt position at a price that makes them absorb the loss.
+//! A counterparty gives up at most its own profit; any loss beyond that is
+//! reported as uncovered.
+//!
+//! ADL rank = PnL% × effective leverage
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::derivatives::{AdlSimulator, PerpPosition};
+//! use precision_core::Decimal;
+//!
+//! let position = |is_long, collateral: i64| PerpPosition {
+//!     size: Decimal::from(10i64),
+//!     entry_price: Decimal::from(100i64),
+//!     is_long,
+//!     leverage: Decimal::from(10i64),
+//!     collateral: Decimal::from(collateral),
+//! };
+//!
+//! let mut sim = AdlSimulator::new(Decimal::ZERO, Decimal::new(1, 2));
+//! sim.add_position(1, position(true, 100)).unwrap();
+//! sim.add_position(2, position(false, 100)).unwrap();
+//!
+//! // A 15% crash bankrupts the long by 50 with an empty fund
+//! let report = sim.run(&[Decimal::from(85i64)]).unwrap();
+//! assert_eq!(report.bad_debt, Decimal::from(50i64));
+//! assert_eq!(report.socialized_loss, Decimal::from(50i64));
+//! ```
+
+use super::{calculate_effective_leverage, calculate_pnl, calculate_pnl_percentage, PerpPosition};
+use crate::storage::{Buffer, Fixed, Storage};
+use precision_core::{ArithmeticError, Decimal};
+
+/// Default number of accounts in an ADL simulation (for no_std fixed allocation).
+pub const MAX_ADL_ACCOUNTS: usize = 32;
+
+/// Calculate a position's auto-deleveraging rank.
+///
+/// score = PnL% × effective leverage
+///
+/// Higher scores are deleveraged first.
+pub fn calculate_adl_score(
+    position: &PerpPosition,
+    current_price: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let pnl_pct = calculate_pnl_percentage(position, current_price)?;
+    let leverage = calculate_effective_leverage(position, current_price)?;
+    pnl_pct.try_mul(leverage)
+}
+
+/// State of an account in the simulation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AccountStatus {
+    /// Position still open.
+    #[default]
+    Open,
+    /// Position liquidated below maintenance margin.
+    Liquidated,
+    /// Position fully closed by auto-deleveraging.
+    Deleveraged,
+}
+
+/// Per-account result of the simulation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AccountOutcome {
+    /// Account identifier.
+    pub account_id: u32,
+    /// Remaining position; collateral includes realised PnL.
+    pub position: PerpPosition,
+    /// Account status.
+    pub status: AccountStatus,
+    /// PnL realised by liquidation or deleveraging.
+    pub realized_pnl: Decimal,
+    /// Size closed by auto-deleveraging.
+    pub deleveraged_size: Decimal,
+    /// Profit given up to absorb other accounts' bad debt.
+    pub socialized_loss: Decimal,
+}
+
+/// Totals across the simulation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AdlReport {
+    /// Insurance fund balance at the end.
+    pub insurance_fund: Decimal,
+    /// Number of liquidations.
+    pub liquidations: u32,
+    /// Negative equity of liquidated accounts.
+    pub bad_debt: Decimal,
+    /// Bad debt paid by the insurance fund.
+    pub insurance_used: Decimal,
+    /// Remaining equity of liquidated accounts paid into the insurance fund.
+    pub insurance_collected: Decimal,
+    /// Bad debt absorbed by deleveraged counterparties.
+    pub socialized_loss: Decimal,
+    /// Bad debt left once the profit of counterparties was exhausted.
+    pub uncovered_loss: Decimal,
+}
+
+/// Replays a price path over a set of positions in one market, liquidating,
+/// drawing on the insurance fund and deleveraging counterparties.
+#[derive(Debug, Clone)]
+pub struct AdlSimulator<S: Storage = Fixed<MAX_ADL_ACCOUNTS>> {
+    accounts: S::Buffer<AccountOutcome>,
+    maintenance_margin_rate: Decimal,
+    report: AdlReport,
+}
+
+impl AdlSimulator {
+    /// Creates a simulator with default fixed storage.
+    pub fn new(insurance_fund: Decimal, maintenance_margin_rate: Decimal) -> Self {
+        Self::with_storage(insurance_fund, maintenance_margin_rate)
+    }
+}
+
+impl<S: Storage> AdlSimulator<S> {
+    /// Creates a simulator with the chosen storage.
+    pub fn with_storage(insurance_fund: Decimal, maintenance_margin_rate: Decimal) -> Self {
+        Self {
+            accounts: Default::default(),
+            maintenance_margin_rate,
+            report: AdlReport {
+                insurance_fund,
+                ..AdlReport::default()
+            },
+        }
+    }
+
+    /// Adds an account's position.
+    ///
+    /// Returns `Overflow` if the storage is full.
+    pub fn add_position(
+        &mut self,
+        account_id: u32,
+        position: PerpPosition,
+    ) -> Result<(), ArithmeticError> {
+        self.accounts.push(AccountOutcome {
+            account_id,
+            position,
+            ..AccountOutcome::default()
+        })
+    }
+
+    /// Returns per-account outcomes in insertion order.
+    pub fn accounts(&self) -> &[AccountOutcome] {
+        self.accounts.as_slice()
+    }
+
+    /// Returns the totals so far.
+    pub fn report(&self) -> AdlReport {
+        self.report
+    }
+
+    /// Processes each price in turn and returns the totals.
+    pub fn run(&mut self, prices: &[Decimal]) -> Result<AdlReport, ArithmeticError> {
+        for &price in prices {
+            self.step(price)?;
+        }
+        Ok(self.report)
+    }
+
+    /// Liquidates every open account below maintenance margin at a price,
+    /// settling each one's bad debt before the next.
+    pub fn step(&mut self, price: Decimal) -> Result<(), ArithmeticError> {
+        for index in 0..self.accounts.len() {
+            let account = self.accounts.as_slice()[index];
+            if account.status == AccountStatus::Open
+                && self.is_liquidatable(&account.position, price)?
+            {
+                self.liquidate(index, price)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_liquidatable(
+        &self,
+        position: &PerpPosition,
+        price: Decimal,
+    ) -> Result<bool, ArithmeticError> {
+        if position.size.is_zero() {
+            return Ok(false);
+        }
+        let equity = position
+            .collateral
+            .try_add(calculate_pnl(position, price)?)?;
+        let maintenance = position
+            .size
+            .try_mul(price)?
+            .try_mul(self.maintenance_margin_rate)?;
+        Ok(equity < maintenance)
+    }
+
+    fn liquidate(&mut self, index: usize, price: Decimal) -> Result<(), ArithmeticError> {
+        let account = &mut self.accounts.as_mut_slice()[index];
+        let position = account.position;
+        let equity = position
+            .collateral
+            .try_add(calculate_pnl(&position, price)?)?;
+
+        account.status = AccountStatus::Liquidated;
+        account.realized_pnl = account.realized_pnl.try_sub(position.collateral)?;
+        account.position.size = Decimal::ZERO;
+        account.position.collateral = Decimal::ZERO;
+        self.report.liquidations += 1;
+
+        if !equity.is_negative() {
+            self.report.insurance_fund = self.report.insurance_fund.try_add(equity)?;
+            self.report.insurance_collected = self.report.insurance_collected.try_add(equity)?;
+            return Ok(());
+        }
+
+        let bad_debt = -equity;
+        let covered = bad_debt.min(self.report.insurance_fund);
+        self.report.bad_debt = self.report.bad_debt.try_add(bad_debt)?;
+        self.report.insurance_fund = self.report.insurance_fund.try_sub(covered)?;
+        self.report.insurance_used = self.report.insurance_used.try_add(covered)?;
+
+        let residual = bad_debt.try_sub(covered)?;
+        if residual.is_positive() {
+            let socialized = self.deleverage(&position, price, residual)?;
+            self.report.socialized_loss = self.report.socialized_loss.try_add(socialized)?;
+            self.report.uncovered_loss = self
+                .report
+                .uncovered_loss
+                .try_add(residual.try_sub(socialized)?)?;
+        }
+        Ok(())
+    }
+
+    /// Closes the bankrupt size against opposite-side profitable positions
+    /// in rank order, at a price shifted from mark by residual / size but
+    /// never past the counterparty's entry price.
+    ///
+    /// Returns the loss absorbed.
+    fn deleverage(
+        &mut self,
+        bankrupt: &PerpPosition,
+        price: Decimal,
+        residual: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        let shift = residual.try_div(bankrupt.size)?;
+        let mut remaining = bankrupt.size;
+        let mut absorbed = Decimal::ZERO;
+        while remaining.is_positive() {
+            let Some(index) = self.top_ranked(!bankrupt.is_long, price)? else {
+                break;
+            };
+            let account = &mut self.accounts.as_mut_slice()[index];
+            let closed = account.position.size.min(remaining);
+
+            let closed_position = PerpPosition {
+                size: closed,
+                ..account.position
+            };
+            let profit = calculate_pnl(&closed_position, price)?;
+            let loss = closed.try_mul(shift)?.min(profit);
+            let realized = profit.try_sub(loss)?;
+
+            account.position.size = account.position.size.try_sub(closed)?;
+            account.position.collateral = account.position.collateral.try_add(realized)?;
+            account.realized_pnl = account.realized_pnl.try_add(realized)?;
+            account.deleveraged_size = account.deleveraged_size.try_add(closed)?;
+            account.socialized_loss = account.socialized_loss.try_add(loss)?;
+            if account.position.size.is_zero() {
+                account.status = AccountStatus::Deleveraged;
+            }
+
+            remaining = remaining.try_sub(closed)?;
+            absorbed = absorbed.try_add(loss)?;
+        }
+        Ok(absorbed)
+    }
+
+    /// Open, profitable account on a side with the highest ADL score.
+    fn top_ranked(&self, is_long: bool, price: Decimal) -> Result<Option<usize>, ArithmeticError> {
+        let mut best: Option<(usize, Decimal)> = None;
+        for (index, account) in self.accounts().iter().enumerate() {
+            let position = &account.position;
+            if account.status != AccountStatus::Open
+                || position.is_long != is_long
+                || position.size.is_zero()
+                || !calculate_pnl(position, price)?.is_positive()
+            {
+                continue;
+            }
+            let score = calculate_adl_score(position, price)?;
+            if best.map_or(true, |(_, top)| score > top) {
+                best = Some((index, score));
+            }
+        }
+        Ok(best.map(|(index, _)| index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn position(size: &str, is_long: bool, collateral: &str) -> PerpPosition {
+        PerpPosition {
+            size: decimal(size),
+            entry_price: decimal("100"),
+            is_long,
+            leverage: decimal("10"),
+            collateral: decimal(collateral),
+        }
+    }
+
+    #[test]
+    fn test_adl_score_ranks_profit_and_leverage() {
+        let low = position("10", false, "500");
+        let high = position("10", false, "100");
+        let price = decimal("90");
+        // Same PnL (100), less collateral gives higher PnL% and leverage
+        assert!(
+            calculate_adl_score(&high, price).unwrap() > calculate_adl_score(&low, price).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_liquidation_surplus_goes_to_fund() {
+        let mut sim = AdlSimulator::new(decimal("10"), decimal("0.05"));
+        sim.add_position(1, position("10", true, "100")).unwrap();
+
+        // Equity 100 - 60 = 40 < maintenance 47
+        let report = sim.run(&[decimal("95"), decimal("94")]).unwrap();
+        assert_eq!(report.liquidations, 1);
+        assert_eq!(report.insurance_collected, decimal("40"));
+        assert_eq!(report.insurance_fund, decimal("50"));
+        assert_eq!(sim.accounts()[0].status, AccountStatus::Liquidated);
+        assert_eq!(sim.accounts()[0].realized_pnl, decimal("-100"));
+    }
+
+    #[test]
+    fn test_insurance_fund_covers_bad_debt() {
+        let mut sim = AdlSimulator::new(decimal("100"), decimal("0.01"));
+        sim.add_position(1, position("10", true, "100")).unwrap();
+        sim.add_position(2, position("10", false, "100")).unwrap();
+
+        let report = sim.run(&[decimal("85")]).unwrap();
+        assert_eq!(report.bad_debt, decimal("50"));
+        assert_eq!(report.insurance_used, decimal("50"));
+        assert_eq!(report.insurance_fund, decimal("50"));
+        assert_eq!(report.socialized_loss, Decimal::ZERO);
+        assert_eq!(sim.accounts()[1].status, AccountStatus::Open);
+    }
+
+    #[test]
+    fn test_deleverages_by_rank_when_fund_exhausted() {
+        let mut sim = AdlSimulator::new(decimal("20"), decimal("0.01"));
+        sim.add_position(1, position("10", true, "100")).unwrap();
+        sim.add_position(2, position("6", false, "300")).unwrap();
+        sim.add_position(3, position("6", false, "60")).unwrap();
+
+        // Long bad debt 50, fund pays 20, 30 socialised over 10 units (3 per unit)
+        let report = sim.run(&[decimal("85")]).unwrap();
+        assert_eq!(report.insurance_fund, Decimal::ZERO);
+        assert_eq!(report.socialized_loss, decimal("30"));
+        assert_eq!(report.uncovered_loss, Decimal::ZERO);
+
+        // Account 3 ranks first and is fully closed at 88
+        let top = sim.accounts()[2];
+        assert_eq!(top.status, AccountStatus::Deleveraged);
+        assert_eq!(top.realized_pnl, decimal("72"));
+        assert_eq!(top.socialized_loss, decimal("18"));
+
+        let next = sim.accounts()[1];
+        assert_eq!(next.deleveraged_size, decimal("4"));
+        assert_eq!(next.position.size, decimal("2"));
+        assert_eq!(next.socialized_loss, decimal("12"));
+    }
+
+    #[test]
+    fn test_uncovered_loss_without_counterparties() {
+        let mut sim = AdlSimulator::new(Decimal::ZERO, decimal("0.01"));
+        sim.add_position(1, position("10", true, "100")).unwrap();
+        sim.add_position(2, position("4", false, "100")).unwrap();
+
+        let report = sim.run(&[decimal("85")]).unwrap();
+        assert_eq!(report.socialized_loss, decimal("20"));
+        assert_eq!(report.uncovered_loss, decimal("30"));
+    }
+
+    #[test]
+    fn test_counterparty_loss_capped_at_profit() {
+        let mut sim = AdlSimulator::new(Decimal::ZERO, decimal("0.01"));
+        sim.add_position(1, position("10", true, "100")).unwrap();
+        sim.add_position(
+            2,
+            PerpPosition {
+                entry_price: decimal("80"),
+                ..position("10", false, "50")
+            },
+        )
+        .unwrap();
+
+        // Bad debt 200 at 70, but the short is only 100 in profit
+        let report = sim.run(&[decimal("70")]).unwrap();
+        assert_eq!(report.bad_debt, decimal("200"));
+        assert_eq!(report.socialized_loss, decimal("100"));
+        assert_eq!(report.uncovered_loss, decimal("100"));
+
+        let short = sim.accounts()[1];
+        assert_eq!(short.status, AccountStatus::Deleveraged);
+        assert_eq!(short.realized_pnl, Decimal::ZERO);
+        assert_eq!(short.position.collateral, decimal("50"));
+        assert_eq!(short.socialized_loss, decimal("100"));
+    }
+}
@@ -0,0 +1,587 @@
+//! Cross-margin accounts holding positions across several markets.
+//!
+//! Under cross margin all positions share one collateral balance, as on
+//! Hyperliquid or GMX cross accounts. Unrealised profit in one market
+//! supports losses in another, and the whole account is liquidated when
+//! its equity falls below the summed maintenance requirement.
+//!
+//! - Equity = collateral + unrealised PnL of all positions
+//! - Initial margin = Σ notional × initial margin rate
+//! - Maintenance margin = Σ notional × maintenance margin rate
+//! - Free collateral = equity − initial margin (withdrawable amount)
+//!
+//! Notionals use each position's mark price.
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::derivatives::{CrossMarginAccount, CrossTrade, MarginRates};
+//! use precision_core::Decimal;
+//!
+//! let rates = MarginRates::from_max_leverage(Decimal::from(20i64)).unwrap();
+//! let mut account = CrossMarginAccount::new(Decimal::from(1000i64));
+//!
+//! // Long 2 ETH at 2000 and short 0.1 BTC at 60000
+//! account.apply_trade(&CrossTrade::new(1, Decimal::from(2i64), true, Decimal::from(2000i64), rates)).unwrap();
+//! account.apply_trade(&CrossTrade::new(2, Decimal::new(1, 1), false, Decimal::from(60000i64), rates)).unwrap();
+//!
+//! account.set_mark_price(1, Decimal::from(1900i64)).unwrap();
+//! assert_eq!(account.equity().unwrap(), Decimal::from(800i64));
+//! assert!(account.liquidation_price(1).unwrap().is_some());
+//! ```
+
+use super::calculate_position_after_fill;
+use crate::storage::{Buffer, Fixed, Storage};
+use precision_core::{ArithmeticError, Decimal};
+
+/// Default number of positions in a cross-margin account (for no_std fixed allocation).
+pub const MAX_CROSS_POSITIONS: usize = 16;
+
+/// Initial and maintenance margin rates for a market.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MarginRates {
+    /// Margin required to open or increase a position, as a fraction of notional.
+    pub initial: Decimal,
+    /// Margin below which the account is liquidated, as a fraction of notional.
+    pub maintenance: Decimal,
+}
+
+impl MarginRates {
+    /// Creates margin rates.
+    pub fn new(initial: Decimal, maintenance: Decimal) -> Self {
+        Self {
+            initial,
+            maintenance,
+        }
+    }
+
+    /// Rates for a market's maximum leverage: initial = 1 / leverage and
+    /// maintenance = half the initial rate.
+    ///
+    /// Returns error if leverage is not positive.
+    pub fn from_max_leverage(max_leverage: Decimal) -> Result<Self, ArithmeticError> {
+        if !max_leverage.is_positive() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let initial = Decimal::ONE.try_div(max_leverage)?;
+        Ok(Self {
+            initial,
+            maintenance: initial.try_div(Decimal::from(2i64))?,
+        })
+    }
+}
+
+/// A position held in a cross-margin account.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrossPosition {
+    /// Market identifier.
+    pub market_id: u32,
+    /// Position size in base asset units.
+    pub size: Decimal,
+    /// Average entry price.
+    pub entry_price: Decimal,
+    /// Current mark price.
+    pub mark_price: Decimal,
+    /// True for long, false for short.
+    pub is_long: bool,
+    /// Margin rates of the market.
+    pub rates: MarginRates,
+}
+
+impl CrossPosition {
+    /// Size with sign: positive for longs, negative for shorts.
+    pub fn signed_size(&self) -> Decimal {
+        if self.is_long {
+            self.size
+        } else {
+            -self.size
+        }
+    }
+
+    /// Notional value at the mark price.
+    pub fn notional(&self) -> Result<Decimal, ArithmeticError> {
+        self.size.try_mul(self.mark_price)
+    }
+
+    /// Unrealised PnL at the mark price.
+    pub fn unrealized_pnl(&self) -> Result<Decimal, ArithmeticError> {
+        self.signed_size()
+            .try_mul(self.mark_price.try_sub(self.entry_price)?)
+    }
+
+    /// Initial margin requirement at the mark price.
+    pub fn initial_margin(&self) -> Result<Decimal, ArithmeticError> {
+        self.notional()?.try_mul(self.rates.initial)
+    }
+
+    /// Maintenance margin requirement at the mark price.
+    pub fn maintenance_margin(&self) -> Result<Decimal, ArithmeticError> {
+        self.notional()?.try_mul(self.rates.maintenance)
+    }
+}
+
+/// An order to trade in a cross-margin account.
+#[derive(Debug, Clone, Copy)]
+pub struct CrossTrade {
+    /// Market identifier.
+    pub market_id: u32,
+    /// Trade size in base asset units.
+    pub size: Decimal,
+    /// True to buy, false to sell.
+    pub is_buy: bool,
+    /// Execution price.
+    pub price: Decimal,
+    /// Margin rates of the market.
+    pub rates: MarginRates,
+}
+
+impl CrossTrade {
+    /// Creates a trade.
+    pub fn new(
+        market_id: u32,
+        size: Decimal,
+        is_buy: bool,
+        price: Decimal,
+        rates: MarginRates,
+    ) -> Self {
+        Self {
+            market_id,
+            size,
+            is_buy,
+            price,
+            rates,
+        }
+    }
+}
+
+/// Account state after a trade.
+#[derive(Debug, Clone, Copy)]
+pub struct TradeImpact {
+    /// PnL realised by the reducing part of the trade.
+    pub realized_pnl: Decimal,
+    /// Account equity after the trade.
+    pub equity: Decimal,
+    /// Initial margin requirement after the trade.
+    pub initial_margin: Decimal,
+    /// Maintenance margin requirement after the trade.
+    pub maintenance_margin: Decimal,
+    /// Free collateral after the trade (negative if under-margined).
+    pub free_collateral: Decimal,
+    /// Liquidation price of the traded market after the trade, if any.
+    pub liquidation_price: Option<Decimal>,
+}
+
+/// A cross-margin account: shared collateral backing positions in many markets.
+///
+/// Positions are held in the chosen [`Storage`]; the default
+/// [`Fixed<MAX_CROSS_POSITIONS>`] needs no allocation.
+#[derive(Debug, Clone)]
+pub struct CrossMarginAccount<S: Storage = Fixed<MAX_CROSS_POSITIONS>> {
+    collateral: Decimal,
+    positions: S::Buffer<CrossPosition>,
+}
+
+impl CrossMarginAccount {
+    /// Creates an account with default fixed storage and no positions.
+    pub fn new(collateral: Decimal) -> Self {
+        Self::with_storage(collateral)
+    }
+}
+
+impl<S: Storage> CrossMarginAccount<S> {
+    /// Creates an account with the chosen storage and no positions.
+    pub fn with_storage(collateral: Decimal) -> Self {
+        Self {
+            collateral,
+            positions: Default::default(),
+        }
+    }
+
+    /// Returns the collateral balance, including realised PnL.
+    pub fn collateral(&self) -> Decimal {
+        self.collateral
+    }
+
+    /// Returns the open positions.
+    pub fn positions(&self) -> &[CrossPosition] {
+        self.positions.as_slice()
+    }
+
+    /// Returns the position in a market, if any.
+    pub fn position(&self, market_id: u32) -> Option<&CrossPosition> {
+        self.positions().iter().find(|p| p.market_id == market_id)
+    }
+
+    /// Adds collateral.
+    ///
+    /// Returns error if the amount is negative.
+    pub fn deposit(&mut self, amount: Decimal) -> Result<(), ArithmeticError> {
+        if amount.is_negative() {
+            return Err(ArithmeticError::Underflow);
+        }
+        self.collateral = self.collateral.try_add(amount)?;
+        Ok(())
+    }
+
+    /// Removes collateral.
+    ///
+    /// Returns `Underflow` if the amount is negative or exceeds free collateral.
+    pub fn withdraw(&mut self, amount: Decimal) -> Result<(), ArithmeticError> {
+        if amount.is_negative() || amount > self.free_collateral()? {
+            return Err(ArithmeticError::Underflow);
+        }
+        self.collateral = self.collateral.try_sub(amount)?;
+        Ok(())
+    }
+
+    /// Updates the mark price of a market.
+    ///
+    /// Returns error if the account has no position in the market.
+    pub fn set_mark_price(
+        &mut self,
+        market_id: u32,
+        price: Decimal,
+    ) -> Result<(), ArithmeticError> {
+        let position = self
+            .positions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|p| p.market_id == market_id)
+            .ok_or(ArithmeticError::DivisionByZero)?;
+        position.mark_price = price;
+        Ok(())
+    }
+
+    /// Total unrealised PnL across positions.
+    pub fn unrealized_pnl(&self) -> Result<Decimal, ArithmeticError> {
+        self.sum(CrossPosition::unrealized_pnl)
+    }
+
+    /// Account equity: collateral plus unrealised PnL.
+    pub fn equity(&self) -> Result<Decimal, ArithmeticError> {
+        self.collateral.try_add(self.unrealized_pnl()?)
+    }
+
+    /// Total notional value across positions.
+    pub fn total_notional(&self) -> Result<Decimal, ArithmeticError> {
+        self.sum(CrossPosition::notional)
+    }
+
+    /// Total initial margin requirement.
+    pub fn initial_margin(&self) -> Result<Decimal, ArithmeticError> {
+        self.sum(CrossPosition::initial_margin)
+    }
+
+    /// Total maintenance margin requirement.
+    pub fn maintenance_margin(&self) -> Result<Decimal, ArithmeticError> {
+        self.sum(CrossPosition::maintenance_margin)
+    }
+
+    /// Collateral available for withdrawal or new positions.
+    ///
+    /// free = max(0, equity - initial margin)
+    pub fn free_collateral(&self) -> Result<Decimal, ArithmeticError> {
+        let free = self.equity()?.try_sub(self.initial_margin()?)?;
+        Ok(free.max(Decimal::ZERO))
+    }
+
+    /// Account margin ratio: equity / total notional.
+    ///
+    /// Returns error if the account has no exposure.
+    pub fn margin_ratio(&self) -> Result<Decimal, ArithmeticError> {
+        self.equity()?.try_div(self.total_notional()?)
+    }
+
+    /// Returns true if equity is below the maintenance requirement.
+    pub fn is_liquidatable(&self) -> Result<bool, ArithmeticError> {
+        if self.positions.is_empty() {
+            return Ok(false);
+        }
+        Ok(self.equity()? < self.maintenance_margin()?)
+    }
+
+    /// Mark price of one market at which the account becomes liquidatable,
+    /// holding every other mark price fixed.
+    ///
+    /// Solves equity(P) = maintenance(P) for the market's price P. Returns
+    /// `None` if no positive price liquidates the account (for example a
+    /// long fully backed by collateral).
+    pub fn liquidation_price(&self, market_id: u32) -> Result<Option<Decimal>, ArithmeticError> {
+        let position = self
+            .position(market_id)
+            .ok_or(ArithmeticError::DivisionByZero)?;
+
+        let mut other_equity = self.collateral;
+        let mut other_maintenance = Decimal::ZERO;
+        for p in self.positions().iter().filter(|p| p.market_id != market_id) {
+            other_equity = other_equity.try_add(p.unrealized_pnl()?)?;
+            other_maintenance = other_maintenance.try_add(p.maintenance_margin()?)?;
+        }
+
+        // other_equity + q (P - entry) = other_maintenance + |q| P mmr
+        let q = position.signed_size();
+        let numerator = other_maintenance
+            .try_sub(other_equity)?
+            .try_add(q.try_mul(position.entry_price)?)?;
+        let denominator = q.try_sub(position.size.try_mul(position.rates.maintenance)?)?;
+        if denominator.is_zero() {
+            return Ok(None);
+        }
+
+        let price = numerator.try_div(denominator)?;
+        Ok(price.is_positive().then_some(price))
+    }
+
+    /// Uniform relative move of all mark prices at which the account
+    /// becomes liquidatable (e.g. -0.2 for a 20% fall in every market).
+    ///
+    /// Returns `None` if no such move keeps prices positive.
+    pub fn liquidation_move(&self) -> Result<Option<Decimal>, ArithmeticError> {
+        let equity = self.equity()?;
+        let maintenance = self.maintenance_margin()?;
+        let mut signed_notional = Decimal::ZERO;
+        for p in self.positions() {
+            signed_notional = signed_notional.try_add(p.signed_size().try_mul(p.mark_price)?)?;
+        }
+
+        // equity + x * signed_notional = maintenance * (1 + x)
+        let denominator = signed_notional.try_sub(maintenance)?;
+        if denominator.is_zero() {
+            return Ok(None);
+        }
+        let move_ = maintenance.try_sub(equity)?.try_div(denominator)?;
+        Ok((move_ > -Decimal::ONE).then_some(move_))
+    }
+
+    /// Account state if the trade were executed, without changing the account.
+    pub fn preview_trade(&self, trade: &CrossTrade) -> Result<TradeImpact, ArithmeticError> {
+        let mut after = Self {
+            collateral: self.collateral,
+            positions: self.positions.clone(),
+        };
+        let realized_pnl = after.execute(trade)?;
+        let equity = after.equity()?;
+        let initial_margin = after.initial_margin()?;
+
+        let liquidation_price = match after.position(trade.market_id) {
+            Some(_) => after.liquidation_price(trade.market_id)?,
+            None => None,
+        };
+
+        Ok(TradeImpact {
+            realized_pnl,
+            equity,
+            initial_margin,
+            maintenance_margin: after.maintenance_margin()?,
+            free_collateral: equity.try_sub(initial_margin)?,
+            liquidation_price,
+        })
+    }
+
+    /// Executes a trade, realising PnL on any reduced size into collateral.
+    ///
+    /// Buying against a short reduces it (and flips to long if larger), and
+    /// vice versa; trades on the same side increase the position at a
+    /// size-weighted entry price. Reducing trades are always accepted.
+    ///
+    /// Returns `Underflow` if a trade that adds exposure would leave equity
+    /// below the initial margin requirement, or `Overflow` if the position
+    /// storage is full.
+    pub fn apply_trade(&mut self, trade: &CrossTrade) -> Result<TradeImpact, ArithmeticError> {
+        let impact = self.preview_trade(trade)?;
+        let reduces_only = self
+            .position(trade.market_id)
+            .is_some_and(|p| p.is_long != trade.is_buy && trade.size <= p.size);
+
+        if !reduces_only && impact.free_collateral.is_negative() {
+            return Err(ArithmeticError::Underflow);
+        }
+        self.execute(trade)?;
+        Ok(impact)
+    }
+
+    /// Applies a trade without margin checks, returning realised PnL.
+    fn execute(&mut self, trade: &CrossTrade) -> Result<Decimal, ArithmeticError> {
+        if !trade.size.is_positive() || !trade.price.is_positive() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+
+        let index = self
+            .positions()
+            .iter()
+            .position(|p| p.market_id == trade.market_id);
+        let opened = CrossPosition {
+            market_id: trade.market_id,
+            size: trade.size,
+            entry_price: trade.price,
+            mark_price: trade.price,
+            is_long: trade.is_buy,
+            rates: trade.rates,
+        };
+
+        let Some(index) = index else {
+            self.positions.push(opened)?;
+            return Ok(Decimal::ZERO);
+        };
+
+        let position = &mut self.positions.as_mut_slice()[index];
+        let fill_size = if trade.is_buy { trade.size } else { -trade.size };
+        let outcome = calculate_position_after_fill(
+            position.signed_size(),
+            position.entry_price,
+            fill_size,
+            trade.price,
+        )?;
+        self.collateral = self.collateral.try_add(outcome.realized_pnl)?;
+
+        if outcome.size.is_zero() {
+            self.positions.remove(index);
+        } else {
+            position.size = outcome.size.abs();
+            position.is_long = outcome.size.is_positive();
+            position.entry_price = outcome.entry_price;
+            position.mark_price = trade.price;
+            position.rates = trade.rates;
+        }
+        Ok(outcome.realized_pnl)
+    }
+
+    fn sum<F>(&self, f: F) -> Result<Decimal, ArithmeticError>
+    where
+        F: Fn(&CrossPosition) -> Result<Decimal, ArithmeticError>,
+    {
+        self.positions()
+            .iter()
+            .try_fold(Decimal::ZERO, |acc, p| acc.try_add(f(p)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn rates() -> MarginRates {
+        MarginRates::new(decimal("0.1"), decimal("0.01"))
+    }
+
+    fn trade(market_id: u32, size: &str, is_buy: bool, price: &str) -> CrossTrade {
+        CrossTrade::new(market_id, decimal(size), is_buy, decimal(price), rates())
+    }
+
+    #[test]
+    fn test_margin_rates_from_leverage() {
+        let r = MarginRates::from_max_leverage(decimal("50")).unwrap();
+        assert_eq!(r.initial, decimal("0.02"));
+        assert_eq!(r.maintenance, decimal("0.01"));
+    }
+
+    #[test]
+    fn test_account_equity_and_margins() {
+        let mut account = CrossMarginAccount::new(decimal("1000"));
+        account.apply_trade(&trade(1, "1.5", true, "2000")).unwrap();
+        account.apply_trade(&trade(2, "10", false, "100")).unwrap();
+
+        account.set_mark_price(1, decimal("2200")).unwrap();
+        account.set_mark_price(2, decimal("110")).unwrap();
+
+        // uPnL = 1.5 * 200 - 10 * 10 = 200
+        assert_eq!(account.unrealized_pnl().unwrap(), decimal("200"));
+        assert_eq!(account.equity().unwrap(), decimal("1200"));
+        // Notional = 3300 + 1100 = 4400
+        assert_eq!(account.initial_margin().unwrap(), decimal("440"));
+        assert_eq!(account.maintenance_margin().unwrap(), decimal("44"));
+        assert_eq!(account.free_collateral().unwrap(), decimal("760"));
+    }
+
+    #[test]
+    fn test_liquidation_price_single_position() {
+        let mut account = CrossMarginAccount::new(decimal("300"));
+        account.apply_trade(&trade(1, "1.5", true, "2000")).unwrap();
+
+        // 300 + 1.5 (P - 2000) = 0.015 P  =>  P = 2700 / 1.485
+        let liq = account.liquidation_price(1).unwrap().unwrap();
+        assert_eq!(liq, decimal("2700") / decimal("1.485"));
+
+        account.set_mark_price(1, liq + decimal("0.01")).unwrap();
+        assert!(!account.is_liquidatable().unwrap());
+        account.set_mark_price(1, liq - decimal("0.01")).unwrap();
+        assert!(account.is_liquidatable().unwrap());
+    }
+
+    #[test]
+    fn test_cross_margin_shares_profit() {
+        let mut account = CrossMarginAccount::new(decimal("400"));
+        account.apply_trade(&trade(1, "1.5", true, "2000")).unwrap();
+        let isolated = account.liquidation_price(1).unwrap().unwrap();
+
+        // A profitable short elsewhere supports the long
+        account.apply_trade(&trade(2, "1", false, "100")).unwrap();
+        account.set_mark_price(2, decimal("50")).unwrap();
+        let cross = account.liquidation_price(1).unwrap().unwrap();
+        assert!(cross < isolated);
+    }
+
+    #[test]
+    fn test_liquidation_move() {
+        let mut account = CrossMarginAccount::new(decimal("300"));
+        account.apply_trade(&trade(1, "1.5", true, "2000")).unwrap();
+
+        let x = account.liquidation_move().unwrap().unwrap();
+        let liq = account.liquidation_price(1).unwrap().unwrap();
+        assert_eq!(decimal("2000") * (Decimal::ONE + x), liq);
+    }
+
+    #[test]
+    fn test_trade_lifecycle() {
+        let mut account = CrossMarginAccount::new(decimal("1000"));
+        account.apply_trade(&trade(1, "1", true, "2000")).unwrap();
+        account.apply_trade(&trade(1, "1", true, "2200")).unwrap();
+        assert_eq!(account.position(1).unwrap().entry_price, decimal("2100"));
+
+        // Partial close realises 0.5 * (2300 - 2100) = 100
+        let impact = account
+            .apply_trade(&trade(1, "0.5", false, "2300"))
+            .unwrap();
+        assert_eq!(impact.realized_pnl, decimal("100"));
+        assert_eq!(account.collateral(), decimal("1100"));
+        assert_eq!(account.position(1).unwrap().size, decimal("1.5"));
+
+        // Selling 2 closes the long and opens a 0.5 short at 2000
+        let impact = account.apply_trade(&trade(1, "2", false, "2000")).unwrap();
+        assert_eq!(impact.realized_pnl, decimal("-150"));
+        let position = account.position(1).unwrap();
+        assert!(!position.is_long);
+        assert_eq!(position.size, decimal("0.5"));
+        assert_eq!(position.entry_price, decimal("2000"));
+
+        // Closing fully removes the position
+        account.apply_trade(&trade(1, "0.5", true, "2000")).unwrap();
+        assert!(account.positions().is_empty());
+    }
+
+    #[test]
+    fn test_initial_margin_enforced() {
+        let mut account = CrossMarginAccount::new(decimal("100"));
+        // 10x max leverage: 1 ETH at 2000 needs 200 initial margin
+        assert_eq!(
+            account
+                .apply_trade(&trade(1, "1", true, "2000"))
+                .unwrap_err(),
+            ArithmeticError::Underflow
+        );
+
+        let preview = account
+            .preview_trade(&trade(1, "0.5", true, "2000"))
+            .unwrap();
+        assert_eq!(preview.free_collateral, Decimal::ZERO);
+        account.apply_trade(&trade(1, "0.5", true, "2000")).unwrap();
+
+        // Nothing left to withdraw
+        assert!(account.withdraw(decimal("1")).is_err());
+    }
+}
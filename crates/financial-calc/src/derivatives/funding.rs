@@ -0,0 +1,416 @@
+//! Cumulative funding index with lazy settlement.
+//!
+//! On-chain perps do not pay funding to every position each interval.
+//! Instead a market keeps a cumulative index of funding owed per unit of
+//! position size, positions snapshot the index when opened or modified, and
+//! funding is settled from the index difference whenever a position changes.
+//!
+//! The index grows by rate × price × elapsed time; positive funding means
+//! longs pay shorts. Three ways of driving the rate are supported:
+//!
+//! - [`FundingIndex::accrue`] - Premium rate from [`calculate_funding_rate`]
+//! - [`FundingIndex::accrue_skew`] - Rate proportional to open interest skew
+//! - [`FundingIndex::accrue_velocity`] - Rate drifting with skew (Synthetix v2)
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::derivatives::{FundingIndex, PositionFunding};
+//! use precision_core::Decimal;
+//!
+//! let mut index = FundingIndex::new(0);
+//! let mut position = PositionFunding::open(Decimal::from(2i64), true, &index);
+//!
+//! // 0.01% per 8h at a price of 2000, for 8 hours
+//! index.accrue_rate(Decimal::new(1, 4), Decimal::from(8i64), Decimal::from(2000i64), 8 * 3600).unwrap();
+//!
+//! // The long owes 2 × 2000 × 0.0001 = 0.4
+//! assert_eq!(position.pending(&index).unwrap(), Decimal::new(-4, 1));
+//! position.settle(&index).unwrap();
+//! assert_eq!(position.realized(), Decimal::new(-4, 1));
+//! ```
+
+use super::{calculate_funding_rate, FundingParams};
+use precision_core::{ArithmeticError, Decimal};
+
+const SECONDS_PER_HOUR: i64 = 3_600;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Skew-proportional funding: the daily rate is the clamped skew ratio
+/// times a maximum rate.
+///
+/// rate = clamp(skew / skew_scale, -1, 1) × max_rate
+#[derive(Debug, Clone, Copy)]
+pub struct SkewFunding {
+    /// Skew (long minus short open interest, in base units) at which the rate saturates.
+    pub skew_scale: Decimal,
+    /// Maximum funding rate per day.
+    pub max_rate: Decimal,
+}
+
+impl SkewFunding {
+    /// Daily funding rate for a skew.
+    pub fn rate(&self, skew: Decimal) -> Result<Decimal, ArithmeticError> {
+        proportional_skew(skew, self.skew_scale)?.try_mul(self.max_rate)
+    }
+}
+
+/// Velocity-based funding (Synthetix perps v2): skew moves the rate rather
+/// than setting it, so funding keeps rising while the market stays skewed.
+///
+/// velocity = clamp(skew / skew_scale, -1, 1) × max_velocity
+#[derive(Debug, Clone, Copy)]
+pub struct VelocityFunding {
+    /// Skew at which the velocity saturates.
+    pub skew_scale: Decimal,
+    /// Maximum change of the daily funding rate per day.
+    pub max_velocity: Decimal,
+}
+
+impl VelocityFunding {
+    /// Daily change of the funding rate for a skew.
+    pub fn velocity(&self, skew: Decimal) -> Result<Decimal, ArithmeticError> {
+        proportional_skew(skew, self.skew_scale)?.try_mul(self.max_velocity)
+    }
+}
+
+/// A market's cumulative funding per unit of position size.
+#[derive(Debug, Clone, Copy)]
+pub struct FundingIndex {
+    value: Decimal,
+    rate: Decimal,
+    last_update: u64,
+}
+
+impl FundingIndex {
+    /// Creates an index at zero with a zero rate, starting at a timestamp in seconds.
+    pub fn new(timestamp: u64) -> Self {
+        Self {
+            value: Decimal::ZERO,
+            rate: Decimal::ZERO,
+            last_update: timestamp,
+        }
+    }
+
+    /// Cumulative funding per unit of size, in quote currency.
+    pub fn value(&self) -> Decimal {
+        self.value
+    }
+
+    /// Daily funding rate recorded at the last update.
+    pub fn rate(&self) -> Decimal {
+        self.rate
+    }
+
+    /// Timestamp of the last update, in seconds.
+    pub fn last_update(&self) -> u64 {
+        self.last_update
+    }
+
+    /// Accrues funding at the rate from [`calculate_funding_rate`] and the
+    /// params' mark price.
+    ///
+    /// Returns the increase of the index.
+    pub fn accrue(&mut self, params: &FundingParams, now: u64) -> Result<Decimal, ArithmeticError> {
+        let rate = calculate_funding_rate(params)?;
+        self.accrue_rate(rate, params.funding_interval_hours, params.mark_price, now)
+    }
+
+    /// Accrues funding at a rate per funding interval over the time since
+    /// the last update.
+    ///
+    /// delta = rate × price × elapsed / interval
+    ///
+    /// Returns the increase of the index, or `Underflow` if `now` is before
+    /// the last update.
+    pub fn accrue_rate(
+        &mut self,
+        rate: Decimal,
+        interval_hours: Decimal,
+        price: Decimal,
+        now: u64,
+    ) -> Result<Decimal, ArithmeticError> {
+        let interval = interval_hours.try_mul(Decimal::from(SECONDS_PER_HOUR))?;
+        let delta = rate
+            .try_mul(price)?
+            .try_mul(self.elapsed_seconds(now)?)?
+            .try_div(interval)?;
+        self.rate = rate
+            .try_mul(Decimal::from(24i64))?
+            .try_div(interval_hours)?;
+        self.advance(delta, now)
+    }
+
+    /// Accrues funding at the skew-proportional rate.
+    ///
+    /// Returns the increase of the index.
+    pub fn accrue_skew(
+        &mut self,
+        model: &SkewFunding,
+        skew: Decimal,
+        price: Decimal,
+        now: u64,
+    ) -> Result<Decimal, ArithmeticError> {
+        let rate = model.rate(skew)?;
+        let delta = rate
+            .try_mul(price)?
+            .try_mul(self.elapsed_seconds(now)?)?
+            .try_div(Decimal::from(SECONDS_PER_DAY))?;
+        self.rate = rate;
+        self.advance(delta, now)
+    }
+
+    /// Accrues velocity-based funding: the rate moves linearly from the last
+    /// recorded rate, and the index grows by the average rate over the period.
+    ///
+    /// Returns the increase of the index.
+    pub fn accrue_velocity(
+        &mut self,
+        model: &VelocityFunding,
+        skew: Decimal,
+        price: Decimal,
+        now: u64,
+    ) -> Result<Decimal, ArithmeticError> {
+        let days = self
+            .elapsed_seconds(now)?
+            .try_div(Decimal::from(SECONDS_PER_DAY))?;
+        let rate = self.rate.try_add(model.velocity(skew)?.try_mul(days)?)?;
+        let average = self.rate.try_add(rate)?.try_div(Decimal::from(2i64))?;
+        let delta = average.try_mul(price)?.try_mul(days)?;
+        self.rate = rate;
+        self.advance(delta, now)
+    }
+
+    fn elapsed_seconds(&self, now: u64) -> Result<Decimal, ArithmeticError> {
+        let elapsed = now
+            .checked_sub(self.last_update)
+            .ok_or(ArithmeticError::Underflow)?;
+        i64::try_from(elapsed)
+            .map(Decimal::from)
+            .map_err(|_| ArithmeticError::Overflow)
+    }
+
+    fn advance(&mut self, delta: Decimal, now: u64) -> Result<Decimal, ArithmeticError> {
+        self.value = self.value.try_add(delta)?;
+        self.last_update = now;
+        Ok(delta)
+    }
+}
+
+/// A position's funding state against a market's [`FundingIndex`].
+#[derive(Debug, Clone, Copy)]
+pub struct PositionFunding {
+    size: Decimal,
+    is_long: bool,
+    entry_index: Decimal,
+    realized: Decimal,
+}
+
+impl PositionFunding {
+    /// Opens funding tracking for a position, snapshotting the index.
+    pub fn open(size: Decimal, is_long: bool, index: &FundingIndex) -> Self {
+        Self {
+            size,
+            is_long,
+            entry_index: index.value(),
+            realized: Decimal::ZERO,
+        }
+    }
+
+    /// Position size in base units.
+    pub fn size(&self) -> Decimal {
+        self.size
+    }
+
+    /// True for long, false for short.
+    pub fn is_long(&self) -> bool {
+        self.is_long
+    }
+
+    /// Index value at the last settlement.
+    pub fn entry_index(&self) -> Decimal {
+        self.entry_index
+    }
+
+    /// Funding settled so far (positive = received).
+    pub fn realized(&self) -> Decimal {
+        self.realized
+    }
+
+    /// Funding accrued since the last settlement (positive = receive).
+    ///
+    /// Longs pay when the index rises, shorts receive.
+    pub fn pending(&self, index: &FundingIndex) -> Result<Decimal, ArithmeticError> {
+        let owed = self
+            .size
+            .try_mul(index.value().try_sub(self.entry_index)?)?;
+        Ok(if self.is_long { -owed } else { owed })
+    }
+
+    /// Realised plus pending funding.
+    pub fn total(&self, index: &FundingIndex) -> Result<Decimal, ArithmeticError> {
+        self.realized.try_add(self.pending(index)?)
+    }
+
+    /// Settles pending funding and re-snapshots the index.
+    ///
+    /// Returns the amount settled.
+    pub fn settle(&mut self, index: &FundingIndex) -> Result<Decimal, ArithmeticError> {
+        let pending = self.pending(index)?;
+        self.realized = self.realized.try_add(pending)?;
+        self.entry_index = index.value();
+        Ok(pending)
+    }
+
+    /// Settles pending funding, then changes the position's size and side.
+    ///
+    /// Returns the amount settled.
+    pub fn modify(
+        &mut self,
+        size: Decimal,
+        is_long: bool,
+        index: &FundingIndex,
+    ) -> Result<Decimal, ArithmeticError> {
+        let settled = self.settle(index)?;
+        self.size = size;
+        self.is_long = is_long;
+        Ok(settled)
+    }
+}
+
+fn proportional_skew(skew: Decimal, skew_scale: Decimal) -> Result<Decimal, ArithmeticError> {
+    let ratio = skew.try_div(skew_scale)?;
+    Ok(ratio.max(-Decimal::ONE).min(Decimal::ONE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derivatives::{calculate_funding_payment, PerpPosition};
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_index_matches_single_interval_payment() {
+        let params = FundingParams {
+            mark_price: decimal("2001"),
+            index_price: decimal("2000"),
+            interest_rate: decimal("0.1095"),
+            premium_cap: decimal("0.01"),
+            funding_interval_hours: decimal("8"),
+        };
+        let position = PerpPosition {
+            size: decimal("1.5"),
+            entry_price: decimal("2000"),
+            is_long: true,
+            leverage: decimal("10"),
+            collateral: decimal("300"),
+        };
+
+        let mut index = FundingIndex::new(1_000);
+        let funding = PositionFunding::open(position.size, true, &index);
+        index.accrue(&params, 1_000 + 8 * 3_600).unwrap();
+
+        let rate = calculate_funding_rate(&params).unwrap();
+        let expected = calculate_funding_payment(&position, params.mark_price, rate).unwrap();
+        assert_eq!(funding.pending(&index).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_lazy_settlement_across_rate_changes() {
+        let mut index = FundingIndex::new(0);
+        let mut short = PositionFunding::open(decimal("4"), false, &index);
+        let price = decimal("100");
+        let hours = decimal("1");
+
+        // 0.1%/h for 2h, then -0.05%/h for 4h
+        index
+            .accrue_rate(decimal("0.001"), hours, price, 7_200)
+            .unwrap();
+        index
+            .accrue_rate(decimal("-0.0005"), hours, price, 21_600)
+            .unwrap();
+
+        // Short receives 4 × 100 × (0.002 - 0.002) = 0
+        assert_eq!(short.pending(&index).unwrap(), Decimal::ZERO);
+
+        index
+            .accrue_rate(decimal("0.001"), hours, price, 25_200)
+            .unwrap();
+        assert_eq!(short.settle(&index).unwrap(), decimal("0.4"));
+        assert_eq!(short.pending(&index).unwrap(), Decimal::ZERO);
+        assert_eq!(short.realized(), decimal("0.4"));
+    }
+
+    #[test]
+    fn test_modify_settles_before_resizing() {
+        let mut index = FundingIndex::new(0);
+        let mut long = PositionFunding::open(decimal("1"), true, &index);
+        index
+            .accrue_rate(decimal("0.01"), decimal("24"), decimal("50"), 86_400)
+            .unwrap();
+
+        assert_eq!(
+            long.modify(decimal("3"), true, &index).unwrap(),
+            decimal("-0.5")
+        );
+        index
+            .accrue_rate(decimal("0.01"), decimal("24"), decimal("50"), 172_800)
+            .unwrap();
+        assert_eq!(long.pending(&index).unwrap(), decimal("-1.5"));
+        assert_eq!(long.total(&index).unwrap(), decimal("-2"));
+    }
+
+    #[test]
+    fn test_skew_funding_clamped() {
+        let model = SkewFunding {
+            skew_scale: decimal("1000"),
+            max_rate: decimal("0.01"),
+        };
+        assert_eq!(model.rate(decimal("500")).unwrap(), decimal("0.005"));
+        assert_eq!(model.rate(decimal("-5000")).unwrap(), decimal("-0.01"));
+
+        let mut index = FundingIndex::new(0);
+        let delta = index
+            .accrue_skew(&model, decimal("500"), decimal("100"), 43_200)
+            .unwrap();
+        // 0.5%/day for half a day at 100
+        assert_eq!(delta, decimal("0.25"));
+    }
+
+    #[test]
+    fn test_velocity_funding_averages_rate() {
+        let model = VelocityFunding {
+            skew_scale: decimal("1000"),
+            max_velocity: decimal("0.02"),
+        };
+        let mut index = FundingIndex::new(0);
+
+        // Full skew for one day: rate ramps 0 -> 2%, average 1%
+        let delta = index
+            .accrue_velocity(&model, decimal("1000"), decimal("100"), 86_400)
+            .unwrap();
+        assert_eq!(index.rate(), decimal("0.02"));
+        assert_eq!(delta, Decimal::ONE);
+
+        // Balanced market: rate holds at 2%
+        let delta = index
+            .accrue_velocity(&model, Decimal::ZERO, decimal("100"), 172_800)
+            .unwrap();
+        assert_eq!(delta, decimal("2"));
+    }
+
+    #[test]
+    fn test_rejects_time_going_backwards() {
+        let mut index = FundingIndex::new(100);
+        assert_eq!(
+            index
+                .accrue_rate(decimal("0.001"), decimal("1"), decimal("1"), 50)
+                .unwrap_err(),
+            ArithmeticError::Underflow
+        );
+    }
+}
@@ -4,6 +4,15 @@
 //! like GMX, Vertex, and Vela on Arbitrum. Includes funding rate,
 //! liquidation price, and PnL calculations.
 //!
+//! Cross-margin accounts sharing collateral across markets are in
+//! [`CrossMarginAccount`]; size-dependent margin brackets are in
+//! [`RiskTierTable`] and the `*_tiered` functions; lazily settled funding is
+//! tracked with [`FundingIndex`] and [`PositionFunding`]; net PnL after fees,
+//! funding and price impact is tracked with [`PositionLedger`]. Failed
+//! liquidations and auto-deleveraging are simulated with [`AdlSimulator`].
+//! Order fill prices and fees come from [`PriceImpactParams`] and
+//! [`SkewParams`].
+//!
 //! # Example
 //!
 //! ```
@@ -23,10 +32,29 @@
 //! let pnl = calculate_pnl(&position, current_price).unwrap();
 //! ```
 
+use crate::storage::Storage;
 use precision_core::{ArithmeticError, Decimal};
 
+mod accounting;
+mod adl;
+mod cross_margin;
+mod funding;
+mod price_impact;
+mod risk_tiers;
+
+pub use accounting::{Fill, PositionLedger};
+pub use adl::{
+    calculate_adl_score, AccountOutcome, AccountStatus, AdlReport, AdlSimulator, MAX_ADL_ACCOUNTS,
+};
+pub use cross_margin::{
+    CrossMarginAccount, CrossPosition, CrossTrade, MarginRates, TradeImpact, MAX_CROSS_POSITIONS,
+};
+pub use funding::{FundingIndex, PositionFunding, SkewFunding, VelocityFunding};
+pub use price_impact::{OrderExecution, PriceImpactParams, SkewParams};
+pub use risk_tiers::{RiskTier, RiskTierTable, MAX_RISK_TIERS};
+
 /// A perpetual futures position.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct PerpPosition {
     /// Position size in base asset units.
     pub size: Decimal,
@@ -122,6 +150,27 @@ pub fn calculate_liquidation_price(
     // Maintenance margin required
     let maintenance_margin = notional.try_mul(maintenance_margin_rate)?;
 
+    liquidation_price_for_margin(position, maintenance_margin)
+}
+
+/// Calculate liquidation price using the maintenance margin of the
+/// position's risk-limit tier.
+///
+/// Returns `Overflow` if the position notional exceeds the risk limit.
+pub fn calculate_liquidation_price_tiered<S: Storage>(
+    position: &PerpPosition,
+    tiers: &RiskTierTable<S>,
+) -> Result<Decimal, ArithmeticError> {
+    let notional = position.size.try_mul(position.entry_price)?;
+    let maintenance_margin = tiers.maintenance_margin(notional)?;
+
+    liquidation_price_for_margin(position, maintenance_margin)
+}
+
+fn liquidation_price_for_margin(
+    position: &PerpPosition,
+    maintenance_margin: Decimal,
+) -> Result<Decimal, ArithmeticError> {
     // Loss that would trigger liquidation
     let max_loss = position.collateral.try_sub(maintenance_margin)?;
 
@@ -276,6 +325,21 @@ pub fn calculate_max_position_size(
     collateral.try_mul(leverage)?.try_div(entry_price)
 }
 
+/// Calculate the maximum position size honouring risk-limit tiers.
+///
+/// Leverage is capped by the tier the resulting notional falls in, and the
+/// size by the table's largest bracket.
+pub fn calculate_max_position_size_tiered<S: Storage>(
+    collateral: Decimal,
+    leverage: Decimal,
+    entry_price: Decimal,
+    tiers: &RiskTierTable<S>,
+) -> Result<Decimal, ArithmeticError> {
+    tiers
+        .max_notional(collateral, leverage)?
+        .try_div(entry_price)
+}
+
 /// Calculate required collateral for a position.
 ///
 /// required_collateral = (size * entry_price) / leverage
@@ -287,6 +351,23 @@ pub fn calculate_required_collateral(
     size.try_mul(entry_price)?.try_div(leverage)
 }
 
+/// Calculate required collateral for a position under risk-limit tiers.
+///
+/// Returns `Overflow` if the leverage exceeds the tier's maximum or the
+/// notional exceeds the risk limit.
+pub fn calculate_required_collateral_tiered<S: Storage>(
+    size: Decimal,
+    entry_price: Decimal,
+    leverage: Decimal,
+    tiers: &RiskTierTable<S>,
+) -> Result<Decimal, ArithmeticError> {
+    let notional = size.try_mul(entry_price)?;
+    if leverage > tiers.max_leverage(notional)? {
+        return Err(ArithmeticError::Overflow);
+    }
+    notional.try_div(leverage)
+}
+
 /// Calculate breakeven price accounting for fees.
 ///
 /// # Arguments
@@ -337,6 +418,73 @@ pub fn calculate_average_entry_price(
     total_cost.try_div(total_size)
 }
 
+/// Result of applying a fill to a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FillOutcome {
+    /// Signed position size after the fill (positive = long).
+    pub size: Decimal,
+    /// Average entry price after the fill (zero if flat).
+    pub entry_price: Decimal,
+    /// PnL realised by the reducing part of the fill.
+    pub realized_pnl: Decimal,
+}
+
+/// Calculate position size, average entry and realised PnL after a fill.
+///
+/// Extends [`calculate_average_entry_price`] to reducing and flipping fills
+/// using signed sizes (positive = long/buy). Fills on the same side average
+/// the entry; opposite fills realise PnL on the closed size and keep the
+/// entry, and any excess opens the other side at the fill price.
+pub fn calculate_position_after_fill(
+    existing_size: Decimal,
+    existing_avg_price: Decimal,
+    fill_size: Decimal,
+    fill_price: Decimal,
+) -> Result<FillOutcome, ArithmeticError> {
+    let size = existing_size.try_add(fill_size)?;
+
+    if existing_size.is_zero() || existing_size.is_negative() == fill_size.is_negative() {
+        let entry_price = if existing_size.is_zero() {
+            fill_price
+        } else {
+            calculate_average_entry_price(
+                existing_size.abs(),
+                existing_avg_price,
+                fill_size.abs(),
+                fill_price,
+            )?
+        };
+        return Ok(FillOutcome {
+            size,
+            entry_price,
+            realized_pnl: Decimal::ZERO,
+        });
+    }
+
+    // Opposite side: realise PnL on the closed size
+    let closed = existing_size.abs().min(fill_size.abs());
+    let signed_closed = if existing_size.is_negative() {
+        -closed
+    } else {
+        closed
+    };
+    let realized_pnl = signed_closed.try_mul(fill_price.try_sub(existing_avg_price)?)?;
+
+    let entry_price = if size.is_zero() {
+        Decimal::ZERO
+    } else if size.is_negative() == existing_size.is_negative() {
+        existing_avg_price
+    } else {
+        fill_price
+    };
+
+    Ok(FillOutcome {
+        size,
+        entry_price,
+        realized_pnl,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -545,6 +693,61 @@ mod tests {
         assert_eq!(required, decimal("1000"));
     }
 
+    fn sample_tiers() -> RiskTierTable {
+        let mut tiers = RiskTierTable::new();
+        tiers
+            .add_bracket(decimal("10000"), decimal("50"), decimal("0.01"))
+            .unwrap();
+        tiers
+            .add_bracket(decimal("100000"), decimal("10"), decimal("0.05"))
+            .unwrap();
+        tiers
+    }
+
+    #[test]
+    fn test_tiered_liquidation_price() {
+        let position = sample_long_position();
+        let tiers = sample_tiers();
+
+        // Notional 3000 is in the first tier: same as a flat 1% rate
+        let flat = calculate_liquidation_price(&position, decimal("0.01")).unwrap();
+        let tiered = calculate_liquidation_price_tiered(&position, &tiers).unwrap();
+        assert_eq!(tiered, flat);
+
+        // 10 ETH (20000 notional) uses tier 2: MM = 20000 * 5% - 400 = 600
+        let large = PerpPosition {
+            size: decimal("10"),
+            collateral: decimal("2000"),
+            ..position
+        };
+        let liq = calculate_liquidation_price_tiered(&large, &tiers).unwrap();
+        // Max loss = 2000 - 600 = 1400 over 10 ETH
+        assert_eq!(liq, decimal("1860"));
+    }
+
+    #[test]
+    fn test_tiered_collateral_and_size() {
+        let tiers = sample_tiers();
+
+        // 50x is allowed for 4000 notional but not for 20000
+        assert_eq!(
+            calculate_required_collateral_tiered(decimal("2"), decimal("2000"), decimal("50"), &tiers)
+                .unwrap(),
+            decimal("80")
+        );
+        assert_eq!(
+            calculate_required_collateral_tiered(decimal("10"), decimal("2000"), decimal("50"), &tiers)
+                .unwrap_err(),
+            ArithmeticError::Overflow
+        );
+
+        // 1000 at 50x would be 50000 notional, but tier 2 allows only 10x
+        let size =
+            calculate_max_position_size_tiered(decimal("1000"), decimal("50"), decimal("2000"), &tiers)
+                .unwrap();
+        assert_eq!(size, decimal("5"));
+    }
+
     #[test]
     fn test_breakeven_price() {
         let position = sample_long_position();
@@ -581,4 +784,42 @@ mod tests {
         let expected = decimal("3050").try_div(decimal("1.5")).unwrap();
         assert_eq!(avg, expected);
     }
+
+    #[test]
+    fn test_position_after_fill() {
+        // Increase: same as the average entry price
+        let fill = calculate_position_after_fill(
+            decimal("1"),
+            decimal("2000"),
+            decimal("1"),
+            decimal("2200"),
+        )
+        .unwrap();
+        assert_eq!(fill.size, decimal("2"));
+        assert_eq!(fill.entry_price, decimal("2100"));
+
+        // Partial close of a short realises (100 - 90) * 2
+        let fill = calculate_position_after_fill(
+            decimal("-5"),
+            decimal("100"),
+            decimal("2"),
+            decimal("90"),
+        )
+        .unwrap();
+        assert_eq!(fill.size, decimal("-3"));
+        assert_eq!(fill.entry_price, decimal("100"));
+        assert_eq!(fill.realized_pnl, decimal("20"));
+
+        // Flip from long 1 to short 1
+        let fill = calculate_position_after_fill(
+            decimal("1"),
+            decimal("2000"),
+            decimal("-2"),
+            decimal("1900"),
+        )
+        .unwrap();
+        assert_eq!(fill.size, decimal("-1"));
+        assert_eq!(fill.entry_price, decimal("1900"));
+        assert_eq!(fill.realized_pnl, decimal("-100"));
+    }
 }
@@ -0,0 +1,394 @@
+//! Perpetual order execution: price impact and skew fees.
+//!
+//! Two venue models turn an order into a fill price and fee:
+//!
+//! - [`PriceImpactParams`] - GMX v2 style: impact in USD from the change in
+//!   open-interest imbalance, `factor × diff^exponent`, with a lower factor
+//!   rewarding trades that improve balance
+//! - [`SkewParams`] - Synthetix perps v2 style: the fill price carries the
+//!   average premium/discount of the skew before and after the order, and
+//!   maker/taker fees depend on whether the order reduces skew
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::derivatives::{PerpPosition, SkewParams};
+//! use precision_core::Decimal;
+//!
+//! let params = SkewParams {
+//!     skew_scale: Decimal::from(1_000_000i64),
+//!     maker_fee: Decimal::new(2, 4),
+//!     taker_fee: Decimal::new(5, 4),
+//! };
+//! let order = PerpPosition {
+//!     size: Decimal::from(1000i64),
+//!     entry_price: Decimal::ZERO,
+//!     is_long: true,
+//!     leverage: Decimal::from(5i64),
+//!     collateral: Decimal::from(400i64),
+//! };
+//!
+//! // Buying into a balanced market pays half the post-trade premium
+//! let fill = params.execute(&order, Decimal::from(2i64), Decimal::ZERO).unwrap();
+//! assert_eq!(fill.fill_price, Decimal::new(2001, 3));
+//! ```
+
+use super::{FundingParams, PerpPosition};
+use precision_core::{ArithmeticError, Decimal};
+
+/// Fill price and costs of an order.
+#[derive(Debug, Clone, Copy)]
+pub struct OrderExecution {
+    /// Execution price including price impact.
+    pub fill_price: Decimal,
+    /// Trading fee in quote currency.
+    pub fee: Decimal,
+    /// Cost of price impact in quote currency (negative for a rebate).
+    pub price_impact: Decimal,
+    /// The order as a position entered at the fill price.
+    pub position: PerpPosition,
+}
+
+/// GMX v2 style price impact on open-interest imbalance.
+///
+/// impact = factor × (initial_diff^exponent − next_diff^exponent)
+///
+/// where diff = |long OI − short OI| in USD. Orders that improve balance use
+/// the positive factor and receive a rebate; orders that worsen it use the
+/// negative factor and pay. An order that flips the imbalance is credited
+/// for the initial side and charged for the next.
+#[derive(Debug, Clone, Copy)]
+pub struct PriceImpactParams {
+    /// Factor for orders improving balance.
+    pub positive_factor: Decimal,
+    /// Factor for orders worsening balance.
+    pub negative_factor: Decimal,
+    /// Impact exponent (typically 2).
+    pub exponent: Decimal,
+}
+
+impl PriceImpactParams {
+    /// Price impact in USD of an order (positive = rebate, negative = cost).
+    pub fn impact_usd(
+        &self,
+        long_open_interest: Decimal,
+        short_open_interest: Decimal,
+        size_usd: Decimal,
+        is_long: bool,
+    ) -> Result<Decimal, ArithmeticError> {
+        let initial = long_open_interest.try_sub(short_open_interest)?;
+        let next = if is_long {
+            initial.try_add(size_usd)?
+        } else {
+            initial.try_sub(size_usd)?
+        };
+
+        let initial_term = power(initial.abs(), self.exponent)?;
+        let next_term = power(next.abs(), self.exponent)?;
+        let crosses =
+            !initial.is_zero() && !next.is_zero() && initial.is_negative() != next.is_negative();
+
+        if crosses {
+            let positive = initial_term.try_mul(self.positive_factor)?;
+            let negative = next_term.try_mul(self.negative_factor)?;
+            return positive.try_sub(negative);
+        }
+
+        let delta = initial_term.try_sub(next_term)?;
+        let factor = if delta.is_positive() {
+            self.positive_factor
+        } else {
+            self.negative_factor
+        };
+        delta.try_mul(factor)
+    }
+
+    /// Executes an order against the pool's open interest.
+    ///
+    /// Impact adjusts the size in tokens, so the fill price is
+    /// price × size_usd / (size_usd ± impact): rebates lower a long's price
+    /// and raise a short's.
+    ///
+    /// Returns `Overflow` if the cost would exceed the order's notional.
+    pub fn execute(
+        &self,
+        order: &PerpPosition,
+        index_price: Decimal,
+        long_open_interest: Decimal,
+        short_open_interest: Decimal,
+        fee_rate: Decimal,
+    ) -> Result<OrderExecution, ArithmeticError> {
+        let size_usd = order.size.try_mul(index_price)?;
+        let impact = self.impact_usd(
+            long_open_interest,
+            short_open_interest,
+            size_usd,
+            order.is_long,
+        )?;
+
+        let adjusted = if order.is_long {
+            size_usd.try_add(impact)?
+        } else {
+            size_usd.try_sub(impact)?
+        };
+        if !adjusted.is_positive() {
+            return Err(ArithmeticError::Overflow);
+        }
+        let fill_price = index_price.try_mul(size_usd)?.try_div(adjusted)?;
+
+        Ok(OrderExecution {
+            fill_price,
+            fee: size_usd.try_mul(fee_rate)?,
+            price_impact: -impact,
+            position: PerpPosition {
+                entry_price: fill_price,
+                ..*order
+            },
+        })
+    }
+}
+
+/// Synthetix perps v2 style skew pricing and dynamic fees.
+///
+/// premium = skew / skew_scale
+/// fill_price = index × (1 + (premium_before + premium_after) / 2)
+///
+/// Skew is long minus short open interest in base units.
+#[derive(Debug, Clone, Copy)]
+pub struct SkewParams {
+    /// Skew at which the premium reaches 100%.
+    pub skew_scale: Decimal,
+    /// Fee on the part of an order that reduces skew.
+    pub maker_fee: Decimal,
+    /// Fee on the part of an order that increases skew.
+    pub taker_fee: Decimal,
+}
+
+impl SkewParams {
+    /// Premium (positive) or discount (negative) for a skew.
+    pub fn premium(&self, skew: Decimal) -> Result<Decimal, ArithmeticError> {
+        skew.try_div(self.skew_scale)
+    }
+
+    /// Skew-adjusted mark price.
+    pub fn mark_price(
+        &self,
+        index_price: Decimal,
+        skew: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        index_price.try_mul(Decimal::ONE.try_add(self.premium(skew)?)?)
+    }
+
+    /// Funding parameters with the mark price taken from the skew premium.
+    pub fn funding_params(
+        &self,
+        index_price: Decimal,
+        skew: Decimal,
+        interest_rate: Decimal,
+        premium_cap: Decimal,
+        funding_interval_hours: Decimal,
+    ) -> Result<FundingParams, ArithmeticError> {
+        Ok(FundingParams {
+            mark_price: self.mark_price(index_price, skew)?,
+            index_price,
+            interest_rate,
+            premium_cap,
+            funding_interval_hours,
+        })
+    }
+
+    /// Fill price of an order of signed size (positive = buy).
+    pub fn fill_price(
+        &self,
+        index_price: Decimal,
+        skew: Decimal,
+        size: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        let before = self.premium(skew)?;
+        let after = self.premium(skew.try_add(size)?)?;
+        let average = before.try_add(after)?.try_div(Decimal::from(2i64))?;
+        index_price.try_mul(Decimal::ONE.try_add(average)?)
+    }
+
+    /// Fee on an order of signed size: maker rate on the size that reduces
+    /// skew, taker rate on the rest.
+    pub fn order_fee(
+        &self,
+        skew: Decimal,
+        size: Decimal,
+        fill_price: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        let reduces = !skew.is_zero() && skew.is_negative() != size.is_negative();
+        let maker_size = if reduces {
+            size.abs().min(skew.abs())
+        } else {
+            Decimal::ZERO
+        };
+        let taker_size = size.abs().try_sub(maker_size)?;
+
+        let maker = maker_size.try_mul(fill_price)?.try_mul(self.maker_fee)?;
+        let taker = taker_size.try_mul(fill_price)?.try_mul(self.taker_fee)?;
+        maker.try_add(taker)
+    }
+
+    /// Executes an order against the market skew.
+    pub fn execute(
+        &self,
+        order: &PerpPosition,
+        index_price: Decimal,
+        skew: Decimal,
+    ) -> Result<OrderExecution, ArithmeticError> {
+        let size = if order.is_long {
+            order.size
+        } else {
+            -order.size
+        };
+        let fill_price = self.fill_price(index_price, skew, size)?;
+
+        Ok(OrderExecution {
+            fill_price,
+            fee: self.order_fee(skew, size, fill_price)?,
+            price_impact: size.try_mul(fill_price.try_sub(index_price)?)?,
+            position: PerpPosition {
+                entry_price: fill_price,
+                ..*order
+            },
+        })
+    }
+}
+
+/// x^e, exact for integer exponents.
+fn power(base: Decimal, exponent: Decimal) -> Result<Decimal, ArithmeticError> {
+    if exponent.floor() == exponent {
+        if let Ok(n) = i32::try_from(exponent.to_parts().0 / 10i128.pow(exponent.scale())) {
+            return base.try_powi(n);
+        }
+    }
+    base.try_pow(exponent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn gmx() -> PriceImpactParams {
+        PriceImpactParams {
+            positive_factor: decimal("0.00005"),
+            negative_factor: decimal("0.0001"),
+            exponent: decimal("2"),
+        }
+    }
+
+    fn order(size: &str, is_long: bool) -> PerpPosition {
+        PerpPosition {
+            size: decimal(size),
+            entry_price: Decimal::ZERO,
+            is_long,
+            leverage: decimal("10"),
+            collateral: decimal("10"),
+        }
+    }
+
+    #[test]
+    fn test_gmx_impact_cases() {
+        let params = gmx();
+        // Worsening from balance: -(100^2) * 0.0001
+        let worsen = params.impact_usd(decimal("1000"), decimal("1000"), decimal("100"), true);
+        assert_eq!(worsen.unwrap(), decimal("-1"));
+        // Improving 200 -> 100: (40000 - 10000) * 0.00005
+        let improve = params.impact_usd(decimal("1000"), decimal("1200"), decimal("100"), true);
+        assert_eq!(improve.unwrap(), decimal("1.5"));
+        // Crossing -50 -> 50: 2500 * 0.00005 - 2500 * 0.0001
+        let cross = params.impact_usd(decimal("1000"), decimal("1050"), decimal("100"), true);
+        assert_eq!(cross.unwrap(), decimal("-0.125"));
+    }
+
+    #[test]
+    fn test_gmx_fill_price() {
+        let params = gmx();
+        let long = params
+            .execute(
+                &order("1", true),
+                decimal("100"),
+                decimal("1000"),
+                decimal("1000"),
+                decimal("0.001"),
+            )
+            .unwrap();
+        // 100 * 100 / 99
+        assert_eq!(long.fill_price, decimal("10000") / decimal("99"));
+        assert_eq!(long.price_impact, Decimal::ONE);
+        assert_eq!(long.fee, decimal("0.1"));
+        assert_eq!(long.position.entry_price, long.fill_price);
+
+        // A short improving balance gets a better (higher) price
+        let short = params
+            .execute(
+                &order("1", false),
+                decimal("100"),
+                decimal("1200"),
+                decimal("1000"),
+                Decimal::ZERO,
+            )
+            .unwrap();
+        assert!(short.fill_price > decimal("100"));
+    }
+
+    #[test]
+    fn test_skew_fill_price_and_mark() {
+        let params = SkewParams {
+            skew_scale: decimal("1000000"),
+            maker_fee: decimal("0.0002"),
+            taker_fee: decimal("0.0005"),
+        };
+        assert_eq!(
+            params.mark_price(decimal("100"), decimal("10000")).unwrap(),
+            decimal("101")
+        );
+
+        // Selling 20000 from +10000: premiums 1% and -1% average to zero
+        let fill = params.fill_price(decimal("100"), decimal("10000"), decimal("-20000"));
+        assert_eq!(fill.unwrap(), decimal("100"));
+
+        let funding = params
+            .funding_params(
+                decimal("100"),
+                decimal("10000"),
+                Decimal::ZERO,
+                decimal("1"),
+                decimal("8"),
+            )
+            .unwrap();
+        assert_eq!(funding.mark_price, decimal("101"));
+    }
+
+    #[test]
+    fn test_skew_fee_splits_maker_taker() {
+        let params = SkewParams {
+            skew_scale: decimal("1000000"),
+            maker_fee: decimal("0.0002"),
+            taker_fee: decimal("0.0005"),
+        };
+        // 500 reduces the -500 skew (maker), 500 adds to the new long skew (taker)
+        let fee = params
+            .order_fee(decimal("-500"), decimal("1000"), decimal("10"))
+            .unwrap();
+        assert_eq!(fee, decimal("3.5"));
+        // Increasing skew is all taker
+        let fee = params
+            .order_fee(decimal("500"), decimal("1000"), decimal("10"))
+            .unwrap();
+        assert_eq!(fee, decimal("5"));
+
+        let short = params
+            .execute(&order("1000", false), decimal("10"), Decimal::ZERO)
+            .unwrap();
+        assert!(short.fill_price < decimal("10"));
+        assert!(short.price_impact.is_positive());
+    }
+}
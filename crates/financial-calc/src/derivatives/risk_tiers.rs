@@ -0,0 +1,325 @@
+//! Risk-limit tiers for perpetual positions.
+//!
+//! Perp venues scale margin with position size: each tier covers a notional
+//! bracket with its own maximum leverage and maintenance margin rate. The
+//! maintenance amount deducted in each tier keeps the requirement continuous
+//! across bracket boundaries:
+//!
+//! maintenance_margin = notional × maintenance_rate − maintenance_amount
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::derivatives::RiskTierTable;
+//! use precision_core::Decimal;
+//!
+//! let mut tiers = RiskTierTable::new();
+//! tiers.add_bracket(Decimal::from(50_000i64), Decimal::from(50i64), Decimal::new(1, 2)).unwrap();
+//! tiers.add_bracket(Decimal::from(250_000i64), Decimal::from(20i64), Decimal::new(25, 3)).unwrap();
+//!
+//! // 100k notional: 100k × 2.5% − 750 = 1750
+//! let mm = tiers.maintenance_margin(Decimal::from(100_000i64)).unwrap();
+//! assert_eq!(mm, Decimal::from(1750i64));
+//! ```
+
+use crate::storage::{Buffer, Fixed, Storage};
+use precision_core::{ArithmeticError, Decimal};
+
+/// Default number of tiers in a risk-limit table (for no_std fixed allocation).
+pub const MAX_RISK_TIERS: usize = 16;
+
+/// A notional bracket with its leverage limit and maintenance requirement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RiskTier {
+    /// Upper bound of the bracket's position notional (inclusive).
+    pub max_notional: Decimal,
+    /// Maximum leverage allowed in the bracket.
+    pub max_leverage: Decimal,
+    /// Maintenance margin rate as decimal (e.g., 0.01 for 1%).
+    pub maintenance_rate: Decimal,
+    /// Amount deducted from notional × rate.
+    pub maintenance_amount: Decimal,
+}
+
+impl RiskTier {
+    /// Creates a tier.
+    pub fn new(
+        max_notional: Decimal,
+        max_leverage: Decimal,
+        maintenance_rate: Decimal,
+        maintenance_amount: Decimal,
+    ) -> Self {
+        Self {
+            max_notional,
+            max_leverage,
+            maintenance_rate,
+            maintenance_amount,
+        }
+    }
+
+    /// Initial margin rate of the bracket (1 / max leverage).
+    pub fn initial_rate(&self) -> Result<Decimal, ArithmeticError> {
+        Decimal::ONE.try_div(self.max_leverage)
+    }
+}
+
+/// Risk-limit tiers ordered by notional bracket.
+///
+/// Notional above the last bracket exceeds the risk limit and is rejected
+/// with `Overflow`.
+#[derive(Debug, Clone)]
+pub struct RiskTierTable<S: Storage = Fixed<MAX_RISK_TIERS>> {
+    tiers: S::Buffer<RiskTier>,
+}
+
+impl RiskTierTable {
+    /// Creates an empty table with default fixed storage.
+    pub fn new() -> Self {
+        Self::with_storage()
+    }
+}
+
+impl Default for RiskTierTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Storage> RiskTierTable<S> {
+    /// Creates an empty table with the chosen storage.
+    pub fn with_storage() -> Self {
+        Self {
+            tiers: Default::default(),
+        }
+    }
+
+    /// Appends a tier with an explicit maintenance amount.
+    ///
+    /// Returns error if the bracket does not extend the previous one or the
+    /// leverage is not positive.
+    pub fn add_tier(&mut self, tier: RiskTier) -> Result<(), ArithmeticError> {
+        if !tier.max_leverage.is_positive() || tier.maintenance_rate.is_negative() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let floor = self.last().map_or(Decimal::ZERO, |t| t.max_notional);
+        if tier.max_notional <= floor {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        self.tiers.push(tier)
+    }
+
+    /// Appends a tier, deriving the maintenance amount that keeps the
+    /// requirement continuous at the previous bracket's upper bound.
+    ///
+    /// amount = previous_amount + floor × (rate − previous_rate)
+    pub fn add_bracket(
+        &mut self,
+        max_notional: Decimal,
+        max_leverage: Decimal,
+        maintenance_rate: Decimal,
+    ) -> Result<(), ArithmeticError> {
+        let maintenance_amount = match self.last() {
+            Some(prev) => prev.maintenance_amount.try_add(
+                prev.max_notional
+                    .try_mul(maintenance_rate.try_sub(prev.maintenance_rate)?)?,
+            )?,
+            None => Decimal::ZERO,
+        };
+        self.add_tier(RiskTier::new(
+            max_notional,
+            max_leverage,
+            maintenance_rate,
+            maintenance_amount,
+        ))
+    }
+
+    /// Returns the tiers in bracket order.
+    pub fn tiers(&self) -> &[RiskTier] {
+        self.tiers.as_slice()
+    }
+
+    /// Returns the number of tiers.
+    pub fn len(&self) -> usize {
+        self.tiers.len()
+    }
+
+    /// Returns true if the table has no tiers.
+    pub fn is_empty(&self) -> bool {
+        self.tiers.is_empty()
+    }
+
+    /// Returns the tier covering a position notional.
+    ///
+    /// Returns `Overflow` if the notional exceeds the last bracket, or
+    /// `DivisionByZero` if the table is empty.
+    pub fn tier(&self, notional: Decimal) -> Result<&RiskTier, ArithmeticError> {
+        if self.is_empty() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let notional = notional.abs();
+        self.tiers()
+            .iter()
+            .find(|t| notional <= t.max_notional)
+            .ok_or(ArithmeticError::Overflow)
+    }
+
+    /// Maximum leverage allowed for a position notional.
+    pub fn max_leverage(&self, notional: Decimal) -> Result<Decimal, ArithmeticError> {
+        Ok(self.tier(notional)?.max_leverage)
+    }
+
+    /// Initial margin for a position notional at the tier's maximum leverage.
+    pub fn initial_margin(&self, notional: Decimal) -> Result<Decimal, ArithmeticError> {
+        let tier = self.tier(notional)?;
+        notional.abs().try_div(tier.max_leverage)
+    }
+
+    /// Maintenance margin for a position notional.
+    ///
+    /// maintenance_margin = max(0, notional × rate − amount)
+    pub fn maintenance_margin(&self, notional: Decimal) -> Result<Decimal, ArithmeticError> {
+        let tier = self.tier(notional)?;
+        let margin = notional
+            .abs()
+            .try_mul(tier.maintenance_rate)?
+            .try_sub(tier.maintenance_amount)?;
+        Ok(margin.max(Decimal::ZERO))
+    }
+
+    /// Largest notional that collateral supports at the requested leverage,
+    /// capped by each tier's leverage limit and bracket.
+    pub fn max_notional(
+        &self,
+        collateral: Decimal,
+        leverage: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        let mut floor = Decimal::ZERO;
+        let mut best = Decimal::ZERO;
+        for tier in self.tiers() {
+            let notional = collateral
+                .try_mul(leverage.min(tier.max_leverage))?
+                .min(tier.max_notional);
+            if notional > floor || floor.is_zero() {
+                best = best.max(notional);
+            }
+            floor = tier.max_notional;
+        }
+        Ok(best)
+    }
+
+    fn last(&self) -> Option<&RiskTier> {
+        self.tiers().last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> RiskTierTable {
+        let mut tiers = RiskTierTable::new();
+        tiers
+            .add_bracket(
+                Decimal::from(50_000i64),
+                Decimal::from(50i64),
+                Decimal::new(1, 2),
+            )
+            .unwrap();
+        tiers
+            .add_bracket(
+                Decimal::from(250_000i64),
+                Decimal::from(20i64),
+                Decimal::new(25, 3),
+            )
+            .unwrap();
+        tiers
+            .add_bracket(
+                Decimal::from(1_000_000i64),
+                Decimal::from(10i64),
+                Decimal::new(5, 2),
+            )
+            .unwrap();
+        tiers
+    }
+
+    #[test]
+    fn test_tier_lookup() {
+        let tiers = table();
+        assert_eq!(
+            tiers.max_leverage(Decimal::from(50_000i64)).unwrap(),
+            Decimal::from(50i64)
+        );
+        assert_eq!(
+            tiers.max_leverage(Decimal::from(50_001i64)).unwrap(),
+            Decimal::from(20i64)
+        );
+        assert_eq!(
+            tiers.tier(Decimal::from(2_000_000i64)).unwrap_err(),
+            ArithmeticError::Overflow
+        );
+        assert_eq!(tiers.tiers()[2].maintenance_amount, Decimal::from(7000i64));
+    }
+
+    #[test]
+    fn test_maintenance_continuous_at_boundaries() {
+        let tiers = table();
+        for bound in [50_000i64, 250_000] {
+            let at = tiers.maintenance_margin(Decimal::from(bound)).unwrap();
+            let above = tiers.maintenance_margin(Decimal::from(bound + 1)).unwrap();
+            assert!(above >= at);
+            assert!(above - at < Decimal::ONE);
+        }
+        assert_eq!(
+            tiers.maintenance_margin(Decimal::from(250_000i64)).unwrap(),
+            Decimal::from(5_500i64)
+        );
+    }
+
+    #[test]
+    fn test_initial_margin_uses_tier_leverage() {
+        let tiers = table();
+        assert_eq!(
+            tiers.initial_margin(Decimal::from(100_000i64)).unwrap(),
+            Decimal::from(5_000i64)
+        );
+    }
+
+    #[test]
+    fn test_max_notional() {
+        let tiers = table();
+        // Low leverage request is not capped by tiers
+        assert_eq!(
+            tiers
+                .max_notional(Decimal::from(1_000i64), Decimal::from(10i64))
+                .unwrap(),
+            Decimal::from(10_000i64)
+        );
+        // 50x on 2000 would be 100k, but 50x is only allowed up to the first
+        // bracket's 50k bound; the 20x bracket allows 40k. The larger is 50k.
+        assert_eq!(
+            tiers
+                .max_notional(Decimal::from(2_000i64), Decimal::from(50i64))
+                .unwrap(),
+            Decimal::from(50_000i64)
+        );
+        // Large collateral is capped by the last bracket
+        assert_eq!(
+            tiers
+                .max_notional(Decimal::from(500_000i64), Decimal::from(50i64))
+                .unwrap(),
+            Decimal::from(1_000_000i64)
+        );
+    }
+
+    #[test]
+    fn test_rejects_unordered_brackets() {
+        let mut tiers = table();
+        assert!(tiers
+            .add_bracket(
+                Decimal::from(100_000i64),
+                Decimal::from(5i64),
+                Decimal::new(1, 1)
+            )
+            .is_err());
+    }
+}
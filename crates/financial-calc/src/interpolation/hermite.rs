@@ -0,0 +1,808 @@
+//! Cubic Hermite interpolators with shape-preserving slopes.
+//!
+//! Each interpolant is a piecewise cubic matching the data values and a
+//! slope at every point. The slope rule decides the shape:
+//!
+//! - [`Pchip`]: Fritsch–Carlson monotone slopes; never overshoots the data
+//! - [`Akima`]: Akima (1970) slopes; follows local trends, damping wiggles
+//! - [`Steffen`]: Steffen (1990) slopes; monotone, with extrema only at data points
+//! - [`Hermite`]: Caller-supplied derivatives at each point
+//!
+//! Beyond the data range values are held flat, consistent with the other
+//! interpolators; derivatives are zero there and integrals accumulate the
+//! end values.
+
+use super::{DataPoint, Interpolator, MAX_INTERP_POINTS};
+use crate::storage::{self, Buffer, Fixed, Storage};
+use core::marker::PhantomData;
+use precision_core::{ArithmeticError, Decimal};
+
+/// Rule for choosing the slope at each data point of a cubic Hermite curve.
+pub trait SlopeMethod {
+    /// Writes the slope at each point into `slopes` (same length as `points`).
+    ///
+    /// Points are sorted by strictly increasing x and there are at least two.
+    fn slopes(points: &[DataPoint], slopes: &mut [Decimal]) -> Result<(), ArithmeticError>;
+}
+
+/// Fritsch–Carlson monotone slopes, as used by PCHIP.
+///
+/// Interior slopes are weighted harmonic means of the adjacent secants, or
+/// zero at local extrema; end slopes use a shape-preserving three-point
+/// formula.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FritschCarlson;
+
+/// Akima slopes, weighting neighbouring secants by how much they change.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AkimaSlopes;
+
+/// Steffen slopes, bounding each slope by the adjacent secants.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SteffenSlopes;
+
+/// A piecewise cubic Hermite interpolator with slopes chosen by `M`.
+///
+/// Slopes are recomputed whenever a point is added, so the interpolator is
+/// always ready to evaluate. Use the [`Pchip`], [`Akima`] and [`Steffen`]
+/// aliases rather than naming the slope method directly.
+#[derive(Debug, Clone)]
+pub struct ShapePreserving<M, S: Storage = Fixed<MAX_INTERP_POINTS>> {
+    points: S::Buffer<DataPoint>,
+    slopes: S::Buffer<Decimal>,
+    method: PhantomData<M>,
+}
+
+/// Fritsch–Carlson monotone cubic interpolation (PCHIP).
+///
+/// Monotone data gives a monotone curve, so a non-negative rate curve never
+/// dips below zero between points.
+///
+/// # Example
+///
+/// ```
+/// use financial_calc::interpolation::{DataPoint, Interpolator, Pchip};
+/// use precision_core::Decimal;
+///
+/// // Kinked utilization-to-rate curve: 2% at 0, 4% at 80%, 60% at 100%
+/// let mut curve = Pchip::new();
+/// curve.add_point(DataPoint::new(Decimal::ZERO, Decimal::new(2, 2))).unwrap();
+/// curve.add_point(DataPoint::new(Decimal::new(8, 1), Decimal::new(4, 2))).unwrap();
+/// curve.add_point(DataPoint::new(Decimal::ONE, Decimal::new(60, 2))).unwrap();
+///
+/// let rate = curve.interpolate(Decimal::new(5, 1)).unwrap();
+/// assert!(rate > Decimal::new(2, 2) && rate < Decimal::new(4, 2));
+/// ```
+pub type Pchip<S = Fixed<MAX_INTERP_POINTS>> = ShapePreserving<FritschCarlson, S>;
+
+/// Akima cubic interpolation.
+pub type Akima<S = Fixed<MAX_INTERP_POINTS>> = ShapePreserving<AkimaSlopes, S>;
+
+/// Steffen monotone cubic interpolation.
+pub type Steffen<S = Fixed<MAX_INTERP_POINTS>> = ShapePreserving<SteffenSlopes, S>;
+
+impl<M: SlopeMethod> ShapePreserving<M> {
+    /// Creates a new empty interpolator with default fixed storage.
+    pub fn new() -> Self {
+        Self::with_storage()
+    }
+}
+
+impl<M: SlopeMethod, S: Storage> ShapePreserving<M, S> {
+    /// Creates a new empty interpolator with the chosen storage.
+    pub fn with_storage() -> Self {
+        Self {
+            points: Default::default(),
+            slopes: Default::default(),
+            method: PhantomData,
+        }
+    }
+
+    /// Builds an interpolator from points sorted by strictly increasing x.
+    ///
+    /// Returns error if the points are not strictly increasing or exceed the
+    /// storage capacity.
+    pub fn from_sorted(points: &[DataPoint]) -> Result<Self, ArithmeticError> {
+        let mut interp = Self::with_storage();
+        interp.points = storage::from_sorted(points, |p| p.x)?;
+        interp.update_slopes()?;
+        Ok(interp)
+    }
+
+    /// Adds a data point, keeping points sorted by x, and recomputes slopes.
+    ///
+    /// Returns error if a point with the same x already exists, the storage
+    /// is full or the slopes cannot be computed with the new point, in which
+    /// case the interpolator is left unchanged.
+    pub fn add_point(&mut self, point: DataPoint) -> Result<(), ArithmeticError> {
+        if self.points().iter().any(|p| p.x == point.x) {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        storage::insert_sorted(&mut self.points, point, |p| p.x)?;
+        let update = self.update_slopes();
+        if update.is_err() {
+            let index = self.points().partition_point(|p| p.x < point.x);
+            self.points.remove(index);
+        }
+        update
+    }
+
+    /// Returns the data points sorted by x.
+    pub fn points(&self) -> &[DataPoint] {
+        self.points.as_slice()
+    }
+
+    /// Returns the slope at each data point.
+    pub fn slopes(&self) -> &[Decimal] {
+        self.slopes.as_slice()
+    }
+
+    /// Returns the number of data points.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if no data points are stored.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// First derivative of the interpolant at x.
+    pub fn derivative(&self, x: Decimal) -> Result<Decimal, ArithmeticError> {
+        derivative(self.points(), self.slopes(), x)
+    }
+
+    /// Integral of the interpolant from a to b.
+    pub fn integral(&self, a: Decimal, b: Decimal) -> Result<Decimal, ArithmeticError> {
+        integral(self.points(), self.slopes(), a, b)
+    }
+
+    fn update_slopes(&mut self) -> Result<(), ArithmeticError> {
+        let n = self.len();
+        let mut slopes: S::Buffer<Decimal> = storage::filled(n, Decimal::ZERO)?;
+        if n >= 2 {
+            M::slopes(self.points.as_slice(), slopes.as_mut_slice())?;
+        }
+        self.slopes = slopes;
+        Ok(())
+    }
+}
+
+impl<M: SlopeMethod> Default for ShapePreserving<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: SlopeMethod, S: Storage> Interpolator for ShapePreserving<M, S> {
+    fn interpolate(&self, x: Decimal) -> Result<Decimal, ArithmeticError> {
+        value(self.points(), self.slopes(), x)
+    }
+
+    fn supports_extrapolation(&self) -> bool {
+        true // Flat extrapolation
+    }
+}
+
+/// A cubic Hermite interpolator with caller-supplied derivatives.
+///
+/// Each point carries its own slope, so kinks and known end derivatives
+/// (clamped boundaries) can be imposed exactly.
+#[derive(Debug, Clone)]
+pub struct Hermite<S: Storage = Fixed<MAX_INTERP_POINTS>> {
+    points: S::Buffer<DataPoint>,
+    slopes: S::Buffer<Decimal>,
+}
+
+impl Hermite {
+    /// Creates a new empty Hermite interpolator with default fixed storage.
+    pub fn new() -> Self {
+        Self::with_storage()
+    }
+}
+
+impl<S: Storage> Hermite<S> {
+    /// Creates a new empty Hermite interpolator with the chosen storage.
+    pub fn with_storage() -> Self {
+        Self {
+            points: Default::default(),
+            slopes: Default::default(),
+        }
+    }
+
+    /// Builds an interpolator from points sorted by strictly increasing x
+    /// and the derivative at each point.
+    ///
+    /// Returns error if the lengths differ, the points are not strictly
+    /// increasing or they exceed the storage capacity.
+    pub fn from_sorted(points: &[DataPoint], slopes: &[Decimal]) -> Result<Self, ArithmeticError> {
+        if points.len() != slopes.len() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let mut interp = Self {
+            points: storage::from_sorted(points, |p| p.x)?,
+            slopes: storage::filled(slopes.len(), Decimal::ZERO)?,
+        };
+        interp.slopes.as_mut_slice().copy_from_slice(slopes);
+        Ok(interp)
+    }
+
+    /// Adds a data point with its derivative, keeping points sorted by x.
+    ///
+    /// Returns error if a point with the same x already exists or the
+    /// storage is full.
+    pub fn add_point(&mut self, point: DataPoint, slope: Decimal) -> Result<(), ArithmeticError> {
+        let points = self.points.as_slice();
+        if points.iter().any(|p| p.x == point.x) {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let index = points.partition_point(|p| p.x < point.x);
+        self.points.insert(index, point)?;
+        self.slopes.insert(index, slope)
+    }
+
+    /// Returns the data points sorted by x.
+    pub fn points(&self) -> &[DataPoint] {
+        self.points.as_slice()
+    }
+
+    /// Returns the derivative at each data point.
+    pub fn slopes(&self) -> &[Decimal] {
+        self.slopes.as_slice()
+    }
+
+    /// Returns the number of data points.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if no data points are stored.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// First derivative of the interpolant at x.
+    pub fn derivative(&self, x: Decimal) -> Result<Decimal, ArithmeticError> {
+        derivative(self.points(), self.slopes(), x)
+    }
+
+    /// Integral of the interpolant from a to b.
+    pub fn integral(&self, a: Decimal, b: Decimal) -> Result<Decimal, ArithmeticError> {
+        integral(self.points(), self.slopes(), a, b)
+    }
+}
+
+impl Default for Hermite {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Storage> Interpolator for Hermite<S> {
+    fn interpolate(&self, x: Decimal) -> Result<Decimal, ArithmeticError> {
+        value(self.points(), self.slopes(), x)
+    }
+
+    fn supports_extrapolation(&self) -> bool {
+        true // Flat extrapolation
+    }
+}
+
+impl SlopeMethod for FritschCarlson {
+    fn slopes(points: &[DataPoint], slopes: &mut [Decimal]) -> Result<(), ArithmeticError> {
+        let n = points.len();
+        if n == 2 {
+            let d = secant(points, 0)?;
+            slopes.fill(d);
+            return Ok(());
+        }
+
+        for (k, slope) in slopes.iter_mut().enumerate().take(n - 1).skip(1) {
+            let (d0, d1) = (secant(points, k - 1)?, secant(points, k)?);
+            if sign(d0).try_mul(sign(d1))? <= Decimal::ZERO {
+                *slope = Decimal::ZERO;
+                continue;
+            }
+            // Weighted harmonic mean of the secants
+            let (h0, h1) = (width(points, k - 1)?, width(points, k)?);
+            let w1 = Decimal::from(2i64).try_mul(h1)?.try_add(h0)?;
+            let w2 = Decimal::from(2i64).try_mul(h0)?.try_add(h1)?;
+            let denom = w1.try_div(d0)?.try_add(w2.try_div(d1)?)?;
+            *slope = w1.try_add(w2)?.try_div(denom)?;
+        }
+
+        slopes[0] = pchip_end_slope(
+            width(points, 0)?,
+            width(points, 1)?,
+            secant(points, 0)?,
+            secant(points, 1)?,
+        )?;
+        slopes[n - 1] = pchip_end_slope(
+            width(points, n - 2)?,
+            width(points, n - 3)?,
+            secant(points, n - 2)?,
+            secant(points, n - 3)?,
+        )?;
+        Ok(())
+    }
+}
+
+/// Three-point end slope, limited to keep the end segment monotone.
+fn pchip_end_slope(
+    h0: Decimal,
+    h1: Decimal,
+    d0: Decimal,
+    d1: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let two = Decimal::from(2i64);
+    let m = two
+        .try_mul(h0)?
+        .try_add(h1)?
+        .try_mul(d0)?
+        .try_sub(h0.try_mul(d1)?)?
+        .try_div(h0.try_add(h1)?)?;
+
+    if sign(m) != sign(d0) || d0.is_zero() {
+        return Ok(Decimal::ZERO);
+    }
+    let three_d0 = Decimal::from(3i64).try_mul(d0)?;
+    if sign(d0) != sign(d1) && m.abs() > three_d0.abs() {
+        return Ok(three_d0);
+    }
+    Ok(m)
+}
+
+impl SlopeMethod for AkimaSlopes {
+    fn slopes(points: &[DataPoint], slopes: &mut [Decimal]) -> Result<(), ArithmeticError> {
+        let n = points.len();
+        if n == 2 {
+            let d = secant(points, 0)?;
+            slopes.fill(d);
+            return Ok(());
+        }
+
+        // Secant j covers segment j; two extra secants are extrapolated
+        // linearly past each end.
+        let last = n as isize - 2;
+        let extended = |j: isize| -> Result<Decimal, ArithmeticError> {
+            let two = Decimal::from(2i64);
+            if j < 0 {
+                let (d0, d1) = (secant(points, 0)?, secant(points, 1)?);
+                let dm1 = two.try_mul(d0)?.try_sub(d1)?;
+                if j == -1 {
+                    Ok(dm1)
+                } else {
+                    two.try_mul(dm1)?.try_sub(d0)
+                }
+            } else if j > last {
+                let l = last as usize;
+                let (dl, dl1) = (secant(points, l)?, secant(points, l - 1)?);
+                let dp1 = two.try_mul(dl)?.try_sub(dl1)?;
+                if j == last + 1 {
+                    Ok(dp1)
+                } else {
+                    two.try_mul(dp1)?.try_sub(dl)
+                }
+            } else {
+                secant(points, j as usize)
+            }
+        };
+
+        for (i, slope) in slopes.iter_mut().enumerate() {
+            let i = i as isize;
+            let (dm2, dm1, d0, d1) = (
+                extended(i - 2)?,
+                extended(i - 1)?,
+                extended(i)?,
+                extended(i + 1)?,
+            );
+            let w0 = d1.try_sub(d0)?.abs();
+            let w1 = dm1.try_sub(dm2)?.abs();
+            let total = w0.try_add(w1)?;
+            *slope = if total.is_zero() {
+                dm1.try_add(d0)?.try_div(Decimal::from(2i64))?
+            } else {
+                w0.try_mul(dm1)?.try_add(w1.try_mul(d0)?)?.try_div(total)?
+            };
+        }
+        Ok(())
+    }
+}
+
+impl SlopeMethod for SteffenSlopes {
+    fn slopes(points: &[DataPoint], slopes: &mut [Decimal]) -> Result<(), ArithmeticError> {
+        let n = points.len();
+        if n == 2 {
+            let d = secant(points, 0)?;
+            slopes.fill(d);
+            return Ok(());
+        }
+
+        let two = Decimal::from(2i64);
+        for (k, slope) in slopes.iter_mut().enumerate().take(n - 1).skip(1) {
+            let (d0, d1) = (secant(points, k - 1)?, secant(points, k)?);
+            let (h0, h1) = (width(points, k - 1)?, width(points, k)?);
+            // Slope of the parabola through the three points
+            let p = d0
+                .try_mul(h1)?
+                .try_add(d1.try_mul(h0)?)?
+                .try_div(h0.try_add(h1)?)?;
+            let bound = d0.abs().min(d1.abs()).min(p.abs().try_div(two)?);
+            *slope = sign(d0).try_add(sign(d1))?.try_mul(bound)?;
+        }
+
+        slopes[0] = steffen_end_slope(
+            width(points, 0)?,
+            width(points, 1)?,
+            secant(points, 0)?,
+            secant(points, 1)?,
+        )?;
+        slopes[n - 1] = steffen_end_slope(
+            width(points, n - 2)?,
+            width(points, n - 3)?,
+            secant(points, n - 2)?,
+            secant(points, n - 3)?,
+        )?;
+        Ok(())
+    }
+}
+
+/// End slope from the parabola through the last three points, limited so
+/// the end segment has no extremum.
+fn steffen_end_slope(
+    h0: Decimal,
+    h1: Decimal,
+    d0: Decimal,
+    d1: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let ratio = h0.try_div(h0.try_add(h1)?)?;
+    let p = d0
+        .try_mul(Decimal::ONE.try_add(ratio)?)?
+        .try_sub(d1.try_mul(ratio)?)?;
+
+    if !p.try_mul(d0)?.is_positive() {
+        return Ok(Decimal::ZERO);
+    }
+    let two_d0 = Decimal::from(2i64).try_mul(d0)?;
+    if p.abs() > two_d0.abs() {
+        return Ok(two_d0);
+    }
+    Ok(p)
+}
+
+fn sign(x: Decimal) -> Decimal {
+    if x.is_positive() {
+        Decimal::ONE
+    } else if x.is_negative() {
+        -Decimal::ONE
+    } else {
+        Decimal::ZERO
+    }
+}
+
+/// Width of segment k.
+fn width(points: &[DataPoint], k: usize) -> Result<Decimal, ArithmeticError> {
+    points[k + 1].x.try_sub(points[k].x)
+}
+
+/// Secant slope of segment k.
+fn secant(points: &[DataPoint], k: usize) -> Result<Decimal, ArithmeticError> {
+    points[k + 1]
+        .y
+        .try_sub(points[k].y)?
+        .try_div(width(points, k)?)
+}
+
+/// Position of x in segment k as (h, s) with s = (x - x_k) / h in [0, 1].
+fn locate(
+    points: &[DataPoint],
+    k: usize,
+    x: Decimal,
+) -> Result<(Decimal, Decimal), ArithmeticError> {
+    let h = width(points, k)?;
+    Ok((h, x.try_sub(points[k].x)?.try_div(h)?))
+}
+
+/// Hermite basis combination y0*a + h*m0*b + y1*c + h*m1*d for segment k.
+fn combine(
+    points: &[DataPoint],
+    slopes: &[Decimal],
+    k: usize,
+    h: Decimal,
+    basis: [Decimal; 4],
+) -> Result<Decimal, ArithmeticError> {
+    let [a, b, c, d] = basis;
+    points[k]
+        .y
+        .try_mul(a)?
+        .try_add(h.try_mul(slopes[k])?.try_mul(b)?)?
+        .try_add(points[k + 1].y.try_mul(c)?)?
+        .try_add(h.try_mul(slopes[k + 1])?.try_mul(d)?)
+}
+
+/// Evaluates a cubic Hermite curve at x with flat extrapolation.
+fn value(points: &[DataPoint], slopes: &[Decimal], x: Decimal) -> Result<Decimal, ArithmeticError> {
+    let n = points.len();
+    if n == 0 {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    if x <= points[0].x {
+        return Ok(points[0].y);
+    }
+    if x >= points[n - 1].x {
+        return Ok(points[n - 1].y);
+    }
+
+    let k = storage::segment_index(points, x, |p| p.x);
+    let (h, s) = locate(points, k, x)?;
+    let s2 = s.try_mul(s)?;
+    let s3 = s2.try_mul(s)?;
+    let two = Decimal::from(2i64);
+    let three = Decimal::from(3i64);
+
+    // h00 = 2s^3 - 3s^2 + 1, h10 = s^3 - 2s^2 + s, h01 = 3s^2 - 2s^3, h11 = s^3 - s^2
+    let basis = [
+        two.try_mul(s3)?
+            .try_sub(three.try_mul(s2)?)?
+            .try_add(Decimal::ONE)?,
+        s3.try_sub(two.try_mul(s2)?)?.try_add(s)?,
+        three.try_mul(s2)?.try_sub(two.try_mul(s3)?)?,
+        s3.try_sub(s2)?,
+    ];
+    combine(points, slopes, k, h, basis)
+}
+
+/// First derivative of a cubic Hermite curve at x; zero outside the data.
+fn derivative(
+    points: &[DataPoint],
+    slopes: &[Decimal],
+    x: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let n = points.len();
+    if n == 0 {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    if n == 1 || x < points[0].x || x > points[n - 1].x {
+        return Ok(Decimal::ZERO);
+    }
+
+    let k = storage::segment_index(points, x, |p| p.x);
+    let (h, s) = locate(points, k, x)?;
+    let s2 = s.try_mul(s)?;
+    let six = Decimal::from(6i64);
+    let three = Decimal::from(3i64);
+
+    // Basis derivatives with respect to s
+    let basis = [
+        six.try_mul(s2)?.try_sub(six.try_mul(s)?)?,
+        three
+            .try_mul(s2)?
+            .try_sub(Decimal::from(4i64).try_mul(s)?)?
+            .try_add(Decimal::ONE)?,
+        six.try_mul(s)?.try_sub(six.try_mul(s2)?)?,
+        three
+            .try_mul(s2)?
+            .try_sub(Decimal::from(2i64).try_mul(s)?)?,
+    ];
+    combine(points, slopes, k, h, basis)?.try_div(h)
+}
+
+/// Integral of a cubic Hermite curve from the first point to x.
+fn antiderivative(
+    points: &[DataPoint],
+    slopes: &[Decimal],
+    x: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let n = points.len();
+    let first = points[0];
+    if x <= first.x || n == 1 {
+        return x.try_sub(first.x)?.try_mul(first.y);
+    }
+
+    let mut total = Decimal::ZERO;
+    for k in 0..n - 1 {
+        let end = points[k + 1].x;
+        let (h, s) = locate(points, k, x.min(end))?;
+        total = total.try_add(segment_integral(points, slopes, k, h, s)?)?;
+        if x <= end {
+            return Ok(total);
+        }
+    }
+
+    // Flat beyond the last point
+    let last = points[n - 1];
+    total.try_add(x.try_sub(last.x)?.try_mul(last.y)?)
+}
+
+/// Integral over segment k from its start to fraction s of its width.
+fn segment_integral(
+    points: &[DataPoint],
+    slopes: &[Decimal],
+    k: usize,
+    h: Decimal,
+    s: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let s2 = s.try_mul(s)?;
+    let s3 = s2.try_mul(s)?;
+    let s4 = s3.try_mul(s)?;
+    let two = Decimal::from(2i64);
+    let three = Decimal::from(3i64);
+    let four = Decimal::from(4i64);
+
+    // Integrals of the basis functions from 0 to s
+    let basis = [
+        s4.try_div(two)?.try_sub(s3)?.try_add(s)?,
+        s4.try_div(four)?
+            .try_sub(two.try_mul(s3)?.try_div(three)?)?
+            .try_add(s2.try_div(two)?)?,
+        s3.try_sub(s4.try_div(two)?)?,
+        s4.try_div(four)?.try_sub(s3.try_div(three)?)?,
+    ];
+    combine(points, slopes, k, h, basis)?.try_mul(h)
+}
+
+/// Integral of a cubic Hermite curve from a to b.
+fn integral(
+    points: &[DataPoint],
+    slopes: &[Decimal],
+    a: Decimal,
+    b: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    if points.is_empty() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    antiderivative(points, slopes, b)?.try_sub(antiderivative(points, slopes, a)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpolation::CubicSpline;
+
+    /// Flat, then a sharp rise: the natural spline undershoots before the kink.
+    fn kinked() -> [DataPoint; 5] {
+        [
+            DataPoint::new(Decimal::ZERO, Decimal::new(1, 2)),
+            DataPoint::new(Decimal::new(4, 1), Decimal::new(1, 2)),
+            DataPoint::new(Decimal::new(8, 1), Decimal::new(1, 2)),
+            DataPoint::new(Decimal::new(9, 1), Decimal::new(20, 2)),
+            DataPoint::new(Decimal::ONE, Decimal::ONE),
+        ]
+    }
+
+    fn grid() -> impl Iterator<Item = Decimal> {
+        (0..=100).map(|i| Decimal::new(i, 2))
+    }
+
+    #[test]
+    fn test_spline_overshoots_kinked_data() {
+        let spline = CubicSpline::<Fixed<8>>::from_sorted(&kinked()).unwrap();
+        assert!(grid().any(|x| spline.interpolate(x).unwrap() < Decimal::new(1, 2)));
+    }
+
+    #[test]
+    fn test_pchip_monotone_on_kinked_data() {
+        let curve = Pchip::<Fixed<8>>::from_sorted(&kinked()).unwrap();
+        let mut prev = Decimal::ZERO;
+        for x in grid() {
+            let y = curve.interpolate(x).unwrap();
+            assert!(y >= prev && y >= Decimal::new(1, 2));
+            prev = y;
+        }
+    }
+
+    #[test]
+    fn test_steffen_monotone_on_kinked_data() {
+        let curve = Steffen::<Fixed<8>>::from_sorted(&kinked()).unwrap();
+        let mut prev = Decimal::ZERO;
+        for x in grid() {
+            let y = curve.interpolate(x).unwrap();
+            assert!(y >= prev);
+            prev = y;
+        }
+    }
+
+    #[test]
+    fn test_akima_flat_region_stays_flat() {
+        let curve = Akima::<Fixed<8>>::from_sorted(&kinked()).unwrap();
+        // Akima keeps the curve flat away from the kink
+        for x in ["0.1", "0.3", "0.5"] {
+            let y = curve.interpolate(x.parse().unwrap()).unwrap();
+            assert_eq!(y, Decimal::new(1, 2));
+        }
+    }
+
+    #[test]
+    fn test_reproduces_linear_data() {
+        let points = [
+            DataPoint::new(Decimal::ZERO, Decimal::ONE),
+            DataPoint::new(Decimal::ONE, Decimal::from(3i64)),
+            DataPoint::new(Decimal::from(3i64), Decimal::from(7i64)),
+        ];
+        let pchip = Pchip::<Fixed<4>>::from_sorted(&points).unwrap();
+        let akima = Akima::<Fixed<4>>::from_sorted(&points).unwrap();
+        let steffen = Steffen::<Fixed<4>>::from_sorted(&points).unwrap();
+
+        let x = Decimal::new(25, 1);
+        for y in [
+            pchip.interpolate(x).unwrap(),
+            akima.interpolate(x).unwrap(),
+            steffen.interpolate(x).unwrap(),
+        ] {
+            assert_eq!(y, Decimal::from(6i64));
+        }
+        assert_eq!(pchip.derivative(x).unwrap(), Decimal::from(2i64));
+    }
+
+    #[test]
+    fn test_hermite_derivative_and_integral() {
+        // y = x^3 has y' = 3x^2; a cubic Hermite reproduces it exactly
+        let points = [
+            DataPoint::new(Decimal::ZERO, Decimal::ZERO),
+            DataPoint::new(Decimal::ONE, Decimal::ONE),
+            DataPoint::new(Decimal::from(2i64), Decimal::from(8i64)),
+        ];
+        let slopes = [Decimal::ZERO, Decimal::from(3i64), Decimal::from(12i64)];
+        let curve = Hermite::<Fixed<4>>::from_sorted(&points, &slopes).unwrap();
+
+        let x = Decimal::new(15, 1);
+        assert_eq!(curve.interpolate(x).unwrap(), Decimal::new(3375, 3));
+        assert_eq!(curve.derivative(x).unwrap(), Decimal::new(675, 2));
+
+        // ∫_0^2 x^3 dx = 4, and the integral is additive
+        let tol = Decimal::new(1, 20);
+        let whole = curve.integral(Decimal::ZERO, Decimal::from(2i64)).unwrap();
+        assert!((whole - Decimal::from(4i64)).abs() < tol);
+        let split = curve.integral(Decimal::ZERO, x).unwrap()
+            + curve.integral(x, Decimal::from(2i64)).unwrap();
+        assert!((split - whole).abs() < tol);
+
+        // Flat extrapolation past the end
+        let tail = curve
+            .integral(Decimal::from(2i64), Decimal::from(3i64))
+            .unwrap();
+        assert_eq!(tail, Decimal::from(8i64));
+    }
+
+    #[test]
+    fn test_hermite_add_point_keeps_slopes_aligned() {
+        let mut curve = Hermite::new();
+        curve
+            .add_point(
+                DataPoint::new(Decimal::from(2i64), Decimal::ONE),
+                Decimal::from(5i64),
+            )
+            .unwrap();
+        curve
+            .add_point(DataPoint::new(Decimal::ZERO, Decimal::ZERO), Decimal::ONE)
+            .unwrap();
+
+        assert_eq!(curve.points()[0].x, Decimal::ZERO);
+        assert_eq!(curve.slopes(), &[Decimal::ONE, Decimal::from(5i64)]);
+        assert!(curve
+            .add_point(DataPoint::new(Decimal::ZERO, Decimal::ONE), Decimal::ONE)
+            .is_err());
+    }
+
+    #[test]
+    fn test_failed_add_point_leaves_interpolator_unchanged() {
+        let mut curve = Pchip::new();
+        curve
+            .add_point(DataPoint::new(Decimal::ZERO, Decimal::ZERO))
+            .unwrap();
+        curve
+            .add_point(DataPoint::new(Decimal::ONE, Decimal::ONE))
+            .unwrap();
+        let slopes = [curve.slopes()[0], curve.slopes()[1]];
+
+        // The secant slope from the origin overflows
+        let steep = DataPoint::new(Decimal::new(1, 10), Decimal::MAX);
+        assert!(curve.add_point(steep).is_err());
+        assert_eq!(curve.len(), 2);
+        assert_eq!(curve.slopes(), slopes);
+        assert_eq!(
+            curve.interpolate(Decimal::new(5, 1)).unwrap(),
+            Decimal::new(5, 1)
+        );
+    }
+}
@@ -9,14 +9,32 @@
 //! - [`Linear`]: Simple linear interpolation between points
 //! - [`LogLinear`]: Interpolation in log space (preserves positive values)
 //! - [`CubicSpline`]: Smooth cubic spline with natural boundary conditions
+//! - [`Pchip`], [`Akima`], [`Steffen`]: Shape-preserving cubic Hermite curves
+//!   that do not overshoot kinked data
+//! - [`Hermite`]: Cubic Hermite curve with caller-supplied derivatives
+//!
+//! The Hermite interpolators also evaluate derivatives and integrals.
+//!
+//! All interpolators are generic over a [`Storage`] backend. The default,
+//! [`Fixed<MAX_INTERP_POINTS>`], keeps points inline without allocation;
+//! larger fixed buffers or the heap-backed `Heap` storage (with the `alloc`
+//! feature) lift the size limit. Lookups use binary search.
+
+mod hermite;
+
+pub use hermite::{
+    Akima, AkimaSlopes, FritschCarlson, Hermite, Pchip, ShapePreserving, SlopeMethod, Steffen,
+    SteffenSlopes,
+};
 
+use crate::storage::{self, Buffer, Fixed, Storage};
 use precision_core::{ArithmeticError, Decimal};
 
-/// Maximum number of points for interpolation (for no_std fixed allocation).
+/// Default number of points for interpolation (for no_std fixed allocation).
 pub const MAX_INTERP_POINTS: usize = 32;
 
 /// A data point for interpolation.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct DataPoint {
     /// X coordinate (typically time)
     pub x: Decimal,
@@ -49,73 +67,53 @@ pub trait Interpolator {
 /// For a point x between x_i and x_{i+1}, the interpolated value is:
 /// y = y_i + (y_{i+1} - y_i) * (x - x_i) / (x_{i+1} - x_i)
 #[derive(Debug, Clone)]
-pub struct Linear {
-    points: [Option<DataPoint>; MAX_INTERP_POINTS],
-    count: usize,
+pub struct Linear<S: Storage = Fixed<MAX_INTERP_POINTS>> {
+    points: S::Buffer<DataPoint>,
 }
 
 impl Linear {
-    /// Creates a new empty linear interpolator.
+    /// Creates a new empty linear interpolator with default fixed storage.
     pub fn new() -> Self {
+        Self::with_storage()
+    }
+}
+
+impl<S: Storage> Linear<S> {
+    /// Creates a new empty linear interpolator with the chosen storage.
+    pub fn with_storage() -> Self {
         Self {
-            points: [None; MAX_INTERP_POINTS],
-            count: 0,
+            points: Default::default(),
         }
     }
 
+    /// Builds an interpolator from points sorted by strictly increasing x.
+    ///
+    /// Returns error if the points are not strictly increasing or exceed the
+    /// storage capacity.
+    pub fn from_sorted(points: &[DataPoint]) -> Result<Self, ArithmeticError> {
+        Ok(Self {
+            points: storage::from_sorted(points, |p| p.x)?,
+        })
+    }
+
     /// Adds a data point, keeping points sorted by x.
     pub fn add_point(&mut self, point: DataPoint) -> Result<(), ArithmeticError> {
-        if self.count >= MAX_INTERP_POINTS {
-            return Err(ArithmeticError::Overflow);
-        }
-
-        // Find insertion point
-        let mut idx = self.count;
-        for i in 0..self.count {
-            if let Some(p) = &self.points[i] {
-                if point.x < p.x {
-                    idx = i;
-                    break;
-                }
-            }
-        }
-
-        // Shift points
-        for i in (idx..self.count).rev() {
-            self.points[i + 1] = self.points[i];
-        }
+        storage::insert_sorted(&mut self.points, point, |p| p.x)
+    }
 
-        self.points[idx] = Some(point);
-        self.count += 1;
-        Ok(())
+    /// Returns the data points sorted by x.
+    pub fn points(&self) -> &[DataPoint] {
+        self.points.as_slice()
     }
 
     /// Returns the number of data points.
     pub fn len(&self) -> usize {
-        self.count
+        self.points.len()
     }
 
     /// Returns true if no data points are stored.
     pub fn is_empty(&self) -> bool {
-        self.count == 0
-    }
-
-    fn find_bracket(&self, x: Decimal) -> (Option<&DataPoint>, Option<&DataPoint>) {
-        let mut lower: Option<&DataPoint> = None;
-        let mut upper: Option<&DataPoint> = None;
-
-        for i in 0..self.count {
-            if let Some(p) = &self.points[i] {
-                if p.x <= x {
-                    lower = Some(p);
-                }
-                if p.x >= x && upper.is_none() {
-                    upper = Some(p);
-                }
-            }
-        }
-
-        (lower, upper)
+        self.points.is_empty()
     }
 }
 
@@ -125,13 +123,13 @@ impl Default for Linear {
     }
 }
 
-impl Interpolator for Linear {
+impl<S: Storage> Interpolator for Linear<S> {
     fn interpolate(&self, x: Decimal) -> Result<Decimal, ArithmeticError> {
-        if self.count == 0 {
+        if self.is_empty() {
             return Err(ArithmeticError::DivisionByZero);
         }
 
-        let (lower, upper) = self.find_bracket(x);
+        let (lower, upper) = storage::bracket(self.points(), x, |p| p.x);
 
         match (lower, upper) {
             (Some(l), Some(u)) if l.x == u.x => Ok(l.y),
@@ -161,20 +159,38 @@ impl Interpolator for Linear {
 ///
 /// y = exp(ln(y_i) + (ln(y_{i+1}) - ln(y_i)) * (x - x_i) / (x_{i+1} - x_i))
 #[derive(Debug, Clone)]
-pub struct LogLinear {
-    points: [Option<DataPoint>; MAX_INTERP_POINTS],
-    count: usize,
+pub struct LogLinear<S: Storage = Fixed<MAX_INTERP_POINTS>> {
+    points: S::Buffer<DataPoint>,
 }
 
 impl LogLinear {
-    /// Creates a new empty log-linear interpolator.
+    /// Creates a new empty log-linear interpolator with default fixed storage.
     pub fn new() -> Self {
+        Self::with_storage()
+    }
+}
+
+impl<S: Storage> LogLinear<S> {
+    /// Creates a new empty log-linear interpolator with the chosen storage.
+    pub fn with_storage() -> Self {
         Self {
-            points: [None; MAX_INTERP_POINTS],
-            count: 0,
+            points: Default::default(),
         }
     }
 
+    /// Builds an interpolator from points sorted by strictly increasing x.
+    ///
+    /// Returns error if any y value is not positive, if the points are not
+    /// strictly increasing or if they exceed the storage capacity.
+    pub fn from_sorted(points: &[DataPoint]) -> Result<Self, ArithmeticError> {
+        if points.iter().any(|p| !p.y.is_positive()) {
+            return Err(ArithmeticError::LogOfNegative);
+        }
+        Ok(Self {
+            points: storage::from_sorted(points, |p| p.x)?,
+        })
+    }
+
     /// Adds a data point.
     ///
     /// Y values must be positive for log-linear interpolation.
@@ -182,57 +198,22 @@ impl LogLinear {
         if !point.y.is_positive() {
             return Err(ArithmeticError::LogOfNegative);
         }
-        if self.count >= MAX_INTERP_POINTS {
-            return Err(ArithmeticError::Overflow);
-        }
-
-        // Find insertion point
-        let mut idx = self.count;
-        for i in 0..self.count {
-            if let Some(p) = &self.points[i] {
-                if point.x < p.x {
-                    idx = i;
-                    break;
-                }
-            }
-        }
-
-        // Shift points
-        for i in (idx..self.count).rev() {
-            self.points[i + 1] = self.points[i];
-        }
+        storage::insert_sorted(&mut self.points, point, |p| p.x)
+    }
 
-        self.points[idx] = Some(point);
-        self.count += 1;
-        Ok(())
+    /// Returns the data points sorted by x.
+    pub fn points(&self) -> &[DataPoint] {
+        self.points.as_slice()
     }
 
     /// Returns the number of data points.
     pub fn len(&self) -> usize {
-        self.count
+        self.points.len()
     }
 
     /// Returns true if no data points are stored.
     pub fn is_empty(&self) -> bool {
-        self.count == 0
-    }
-
-    fn find_bracket(&self, x: Decimal) -> (Option<&DataPoint>, Option<&DataPoint>) {
-        let mut lower: Option<&DataPoint> = None;
-        let mut upper: Option<&DataPoint> = None;
-
-        for i in 0..self.count {
-            if let Some(p) = &self.points[i] {
-                if p.x <= x {
-                    lower = Some(p);
-                }
-                if p.x >= x && upper.is_none() {
-                    upper = Some(p);
-                }
-            }
-        }
-
-        (lower, upper)
+        self.points.is_empty()
     }
 }
 
@@ -242,13 +223,13 @@ impl Default for LogLinear {
     }
 }
 
-impl Interpolator for LogLinear {
+impl<S: Storage> Interpolator for LogLinear<S> {
     fn interpolate(&self, x: Decimal) -> Result<Decimal, ArithmeticError> {
-        if self.count == 0 {
+        if self.is_empty() {
             return Err(ArithmeticError::DivisionByZero);
         }
 
-        let (lower, upper) = self.find_bracket(x);
+        let (lower, upper) = storage::bracket(self.points(), x, |p| p.x);
 
         match (lower, upper) {
             (Some(l), Some(u)) if l.x == u.x => Ok(l.y),
@@ -284,64 +265,68 @@ impl Interpolator for LogLinear {
 /// This is the gold standard for yield curve interpolation as it produces
 /// smooth forward rate curves without artificial kinks.
 #[derive(Debug, Clone)]
-pub struct CubicSpline {
-    points: [Option<DataPoint>; MAX_INTERP_POINTS],
+pub struct CubicSpline<S: Storage = Fixed<MAX_INTERP_POINTS>> {
+    points: S::Buffer<DataPoint>,
     /// Second derivatives at each point (computed after all points are added)
-    second_derivs: [Decimal; MAX_INTERP_POINTS],
-    count: usize,
+    second_derivs: S::Buffer<Decimal>,
     /// Whether the spline coefficients have been computed
     computed: bool,
 }
 
 impl CubicSpline {
-    /// Creates a new empty cubic spline interpolator.
+    /// Creates a new empty cubic spline interpolator with default fixed storage.
     pub fn new() -> Self {
+        Self::with_storage()
+    }
+}
+
+impl<S: Storage> CubicSpline<S> {
+    /// Creates a new empty cubic spline interpolator with the chosen storage.
+    pub fn with_storage() -> Self {
         Self {
-            points: [None; MAX_INTERP_POINTS],
-            second_derivs: [Decimal::ZERO; MAX_INTERP_POINTS],
-            count: 0,
+            points: Default::default(),
+            second_derivs: Default::default(),
             computed: false,
         }
     }
 
+    /// Builds a spline from points sorted by strictly increasing x.
+    ///
+    /// The spline coefficients are computed immediately, so the result is
+    /// ready for interpolation. Returns error if the points are not strictly
+    /// increasing or exceed the storage capacity.
+    pub fn from_sorted(points: &[DataPoint]) -> Result<Self, ArithmeticError> {
+        let mut spline = Self {
+            points: storage::from_sorted(points, |p| p.x)?,
+            second_derivs: Default::default(),
+            computed: false,
+        };
+        spline.compute()?;
+        Ok(spline)
+    }
+
     /// Adds a data point.
     ///
     /// Note: After adding all points, call `compute()` to calculate spline coefficients.
     pub fn add_point(&mut self, point: DataPoint) -> Result<(), ArithmeticError> {
-        if self.count >= MAX_INTERP_POINTS {
-            return Err(ArithmeticError::Overflow);
-        }
-
-        // Find insertion point
-        let mut idx = self.count;
-        for i in 0..self.count {
-            if let Some(p) = &self.points[i] {
-                if point.x < p.x {
-                    idx = i;
-                    break;
-                }
-            }
-        }
-
-        // Shift points
-        for i in (idx..self.count).rev() {
-            self.points[i + 1] = self.points[i];
-        }
-
-        self.points[idx] = Some(point);
-        self.count += 1;
+        storage::insert_sorted(&mut self.points, point, |p| p.x)?;
         self.computed = false; // Need to recompute
         Ok(())
     }
 
+    /// Returns the data points sorted by x.
+    pub fn points(&self) -> &[DataPoint] {
+        self.points.as_slice()
+    }
+
     /// Returns the number of data points.
     pub fn len(&self) -> usize {
-        self.count
+        self.points.len()
     }
 
     /// Returns true if no data points are stored.
     pub fn is_empty(&self) -> bool {
-        self.count == 0
+        self.points.is_empty()
     }
 
     /// Computes the spline coefficients using the Thomas Algorithm.
@@ -349,73 +334,14 @@ impl CubicSpline {
     /// Must be called after all points are added and before interpolation.
     /// Uses natural spline boundary conditions (second derivative = 0 at endpoints).
     pub fn compute(&mut self) -> Result<(), ArithmeticError> {
-        if self.count < 2 {
-            self.computed = true;
-            return Ok(());
-        }
-
-        let n = self.count;
-
-        // Working arrays for Thomas Algorithm
-        let mut a = [Decimal::ZERO; MAX_INTERP_POINTS]; // Sub-diagonal
-        let mut b = [Decimal::ZERO; MAX_INTERP_POINTS]; // Main diagonal
-        let mut c = [Decimal::ZERO; MAX_INTERP_POINTS]; // Super-diagonal
-        let mut d = [Decimal::ZERO; MAX_INTERP_POINTS]; // Right-hand side
-
-        // Build the tridiagonal system for natural spline
-        // Natural boundary: M_0 = M_{n-1} = 0
-        self.second_derivs[0] = Decimal::ZERO;
-        self.second_derivs[n - 1] = Decimal::ZERO;
-
-        // Set up equations for interior points
-        for i in 1..n - 1 {
-            let p0 = self.points[i - 1].as_ref().unwrap();
-            let p1 = self.points[i].as_ref().unwrap();
-            let p2 = self.points[i + 1].as_ref().unwrap();
-
-            let h0 = p1.x.try_sub(p0.x)?;
-            let h1 = p2.x.try_sub(p1.x)?;
-
-            a[i] = h0;
-            b[i] = Decimal::from(2i64).try_mul(h0.try_add(h1)?)?;
-            c[i] = h1;
-
-            let dy0 = p1.y.try_sub(p0.y)?.try_div(h0)?;
-            let dy1 = p2.y.try_sub(p1.y)?.try_div(h1)?;
-            d[i] = Decimal::from(6i64).try_mul(dy1.try_sub(dy0)?)?;
-        }
-
-        // Thomas Algorithm (forward elimination)
-        for i in 2..n - 1 {
-            let m = a[i].try_div(b[i - 1])?;
-            b[i] = b[i].try_sub(m.try_mul(c[i - 1])?)?;
-            d[i] = d[i].try_sub(m.try_mul(d[i - 1])?)?;
-        }
-
-        // Back substitution
-        if n > 2 {
-            self.second_derivs[n - 2] = d[n - 2].try_div(b[n - 2])?;
-        }
-        for i in (1..n - 2).rev() {
-            self.second_derivs[i] = d[i]
-                .try_sub(c[i].try_mul(self.second_derivs[i + 1])?)?
-                .try_div(b[i])?;
-        }
-
+        self.second_derivs = natural_spline_second_derivs::<S, _, _, _>(
+            self.points.as_slice(),
+            |p| p.x,
+            |p| p.y,
+        )?;
         self.computed = true;
         Ok(())
     }
-
-    fn find_segment(&self, x: Decimal) -> Option<(usize, &DataPoint, &DataPoint)> {
-        for i in 0..self.count - 1 {
-            let p0 = self.points[i].as_ref()?;
-            let p1 = self.points[i + 1].as_ref()?;
-            if x >= p0.x && x <= p1.x {
-                return Some((i, p0, p1));
-            }
-        }
-        None
-    }
 }
 
 impl Default for CubicSpline {
@@ -424,21 +350,22 @@ impl Default for CubicSpline {
     }
 }
 
-impl Interpolator for CubicSpline {
+impl<S: Storage> Interpolator for CubicSpline<S> {
     fn interpolate(&self, x: Decimal) -> Result<Decimal, ArithmeticError> {
         if !self.computed {
             return Err(ArithmeticError::DivisionByZero); // Not computed
         }
-        if self.count == 0 {
+        let points = self.points();
+        if points.is_empty() {
             return Err(ArithmeticError::DivisionByZero);
         }
-        if self.count == 1 {
-            return Ok(self.points[0].as_ref().unwrap().y);
+        if points.len() == 1 {
+            return Ok(points[0].y);
         }
 
         // Handle extrapolation with flat extension
-        let first = self.points[0].as_ref().unwrap();
-        let last = self.points[self.count - 1].as_ref().unwrap();
+        let first = &points[0];
+        let last = &points[points.len() - 1];
 
         if x <= first.x {
             return Ok(first.y);
@@ -448,27 +375,12 @@ impl Interpolator for CubicSpline {
         }
 
         // Find the segment containing x
-        let (i, p0, p1) = self.find_segment(x).ok_or(ArithmeticError::DivisionByZero)?;
+        let i = storage::segment_index(points, x, |p| p.x);
+        let p0 = &points[i];
+        let p1 = &points[i + 1];
 
-        let h = p1.x.try_sub(p0.x)?;
-        let a = p1.x.try_sub(x)?.try_div(h)?;
-        let b = x.try_sub(p0.x)?.try_div(h)?;
-
-        let m0 = self.second_derivs[i];
-        let m1 = self.second_derivs[i + 1];
-
-        // Cubic spline formula:
-        // S(x) = a*y0 + b*y1 + ((a^3 - a)*M0 + (b^3 - b)*M1) * h^2 / 6
-        let a3 = a.try_mul(a)?.try_mul(a)?;
-        let b3 = b.try_mul(b)?.try_mul(b)?;
-        let h2_6 = h.try_mul(h)?.try_div(Decimal::from(6i64))?;
-
-        let term1 = a.try_mul(p0.y)?;
-        let term2 = b.try_mul(p1.y)?;
-        let term3 = a3.try_sub(a)?.try_mul(m0)?.try_mul(h2_6)?;
-        let term4 = b3.try_sub(b)?.try_mul(m1)?.try_mul(h2_6)?;
-
-        term1.try_add(term2)?.try_add(term3)?.try_add(term4)
+        let second_derivs = self.second_derivs.as_slice();
+        spline_segment(*p0, *p1, second_derivs[i], second_derivs[i + 1], x)
     }
 
     fn supports_extrapolation(&self) -> bool {
@@ -476,6 +388,100 @@ impl Interpolator for CubicSpline {
     }
 }
 
+/// Second derivatives of the natural cubic spline through `points`.
+///
+/// Solves the tridiagonal system with the Thomas Algorithm using natural
+/// boundary conditions (M = 0 at both endpoints). Points must be sorted by
+/// strictly increasing x.
+pub(crate) fn natural_spline_second_derivs<S, T, X, Y>(
+    points: &[T],
+    x: X,
+    y: Y,
+) -> Result<S::Buffer<Decimal>, ArithmeticError>
+where
+    S: Storage,
+    X: Fn(&T) -> Decimal,
+    Y: Fn(&T) -> Decimal,
+{
+    let n = points.len();
+    let mut second_derivs: S::Buffer<Decimal> = storage::filled(n, Decimal::ZERO)?;
+
+    if n < 3 {
+        // Two points (or fewer) give a straight line: M = 0 everywhere
+        return Ok(second_derivs);
+    }
+
+    // Working buffers for Thomas Algorithm. Sub- and super-diagonals are
+    // the interval widths, recomputed from the points when needed.
+    let mut b: S::Buffer<Decimal> = storage::filled(n, Decimal::ZERO)?; // Main diagonal
+    let mut d: S::Buffer<Decimal> = storage::filled(n, Decimal::ZERO)?; // Right-hand side
+    let b = b.as_mut_slice();
+    let d = d.as_mut_slice();
+
+    // Build the tridiagonal system for natural spline
+    // Natural boundary: M_0 = M_{n-1} = 0
+    for i in 1..n - 1 {
+        let (x0, x1, x2) = (x(&points[i - 1]), x(&points[i]), x(&points[i + 1]));
+        let (y0, y1, y2) = (y(&points[i - 1]), y(&points[i]), y(&points[i + 1]));
+
+        let h0 = x1.try_sub(x0)?;
+        let h1 = x2.try_sub(x1)?;
+
+        b[i] = Decimal::from(2i64).try_mul(h0.try_add(h1)?)?;
+
+        let dy0 = y1.try_sub(y0)?.try_div(h0)?;
+        let dy1 = y2.try_sub(y1)?.try_div(h1)?;
+        d[i] = Decimal::from(6i64).try_mul(dy1.try_sub(dy0)?)?;
+    }
+
+    // Thomas Algorithm (forward elimination)
+    // a[i] = h_{i-1} = x_i - x_{i-1}, c[i-1] = h_{i-1}
+    for i in 2..n - 1 {
+        let h = x(&points[i]).try_sub(x(&points[i - 1]))?;
+        let m = h.try_div(b[i - 1])?;
+        b[i] = b[i].try_sub(m.try_mul(h)?)?;
+        d[i] = d[i].try_sub(m.try_mul(d[i - 1])?)?;
+    }
+
+    // Back substitution
+    let m = second_derivs.as_mut_slice();
+    m[n - 2] = d[n - 2].try_div(b[n - 2])?;
+    for i in (1..n - 2).rev() {
+        let c = x(&points[i + 1]).try_sub(x(&points[i]))?;
+        m[i] = d[i].try_sub(c.try_mul(m[i + 1])?)?.try_div(b[i])?;
+    }
+
+    Ok(second_derivs)
+}
+
+/// Evaluates the cubic spline segment between `p0` and `p1` at `x`.
+///
+/// `m0` and `m1` are the second derivatives at the segment endpoints.
+pub(crate) fn spline_segment(
+    p0: DataPoint,
+    p1: DataPoint,
+    m0: Decimal,
+    m1: Decimal,
+    x: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let h = p1.x.try_sub(p0.x)?;
+    let a = p1.x.try_sub(x)?.try_div(h)?;
+    let b = x.try_sub(p0.x)?.try_div(h)?;
+
+    // Cubic spline formula:
+    // S(x) = a*y0 + b*y1 + ((a^3 - a)*M0 + (b^3 - b)*M1) * h^2 / 6
+    let a3 = a.try_mul(a)?.try_mul(a)?;
+    let b3 = b.try_mul(b)?.try_mul(b)?;
+    let h2_6 = h.try_mul(h)?.try_div(Decimal::from(6i64))?;
+
+    let term1 = a.try_mul(p0.y)?;
+    let term2 = b.try_mul(p1.y)?;
+    let term3 = a3.try_sub(a)?.try_mul(m0)?.try_mul(h2_6)?;
+    let term4 = b3.try_sub(b)?.try_mul(m1)?.try_mul(h2_6)?;
+
+    term1.try_add(term2)?.try_add(term3)?.try_add(term4)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -600,4 +606,70 @@ mod tests {
         let result = spline.interpolate(Decimal::new(5, 1));
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_linear_from_sorted_large() {
+        // More points than the default capacity using a larger fixed buffer
+        let mut points = [DataPoint::default(); 100];
+        for (i, p) in points.iter_mut().enumerate() {
+            let x = Decimal::from(i as i64);
+            *p = DataPoint::new(x, x.try_mul(Decimal::from(3i64)).unwrap());
+        }
+
+        let interp = Linear::<Fixed<128>>::from_sorted(&points).unwrap();
+        assert_eq!(interp.len(), 100);
+
+        let result = interp.interpolate(Decimal::new(905, 1)).unwrap(); // x = 90.5
+        assert_eq!(result, Decimal::new(2715, 1));
+
+        assert!(Linear::<Fixed<MAX_INTERP_POINTS>>::from_sorted(&points).is_err());
+    }
+
+    #[test]
+    fn test_from_sorted_rejects_unsorted() {
+        let points = [
+            DataPoint::new(Decimal::ONE, Decimal::ONE),
+            DataPoint::new(Decimal::ZERO, Decimal::ONE),
+        ];
+        assert!(Linear::<Fixed<4>>::from_sorted(&points).is_err());
+        assert!(CubicSpline::<Fixed<4>>::from_sorted(&points).is_err());
+    }
+
+    #[test]
+    fn test_cubic_spline_from_sorted_matches_incremental() {
+        let xs = [0i64, 1, 3, 4, 7];
+        let mut incremental = CubicSpline::new();
+        let mut points = [DataPoint::default(); 5];
+        for (p, &x) in points.iter_mut().zip(xs.iter()) {
+            *p = DataPoint::new(Decimal::from(x), Decimal::from(x * x - 2 * x));
+            incremental.add_point(*p).unwrap();
+        }
+        incremental.compute().unwrap();
+
+        let bulk = CubicSpline::<Fixed<8>>::from_sorted(&points).unwrap();
+
+        for x in ["0.5", "2", "3.5", "6.25"] {
+            let x: Decimal = x.parse().unwrap();
+            assert_eq!(
+                incremental.interpolate(x).unwrap(),
+                bulk.interpolate(x).unwrap()
+            );
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_heap_storage_unbounded() {
+        use crate::storage::Heap;
+
+        let mut spline = CubicSpline::<Heap>::with_storage();
+        for i in 0..500 {
+            let x = Decimal::from(i as i64);
+            spline.add_point(DataPoint::new(x, x)).unwrap();
+        }
+        spline.compute().unwrap();
+
+        let y = spline.interpolate(Decimal::new(4005, 1)).unwrap();
+        assert!((y - Decimal::new(4005, 1)).abs() < Decimal::new(1, 10));
+    }
 }
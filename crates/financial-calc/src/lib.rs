@@ -10,51 +10,93 @@
 //! - Interest calculations (simple, compound, continuous)
 //! - Time value of money (present value, future value, NPV)
 //! - Percentage operations and basis points
-//! - **Options pricing** (Black-Scholes model, Greeks, implied volatility)
-//! - **Term structures** (yield curves, discount factors, forward rates)
+//! - **Options pricing** (Black-Scholes, generalised carry, Black-76, Bachelier, American lattices, exotics, Greeks, implied volatility)
+//! - **Term structures** (yield curves, discount factors, forward rates, Nelson-Siegel/Svensson)
+//! - **Monte Carlo** (seeded RNG, GBM/jump-diffusion/Heston paths, variance reduction)
+//! - **Surfaces** (bilinear and bicubic grids, implied volatility by strike and expiry)
 //! - **Day count conventions** (Actual/360, 30/360, etc.)
 //! - **Derivatives** (perpetual futures, funding rates, liquidations)
 //! - **AMM** (constant product, concentrated liquidity, impermanent loss)
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod amm;
 pub mod day_count;
 pub mod derivatives;
 pub mod interpolation;
 mod interest;
+pub mod monte_carlo;
 pub mod options;
 mod percentage;
 pub mod solver;
+pub mod storage;
+pub mod surface;
 pub mod term_structure;
 mod time_value;
 
 pub use day_count::{Date, DayCountConvention, YearFraction};
-pub use interpolation::{CubicSpline, DataPoint, Interpolator, Linear, LogLinear};
+pub use interpolation::{
+    Akima, CubicSpline, DataPoint, Hermite, Interpolator, Linear, LogLinear, Pchip, Steffen,
+    MAX_INTERP_POINTS,
+};
 pub use interest::{compound_interest, effective_annual_rate, simple_interest};
+pub use monte_carlo::{
+    inverse_normal_cdf, Estimate, Gbm, Heston, JumpDiffusion, MonteCarlo, PathModel,
+    RandomSource, Xoshiro256StarStar,
+};
 pub use options::{
-    black_scholes_call, black_scholes_put, call_greeks, implied_volatility, normal_cdf, normal_pdf,
-    put_greeks, Greeks, OptionParams,
+    arithmetic_asian_price, asset_or_nothing_price, bachelier_price, barone_adesi_whaley,
+    barrier_price, black_76_price, black_scholes_call, black_scholes_put, call_greeks,
+    cash_or_nothing_price, generalized_price, geometric_asian_price, higher_order_greeks,
+    implied_volatility, normal_cdf, normal_pdf, put_greeks, solve_implied_volatility,
+    BachelierParams, Barrier, BarrierKind, Black76Params, ExerciseStyle, GeneralizedParams,
+    GreekUnits, Greeks, HigherOrderGreeks, ImpliedVolError, Lattice, LatticeMethod,
+    OptionParams, OptionPortfolio, OptionStrategy, PortfolioGreeks, Sabr, Smile, SmileQuote, Svi,
+    VolatilitySurface,
 };
 pub use percentage::{basis_points_to_decimal, percentage_change, percentage_of};
 pub use precision_core::{ArithmeticError, Decimal, RoundingMode};
 pub use term_structure::{
-    CurveNode, FlatTermStructure, PiecewiseTermStructure, TermStructure, MAX_CURVE_NODES,
+    dv01, key_rate_dv01, BondQuote, CalibrationResult, CurveInterpolation, CurveNode,
+    Extrapolation, FlatTermStructure, ForwardCurve, KeyRateShift, NelsonSiegel, ParallelShift,
+    PiecewiseTermStructure, SpreadCurve, Svensson, TermStructure, MAX_CURVE_NODES,
 };
+pub use storage::{ArrayBuffer, Buffer, Fixed, Storage};
+pub use surface::{Bicubic, Bilinear, Grid, SurfaceInterpolator, VolSurface, MAX_GRID_VALUES};
+#[cfg(feature = "alloc")]
+pub use storage::Heap;
 pub use solver::{
-    bisection, brent, default_tolerance, newton_raphson, newton_raphson_numerical, secant,
-    SolverResult, DEFAULT_MAX_ITER,
+    bisection, brent, default_tolerance, levenberg_marquardt, newton_raphson,
+    newton_raphson_numerical, secant, LeastSquaresResult, SolverResult, DEFAULT_MAX_ITER,
 };
 pub use time_value::{future_value, net_present_value, present_value};
 pub use derivatives::{
-    calculate_average_entry_price, calculate_breakeven_price, calculate_effective_leverage,
-    calculate_funding_payment, calculate_funding_rate, calculate_liquidation_distance,
-    calculate_liquidation_price, calculate_margin_ratio, calculate_max_position_size, calculate_pnl,
-    calculate_pnl_percentage, calculate_required_collateral, calculate_roe, FundingParams,
-    PerpPosition,
+    calculate_adl_score, calculate_average_entry_price, calculate_breakeven_price,
+    calculate_effective_leverage, calculate_funding_payment, calculate_funding_rate,
+    calculate_liquidation_distance, calculate_liquidation_price, calculate_liquidation_price_tiered,
+    calculate_margin_ratio, calculate_max_position_size, calculate_max_position_size_tiered,
+    calculate_pnl, calculate_pnl_percentage, calculate_position_after_fill,
+    calculate_required_collateral, calculate_required_collateral_tiered, calculate_roe,
+    AdlSimulator, CrossMarginAccount, CrossPosition, CrossTrade, Fill, FillOutcome, FundingIndex,
+    FundingParams, MarginRates, OrderExecution, PerpPosition, PositionFunding, PositionLedger,
+    PriceImpactParams, RiskTier, RiskTierTable, SkewFunding, SkewParams, TradeImpact,
+    VelocityFunding, MAX_CROSS_POSITIONS,
 };
 pub use amm::{
     calculate_amounts_from_liquidity, calculate_impermanent_loss, calculate_liquidity_burn,
     calculate_liquidity_from_amounts, calculate_liquidity_mint, calculate_position_value,
     calculate_price_impact, calculate_spot_price, calculate_swap_input, calculate_swap_output,
-    sqrt_price_to_tick, tick_spacing_to_fee_bps, tick_to_sqrt_price, ConcentratedPosition,
-    MAX_TICK, MIN_TICK, TICK_SPACING_HIGH, TICK_SPACING_LOW, TICK_SPACING_MEDIUM,
+    cryptoswap_d, cryptoswap_y, find_best_route, optimize_split, quote_route, sqrt_price_to_tick,
+    stable_bpt_in_given_exact_tokens_out, stable_bpt_out_given_exact_tokens_in,
+    stable_token_in_given_exact_bpt_out, stable_token_out_given_exact_bpt_in, stableswap_d,
+    stableswap_y, stableswap_y_d, tick_spacing_to_fee_bps, tick_to_sqrt_price,
+    weighted_bpt_in_given_exact_tokens_out, weighted_bpt_out_given_exact_tokens_in,
+    weighted_in_given_out, weighted_invariant, weighted_out_given_in, weighted_spot_price,
+    weighted_token_in_given_exact_bpt_out, weighted_token_out_given_exact_bpt_in,
+    AmplificationRamp, ComposableStablePool, ConcentratedPool, ConcentratedPosition, Hop, Route,
+    RoutePool, RouteQuote, SplitQuote, StableSwapPool, SwapResult, TickInfo, WeightedPool,
+    MAX_AMPLIFICATION, MAX_AMPLIFICATION_CHANGE, MAX_INITIALIZED_TICKS, MAX_ROUTE_HOPS,
+    MAX_ROUTE_SPLITS, MAX_STABLE_COINS, MAX_TICK, MAX_WEIGHTED_TOKENS, MIN_RAMP_TIME, MIN_TICK,
+    TICK_SPACING_HIGH, TICK_SPACING_LOW, TICK_SPACING_MEDIUM,
 };
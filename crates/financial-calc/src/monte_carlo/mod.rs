@@ -0,0 +1,399 @@
+//! Deterministic Monte Carlo simulation.
+//!
+//! All randomness comes from a seeded [`Xoshiro256StarStar`] generator and
+//! all arithmetic is decimal, so a simulation is reproducible bit-for-bit
+//! on any platform, including inside a zkVM.
+//!
+//! - [`Gbm`], [`JumpDiffusion`], [`Heston`] - Price path generators
+//!   implementing [`PathModel`]
+//! - [`MonteCarlo`] - Simulation engine with antithetic sampling and
+//!   control variates, returning an [`Estimate`] with its standard error
+//! - [`inverse_normal_cdf`] - Normal sampling by inverse transform
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::monte_carlo::{Gbm, MonteCarlo};
+//! use precision_core::Decimal;
+//!
+//! let model = Gbm {
+//!     spot: Decimal::from(100i64),
+//!     drift: Decimal::new(5, 2),
+//!     volatility: Decimal::new(2, 1),
+//! };
+//! let strike = Decimal::from(100i64);
+//!
+//! // Arithmetic-average Asian call, monthly fixings over a year
+//! let engine = MonteCarlo::new(500, 12, 42).unwrap().with_antithetic(true);
+//! let estimate = engine
+//!     .estimate(&model, Decimal::ONE, |path| {
+//!         let sum = path[1..].iter().fold(Decimal::ZERO, |acc, &s| acc + s);
+//!         Ok((sum / Decimal::from(12i64) - strike).max(Decimal::ZERO))
+//!     })
+//!     .unwrap()
+//!     .discounted(Decimal::new(5, 2), Decimal::ONE)
+//!     .unwrap();
+//!
+//! let (low, high) = estimate.confidence_interval(Decimal::new(95, 2)).unwrap();
+//! assert!(low < estimate.mean && estimate.mean < high);
+//! ```
+
+use crate::options::GeneralizedParams;
+use precision_core::{ArithmeticError, Decimal};
+
+mod paths;
+mod rng;
+
+pub use paths::{Gbm, Heston, JumpDiffusion, PathModel};
+pub use rng::{inverse_normal_cdf, Antithetic, RandomSource, Xoshiro256StarStar};
+
+/// Maximum time steps per path (for no_std fixed allocation).
+pub const MAX_PATH_STEPS: usize = 512;
+
+/// A Monte Carlo estimate with its sampling error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Estimate {
+    /// Sample mean.
+    pub mean: Decimal,
+    /// Standard error of the mean.
+    pub std_error: Decimal,
+    /// Number of independent samples.
+    pub samples: u32,
+}
+
+impl Estimate {
+    /// Two-sided normal confidence interval at `level` (e.g. 0.95).
+    pub fn confidence_interval(
+        &self,
+        level: Decimal,
+    ) -> Result<(Decimal, Decimal), ArithmeticError> {
+        let z = inverse_normal_cdf(Decimal::ONE.try_add(level)?.try_div(Decimal::from(2i64))?)?;
+        let half_width = z.try_mul(self.std_error)?;
+        Ok((
+            self.mean.try_sub(half_width)?,
+            self.mean.try_add(half_width)?,
+        ))
+    }
+
+    /// Scales the estimate by the discount factor e^(-rT).
+    pub fn discounted(&self, rate: Decimal, time: Decimal) -> Result<Self, ArithmeticError> {
+        let factor = (-rate.try_mul(time)?).try_exp()?;
+        Ok(Self {
+            mean: self.mean.try_mul(factor)?,
+            std_error: self.std_error.try_mul(factor)?,
+            samples: self.samples,
+        })
+    }
+}
+
+/// Monte Carlo simulation engine.
+///
+/// Each of `paths` samples simulates one path of `steps` equal time steps.
+/// With antithetic sampling a sample is the average of a path and its
+/// mirror image (every normal draw negated), doubling the simulation cost.
+#[derive(Debug, Clone, Copy)]
+pub struct MonteCarlo {
+    paths: u32,
+    steps: usize,
+    seed: u64,
+    antithetic: bool,
+}
+
+impl MonteCarlo {
+    /// Creates an engine.
+    ///
+    /// Returns error unless there are at least two paths and
+    /// 1 ≤ steps ≤ [`MAX_PATH_STEPS`].
+    pub fn new(paths: u32, steps: usize, seed: u64) -> Result<Self, ArithmeticError> {
+        if paths < 2 || steps == 0 {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        if steps > MAX_PATH_STEPS {
+            return Err(ArithmeticError::Overflow);
+        }
+        Ok(Self {
+            paths,
+            steps,
+            seed,
+            antithetic: false,
+        })
+    }
+
+    /// Enables or disables antithetic sampling.
+    pub fn with_antithetic(mut self, antithetic: bool) -> Self {
+        self.antithetic = antithetic;
+        self
+    }
+
+    /// Estimates the expected payoff of a path.
+    ///
+    /// `payoff` receives prices at times 0, dt, ..., maturity. The result
+    /// is undiscounted; see [`Estimate::discounted`].
+    pub fn estimate<M, F>(
+        &self,
+        model: &M,
+        maturity: Decimal,
+        payoff: F,
+    ) -> Result<Estimate, ArithmeticError>
+    where
+        M: PathModel,
+        F: Fn(&[Decimal]) -> Result<Decimal, ArithmeticError>,
+    {
+        let moments = self.run(model, maturity, |path| Ok((payoff(path)?, Decimal::ZERO)))?;
+        let n = Decimal::from(self.paths);
+        let variance = moments.yy.try_div(n.try_sub(Decimal::ONE)?)?;
+        self.finish(moments.y_mean, variance)
+    }
+
+    /// Estimates the expected payoff using a control variate.
+    ///
+    /// `control` is evaluated on the same paths and must have known
+    /// expectation `control_mean`. The payoff mean is adjusted by
+    /// β(mean(control) − `control_mean`), with β = Cov(payoff, control) /
+    /// Var(control) estimated from the samples, which removes the fraction
+    /// ρ² of the variance explained by the control.
+    pub fn estimate_with_control<M, F, G>(
+        &self,
+        model: &M,
+        maturity: Decimal,
+        payoff: F,
+        control: G,
+        control_mean: Decimal,
+    ) -> Result<Estimate, ArithmeticError>
+    where
+        M: PathModel,
+        F: Fn(&[Decimal]) -> Result<Decimal, ArithmeticError>,
+        G: Fn(&[Decimal]) -> Result<Decimal, ArithmeticError>,
+    {
+        let moments = self.run(model, maturity, |path| Ok((payoff(path)?, control(path)?)))?;
+        let (beta, explained) = if moments.cc.is_zero() {
+            (Decimal::ZERO, Decimal::ZERO)
+        } else {
+            let beta = moments.yc.try_div(moments.cc)?;
+            (beta, beta.try_mul(moments.yc)?)
+        };
+        let mean = moments
+            .y_mean
+            .try_sub(beta.try_mul(moments.c_mean.try_sub(control_mean)?)?)?;
+        let n = Decimal::from(self.paths);
+        let variance = moments
+            .yy
+            .try_sub(explained)?
+            .max(Decimal::ZERO)
+            .try_div(n.try_sub(Decimal::ONE)?)?;
+        self.finish(mean, variance)
+    }
+
+    /// Prices a European option under risk-neutral GBM.
+    ///
+    /// Uses the terminal price, with known mean S e^(bT), as a control
+    /// variate. Intended as a benchmark against the closed form.
+    pub fn european_price(
+        &self,
+        params: &GeneralizedParams,
+        is_call: bool,
+    ) -> Result<Estimate, ArithmeticError> {
+        let model = Gbm::risk_neutral(params)?;
+        let forward = params
+            .spot
+            .try_mul(model.drift.try_mul(params.time)?.try_exp()?)?;
+        let payoff = |path: &[Decimal]| {
+            let terminal = path[path.len() - 1];
+            let intrinsic = if is_call {
+                terminal.try_sub(params.strike)?
+            } else {
+                params.strike.try_sub(terminal)?
+            };
+            Ok(intrinsic.max(Decimal::ZERO))
+        };
+        let terminal = |path: &[Decimal]| Ok(path[path.len() - 1]);
+        self.estimate_with_control(&model, params.time, payoff, terminal, forward)?
+            .discounted(params.rate, params.time)
+    }
+
+    /// Number of samples.
+    pub fn paths(&self) -> u32 {
+        self.paths
+    }
+
+    /// Time steps per path.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Generator seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Whether antithetic sampling is enabled.
+    pub fn antithetic(&self) -> bool {
+        self.antithetic
+    }
+
+    /// Simulates all samples and returns the means and centred
+    /// cross-products of (payoff, control).
+    fn run<M, F>(&self, model: &M, maturity: Decimal, sample: F) -> Result<Moments, ArithmeticError>
+    where
+        M: PathModel,
+        F: Fn(&[Decimal]) -> Result<(Decimal, Decimal), ArithmeticError>,
+    {
+        let mut buffer = [Decimal::ZERO; MAX_PATH_STEPS + 1];
+        let path = &mut buffer[..=self.steps];
+        let mut rng = Xoshiro256StarStar::new(self.seed);
+        let two = Decimal::from(2i64);
+
+        let mut ys = Decimal::ZERO;
+        let mut cs = Decimal::ZERO;
+        let mut yys = Decimal::ZERO;
+        let mut ccs = Decimal::ZERO;
+        let mut ycs = Decimal::ZERO;
+        for _ in 0..self.paths {
+            let mirror = rng;
+            model.simulate(&mut rng, maturity, path)?;
+            let (mut y, mut c) = sample(path)?;
+            if self.antithetic {
+                model.simulate(&mut Antithetic::new(mirror), maturity, path)?;
+                let (y2, c2) = sample(path)?;
+                y = y.try_add(y2)?.try_div(two)?;
+                c = c.try_add(c2)?.try_div(two)?;
+            }
+            ys = ys.try_add(y)?;
+            cs = cs.try_add(c)?;
+            yys = yys.try_add(y.try_mul(y)?)?;
+            ccs = ccs.try_add(c.try_mul(c)?)?;
+            ycs = ycs.try_add(y.try_mul(c)?)?;
+        }
+
+        let n = Decimal::from(self.paths);
+        let y_mean = ys.try_div(n)?;
+        let c_mean = cs.try_div(n)?;
+        Ok(Moments {
+            y_mean,
+            c_mean,
+            yy: yys.try_sub(ys.try_mul(y_mean)?)?.max(Decimal::ZERO),
+            cc: ccs.try_sub(cs.try_mul(c_mean)?)?.max(Decimal::ZERO),
+            yc: ycs.try_sub(ys.try_mul(c_mean)?)?,
+        })
+    }
+
+    fn finish(&self, mean: Decimal, variance: Decimal) -> Result<Estimate, ArithmeticError> {
+        Ok(Estimate {
+            mean,
+            std_error: variance.try_div(Decimal::from(self.paths))?.try_sqrt()?,
+            samples: self.paths,
+        })
+    }
+}
+
+/// Sample means and centred sums of squares and cross-products.
+struct Moments {
+    y_mean: Decimal,
+    c_mean: Decimal,
+    yy: Decimal,
+    cc: Decimal,
+    yc: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::generalized_price;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn params() -> GeneralizedParams {
+        GeneralizedParams {
+            spot: decimal("100"),
+            strike: decimal("105"),
+            rate: decimal("0.05"),
+            yield_rate: decimal("0.01"),
+            time: decimal("0.5"),
+            volatility: decimal("0.25"),
+        }
+    }
+
+    fn call_payoff(path: &[Decimal]) -> Result<Decimal, ArithmeticError> {
+        Ok((path[path.len() - 1] - decimal("105")).max(Decimal::ZERO))
+    }
+
+    #[test]
+    fn test_reproducible_for_seed() {
+        let model = Gbm::risk_neutral(&params()).unwrap();
+        let engine = MonteCarlo::new(200, 4, 9).unwrap();
+        let a = engine
+            .estimate(&model, decimal("0.5"), call_payoff)
+            .unwrap();
+        let b = engine
+            .estimate(&model, decimal("0.5"), call_payoff)
+            .unwrap();
+        assert_eq!(a, b);
+
+        let other = MonteCarlo::new(200, 4, 10).unwrap();
+        assert_ne!(
+            other.estimate(&model, decimal("0.5"), call_payoff).unwrap(),
+            a
+        );
+    }
+
+    #[test]
+    fn test_european_matches_closed_form() {
+        let params = params();
+        let exact = generalized_price(&params, true).unwrap();
+        for antithetic in [false, true] {
+            let engine = MonteCarlo::new(2000, 1, 3)
+                .unwrap()
+                .with_antithetic(antithetic);
+            let estimate = engine.european_price(&params, true).unwrap();
+            let (low, high) = estimate.confidence_interval(decimal("0.999")).unwrap();
+            assert!(low < exact && exact < high, "{estimate:?} vs {exact}");
+        }
+
+        let put = MonteCarlo::new(2000, 1, 5)
+            .unwrap()
+            .european_price(&params, false)
+            .unwrap();
+        let exact_put = generalized_price(&params, false).unwrap();
+        assert!((put.mean - exact_put).abs() < decimal("4") * put.std_error);
+    }
+
+    #[test]
+    fn test_variance_reduction() {
+        let params = params();
+        let model = Gbm::risk_neutral(&params).unwrap();
+        let engine = MonteCarlo::new(1000, 1, 21).unwrap();
+        let plain = engine.estimate(&model, params.time, call_payoff).unwrap();
+        let antithetic = engine
+            .with_antithetic(true)
+            .estimate(&model, params.time, call_payoff)
+            .unwrap();
+        let controlled = engine.european_price(&params, true).unwrap();
+
+        // Antithetic pairs cost two paths, so must beat plain by more than √2
+        assert!(antithetic.std_error * decimal("1.414") < plain.std_error);
+        let plain_discounted = plain.discounted(params.rate, params.time).unwrap();
+        assert!(controlled.std_error * decimal("1.5") < plain_discounted.std_error);
+    }
+
+    #[test]
+    fn test_confidence_interval_and_validation() {
+        let estimate = Estimate {
+            mean: decimal("10"),
+            std_error: decimal("0.5"),
+            samples: 100,
+        };
+        let (low, high) = estimate.confidence_interval(decimal("0.95")).unwrap();
+        assert!((high - decimal("10.98")).abs() < decimal("0.001"));
+        assert!((low + high - decimal("20")).abs() < decimal("0.0000000001"));
+
+        assert!(MonteCarlo::new(1, 1, 0).is_err());
+        assert!(MonteCarlo::new(10, 0, 0).is_err());
+        assert_eq!(
+            MonteCarlo::new(10, MAX_PATH_STEPS + 1, 0).unwrap_err(),
+            ArithmeticError::Overflow
+        );
+    }
+}
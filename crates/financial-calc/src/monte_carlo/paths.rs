@@ -0,0 +1,374 @@
+//! Price path generators.
+
+use super::rng::RandomSource;
+use crate::options::GeneralizedParams;
+use precision_core::{ArithmeticError, Decimal};
+
+/// Upper bound on jumps drawn in one time step.
+const MAX_JUMPS_PER_STEP: u32 = 64;
+
+/// A model that simulates price paths.
+pub trait PathModel {
+    /// Price at time zero.
+    fn spot(&self) -> Decimal;
+
+    /// Fills `path` with prices at equally spaced times 0, dt, ..., maturity,
+    /// where dt = maturity / (path.len() − 1).
+    ///
+    /// Each step consumes a fixed number of draws from `rng`, so replaying a
+    /// cloned generator through [`Antithetic`](super::Antithetic) yields the
+    /// mirrored path.
+    fn simulate<R: RandomSource>(
+        &self,
+        rng: &mut R,
+        maturity: Decimal,
+        path: &mut [Decimal],
+    ) -> Result<(), ArithmeticError>;
+}
+
+/// Geometric Brownian motion, dS = μS dt + σS dW.
+///
+/// Steps use the exact log-normal transition, so there is no
+/// discretisation bias.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Gbm {
+    /// Initial price.
+    pub spot: Decimal,
+    /// Drift μ (annualized); the cost of carry r − q under the risk-neutral
+    /// measure.
+    pub drift: Decimal,
+    /// Volatility σ (annualized).
+    pub volatility: Decimal,
+}
+
+impl Gbm {
+    /// Risk-neutral dynamics for generalised Black-Scholes parameters.
+    pub fn risk_neutral(params: &GeneralizedParams) -> Result<Self, ArithmeticError> {
+        Ok(Self {
+            spot: params.spot,
+            drift: params.cost_of_carry()?,
+            volatility: params.volatility,
+        })
+    }
+}
+
+impl PathModel for Gbm {
+    fn spot(&self) -> Decimal {
+        self.spot
+    }
+
+    fn simulate<R: RandomSource>(
+        &self,
+        rng: &mut R,
+        maturity: Decimal,
+        path: &mut [Decimal],
+    ) -> Result<(), ArithmeticError> {
+        let dt = time_step(self.spot, maturity, path)?;
+        if self.volatility.is_negative() {
+            return Err(ArithmeticError::NegativeSqrt);
+        }
+        let drift = log_drift(self.drift, self.volatility, dt)?;
+        let diffusion = self.volatility.try_mul(dt.try_sqrt()?)?;
+
+        path[0] = self.spot;
+        for i in 1..path.len() {
+            let z = rng.next_normal()?;
+            let step = drift.try_add(diffusion.try_mul(z)?)?;
+            path[i] = path[i - 1].try_mul(step.try_exp()?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Merton jump-diffusion: GBM plus Poisson jumps with log-normal sizes.
+///
+/// The drift is compensated by λκ with κ = e^(μ_J + δ²/2) − 1, so the
+/// expected return matches `drift`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JumpDiffusion {
+    /// Initial price.
+    pub spot: Decimal,
+    /// Expected return μ (annualized).
+    pub drift: Decimal,
+    /// Diffusive volatility σ (annualized).
+    pub volatility: Decimal,
+    /// Jump intensity λ (expected jumps per year).
+    pub intensity: Decimal,
+    /// Mean log jump size μ_J.
+    pub jump_mean: Decimal,
+    /// Standard deviation of log jump size δ.
+    pub jump_volatility: Decimal,
+}
+
+impl PathModel for JumpDiffusion {
+    fn spot(&self) -> Decimal {
+        self.spot
+    }
+
+    fn simulate<R: RandomSource>(
+        &self,
+        rng: &mut R,
+        maturity: Decimal,
+        path: &mut [Decimal],
+    ) -> Result<(), ArithmeticError> {
+        let dt = time_step(self.spot, maturity, path)?;
+        if self.volatility.is_negative()
+            || self.intensity.is_negative()
+            || self.jump_volatility.is_negative()
+        {
+            return Err(ArithmeticError::NegativeSqrt);
+        }
+        let half_jump_var = self
+            .jump_volatility
+            .try_mul(self.jump_volatility)?
+            .try_div(Decimal::from(2i64))?;
+        let kappa = self
+            .jump_mean
+            .try_add(half_jump_var)?
+            .try_exp()?
+            .try_sub(Decimal::ONE)?;
+        let compensated = self.drift.try_sub(self.intensity.try_mul(kappa)?)?;
+        let drift = log_drift(compensated, self.volatility, dt)?;
+        let diffusion = self.volatility.try_mul(dt.try_sqrt()?)?;
+        let jump_rate = self.intensity.try_mul(dt)?;
+        let no_jump = (-jump_rate).try_exp()?;
+
+        path[0] = self.spot;
+        for i in 1..path.len() {
+            let z = rng.next_normal()?;
+            let jumps = poisson(rng.next_uniform()?, jump_rate, no_jump)?;
+            let jump_z = rng.next_normal()?;
+
+            // The sum of n normal log jumps is N(n μ_J, n δ²)
+            let n = Decimal::from(jumps);
+            let jump = n.try_mul(self.jump_mean)?.try_add(
+                n.try_sqrt()?
+                    .try_mul(self.jump_volatility)?
+                    .try_mul(jump_z)?,
+            )?;
+            let step = drift.try_add(diffusion.try_mul(z)?)?.try_add(jump)?;
+            path[i] = path[i - 1].try_mul(step.try_exp()?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Heston stochastic volatility.
+///
+/// dS = μS dt + √v S dW₁, dv = κ(θ − v) dt + ξ√v dW₂, d⟨W₁, W₂⟩ = ρ dt.
+/// Discretised with full-truncation Euler on the variance and a log-Euler
+/// step on the price.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Heston {
+    /// Initial price.
+    pub spot: Decimal,
+    /// Drift μ (annualized).
+    pub drift: Decimal,
+    /// Initial variance v₀.
+    pub variance: Decimal,
+    /// Mean reversion speed κ.
+    pub mean_reversion: Decimal,
+    /// Long-run variance θ.
+    pub long_run_variance: Decimal,
+    /// Volatility of variance ξ.
+    pub vol_of_vol: Decimal,
+    /// Correlation ρ between price and variance shocks.
+    pub correlation: Decimal,
+}
+
+impl PathModel for Heston {
+    fn spot(&self) -> Decimal {
+        self.spot
+    }
+
+    fn simulate<R: RandomSource>(
+        &self,
+        rng: &mut R,
+        maturity: Decimal,
+        path: &mut [Decimal],
+    ) -> Result<(), ArithmeticError> {
+        let dt = time_step(self.spot, maturity, path)?;
+        if self.variance.is_negative()
+            || self.mean_reversion.is_negative()
+            || self.long_run_variance.is_negative()
+            || self.vol_of_vol.is_negative()
+            || self.correlation.abs() > Decimal::ONE
+        {
+            return Err(ArithmeticError::NegativeSqrt);
+        }
+        let independent = Decimal::ONE
+            .try_sub(self.correlation.try_mul(self.correlation)?)?
+            .try_sqrt()?;
+        let half = Decimal::new(5, 1);
+
+        let mut variance = self.variance;
+        path[0] = self.spot;
+        for i in 1..path.len() {
+            let z_variance = rng.next_normal()?;
+            let z_price = self
+                .correlation
+                .try_mul(z_variance)?
+                .try_add(independent.try_mul(rng.next_normal()?)?)?;
+
+            let v = variance.max(Decimal::ZERO);
+            let root = v.try_mul(dt)?.try_sqrt()?;
+            let step = self
+                .drift
+                .try_sub(half.try_mul(v)?)?
+                .try_mul(dt)?
+                .try_add(root.try_mul(z_price)?)?;
+            path[i] = path[i - 1].try_mul(step.try_exp()?)?;
+
+            variance = variance
+                .try_add(
+                    self.mean_reversion
+                        .try_mul(self.long_run_variance.try_sub(v)?)?
+                        .try_mul(dt)?,
+                )?
+                .try_add(self.vol_of_vol.try_mul(root)?.try_mul(z_variance)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Validates inputs and returns the time step.
+fn time_step(
+    spot: Decimal,
+    maturity: Decimal,
+    path: &[Decimal],
+) -> Result<Decimal, ArithmeticError> {
+    if path.len() < 2 || !maturity.is_positive() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    if !spot.is_positive() {
+        return Err(ArithmeticError::LogOfNegative);
+    }
+    maturity.try_div(Decimal::from((path.len() - 1) as u64))
+}
+
+/// (μ − σ²/2) dt
+fn log_drift(drift: Decimal, volatility: Decimal, dt: Decimal) -> Result<Decimal, ArithmeticError> {
+    let half_var = volatility
+        .try_mul(volatility)?
+        .try_div(Decimal::from(2i64))?;
+    drift.try_sub(half_var)?.try_mul(dt)
+}
+
+/// Poisson draw by inverse transform of a uniform.
+fn poisson(u: Decimal, mean: Decimal, p0: Decimal) -> Result<u32, ArithmeticError> {
+    let mut k = 0;
+    let mut p = p0;
+    let mut cdf = p0;
+    while u > cdf && k < MAX_JUMPS_PER_STEP {
+        k += 1;
+        p = p.try_mul(mean)?.try_div(Decimal::from(k))?;
+        cdf = cdf.try_add(p)?;
+    }
+    Ok(k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monte_carlo::Xoshiro256StarStar;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn mean_terminal<M: PathModel>(model: &M, paths: u32) -> Decimal {
+        let mut rng = Xoshiro256StarStar::new(11);
+        let mut path = [Decimal::ZERO; 5];
+        let mut sum = Decimal::ZERO;
+        for _ in 0..paths {
+            model.simulate(&mut rng, Decimal::ONE, &mut path).unwrap();
+            sum = sum + path[4];
+        }
+        sum / Decimal::from(paths)
+    }
+
+    #[test]
+    fn test_gbm_zero_volatility_is_deterministic() {
+        let gbm = Gbm {
+            spot: decimal("100"),
+            drift: decimal("0.05"),
+            volatility: Decimal::ZERO,
+        };
+        let mut path = [Decimal::ZERO; 3];
+        gbm.simulate(&mut Xoshiro256StarStar::new(1), Decimal::ONE, &mut path)
+            .unwrap();
+        let expected = decimal("100") * decimal("0.05").exp().unwrap();
+        assert!((path[2] - expected).abs() < decimal("0.0000001"));
+    }
+
+    #[test]
+    fn test_models_are_martingales_after_drift() {
+        let spot = decimal("100");
+        let forward = spot * decimal("0.03").exp().unwrap();
+        let gbm = Gbm {
+            spot,
+            drift: decimal("0.03"),
+            volatility: decimal("0.2"),
+        };
+        let jumps = JumpDiffusion {
+            spot,
+            drift: decimal("0.03"),
+            volatility: decimal("0.15"),
+            intensity: decimal("0.5"),
+            jump_mean: decimal("-0.1"),
+            jump_volatility: decimal("0.15"),
+        };
+        let heston = Heston {
+            spot,
+            drift: decimal("0.03"),
+            variance: decimal("0.04"),
+            mean_reversion: decimal("2"),
+            long_run_variance: decimal("0.04"),
+            vol_of_vol: decimal("0.3"),
+            correlation: decimal("-0.7"),
+        };
+        for mean in [
+            mean_terminal(&gbm, 2000),
+            mean_terminal(&jumps, 2000),
+            mean_terminal(&heston, 2000),
+        ] {
+            // Within about three standard errors
+            assert!((mean - forward).abs() < decimal("1.5"), "{mean}");
+        }
+    }
+
+    #[test]
+    fn test_poisson_inverse_transform() {
+        let mean = decimal("0.5");
+        let p0 = (-mean).exp().unwrap();
+        assert_eq!(poisson(decimal("0.1"), mean, p0).unwrap(), 0);
+        // P(N ≤ 1) = e^-0.5 × 1.5 ≈ 0.9098
+        assert_eq!(poisson(decimal("0.9"), mean, p0).unwrap(), 1);
+        assert_eq!(poisson(decimal("0.95"), mean, p0).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let gbm = Gbm {
+            spot: decimal("100"),
+            drift: Decimal::ZERO,
+            volatility: decimal("0.2"),
+        };
+        let mut rng = Xoshiro256StarStar::new(1);
+        assert!(gbm
+            .simulate(&mut rng, Decimal::ONE, &mut [Decimal::ZERO; 1])
+            .is_err());
+        assert!(gbm
+            .simulate(&mut rng, Decimal::ZERO, &mut [Decimal::ZERO; 2])
+            .is_err());
+        let heston = Heston {
+            correlation: decimal("1.5"),
+            spot: decimal("100"),
+            ..Heston::default()
+        };
+        assert!(heston
+            .simulate(&mut rng, Decimal::ONE, &mut [Decimal::ZERO; 2])
+            .is_err());
+    }
+}
@@ -0,0 +1,323 @@
+//! Seedable pseudo-random numbers and normal sampling.
+
+use precision_core::{ArithmeticError, Decimal};
+
+/// A source of uniform and standard normal draws.
+pub trait RandomSource {
+    /// Next uniform draw in the open interval (0, 1).
+    fn next_uniform(&mut self) -> Result<Decimal, ArithmeticError>;
+
+    /// Next standard normal draw, by inverse transform of a uniform.
+    fn next_normal(&mut self) -> Result<Decimal, ArithmeticError> {
+        inverse_normal_cdf(self.next_uniform()?)
+    }
+}
+
+/// xoshiro256** generator (Blackman and Vigna).
+///
+/// Integer-only, so a given seed produces the same sequence on every
+/// platform, including zkVM guests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Xoshiro256StarStar {
+    state: [u64; 4],
+}
+
+impl Xoshiro256StarStar {
+    /// Creates a generator, expanding the seed with SplitMix64.
+    pub fn new(seed: u64) -> Self {
+        let mut x = seed;
+        let mut state = [0u64; 4];
+        for word in &mut state {
+            x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            *word = z ^ (z >> 31);
+        }
+        Self { state }
+    }
+
+    /// Next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Advances the generator by 2^128 draws.
+    ///
+    /// Successive jumps from one seed give non-overlapping streams, e.g.
+    /// one per risk factor or per batch of paths.
+    pub fn jump(&mut self) {
+        const JUMP: [u64; 4] = [
+            0x180e_c6d3_3cfd_0aba,
+            0xd5a6_1266_f0c9_392c,
+            0xa958_2618_e03f_c9aa,
+            0x39ab_dc45_29b1_661c,
+        ];
+        let mut next = [0u64; 4];
+        for word in JUMP {
+            for bit in 0..64 {
+                if word & (1u64 << bit) != 0 {
+                    for (n, s) in next.iter_mut().zip(self.state) {
+                        *n ^= s;
+                    }
+                }
+                self.next_u64();
+            }
+        }
+        self.state = next;
+    }
+}
+
+impl RandomSource for Xoshiro256StarStar {
+    /// Uses the top 53 bits: (2m + 1) / 2^54, never exactly 0 or 1.
+    fn next_uniform(&mut self) -> Result<Decimal, ArithmeticError> {
+        let bits = (self.next_u64() >> 11) as i64;
+        Decimal::from(2 * bits + 1).try_div(Decimal::from(1i64 << 54))
+    }
+}
+
+/// Mirrors another source's uniforms, u → 1 − u.
+///
+/// Replaying a cloned generator through `Antithetic` produces the
+/// antithetic path: every normal draw is exactly negated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Antithetic<R> {
+    inner: R,
+}
+
+impl<R: RandomSource> Antithetic<R> {
+    /// Wraps a source.
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the wrapped source.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: RandomSource> RandomSource for Antithetic<R> {
+    fn next_uniform(&mut self) -> Result<Decimal, ArithmeticError> {
+        Decimal::ONE.try_sub(self.inner.next_uniform()?)
+    }
+}
+
+/// Inverse of the standard normal CDF.
+///
+/// Wichura's AS241 rational approximation, accurate to about 1e-16.
+/// Symmetric: Φ⁻¹(1 − p) = −Φ⁻¹(p) exactly.
+///
+/// Returns error unless 0 < p < 1.
+pub fn inverse_normal_cdf(p: Decimal) -> Result<Decimal, ArithmeticError> {
+    if p.is_negative() {
+        return Err(ArithmeticError::LogOfNegative);
+    }
+    if p.is_zero() || p >= Decimal::ONE {
+        return Err(ArithmeticError::LogOfZero);
+    }
+
+    let q = p.try_sub(Decimal::new(5, 1))?;
+    if q.abs() <= Decimal::new(425, 3) {
+        let r = Decimal::new(180625, 6).try_sub(q.try_mul(q)?)?;
+        return q.try_mul(rational(r, &CENTRAL_NUM, &CENTRAL_DEN)?);
+    }
+
+    let tail = if q.is_negative() {
+        p
+    } else {
+        Decimal::ONE.try_sub(p)?
+    };
+    let r = (-tail.try_ln()?).try_sqrt()?;
+    let x = if r <= Decimal::from(5i64) {
+        rational(r.try_sub(Decimal::new(16, 1))?, &NEAR_NUM, &NEAR_DEN)?
+    } else {
+        rational(r.try_sub(Decimal::from(5i64))?, &FAR_NUM, &FAR_DEN)?
+    };
+    Ok(if q.is_negative() { -x } else { x })
+}
+
+/// AS241 coefficients as (mantissa, scale), lowest degree first.
+type Coefficients = [(i64, u32); 8];
+
+const CENTRAL_NUM: Coefficients = [
+    (33871328727963666, 16),
+    (13314166789178438, 14),
+    (19715909503065514, 13),
+    (13731693765509461, 12),
+    (45921953931549871, 12),
+    (67265770927008701, 12),
+    (33430575583588128, 12),
+    (25090809287301227, 13),
+];
+
+const CENTRAL_DEN: Coefficients = [
+    (1, 0),
+    (42313330701600911, 15),
+    (68718700749205791, 14),
+    (53941960214247511, 13),
+    (21213794301586596, 12),
+    (39307895800092711, 12),
+    (28729085735721943, 12),
+    (52264952788528546, 13),
+];
+
+const NEAR_NUM: Coefficients = [
+    (14234371107496836, 16),
+    (46303378461565453, 16),
+    (57694972214606914, 16),
+    (36478483247632046, 16),
+    (12704582524523684, 16),
+    (24178072517745061, 17),
+    (22723844989269185, 18),
+    (77454501427834141, 20),
+];
+
+const NEAR_DEN: Coefficients = [
+    (1, 0),
+    (20531916266377588, 16),
+    (16763848301838038, 16),
+    (68976733498510000, 17),
+    (14810397642748007, 17),
+    (15198666563616457, 18),
+    (54759380849953449, 20),
+    (10507500716444168, 25),
+];
+
+const FAR_NUM: Coefficients = [
+    (66579046435011038, 16),
+    (54637849111641144, 16),
+    (17848265399172913, 16),
+    (29656057182850489, 17),
+    (26532189526576123, 18),
+    (12426609473880784, 19),
+    (27115555687434876, 21),
+    (20103343992922881, 23),
+];
+
+const FAR_DEN: Coefficients = [
+    (1, 0),
+    (59983220655588794, 17),
+    (13692988092273581, 17),
+    (14875361290850615, 18),
+    (78686913114561326, 20),
+    (18463183175100547, 21),
+    (14215117583164459, 23),
+    (2044263103389, 27),
+];
+
+fn rational(
+    x: Decimal,
+    numerator: &Coefficients,
+    denominator: &Coefficients,
+) -> Result<Decimal, ArithmeticError> {
+    polynomial(x, numerator)?.try_div(polynomial(x, denominator)?)
+}
+
+fn polynomial(x: Decimal, coefficients: &Coefficients) -> Result<Decimal, ArithmeticError> {
+    coefficients
+        .iter()
+        .rev()
+        .try_fold(Decimal::ZERO, |acc, &(mantissa, scale)| {
+            acc.try_mul(x)?.try_add(Decimal::new(mantissa, scale))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::normal_cdf;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_xoshiro_reference_sequence() {
+        // Reference output of xoshiro256** for state [1, 2, 3, 4]
+        let mut rng = Xoshiro256StarStar {
+            state: [1, 2, 3, 4],
+        };
+        let expected = [11520, 0, 1509978240, 1215971899390074240];
+        for value in expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    fn test_seeding_and_jump_are_deterministic() {
+        let mut a = Xoshiro256StarStar::new(42);
+        let mut b = Xoshiro256StarStar::new(42);
+        assert_eq!(a.next_uniform().unwrap(), b.next_uniform().unwrap());
+        assert_ne!(Xoshiro256StarStar::new(43), Xoshiro256StarStar::new(42));
+
+        let mut jumped = a;
+        jumped.jump();
+        assert_ne!(jumped.next_u64(), a.next_u64());
+        b.jump();
+        assert_eq!(jumped, {
+            b.next_u64();
+            b
+        });
+    }
+
+    #[test]
+    fn test_uniforms_stay_in_open_interval() {
+        let mut rng = Xoshiro256StarStar::new(7);
+        let mut sum = Decimal::ZERO;
+        for _ in 0..1000 {
+            let u = rng.next_uniform().unwrap();
+            assert!(u.is_positive() && u < Decimal::ONE);
+            sum = sum + u;
+        }
+        let mean = sum / Decimal::from(1000i64);
+        assert!((mean - decimal("0.5")).abs() < decimal("0.03"));
+    }
+
+    #[test]
+    fn test_inverse_normal_cdf() {
+        assert_eq!(inverse_normal_cdf(decimal("0.5")).unwrap(), Decimal::ZERO);
+        let known = [
+            ("0.975", "1.959963984540054"),
+            ("0.841344746068543", "1"),
+            ("0.001", "-3.090232306167814"),
+            ("0.0000001", "-5.199337582192817"),
+        ];
+        for (p, x) in known {
+            let diff = inverse_normal_cdf(decimal(p)).unwrap() - decimal(x);
+            assert!(diff.abs() < decimal("0.000000000001"), "p = {p}");
+        }
+        for p in ["0.01", "0.3", "0.9"] {
+            let p = decimal(p);
+            let x = inverse_normal_cdf(p).unwrap();
+            assert_eq!(inverse_normal_cdf(Decimal::ONE - p).unwrap(), -x);
+            assert!((normal_cdf(x).unwrap() - p).abs() < decimal("0.0000001"));
+        }
+        assert!(inverse_normal_cdf(Decimal::ZERO).is_err());
+        assert!(inverse_normal_cdf(Decimal::ONE).is_err());
+    }
+
+    #[test]
+    fn test_antithetic_negates_normals() {
+        let rng = Xoshiro256StarStar::new(1);
+        let mut plain = rng;
+        let mut mirrored = Antithetic::new(rng);
+        for _ in 0..10 {
+            assert_eq!(
+                mirrored.next_normal().unwrap(),
+                -plain.next_normal().unwrap()
+            );
+        }
+        assert_eq!(mirrored.into_inner(), plain);
+    }
+}
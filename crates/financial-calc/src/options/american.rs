@@ -0,0 +1,748 @@
+//! American option pricing on recombining lattices.
+//!
+//! [`Lattice`] prices European or American exercise under the generalised
+//! Black-Scholes dynamics of [`GeneralizedParams`], so dividend yields and
+//! FX rates carry through to the early-exercise decision:
+//!
+//! - [`LatticeMethod::CoxRossRubinstein`] - Binomial with u = e^(σ√Δt), d = 1/u
+//! - [`LatticeMethod::LeisenReimer`] - Binomial matched to d1/d2 by Peizer-Pratt
+//!   inversion; converges much faster, uses an odd step count
+//! - [`LatticeMethod::Trinomial`] - Boyle trinomial with a middle branch
+//!
+//! Step counts are bounded by [`MAX_LATTICE_STEPS`] so node storage stays on
+//! the stack and cost stays predictable for on-chain use.
+//! [`barone_adesi_whaley`] gives a closed-form approximation for a fraction
+//! of the cost.
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::options::{
+//!     ExerciseStyle, GeneralizedParams, Lattice, LatticeMethod, OptionParams,
+//! };
+//! use precision_core::Decimal;
+//!
+//! let params = GeneralizedParams::with_yield(
+//!     &OptionParams {
+//!         spot: Decimal::from(100i64),
+//!         strike: Decimal::from(100i64),
+//!         rate: Decimal::new(5, 2),
+//!         time: Decimal::ONE,
+//!         volatility: Decimal::new(2, 1),
+//!     },
+//!     Decimal::ZERO,
+//! );
+//!
+//! let lattice = Lattice::new(LatticeMethod::LeisenReimer, 101);
+//! let american = lattice.price(&params, false, ExerciseStyle::American).unwrap();
+//! let premium = lattice.early_exercise_premium(&params, false).unwrap();
+//! assert!(premium > Decimal::ZERO && premium < american);
+//! ```
+
+use super::{generalized_price, normal_cdf, GeneralizedParams, Greeks};
+use crate::solver::brent;
+use precision_core::{ArithmeticError, Decimal};
+
+/// Maximum number of time steps in a [`Lattice`].
+pub const MAX_LATTICE_STEPS: usize = 256;
+
+const MAX_NODES: usize = 2 * MAX_LATTICE_STEPS + 1;
+
+/// Lattice construction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatticeMethod {
+    /// Cox-Ross-Rubinstein binomial tree.
+    CoxRossRubinstein,
+    /// Leisen-Reimer binomial tree.
+    LeisenReimer,
+    /// Boyle trinomial tree.
+    Trinomial,
+}
+
+/// When an option may be exercised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExerciseStyle {
+    /// At expiry only.
+    European,
+    /// At any time up to expiry.
+    American,
+}
+
+/// A recombining lattice with a fixed number of time steps.
+#[derive(Debug, Clone, Copy)]
+pub struct Lattice {
+    /// Tree construction.
+    pub method: LatticeMethod,
+    /// Number of time steps. Leisen-Reimer rounds even counts up to the
+    /// next odd number, which must still be within [`MAX_LATTICE_STEPS`].
+    pub steps: usize,
+}
+
+/// Node values near the root, used for lattice Greeks.
+struct Rollback {
+    price: Decimal,
+    // (spot, value) pairs one and two steps in, lowest spot first
+    step1: [(Decimal, Decimal); 3],
+    step2: [(Decimal, Decimal); 3],
+}
+
+impl Lattice {
+    /// Creates a lattice.
+    pub fn new(method: LatticeMethod, steps: usize) -> Self {
+        Self { method, steps }
+    }
+
+    /// Option price.
+    ///
+    /// Returns `Overflow` if the step count, after any rounding to odd, is
+    /// above [`MAX_LATTICE_STEPS`] and `DivisionByZero` if it is zero.
+    pub fn price(
+        &self,
+        params: &GeneralizedParams,
+        is_call: bool,
+        style: ExerciseStyle,
+    ) -> Result<Decimal, ArithmeticError> {
+        Ok(self
+            .rollback(params, is_call, style == ExerciseStyle::American)?
+            .price)
+    }
+
+    /// Value of the right to exercise early: the American price less the
+    /// European price on the same lattice, so discretisation error cancels.
+    pub fn early_exercise_premium(
+        &self,
+        params: &GeneralizedParams,
+        is_call: bool,
+    ) -> Result<Decimal, ArithmeticError> {
+        let american = self.price(params, is_call, ExerciseStyle::American)?;
+        let european = self.price(params, is_call, ExerciseStyle::European)?;
+        Ok(american.try_sub(european)?.max(Decimal::ZERO))
+    }
+
+    /// Greeks read off the lattice.
+    ///
+    /// Delta and gamma come from the nodes nearest the root, theta from the
+    /// middle node a step (trinomial) or two steps (binomial) ahead. Vega
+    /// and rho reprice the lattice with bumped volatility and rate. Units
+    /// follow [`call_greeks`](super::call_greeks).
+    ///
+    /// Returns `DivisionByZero` if the lattice has fewer than three binomial
+    /// or two trinomial steps, too few to read the Greeks from.
+    pub fn greeks(
+        &self,
+        params: &GeneralizedParams,
+        is_call: bool,
+        style: ExerciseStyle,
+    ) -> Result<Greeks, ArithmeticError> {
+        let steps = self.step_count()?;
+        let min_steps = match self.method {
+            LatticeMethod::Trinomial => 2,
+            _ => 3,
+        };
+        if steps < min_steps {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let american = style == ExerciseStyle::American;
+        let result = self.rollback(params, is_call, american)?;
+        let dt = params.time.try_div(Decimal::from(steps as i64))?;
+
+        let (delta, gamma, theta) = match self.method {
+            LatticeMethod::Trinomial => {
+                let [down, middle, up] = result.step1;
+                (
+                    slope(down, up)?,
+                    curvature(result.step1)?,
+                    middle.1.try_sub(result.price)?.try_div(dt)?,
+                )
+            }
+            _ => {
+                let [down, up, _] = result.step1;
+                let middle = result.step2[1];
+                (
+                    slope(down, up)?,
+                    curvature(result.step2)?,
+                    middle
+                        .1
+                        .try_sub(result.price)?
+                        .try_div(Decimal::from(2i64).try_mul(dt)?)?,
+                )
+            }
+        };
+
+        // Central differences, scaled to a 1% move
+        let hundred = Decimal::from(100i64);
+        let vol_bump = params.volatility.try_div(hundred)?;
+        let mut up = *params;
+        let mut down = *params;
+        up.volatility = params.volatility.try_add(vol_bump)?;
+        down.volatility = params.volatility.try_sub(vol_bump)?;
+        let vega = self
+            .rollback(&up, is_call, american)?
+            .price
+            .try_sub(self.rollback(&down, is_call, american)?.price)?
+            .try_div(Decimal::from(2i64).try_mul(vol_bump)?)?
+            .try_div(hundred)?;
+
+        let rate_bump = Decimal::new(1, 4);
+        let mut up = *params;
+        let mut down = *params;
+        up.rate = params.rate.try_add(rate_bump)?;
+        down.rate = params.rate.try_sub(rate_bump)?;
+        let rho = self
+            .rollback(&up, is_call, american)?
+            .price
+            .try_sub(self.rollback(&down, is_call, american)?.price)?
+            .try_div(Decimal::from(2i64).try_mul(rate_bump)?)?
+            .try_div(hundred)?;
+
+        Ok(Greeks {
+            delta,
+            gamma,
+            theta: theta.try_div(Decimal::from(365i64))?,
+            vega,
+            rho,
+        })
+    }
+
+    fn step_count(&self) -> Result<usize, ArithmeticError> {
+        if self.steps == 0 {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let steps = match self.method {
+            LatticeMethod::LeisenReimer => self.steps | 1,
+            _ => self.steps,
+        };
+        if steps > MAX_LATTICE_STEPS {
+            return Err(ArithmeticError::Overflow);
+        }
+        Ok(steps)
+    }
+
+    fn rollback(
+        &self,
+        params: &GeneralizedParams,
+        is_call: bool,
+        american: bool,
+    ) -> Result<Rollback, ArithmeticError> {
+        validate(params)?;
+        let steps = self.step_count()?;
+        let dt = params.time.try_div(Decimal::from(steps as i64))?;
+        let discount = (-params.rate.try_mul(dt)?).try_exp()?;
+        let carry = params.cost_of_carry()?;
+
+        match self.method {
+            LatticeMethod::CoxRossRubinstein => {
+                let up = params.volatility.try_mul(dt.try_sqrt()?)?.try_exp()?;
+                let down = Decimal::ONE.try_div(up)?;
+                let p = carry
+                    .try_mul(dt)?
+                    .try_exp()?
+                    .try_sub(down)?
+                    .try_div(up.try_sub(down)?)?;
+                binomial(params, is_call, american, steps, (up, down, p), discount)
+            }
+            LatticeMethod::LeisenReimer => {
+                let tree = leisen_reimer(params, steps, dt, carry)?;
+                binomial(params, is_call, american, steps, tree, discount)
+            }
+            LatticeMethod::Trinomial => {
+                trinomial(params, is_call, american, steps, dt, carry, discount)
+            }
+        }
+    }
+}
+
+/// Barone-Adesi-Whaley approximation of an American option price.
+///
+/// Adds a quadratic early-exercise premium to the generalised
+/// Black-Scholes price, solving for the critical spot price with Brent's
+/// method. Calls with cost of carry at or above the rate, and puts with a
+/// non-positive rate, are never worth exercising early and price as
+/// European.
+pub fn barone_adesi_whaley(
+    params: &GeneralizedParams,
+    is_call: bool,
+) -> Result<Decimal, ArithmeticError> {
+    validate(params)?;
+    let european = generalized_price(params, is_call)?;
+    let carry = params.cost_of_carry()?;
+    if (is_call && carry >= params.rate) || (!is_call && params.rate <= Decimal::ZERO) {
+        return Ok(european);
+    }
+
+    let two = Decimal::from(2i64);
+    let variance = params.volatility.try_mul(params.volatility)?;
+    let n = two.try_mul(carry)?.try_div(variance)?;
+    let m = two.try_mul(params.rate)?.try_div(variance)?;
+    let k = Decimal::ONE.try_sub((-params.rate.try_mul(params.time)?).try_exp()?)?;
+    let n_minus_one = n.try_sub(Decimal::ONE)?;
+    let root = n_minus_one
+        .try_mul(n_minus_one)?
+        .try_add(Decimal::from(4i64).try_mul(m)?.try_div(k)?)?
+        .try_sqrt()?;
+    let q = if is_call {
+        root.try_sub(n_minus_one)?.try_div(two)?
+    } else {
+        (-n_minus_one).try_sub(root)?.try_div(two)?
+    };
+    let carry_discount = carry
+        .try_sub(params.rate)?
+        .try_mul(params.time)?
+        .try_exp()?;
+    let strike = params.strike;
+
+    // A(S) = ±(S/q)(1 − e^((b−r)T) N(±d1(S)))
+    let premium_factor = |spot: Decimal| -> Result<Decimal, ArithmeticError> {
+        let d1 = d1(params, spot)?;
+        let n_d1 = if is_call {
+            normal_cdf(d1)?
+        } else {
+            normal_cdf(-d1)?
+        };
+        spot.try_div(q)?
+            .try_mul(Decimal::ONE.try_sub(carry_discount.try_mul(n_d1)?)?)
+    };
+    let price_at = |spot: Decimal| -> Result<Decimal, ArithmeticError> {
+        let mut bumped = *params;
+        bumped.spot = spot;
+        generalized_price(&bumped, is_call)
+    };
+    // Critical spot: exercise value equals continuation value
+    let boundary = |spot: Decimal| -> Result<Decimal, ArithmeticError> {
+        if is_call {
+            spot.try_sub(strike)?
+                .try_sub(price_at(spot)?)?
+                .try_sub(premium_factor(spot)?)
+        } else {
+            strike
+                .try_sub(spot)?
+                .try_sub(price_at(spot)?)?
+                .try_add(premium_factor(spot)?)
+        }
+    };
+
+    // Bracket the critical price, moving away from the strike
+    let mut far = strike;
+    let mut found = false;
+    for _ in 0..64 {
+        far = if is_call {
+            far.try_mul(two)?
+        } else {
+            far.try_div(two)?
+        };
+        if boundary(far)? > Decimal::ZERO {
+            found = true;
+            break;
+        }
+    }
+    if !found {
+        return Ok(european);
+    }
+    let critical = brent(boundary, strike, far, Some(Decimal::new(1, 12)), Some(200))?.root;
+
+    let spot = params.spot;
+    let exercise_now = if is_call {
+        spot >= critical
+    } else {
+        spot <= critical
+    };
+    if exercise_now {
+        return Ok(intrinsic(spot, strike, is_call)?.max(european));
+    }
+
+    // V = v(S) + A (S/S*)^q
+    let amplitude = if is_call {
+        premium_factor(critical)?
+    } else {
+        -premium_factor(critical)?
+    };
+    let scale = q.try_mul(spot.try_div(critical)?.try_ln()?)?.try_exp()?;
+    european.try_add(amplitude.try_mul(scale)?)
+}
+
+fn binomial(
+    params: &GeneralizedParams,
+    is_call: bool,
+    american: bool,
+    steps: usize,
+    (up, down, p): (Decimal, Decimal, Decimal),
+    discount: Decimal,
+) -> Result<Rollback, ArithmeticError> {
+    if p <= Decimal::ZERO || p >= Decimal::ONE {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let q = Decimal::ONE.try_sub(p)?;
+    let ratio = up.try_div(down)?;
+    let mut values = [Decimal::ZERO; MAX_NODES];
+    let mut step1 = [(Decimal::ZERO, Decimal::ZERO); 3];
+    let mut step2 = [(Decimal::ZERO, Decimal::ZERO); 3];
+
+    // Node j at step i has spot S u^j d^(i−j)
+    let mut spot = params.spot.try_mul(down.try_powi(steps as i32)?)?;
+    for value in values.iter_mut().take(steps + 1) {
+        *value = intrinsic(spot, params.strike, is_call)?;
+        spot = spot.try_mul(ratio)?;
+    }
+
+    for i in (0..steps).rev() {
+        let mut spot = params.spot.try_mul(down.try_powi(i as i32)?)?;
+        for j in 0..=i {
+            let continuation =
+                discount.try_mul(p.try_mul(values[j + 1])?.try_add(q.try_mul(values[j])?)?)?;
+            values[j] = if american {
+                continuation.max(intrinsic(spot, params.strike, is_call)?)
+            } else {
+                continuation
+            };
+            if i <= 2 && j < 3 {
+                let node = (spot, values[j]);
+                if i == 1 {
+                    step1[j] = node;
+                } else if i == 2 {
+                    step2[j] = node;
+                }
+            }
+            spot = spot.try_mul(ratio)?;
+        }
+    }
+
+    Ok(Rollback {
+        price: values[0],
+        step1,
+        step2,
+    })
+}
+
+fn trinomial(
+    params: &GeneralizedParams,
+    is_call: bool,
+    american: bool,
+    steps: usize,
+    dt: Decimal,
+    carry: Decimal,
+    discount: Decimal,
+) -> Result<Rollback, ArithmeticError> {
+    let two = Decimal::from(2i64);
+    let up = params
+        .volatility
+        .try_mul(two.try_mul(dt)?.try_sqrt()?)?
+        .try_exp()?;
+    let down = Decimal::ONE.try_div(up)?;
+
+    // pu = ((e^(bΔt/2) − e^(−σ√(Δt/2))) / (e^(σ√(Δt/2)) − e^(−σ√(Δt/2))))²
+    let half_carry = carry.try_mul(dt)?.try_div(two)?.try_exp()?;
+    let half_up = params
+        .volatility
+        .try_mul(dt.try_div(two)?.try_sqrt()?)?
+        .try_exp()?;
+    let half_down = Decimal::ONE.try_div(half_up)?;
+    let width = half_up.try_sub(half_down)?;
+    let pu = half_carry.try_sub(half_down)?.try_div(width)?;
+    let pu = pu.try_mul(pu)?;
+    let pd = half_up.try_sub(half_carry)?.try_div(width)?;
+    let pd = pd.try_mul(pd)?;
+    let pm = Decimal::ONE.try_sub(pu)?.try_sub(pd)?;
+    if pu <= Decimal::ZERO || pd <= Decimal::ZERO || pm < Decimal::ZERO {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+
+    let mut values = [Decimal::ZERO; MAX_NODES];
+    let mut step1 = [(Decimal::ZERO, Decimal::ZERO); 3];
+
+    // Node k at step i has spot S u^(k−i)
+    let mut spot = params.spot.try_mul(down.try_powi(steps as i32)?)?;
+    for value in values.iter_mut().take(2 * steps + 1) {
+        *value = intrinsic(spot, params.strike, is_call)?;
+        spot = spot.try_mul(up)?;
+    }
+
+    for i in (0..steps).rev() {
+        let mut spot = params.spot.try_mul(down.try_powi(i as i32)?)?;
+        for k in 0..=2 * i {
+            let continuation = discount.try_mul(
+                pu.try_mul(values[k + 2])?
+                    .try_add(pm.try_mul(values[k + 1])?)?
+                    .try_add(pd.try_mul(values[k])?)?,
+            )?;
+            values[k] = if american {
+                continuation.max(intrinsic(spot, params.strike, is_call)?)
+            } else {
+                continuation
+            };
+            if i == 1 {
+                step1[k] = (spot, values[k]);
+            }
+            spot = spot.try_mul(up)?;
+        }
+    }
+
+    Ok(Rollback {
+        price: values[0],
+        step1,
+        step2: step1,
+    })
+}
+
+fn leisen_reimer(
+    params: &GeneralizedParams,
+    steps: usize,
+    dt: Decimal,
+    carry: Decimal,
+) -> Result<(Decimal, Decimal, Decimal), ArithmeticError> {
+    let d1 = d1(params, params.spot)?;
+    let d2 = d1.try_sub(params.volatility.try_mul(params.time.try_sqrt()?)?)?;
+    let n = Decimal::from(steps as i64);
+    let p = peizer_pratt(d2, n)?;
+    let p_star = peizer_pratt(d1, n)?;
+
+    let growth = carry.try_mul(dt)?.try_exp()?;
+    let up = growth.try_mul(p_star)?.try_div(p)?;
+    let down = growth
+        .try_sub(p.try_mul(up)?)?
+        .try_div(Decimal::ONE.try_sub(p)?)?;
+    Ok((up, down, p))
+}
+
+/// Peizer-Pratt method 2 inversion of the normal CDF onto a binomial.
+fn peizer_pratt(z: Decimal, n: Decimal) -> Result<Decimal, ArithmeticError> {
+    let one = Decimal::ONE;
+    let three = Decimal::from(3i64);
+    let six = Decimal::from(6i64);
+    let denominator = n
+        .try_add(one.try_div(three)?)?
+        .try_add(Decimal::new(1, 1).try_div(n.try_add(one)?)?)?;
+    let ratio = z.try_div(denominator)?;
+    let exponent = -ratio
+        .try_mul(ratio)?
+        .try_mul(n.try_add(one.try_div(six)?)?)?;
+    let quarter = Decimal::new(25, 2);
+    let half_width = quarter
+        .try_sub(quarter.try_mul(exponent.try_exp()?)?)?
+        .max(Decimal::ZERO)
+        .try_sqrt()?;
+    let half = Decimal::new(5, 1);
+    if z.is_negative() {
+        half.try_sub(half_width)
+    } else {
+        half.try_add(half_width)
+    }
+}
+
+fn d1(params: &GeneralizedParams, spot: Decimal) -> Result<Decimal, ArithmeticError> {
+    let vol_sqrt_t = params.volatility.try_mul(params.time.try_sqrt()?)?;
+    let half_var = params
+        .volatility
+        .try_mul(params.volatility)?
+        .try_div(Decimal::from(2i64))?;
+    spot.try_div(params.strike)?
+        .try_ln()?
+        .try_add(
+            params
+                .cost_of_carry()?
+                .try_add(half_var)?
+                .try_mul(params.time)?,
+        )?
+        .try_div(vol_sqrt_t)
+}
+
+fn intrinsic(spot: Decimal, strike: Decimal, is_call: bool) -> Result<Decimal, ArithmeticError> {
+    let payoff = if is_call {
+        spot.try_sub(strike)?
+    } else {
+        strike.try_sub(spot)?
+    };
+    Ok(payoff.max(Decimal::ZERO))
+}
+
+fn slope(low: (Decimal, Decimal), high: (Decimal, Decimal)) -> Result<Decimal, ArithmeticError> {
+    high.1.try_sub(low.1)?.try_div(high.0.try_sub(low.0)?)
+}
+
+fn curvature(nodes: [(Decimal, Decimal); 3]) -> Result<Decimal, ArithmeticError> {
+    let [low, middle, high] = nodes;
+    let width = high.0.try_sub(low.0)?.try_div(Decimal::from(2i64))?;
+    slope(middle, high)?
+        .try_sub(slope(low, middle)?)?
+        .try_div(width)
+}
+
+fn validate(params: &GeneralizedParams) -> Result<(), ArithmeticError> {
+    if params.spot <= Decimal::ZERO
+        || params.strike <= Decimal::ZERO
+        || params.volatility <= Decimal::ZERO
+    {
+        return Err(ArithmeticError::LogOfNegative);
+    }
+    if params.time <= Decimal::ZERO {
+        return Err(ArithmeticError::NegativeSqrt);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{generalized_greeks, OptionParams};
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn params(yield_rate: &str) -> GeneralizedParams {
+        GeneralizedParams::with_yield(
+            &OptionParams {
+                spot: decimal("100"),
+                strike: decimal("100"),
+                rate: decimal("0.05"),
+                time: decimal("1"),
+                volatility: decimal("0.2"),
+            },
+            decimal(yield_rate),
+        )
+    }
+
+    #[test]
+    fn test_european_lattices_converge_to_black_scholes() {
+        let params = params("0.02");
+        for is_call in [true, false] {
+            let exact = generalized_price(&params, is_call).unwrap();
+            let cases = [
+                (LatticeMethod::CoxRossRubinstein, 200, "0.02"),
+                (LatticeMethod::LeisenReimer, 101, "0.0005"),
+                (LatticeMethod::Trinomial, 100, "0.02"),
+            ];
+            for (method, steps, tolerance) in cases {
+                let price = Lattice::new(method, steps)
+                    .price(&params, is_call, ExerciseStyle::European)
+                    .unwrap();
+                assert!((price - exact).abs() < decimal(tolerance));
+            }
+        }
+    }
+
+    #[test]
+    fn test_american_put_early_exercise() {
+        let params = params("0");
+        let lr = Lattice::new(LatticeMethod::LeisenReimer, 201);
+        let american = lr.price(&params, false, ExerciseStyle::American).unwrap();
+        let european = generalized_price(&params, false).unwrap();
+        assert!(american > european);
+
+        // Methods agree on the American price
+        for method in [LatticeMethod::CoxRossRubinstein, LatticeMethod::Trinomial] {
+            let price = Lattice::new(method, 200)
+                .price(&params, false, ExerciseStyle::American)
+                .unwrap();
+            assert!((price - american).abs() < decimal("0.02"));
+        }
+
+        let baw = barone_adesi_whaley(&params, false).unwrap();
+        assert!((baw - american).abs() < decimal("0.05"));
+    }
+
+    #[test]
+    fn test_american_call_without_yield_is_european() {
+        let params = params("0");
+        let lattice = Lattice::new(LatticeMethod::CoxRossRubinstein, 100);
+        assert_eq!(
+            lattice.early_exercise_premium(&params, true).unwrap(),
+            Decimal::ZERO
+        );
+        assert_eq!(
+            barone_adesi_whaley(&params, true).unwrap(),
+            generalized_price(&params, true).unwrap()
+        );
+
+        // A high dividend yield makes early exercise worthwhile
+        let params = self::params("0.1");
+        assert!(lattice.early_exercise_premium(&params, true).unwrap() > Decimal::ZERO);
+        let american = Lattice::new(LatticeMethod::LeisenReimer, 201)
+            .price(&params, true, ExerciseStyle::American)
+            .unwrap();
+        let baw = barone_adesi_whaley(&params, true).unwrap();
+        assert!((baw - american).abs() < decimal("0.05"));
+    }
+
+    #[test]
+    fn test_deep_itm_baw_is_intrinsic() {
+        let mut params = params("0");
+        params.spot = decimal("40");
+        assert_eq!(barone_adesi_whaley(&params, false).unwrap(), decimal("60"));
+    }
+
+    #[test]
+    fn test_lattice_greeks_match_analytical() {
+        let params = params("0.02");
+        let analytical = generalized_greeks(&params, true).unwrap();
+        for method in [LatticeMethod::LeisenReimer, LatticeMethod::Trinomial] {
+            let greeks = Lattice::new(method, 101)
+                .greeks(&params, true, ExerciseStyle::European)
+                .unwrap();
+            assert!((greeks.delta - analytical.delta).abs() < decimal("0.005"));
+            assert!((greeks.gamma - analytical.gamma).abs() < decimal("0.001"));
+            assert!((greeks.theta - analytical.theta).abs() < decimal("0.001"));
+            assert!((greeks.vega - analytical.vega).abs() < decimal("0.01"));
+            assert!((greeks.rho - analytical.rho).abs() < decimal("0.01"));
+        }
+    }
+
+    #[test]
+    fn test_greeks_need_nodes_near_the_root() {
+        let params = params("0.02");
+        let style = ExerciseStyle::European;
+        for (method, steps) in [
+            (LatticeMethod::CoxRossRubinstein, 2),
+            (LatticeMethod::LeisenReimer, 1),
+            (LatticeMethod::Trinomial, 1),
+        ] {
+            let lattice = Lattice::new(method, steps);
+            assert!(lattice.price(&params, true, style).is_ok());
+            assert_eq!(
+                lattice.greeks(&params, true, style).unwrap_err(),
+                ArithmeticError::DivisionByZero
+            );
+        }
+        // Leisen-Reimer rounds two steps up to three
+        for (method, steps) in [
+            (LatticeMethod::CoxRossRubinstein, 3),
+            (LatticeMethod::LeisenReimer, 2),
+            (LatticeMethod::Trinomial, 2),
+        ] {
+            let greeks = Lattice::new(method, steps)
+                .greeks(&params, true, style)
+                .unwrap();
+            assert!(greeks.delta > Decimal::ZERO && greeks.gamma > Decimal::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_step_bounds() {
+        let params = params("0");
+        let style = ExerciseStyle::American;
+        assert_eq!(
+            Lattice::new(LatticeMethod::Trinomial, 0)
+                .price(&params, true, style)
+                .unwrap_err(),
+            ArithmeticError::DivisionByZero
+        );
+        assert_eq!(
+            Lattice::new(LatticeMethod::CoxRossRubinstein, MAX_LATTICE_STEPS + 1)
+                .price(&params, true, style)
+                .unwrap_err(),
+            ArithmeticError::Overflow
+        );
+        // Leisen-Reimer rounds 256 up to 257 steps
+        assert_eq!(
+            Lattice::new(LatticeMethod::LeisenReimer, MAX_LATTICE_STEPS)
+                .price(&params, true, style)
+                .unwrap_err(),
+            ArithmeticError::Overflow
+        );
+        assert!(
+            Lattice::new(LatticeMethod::LeisenReimer, MAX_LATTICE_STEPS - 1)
+                .price(&params, true, style)
+                .is_ok()
+        );
+    }
+}
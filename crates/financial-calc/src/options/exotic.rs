@@ -0,0 +1,696 @@
+//! Digital, barrier and Asian option pricing.
+//!
+//! Closed-form prices under the Black-Scholes assumptions of
+//! [`OptionParams`]:
+//!
+//! - Cash-or-nothing and asset-or-nothing digitals
+//! - Single-barrier knock-in and knock-out options with rebates
+//!   (Reiner-Rubinstein), monitored continuously
+//! - Geometric-average Asian options (Kemna-Vorst)
+//! - Arithmetic-average Asian options by the Turnbull-Wakeman moment
+//!   matching approximation, including periods already partly averaged
+//!
+//! Greeks use [`finite_difference_greeks`], which bumps the inputs of any
+//! pricing function and reports in the units of [`call_greeks`](super::call_greeks).
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::options::{barrier_price, Barrier, BarrierKind, OptionParams};
+//! use precision_core::Decimal;
+//!
+//! let params = OptionParams {
+//!     spot: Decimal::from(100i64),
+//!     strike: Decimal::from(100i64),
+//!     rate: Decimal::new(5, 2),
+//!     time: Decimal::new(5, 1),
+//!     volatility: Decimal::new(25, 2),
+//! };
+//!
+//! // Call that dies if spot trades down to 90, paying 2 on knock-out
+//! let (level, rebate) = (Decimal::from(90i64), Decimal::from(2i64));
+//! let barrier = Barrier::new(BarrierKind::DownAndOut, level, rebate);
+//! let price = barrier_price(&params, &barrier, true).unwrap();
+//! assert!(price > Decimal::ZERO);
+//! ```
+
+use super::{generalized_price, normal_cdf, GeneralizedParams, Greeks, OptionParams};
+use precision_core::{ArithmeticError, Decimal};
+
+/// Barrier direction and effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarrierKind {
+    /// Activated when spot falls to the barrier.
+    DownAndIn,
+    /// Extinguished when spot falls to the barrier.
+    DownAndOut,
+    /// Activated when spot rises to the barrier.
+    UpAndIn,
+    /// Extinguished when spot rises to the barrier.
+    UpAndOut,
+}
+
+impl BarrierKind {
+    fn is_down(self) -> bool {
+        matches!(self, Self::DownAndIn | Self::DownAndOut)
+    }
+
+    fn is_knock_in(self) -> bool {
+        matches!(self, Self::DownAndIn | Self::UpAndIn)
+    }
+}
+
+/// A single continuously monitored barrier.
+#[derive(Debug, Clone, Copy)]
+pub struct Barrier {
+    /// Barrier direction and effect.
+    pub kind: BarrierKind,
+    /// Barrier level.
+    pub level: Decimal,
+    /// Cash rebate: paid on knock-out when the barrier is hit, or at expiry
+    /// for a knock-in that never activated.
+    pub rebate: Decimal,
+}
+
+impl Barrier {
+    /// Creates a barrier.
+    pub fn new(kind: BarrierKind, level: Decimal, rebate: Decimal) -> Self {
+        Self {
+            kind,
+            level,
+            rebate,
+        }
+    }
+}
+
+/// Averaging already observed on an arithmetic Asian option.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealizedAverage {
+    /// Arithmetic average of fixings so far.
+    pub average: Decimal,
+    /// Years of the averaging period already elapsed.
+    pub elapsed: Decimal,
+}
+
+/// Cash-or-nothing digital: pays `cash` at expiry if the option finishes
+/// in the money.
+///
+/// Call = cash × e^(-rT) N(d2), Put = cash × e^(-rT) N(-d2)
+pub fn cash_or_nothing_price(
+    params: &OptionParams,
+    cash: Decimal,
+    is_call: bool,
+) -> Result<Decimal, ArithmeticError> {
+    let (_, d2) = d1_d2(params)?;
+    let n_d2 = if is_call {
+        normal_cdf(d2)?
+    } else {
+        normal_cdf(-d2)?
+    };
+    cash.try_mul(discount(params)?)?.try_mul(n_d2)
+}
+
+/// Asset-or-nothing digital: delivers one unit of the underlying at expiry
+/// if the option finishes in the money.
+///
+/// Call = S N(d1), Put = S N(-d1)
+pub fn asset_or_nothing_price(
+    params: &OptionParams,
+    is_call: bool,
+) -> Result<Decimal, ArithmeticError> {
+    let (d1, _) = d1_d2(params)?;
+    let n_d1 = if is_call {
+        normal_cdf(d1)?
+    } else {
+        normal_cdf(-d1)?
+    };
+    params.spot.try_mul(n_d1)
+}
+
+/// Single-barrier option price (Reiner-Rubinstein).
+///
+/// If spot is already through the barrier, a knock-out is worth its
+/// rebate and a knock-in is worth the vanilla option.
+pub fn barrier_price(
+    params: &OptionParams,
+    barrier: &Barrier,
+    is_call: bool,
+) -> Result<Decimal, ArithmeticError> {
+    validate(params)?;
+    if barrier.level <= Decimal::ZERO {
+        return Err(ArithmeticError::LogOfNegative);
+    }
+
+    let breached = if barrier.kind.is_down() {
+        params.spot <= barrier.level
+    } else {
+        params.spot >= barrier.level
+    };
+    if breached {
+        return if barrier.kind.is_knock_in() {
+            generalized_price(
+                &GeneralizedParams::with_yield(params, Decimal::ZERO),
+                is_call,
+            )
+        } else {
+            Ok(barrier.rebate)
+        };
+    }
+
+    let terms = BarrierTerms::new(params, barrier, is_call)?;
+    let above = params.strike > barrier.level;
+    let (a, b, c, d, e, f) = (terms.a, terms.b, terms.c, terms.d, terms.e, terms.f);
+
+    // Haug, The Complete Guide to Option Pricing Formulas, 4.17.1
+    let price = match (barrier.kind, is_call, above) {
+        (BarrierKind::DownAndIn, true, true) => c.try_add(e)?,
+        (BarrierKind::DownAndIn, true, false) => a.try_sub(b)?.try_add(d)?.try_add(e)?,
+        (BarrierKind::UpAndIn, true, true) => a.try_add(e)?,
+        (BarrierKind::UpAndIn, true, false) => b.try_sub(c)?.try_add(d)?.try_add(e)?,
+        (BarrierKind::DownAndIn, false, true) => b.try_sub(c)?.try_add(d)?.try_add(e)?,
+        (BarrierKind::DownAndIn, false, false) => a.try_add(e)?,
+        (BarrierKind::UpAndIn, false, true) => a.try_sub(b)?.try_add(d)?.try_add(e)?,
+        (BarrierKind::UpAndIn, false, false) => c.try_add(e)?,
+        (BarrierKind::DownAndOut, true, true) => a.try_sub(c)?.try_add(f)?,
+        (BarrierKind::DownAndOut, true, false) => b.try_sub(d)?.try_add(f)?,
+        (BarrierKind::UpAndOut, true, true) => f,
+        (BarrierKind::UpAndOut, true, false) => a.try_sub(b)?.try_add(c)?.try_sub(d)?.try_add(f)?,
+        (BarrierKind::DownAndOut, false, true) => {
+            a.try_sub(b)?.try_add(c)?.try_sub(d)?.try_add(f)?
+        }
+        (BarrierKind::DownAndOut, false, false) => f,
+        (BarrierKind::UpAndOut, false, true) => b.try_sub(d)?.try_add(f)?,
+        (BarrierKind::UpAndOut, false, false) => a.try_sub(c)?.try_add(f)?,
+    };
+    Ok(price.max(Decimal::ZERO))
+}
+
+/// Continuously averaged geometric Asian option price (Kemna-Vorst).
+///
+/// Black-Scholes on the average with σ_A = σ/√3 and carry b_A = (r − σ²/6)/2.
+pub fn geometric_asian_price(
+    params: &OptionParams,
+    is_call: bool,
+) -> Result<Decimal, ArithmeticError> {
+    validate(params)?;
+    let variance = params.volatility.try_mul(params.volatility)?;
+    let carry = params
+        .rate
+        .try_sub(variance.try_div(Decimal::from(6i64))?)?
+        .try_div(Decimal::from(2i64))?;
+    let averaged = GeneralizedParams {
+        spot: params.spot,
+        strike: params.strike,
+        rate: params.rate,
+        yield_rate: params.rate.try_sub(carry)?,
+        time: params.time,
+        volatility: params.volatility.try_div(Decimal::from(3i64).try_sqrt()?)?,
+    };
+    generalized_price(&averaged, is_call)
+}
+
+/// Arithmetic Asian option price by the Turnbull-Wakeman approximation.
+///
+/// The average over the remaining `params.time` is matched to a lognormal
+/// by its first two moments. Averaging already done is folded into an
+/// adjusted strike; if that strike is not positive the call is certain to
+/// finish in the money and is priced from the expected average.
+pub fn arithmetic_asian_price(
+    params: &OptionParams,
+    realized: &RealizedAverage,
+    is_call: bool,
+) -> Result<Decimal, ArithmeticError> {
+    validate(params)?;
+    if realized.elapsed.is_negative() {
+        return Err(ArithmeticError::Underflow);
+    }
+
+    let two = Decimal::from(2i64);
+    let time = params.time;
+    let b = params.rate;
+    let variance = params.volatility.try_mul(params.volatility)?;
+
+    // First two moments of the average relative to spot, written with
+    // φ(x) = (eˣ - 1) / x so they stay accurate as bT goes to zero:
+    // M1 = φ(bT), M2 = 2 (φ((2b + σ²)T) - φ(bT)) / ((b + σ²)T)
+    let m1 = exp_ratio(b.try_mul(time)?)?;
+    let two_b_var = two.try_mul(b)?.try_add(variance)?;
+    let b_var = b.try_add(variance)?;
+    let m2 = two
+        .try_mul(exp_ratio(two_b_var.try_mul(time)?)?.try_sub(m1)?)?
+        .try_div(b_var.try_mul(time)?)?;
+
+    let total_period = time.try_add(realized.elapsed)?;
+    let weight = time.try_div(total_period)?;
+    let strike = params
+        .strike
+        .try_sub(
+            realized
+                .elapsed
+                .try_div(total_period)?
+                .try_mul(realized.average)?,
+        )?
+        .try_div(weight)?;
+
+    if !strike.is_positive() {
+        if !is_call {
+            return Ok(Decimal::ZERO);
+        }
+        let expected = params.spot.try_mul(m1)?.try_sub(strike)?.try_mul(weight)?;
+        return expected.try_mul(discount(params)?);
+    }
+
+    let average_carry = m1.try_ln()?.try_div(time)?;
+    let average_variance = m2
+        .try_ln()?
+        .try_div(time)?
+        .try_sub(two.try_mul(average_carry)?)?;
+    let matched = GeneralizedParams {
+        spot: params.spot,
+        strike,
+        rate: params.rate,
+        yield_rate: params.rate.try_sub(average_carry)?,
+        time,
+        volatility: average_variance.try_sqrt()?,
+    };
+    weight.try_mul(generalized_price(&matched, is_call)?)
+}
+
+/// Greeks of any pricing function by central finite differences.
+///
+/// Bumps spot and volatility by 0.01% relative, time by 0.01% of expiry
+/// and the rate by one basis point. Theta is per day, vega and rho per 1%.
+pub fn finite_difference_greeks<F>(
+    params: &OptionParams,
+    price: F,
+) -> Result<Greeks, ArithmeticError>
+where
+    F: Fn(&OptionParams) -> Result<Decimal, ArithmeticError>,
+{
+    validate(params)?;
+    let two = Decimal::from(2i64);
+    let hundred = Decimal::from(100i64);
+    let relative = Decimal::new(1, 4);
+    let base = price(params)?;
+
+    let h = params.spot.try_mul(relative)?;
+    let up = price(&OptionParams {
+        spot: params.spot.try_add(h)?,
+        ..*params
+    })?;
+    let down = price(&OptionParams {
+        spot: params.spot.try_sub(h)?,
+        ..*params
+    })?;
+    let delta = up.try_sub(down)?.try_div(two.try_mul(h)?)?;
+    let gamma = up
+        .try_sub(two.try_mul(base)?)?
+        .try_add(down)?
+        .try_div(h.try_mul(h)?)?;
+
+    let h = params.time.try_mul(relative)?;
+    let later = price(&OptionParams {
+        time: params.time.try_sub(h)?,
+        ..*params
+    })?;
+    let earlier = price(&OptionParams {
+        time: params.time.try_add(h)?,
+        ..*params
+    })?;
+    let theta = later
+        .try_sub(earlier)?
+        .try_div(two.try_mul(h)?)?
+        .try_div(Decimal::from(365i64))?;
+
+    let h = params.volatility.try_mul(relative)?;
+    let vega = price(&OptionParams {
+        volatility: params.volatility.try_add(h)?,
+        ..*params
+    })?
+    .try_sub(price(&OptionParams {
+        volatility: params.volatility.try_sub(h)?,
+        ..*params
+    })?)?
+    .try_div(two.try_mul(h)?)?
+    .try_div(hundred)?;
+
+    let h = relative;
+    let rho = price(&OptionParams {
+        rate: params.rate.try_add(h)?,
+        ..*params
+    })?
+    .try_sub(price(&OptionParams {
+        rate: params.rate.try_sub(h)?,
+        ..*params
+    })?)?
+    .try_div(two.try_mul(h)?)?
+    .try_div(hundred)?;
+
+    Ok(Greeks {
+        delta,
+        gamma,
+        theta,
+        vega,
+        rho,
+    })
+}
+
+/// Reiner-Rubinstein building blocks with cost of carry b = r.
+struct BarrierTerms {
+    a: Decimal,
+    b: Decimal,
+    c: Decimal,
+    d: Decimal,
+    e: Decimal,
+    f: Decimal,
+}
+
+impl BarrierTerms {
+    fn new(
+        params: &OptionParams,
+        barrier: &Barrier,
+        is_call: bool,
+    ) -> Result<Self, ArithmeticError> {
+        let one = Decimal::ONE;
+        let two = Decimal::from(2i64);
+        let spot = params.spot;
+        let strike = params.strike;
+        let level = barrier.level;
+        let phi = if is_call { one } else { -one };
+        let eta = if barrier.kind.is_down() { one } else { -one };
+
+        let variance = params.volatility.try_mul(params.volatility)?;
+        let vol_sqrt_t = params.volatility.try_mul(params.time.try_sqrt()?)?;
+        let mu = params
+            .rate
+            .try_sub(variance.try_div(two)?)?
+            .try_div(variance)?;
+        let lambda = mu
+            .try_mul(mu)?
+            .try_add(two.try_mul(params.rate)?.try_div(variance)?)?
+            .try_sqrt()?;
+        let shift = one.try_add(mu)?.try_mul(vol_sqrt_t)?;
+        let standardise = |ratio: Decimal| -> Result<Decimal, ArithmeticError> {
+            ratio.try_ln()?.try_div(vol_sqrt_t)
+        };
+
+        let x1 = standardise(spot.try_div(strike)?)?.try_add(shift)?;
+        let x2 = standardise(spot.try_div(level)?)?.try_add(shift)?;
+        let y1 =
+            standardise(level.try_mul(level)?.try_div(spot.try_mul(strike)?)?)?.try_add(shift)?;
+        let y2 = standardise(level.try_div(spot)?)?.try_add(shift)?;
+        let z = standardise(level.try_div(spot)?)?.try_add(lambda.try_mul(vol_sqrt_t)?)?;
+
+        let discount = discount(params)?;
+        let strike_value = strike.try_mul(discount)?;
+        let ln_ratio = level.try_div(spot)?.try_ln()?;
+        let ratio_pow = |power: Decimal| -> Result<Decimal, ArithmeticError> {
+            power.try_mul(ln_ratio)?.try_exp()
+        };
+        let asset_reflection = ratio_pow(two.try_mul(mu.try_add(one)?)?)?;
+        let cash_reflection = ratio_pow(two.try_mul(mu)?)?;
+
+        // φS N(φx) − φK e^(-rT) N(φx − φσ√T)
+        let vanilla_term = |x: Decimal| -> Result<Decimal, ArithmeticError> {
+            let asset = phi.try_mul(spot)?.try_mul(normal_cdf(phi.try_mul(x)?)?)?;
+            let cash = phi
+                .try_mul(strike_value)?
+                .try_mul(normal_cdf(phi.try_mul(x.try_sub(vol_sqrt_t)?)?)?)?;
+            asset.try_sub(cash)
+        };
+        let reflected_term = |y: Decimal| -> Result<Decimal, ArithmeticError> {
+            let asset = phi
+                .try_mul(spot)?
+                .try_mul(asset_reflection)?
+                .try_mul(normal_cdf(eta.try_mul(y)?)?)?;
+            let cash = phi
+                .try_mul(strike_value)?
+                .try_mul(cash_reflection)?
+                .try_mul(normal_cdf(eta.try_mul(y.try_sub(vol_sqrt_t)?)?)?)?;
+            asset.try_sub(cash)
+        };
+
+        let e = barrier.rebate.try_mul(discount)?.try_mul(
+            normal_cdf(eta.try_mul(x2.try_sub(vol_sqrt_t)?)?)?.try_sub(
+                cash_reflection.try_mul(normal_cdf(eta.try_mul(y2.try_sub(vol_sqrt_t)?)?)?)?,
+            )?,
+        )?;
+        let f = barrier.rebate.try_mul(
+            ratio_pow(mu.try_add(lambda)?)?
+                .try_mul(normal_cdf(eta.try_mul(z)?)?)?
+                .try_add(ratio_pow(mu.try_sub(lambda)?)?.try_mul(normal_cdf(
+                    eta.try_mul(z.try_sub(two.try_mul(lambda)?.try_mul(vol_sqrt_t)?)?)?,
+                )?)?)?,
+        )?;
+
+        Ok(Self {
+            a: vanilla_term(x1)?,
+            b: vanilla_term(x2)?,
+            c: reflected_term(y1)?,
+            d: reflected_term(y2)?,
+            e,
+            f,
+        })
+    }
+}
+
+fn d1_d2(params: &OptionParams) -> Result<(Decimal, Decimal), ArithmeticError> {
+    validate(params)?;
+    let vol_sqrt_t = params.volatility.try_mul(params.time.try_sqrt()?)?;
+    let half_var = params
+        .volatility
+        .try_mul(params.volatility)?
+        .try_div(Decimal::from(2i64))?;
+    let d1 = params
+        .spot
+        .try_div(params.strike)?
+        .try_ln()?
+        .try_add(params.rate.try_add(half_var)?.try_mul(params.time)?)?
+        .try_div(vol_sqrt_t)?;
+    Ok((d1, d1.try_sub(vol_sqrt_t)?))
+}
+
+/// (eˣ - 1) / x, by its Taylor series near zero where the direct form
+/// cancels.
+fn exp_ratio(x: Decimal) -> Result<Decimal, ArithmeticError> {
+    if x.abs() >= Decimal::new(1, 2) {
+        return x.try_exp()?.try_sub(Decimal::ONE)?.try_div(x);
+    }
+    // Σ xᵏ / (k + 1)!; with |x| < 0.01 twelve terms reach full precision
+    let mut term = Decimal::ONE;
+    let mut sum = Decimal::ONE;
+    for k in 2..=13i64 {
+        term = term.try_mul(x)?.try_div(Decimal::from(k))?;
+        sum = sum.try_add(term)?;
+    }
+    Ok(sum)
+}
+
+fn discount(params: &OptionParams) -> Result<Decimal, ArithmeticError> {
+    (-params.rate.try_mul(params.time)?).try_exp()
+}
+
+fn validate(params: &OptionParams) -> Result<(), ArithmeticError> {
+    if params.spot <= Decimal::ZERO
+        || params.strike <= Decimal::ZERO
+        || params.volatility <= Decimal::ZERO
+    {
+        return Err(ArithmeticError::LogOfNegative);
+    }
+    if params.time <= Decimal::ZERO {
+        return Err(ArithmeticError::NegativeSqrt);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{black_scholes_call, black_scholes_put, call_greeks};
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn close(a: Decimal, b: Decimal, tolerance: &str) -> bool {
+        (a - b).abs() < decimal(tolerance)
+    }
+
+    fn params() -> OptionParams {
+        OptionParams {
+            spot: decimal("100"),
+            strike: decimal("100"),
+            rate: decimal("0.05"),
+            time: decimal("0.5"),
+            volatility: decimal("0.25"),
+        }
+    }
+
+    #[test]
+    fn test_digital_parity() {
+        let params = params();
+        let cash = decimal("10");
+        let calls = cash_or_nothing_price(&params, cash, true).unwrap();
+        let puts = cash_or_nothing_price(&params, cash, false).unwrap();
+        assert!(close(
+            calls + puts,
+            cash * discount(&params).unwrap(),
+            "0.0000001"
+        ));
+
+        let asset_call = asset_or_nothing_price(&params, true).unwrap();
+        let asset_put = asset_or_nothing_price(&params, false).unwrap();
+        assert!(close(asset_call + asset_put, params.spot, "0.0000001"));
+
+        // A vanilla call is long an asset digital and short K cash digitals
+        let replicated = asset_call - cash_or_nothing_price(&params, params.strike, true).unwrap();
+        assert!(close(
+            replicated,
+            black_scholes_call(&params).unwrap(),
+            "0.0000001"
+        ));
+    }
+
+    #[test]
+    fn test_barrier_in_out_parity() {
+        let params = params();
+        let vanillas = [
+            (true, black_scholes_call(&params).unwrap()),
+            (false, black_scholes_put(&params).unwrap()),
+        ];
+        let pairs = [
+            (BarrierKind::DownAndIn, BarrierKind::DownAndOut, "90"),
+            (BarrierKind::DownAndIn, BarrierKind::DownAndOut, "99"),
+            (BarrierKind::UpAndIn, BarrierKind::UpAndOut, "110"),
+            (BarrierKind::UpAndIn, BarrierKind::UpAndOut, "101"),
+        ];
+        for (is_call, vanilla) in vanillas {
+            for (knock_in, knock_out, level) in pairs {
+                let level = decimal(level);
+                let price_in = barrier_price(
+                    &params,
+                    &Barrier::new(knock_in, level, Decimal::ZERO),
+                    is_call,
+                )
+                .unwrap();
+                let price_out = barrier_price(
+                    &params,
+                    &Barrier::new(knock_out, level, Decimal::ZERO),
+                    is_call,
+                )
+                .unwrap();
+                assert!(close(price_in + price_out, vanilla, "0.00001"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_barrier_limits_and_rebates() {
+        let params = params();
+        let vanilla = black_scholes_call(&params).unwrap();
+
+        // A distant barrier barely matters
+        let far = Barrier::new(BarrierKind::DownAndOut, decimal("10"), Decimal::ZERO);
+        assert!(close(
+            barrier_price(&params, &far, true).unwrap(),
+            vanilla,
+            "0.0001"
+        ));
+
+        // Already knocked out pays the rebate; knocked in is the vanilla
+        let hit = Barrier::new(BarrierKind::DownAndOut, decimal("100"), decimal("3"));
+        assert_eq!(barrier_price(&params, &hit, true).unwrap(), decimal("3"));
+        let hit = Barrier::new(BarrierKind::DownAndIn, decimal("100"), decimal("3"));
+        assert!(close(
+            barrier_price(&params, &hit, true).unwrap(),
+            vanilla,
+            "0.0000001"
+        ));
+
+        // Rebate adds value to a knock-out
+        let without = Barrier::new(BarrierKind::UpAndOut, decimal("120"), Decimal::ZERO);
+        let with = Barrier::new(BarrierKind::UpAndOut, decimal("120"), decimal("5"));
+        assert!(
+            barrier_price(&params, &with, true).unwrap()
+                > barrier_price(&params, &without, true).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_asian_ordering() {
+        let params = params();
+        let geometric = geometric_asian_price(&params, true).unwrap();
+        let arithmetic =
+            arithmetic_asian_price(&params, &RealizedAverage::default(), true).unwrap();
+        let vanilla = black_scholes_call(&params).unwrap();
+        assert!(geometric < arithmetic);
+        assert!(arithmetic < vanilla);
+
+        // Zero rate uses the limiting moments
+        let zero_rate = OptionParams {
+            rate: Decimal::ZERO,
+            ..params
+        };
+        let price = arithmetic_asian_price(&zero_rate, &RealizedAverage::default(), true).unwrap();
+        assert!(price > geometric_asian_price(&zero_rate, true).unwrap());
+    }
+
+    #[test]
+    fn test_arithmetic_asian_tiny_carry() {
+        let price = |rate: &str| {
+            let params = OptionParams {
+                rate: decimal(rate),
+                ..params()
+            };
+            arithmetic_asian_price(&params, &RealizedAverage::default(), true).unwrap()
+        };
+        // The moments are continuous through b = 0 instead of cancelling
+        let zero = price("0");
+        assert!(close(price("0.000000000001"), zero, "0.00000000005"));
+        assert!(close(price("-0.000000000001"), zero, "0.00000000005"));
+        assert!(close(price("0.00000000000000000001"), zero, "0.000000000000000001"));
+    }
+
+    #[test]
+    fn test_arithmetic_asian_realized_average() {
+        let params = params();
+        // Half the period averaged at 250: the average cannot finish below 125
+        let realized = RealizedAverage {
+            average: decimal("250"),
+            elapsed: decimal("0.5"),
+        };
+        assert_eq!(
+            arithmetic_asian_price(&params, &realized, false).unwrap(),
+            Decimal::ZERO
+        );
+        let call = arithmetic_asian_price(&params, &realized, true).unwrap();
+        assert!(call > decimal("73") && call < decimal("75"));
+
+        // A low realised average cheapens the call
+        let low = RealizedAverage {
+            average: decimal("80"),
+            elapsed: decimal("0.5"),
+        };
+        let cheap = arithmetic_asian_price(&params, &low, true).unwrap();
+        let fresh = arithmetic_asian_price(&params, &RealizedAverage::default(), true).unwrap();
+        assert!(cheap < fresh);
+    }
+
+    #[test]
+    fn test_finite_difference_greeks_match_analytical() {
+        let params = params();
+        let numeric = finite_difference_greeks(&params, black_scholes_call).unwrap();
+        let analytical = call_greeks(&params).unwrap();
+        assert!(close(numeric.delta, analytical.delta, "0.00001"));
+        assert!(close(numeric.gamma, analytical.gamma, "0.0001"));
+        assert!(close(numeric.theta, analytical.theta, "0.0001"));
+        assert!(close(numeric.vega, analytical.vega, "0.0001"));
+        assert!(close(numeric.rho, analytical.rho, "0.0001"));
+
+        let barrier = Barrier::new(BarrierKind::DownAndOut, decimal("90"), Decimal::ZERO);
+        let greeks =
+            finite_difference_greeks(&params, |p| barrier_price(p, &barrier, true)).unwrap();
+        assert!(greeks.delta > analytical.delta);
+    }
+}
@@ -0,0 +1,507 @@
+//! Higher-order Greeks, unit conventions and portfolio aggregation.
+//!
+//! [`higher_order_greeks`] gives the second- and third-order sensitivities
+//! of the generalised Black-Scholes model. Like [`Greeks`], they are quoted
+//! per day and per 1 vol point by default; [`GreekUnits`] converts either
+//! set to per-year or per-unit conventions.
+//!
+//! [`OptionPortfolio`] sums signed, quantity-weighted Greeks across option
+//! legs and underlying hedges.
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::options::{GeneralizedParams, OptionParams, OptionPortfolio};
+//! use precision_core::Decimal;
+//!
+//! let params = GeneralizedParams::with_yield(
+//!     &OptionParams {
+//!         spot: Decimal::from(2000i64),
+//!         strike: Decimal::from(2000i64),
+//!         rate: Decimal::new(5, 2),
+//!         time: Decimal::new(25, 2),
+//!         volatility: Decimal::new(6, 1),
+//!     },
+//!     Decimal::ZERO,
+//! );
+//!
+//! // Short 10 straddles, delta hedged with the underlying
+//! let mut book = OptionPortfolio::new();
+//! book.add_option(params, true, Decimal::from(-10i64)).unwrap();
+//! book.add_option(params, false, Decimal::from(-10i64)).unwrap();
+//! let delta = book.greeks().unwrap().greeks.delta;
+//! book.add_underlying(-delta).unwrap();
+//!
+//! let hedged = book.greeks().unwrap();
+//! assert!(hedged.greeks.delta.abs() < Decimal::new(1, 20));
+//! assert!(hedged.greeks.gamma.is_negative());
+//! ```
+
+use super::{generalized_greeks, normal_cdf, normal_pdf, GeneralizedParams, Greeks};
+use crate::storage::{Buffer, Fixed, Storage};
+use precision_core::{ArithmeticError, Decimal};
+
+/// Default number of option legs in a portfolio (for no_std fixed allocation).
+pub const MAX_PORTFOLIO_LEGS: usize = 32;
+
+const DAYS_PER_YEAR: i64 = 365;
+const VOL_POINT: i64 = 100;
+
+/// Second- and third-order Greeks.
+///
+/// Time derivatives are for the passage of time (so a decaying delta has
+/// negative charm) per day; volatility derivatives are per 1 vol point.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HigherOrderGreeks {
+    /// Change in delta per vol point (∂Δ/∂σ).
+    pub vanna: Decimal,
+    /// Change in vega per vol point (∂ν/∂σ), also called vomma.
+    pub volga: Decimal,
+    /// Change in delta per day (∂Δ/∂t).
+    pub charm: Decimal,
+    /// Change in gamma per unit of spot (∂Γ/∂S).
+    pub speed: Decimal,
+    /// Change in gamma per day (∂Γ/∂t).
+    pub color: Decimal,
+    /// Change in gamma per vol point (∂Γ/∂σ).
+    pub zomma: Decimal,
+}
+
+/// Unit for time sensitivities.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeUnit {
+    /// Per calendar day (365 per year).
+    #[default]
+    PerDay,
+    /// Per year.
+    PerYear,
+}
+
+/// Unit for volatility and rate sensitivities.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShiftUnit {
+    /// Per 1 point (0.01) move.
+    #[default]
+    PerPoint,
+    /// Per 1.00 move.
+    PerUnit,
+}
+
+/// Quoting convention for Greeks. The default matches the crate: theta
+/// per day, vega and rho per 1 point.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GreekUnits {
+    /// Unit for theta, charm and color.
+    pub time: TimeUnit,
+    /// Unit for vega, vanna, volga and zomma.
+    pub volatility: ShiftUnit,
+    /// Unit for rho.
+    pub rate: ShiftUnit,
+}
+
+impl GreekUnits {
+    /// Per year and per 1.00 move, as in the textbook formulas.
+    pub const ANNUAL: Self = Self {
+        time: TimeUnit::PerYear,
+        volatility: ShiftUnit::PerUnit,
+        rate: ShiftUnit::PerUnit,
+    };
+
+    fn time_factor(&self) -> Decimal {
+        match self.time {
+            TimeUnit::PerDay => Decimal::ONE,
+            TimeUnit::PerYear => Decimal::from(DAYS_PER_YEAR),
+        }
+    }
+
+    fn shift_factor(unit: ShiftUnit) -> Decimal {
+        match unit {
+            ShiftUnit::PerPoint => Decimal::ONE,
+            ShiftUnit::PerUnit => Decimal::from(VOL_POINT),
+        }
+    }
+}
+
+impl Greeks {
+    /// Converts Greeks from the crate convention to `units`.
+    pub fn in_units(&self, units: &GreekUnits) -> Result<Self, ArithmeticError> {
+        Ok(Self {
+            delta: self.delta,
+            gamma: self.gamma,
+            theta: self.theta.try_mul(units.time_factor())?,
+            vega: self
+                .vega
+                .try_mul(GreekUnits::shift_factor(units.volatility))?,
+            rho: self.rho.try_mul(GreekUnits::shift_factor(units.rate))?,
+        })
+    }
+}
+
+impl HigherOrderGreeks {
+    /// Converts Greeks from the crate convention to `units`.
+    pub fn in_units(&self, units: &GreekUnits) -> Result<Self, ArithmeticError> {
+        let time = units.time_factor();
+        let vol = GreekUnits::shift_factor(units.volatility);
+        Ok(Self {
+            vanna: self.vanna.try_mul(vol)?,
+            volga: self.volga.try_mul(vol)?.try_mul(vol)?,
+            charm: self.charm.try_mul(time)?,
+            speed: self.speed,
+            color: self.color.try_mul(time)?,
+            zomma: self.zomma.try_mul(vol)?,
+        })
+    }
+}
+
+/// Second- and third-order Greeks under the generalised Black-Scholes model.
+///
+/// With b the cost of carry and d1, d2 as usual:
+///
+/// - Vanna = −e^((b−r)T) n(d1) d2 / σ
+/// - Volga = S e^((b−r)T) n(d1) √T d1 d2 / σ
+/// - Charm = −e^((b−r)T) [n(d1)(b/(σ√T) − d2/(2T)) ± (b−r) N(±d1)]
+/// - Speed = −Γ/S (d1/(σ√T) + 1)
+/// - Color = Γ (r − b + b d1/(σ√T) + (1 − d1 d2)/(2T))
+/// - Zomma = Γ (d1 d2 − 1) / σ
+pub fn higher_order_greeks(
+    params: &GeneralizedParams,
+    is_call: bool,
+) -> Result<HigherOrderGreeks, ArithmeticError> {
+    if params.spot <= Decimal::ZERO
+        || params.strike <= Decimal::ZERO
+        || params.volatility <= Decimal::ZERO
+    {
+        return Err(ArithmeticError::LogOfNegative);
+    }
+    if params.time <= Decimal::ZERO {
+        return Err(ArithmeticError::NegativeSqrt);
+    }
+
+    let one = Decimal::ONE;
+    let two = Decimal::from(2i64);
+    let sigma = params.volatility;
+    let time = params.time;
+    let carry = params.cost_of_carry()?;
+    let sqrt_t = time.try_sqrt()?;
+    let vol_sqrt_t = sigma.try_mul(sqrt_t)?;
+    let half_var = sigma.try_mul(sigma)?.try_div(two)?;
+    let d1 = params
+        .spot
+        .try_div(params.strike)?
+        .try_ln()?
+        .try_add(carry.try_add(half_var)?.try_mul(time)?)?
+        .try_div(vol_sqrt_t)?;
+    let d2 = d1.try_sub(vol_sqrt_t)?;
+    let d1_d2 = d1.try_mul(d2)?;
+
+    let carry_discount = carry.try_sub(params.rate)?.try_mul(time)?.try_exp()?;
+    let pdf = normal_pdf(d1)?;
+    let gamma = carry_discount
+        .try_mul(pdf)?
+        .try_div(params.spot.try_mul(vol_sqrt_t)?)?;
+    let vega = params
+        .spot
+        .try_mul(carry_discount)?
+        .try_mul(pdf)?
+        .try_mul(sqrt_t)?;
+
+    let vanna = -carry_discount.try_mul(pdf)?.try_mul(d2)?.try_div(sigma)?;
+    let volga = vega.try_mul(d1_d2)?.try_div(sigma)?;
+
+    let drift = carry
+        .try_div(vol_sqrt_t)?
+        .try_sub(d2.try_div(two.try_mul(time)?)?)?;
+    let carry_term = if is_call {
+        carry.try_sub(params.rate)?.try_mul(normal_cdf(d1)?)?
+    } else {
+        -carry.try_sub(params.rate)?.try_mul(normal_cdf(-d1)?)?
+    };
+    let charm = -carry_discount.try_mul(pdf.try_mul(drift)?.try_add(carry_term)?)?;
+
+    let speed = -gamma
+        .try_div(params.spot)?
+        .try_mul(d1.try_div(vol_sqrt_t)?.try_add(one)?)?;
+    let color = gamma.try_mul(
+        params
+            .rate
+            .try_sub(carry)?
+            .try_add(carry.try_mul(d1)?.try_div(vol_sqrt_t)?)?
+            .try_add(one.try_sub(d1_d2)?.try_div(two.try_mul(time)?)?)?,
+    )?;
+    let zomma = gamma.try_mul(d1_d2.try_sub(one)?)?.try_div(sigma)?;
+
+    let days = Decimal::from(DAYS_PER_YEAR);
+    let point = Decimal::from(VOL_POINT);
+    Ok(HigherOrderGreeks {
+        vanna: vanna.try_div(point)?,
+        volga: volga.try_div(point)?.try_div(point)?,
+        charm: charm.try_div(days)?,
+        speed,
+        color: color.try_div(days)?,
+        zomma: zomma.try_div(point)?,
+    })
+}
+
+/// An option position in a portfolio.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OptionLeg {
+    /// Pricing parameters.
+    pub params: GeneralizedParams,
+    /// True for a call, false for a put.
+    pub is_call: bool,
+    /// Signed number of contracts (negative for short).
+    pub quantity: Decimal,
+}
+
+/// Aggregated first- and higher-order Greeks.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PortfolioGreeks {
+    /// Delta, gamma, theta, vega and rho.
+    pub greeks: Greeks,
+    /// Vanna, volga, charm, speed, color and zomma.
+    pub higher_order: HigherOrderGreeks,
+}
+
+impl PortfolioGreeks {
+    /// Converts both sets of Greeks from the crate convention to `units`.
+    pub fn in_units(&self, units: &GreekUnits) -> Result<Self, ArithmeticError> {
+        Ok(Self {
+            greeks: self.greeks.in_units(units)?,
+            higher_order: self.higher_order.in_units(units)?,
+        })
+    }
+}
+
+/// A book of option legs and underlying hedges.
+#[derive(Debug, Clone)]
+pub struct OptionPortfolio<S: Storage = Fixed<MAX_PORTFOLIO_LEGS>> {
+    legs: S::Buffer<OptionLeg>,
+    underlying: Decimal,
+}
+
+impl OptionPortfolio {
+    /// Creates an empty portfolio with default fixed storage.
+    pub fn new() -> Self {
+        Self::with_storage()
+    }
+}
+
+impl Default for OptionPortfolio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Storage> OptionPortfolio<S> {
+    /// Creates an empty portfolio with the chosen storage.
+    pub fn with_storage() -> Self {
+        Self {
+            legs: Default::default(),
+            underlying: Decimal::ZERO,
+        }
+    }
+
+    /// Adds an option leg.
+    ///
+    /// Returns `Overflow` if the storage is full.
+    pub fn add_option(
+        &mut self,
+        params: GeneralizedParams,
+        is_call: bool,
+        quantity: Decimal,
+    ) -> Result<(), ArithmeticError> {
+        self.legs.push(OptionLeg {
+            params,
+            is_call,
+            quantity,
+        })
+    }
+
+    /// Adds signed units of the underlying, each with delta 1.
+    pub fn add_underlying(&mut self, quantity: Decimal) -> Result<(), ArithmeticError> {
+        self.underlying = self.underlying.try_add(quantity)?;
+        Ok(())
+    }
+
+    /// Option legs.
+    pub fn legs(&self) -> &[OptionLeg] {
+        self.legs.as_slice()
+    }
+
+    /// Net units of the underlying held.
+    pub fn underlying(&self) -> Decimal {
+        self.underlying
+    }
+
+    /// Moves every leg to a new spot price.
+    pub fn set_spot(&mut self, spot: Decimal) {
+        for leg in self.legs.as_mut_slice() {
+            leg.params.spot = spot;
+        }
+    }
+
+    /// Sum of quantity-weighted Greeks over all legs and hedges, in the
+    /// crate convention.
+    pub fn greeks(&self) -> Result<PortfolioGreeks, ArithmeticError> {
+        let mut total = PortfolioGreeks::default();
+        total.greeks.delta = self.underlying;
+
+        for leg in self.legs.as_slice() {
+            let q = leg.quantity;
+            let first = generalized_greeks(&leg.params, leg.is_call)?;
+            let higher = higher_order_greeks(&leg.params, leg.is_call)?;
+
+            let g = &mut total.greeks;
+            g.delta = g.delta.try_add(q.try_mul(first.delta)?)?;
+            g.gamma = g.gamma.try_add(q.try_mul(first.gamma)?)?;
+            g.theta = g.theta.try_add(q.try_mul(first.theta)?)?;
+            g.vega = g.vega.try_add(q.try_mul(first.vega)?)?;
+            g.rho = g.rho.try_add(q.try_mul(first.rho)?)?;
+
+            let h = &mut total.higher_order;
+            h.vanna = h.vanna.try_add(q.try_mul(higher.vanna)?)?;
+            h.volga = h.volga.try_add(q.try_mul(higher.volga)?)?;
+            h.charm = h.charm.try_add(q.try_mul(higher.charm)?)?;
+            h.speed = h.speed.try_add(q.try_mul(higher.speed)?)?;
+            h.color = h.color.try_add(q.try_mul(higher.color)?)?;
+            h.zomma = h.zomma.try_add(q.try_mul(higher.zomma)?)?;
+        }
+
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::OptionParams;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn params() -> GeneralizedParams {
+        GeneralizedParams::with_yield(
+            &OptionParams {
+                spot: decimal("100"),
+                strike: decimal("105"),
+                rate: decimal("0.05"),
+                time: decimal("0.5"),
+                volatility: decimal("0.3"),
+            },
+            decimal("0.02"),
+        )
+    }
+
+    fn bumped(params: &GeneralizedParams, field: &str, h: Decimal) -> GeneralizedParams {
+        let mut p = *params;
+        match field {
+            "spot" => p.spot = p.spot + h,
+            "vol" => p.volatility = p.volatility + h,
+            _ => p.time = p.time + h,
+        }
+        p
+    }
+
+    /// Central difference of a first-order Greek, converted to per-point
+    /// or per-day units to match [`HigherOrderGreeks`].
+    fn difference<F>(params: &GeneralizedParams, field: &str, greek: F) -> Decimal
+    where
+        F: Fn(&Greeks) -> Decimal,
+    {
+        let h = decimal("0.0001");
+        let up = greek(&generalized_greeks(&bumped(params, field, h), true).unwrap());
+        let down = greek(&generalized_greeks(&bumped(params, field, -h), true).unwrap());
+        let slope = (up - down) / (h + h);
+        match field {
+            "spot" => slope,
+            "vol" => slope / decimal("100"),
+            // Passage of time shortens expiry
+            _ => -slope / decimal("365"),
+        }
+    }
+
+    #[test]
+    fn test_higher_order_match_finite_differences() {
+        let params = params();
+        let higher = higher_order_greeks(&params, true).unwrap();
+        let close = |a: Decimal, b: Decimal| (a - b).abs() < decimal("0.0000001");
+
+        assert!(close(higher.vanna, difference(&params, "vol", |g| g.delta)));
+        assert!(close(higher.volga, difference(&params, "vol", |g| g.vega)));
+        assert!(close(
+            higher.charm,
+            difference(&params, "time", |g| g.delta)
+        ));
+        assert!(close(
+            higher.speed,
+            difference(&params, "spot", |g| g.gamma)
+        ));
+        assert!(close(
+            higher.color,
+            difference(&params, "time", |g| g.gamma)
+        ));
+        assert!(close(higher.zomma, difference(&params, "vol", |g| g.gamma)));
+    }
+
+    #[test]
+    fn test_put_call_share_second_order() {
+        let params = params();
+        let call = higher_order_greeks(&params, true).unwrap();
+        let put = higher_order_greeks(&params, false).unwrap();
+        assert_eq!(call.vanna, put.vanna);
+        assert_eq!(call.volga, put.volga);
+        assert_eq!(call.speed, put.speed);
+        // Charm differs by the carry on the delta gap
+        assert_ne!(call.charm, put.charm);
+    }
+
+    #[test]
+    fn test_unit_conversion() {
+        let greeks = generalized_greeks(&params(), true).unwrap();
+        let annual = greeks.in_units(&GreekUnits::ANNUAL).unwrap();
+        assert_eq!(annual.theta, greeks.theta * decimal("365"));
+        assert_eq!(annual.vega, greeks.vega * decimal("100"));
+        assert_eq!(annual.rho, greeks.rho * decimal("100"));
+        assert_eq!(annual.delta, greeks.delta);
+
+        let higher = higher_order_greeks(&params(), true).unwrap();
+        let annual = higher.in_units(&GreekUnits::ANNUAL).unwrap();
+        assert_eq!(annual.volga, higher.volga * decimal("10000"));
+        assert_eq!(annual.charm, higher.charm * decimal("365"));
+
+        let same = greeks.in_units(&GreekUnits::default()).unwrap();
+        assert_eq!(same.theta, greeks.theta);
+    }
+
+    #[test]
+    fn test_portfolio_aggregation() {
+        let params = params();
+        let mut book = OptionPortfolio::new();
+        book.add_option(params, true, decimal("2")).unwrap();
+        book.add_option(params, false, decimal("-3")).unwrap();
+        book.add_underlying(decimal("-0.5")).unwrap();
+
+        let call = generalized_greeks(&params, true).unwrap();
+        let put = generalized_greeks(&params, false).unwrap();
+        let total = book.greeks().unwrap();
+        let close = |a: Decimal, b: Decimal| (a - b).abs() < decimal("0.0000000001");
+        assert!(close(
+            total.greeks.delta,
+            decimal("2") * call.delta - decimal("3") * put.delta - decimal("0.5")
+        ));
+        assert!(close(
+            total.greeks.vega,
+            decimal("2") * call.vega - decimal("3") * put.vega
+        ));
+
+        let call_higher = higher_order_greeks(&params, true).unwrap();
+        assert!(close(total.higher_order.zomma, -call_higher.zomma));
+
+        // Repricing at a new spot moves every leg
+        book.set_spot(decimal("120"));
+        // Net short gamma: delta falls as spot rises
+        assert!(book.greeks().unwrap().greeks.delta < total.greeks.delta);
+    }
+}
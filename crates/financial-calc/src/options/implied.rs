@@ -0,0 +1,742 @@
+//! Black-Scholes implied volatility with guaranteed convergence.
+//!
+//! The price is first checked against the no-arbitrage bounds
+//!
+//! - Call: max(S − K e^(-rT), 0) < C < S
+//! - Put: max(K e^(-rT) − S, 0) < P < K e^(-rT)
+//!
+//! and a volatility bracket is built around the root. Newton iterations
+//! start from Jäckel's closed-form initial guess and shrink the
+//! bracket as they go; once a step leaves the bracket or stops halving the
+//! error (tiny vega on deep out-of-the-money or short-dated options), the
+//! remaining bracket is handed to [`brent`].
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::options::{
+//!     black_scholes_call, solve_implied_volatility, ImpliedVolError, OptionParams,
+//! };
+//! use precision_core::Decimal;
+//!
+//! let params = OptionParams {
+//!     spot: Decimal::from(100i64),
+//!     strike: Decimal::from(150i64),
+//!     rate: Decimal::new(5, 2),
+//!     time: Decimal::new(2, 2),
+//!     volatility: Decimal::new(8, 1),
+//! };
+//! let price = black_scholes_call(&params).unwrap();
+//!
+//! let result = solve_implied_volatility(price, &params, true, None, None).unwrap();
+//! assert!(result.converged);
+//!
+//! // A call cannot be worth more than the underlying
+//! let err = solve_implied_volatility(Decimal::from(101i64), &params, true, None, None);
+//! assert_eq!(err.unwrap_err(), ImpliedVolError::AboveMaximum);
+//! ```
+
+use super::{
+    black_scholes_call, black_scholes_put, calculate_d1_d2, normal_cdf, normal_pdf, OptionParams,
+};
+use crate::monte_carlo::inverse_normal_cdf;
+use crate::solver::{brent, default_tolerance, SolverResult, DEFAULT_MAX_ITER};
+use core::fmt;
+use precision_core::{ArithmeticError, Decimal};
+
+/// Error returned when implied volatility cannot be solved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImpliedVolError {
+    /// Price is at or below the option's discounted intrinsic value.
+    BelowIntrinsic,
+    /// Price is at or above the most the option can be worth.
+    AboveMaximum,
+    /// The price error did not reach the tolerance within the iteration
+    /// budget.
+    NotConverged,
+    /// Invalid parameters or an arithmetic failure while solving.
+    Arithmetic(ArithmeticError),
+}
+
+impl fmt::Display for ImpliedVolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BelowIntrinsic => write!(f, "price below intrinsic value"),
+            Self::AboveMaximum => write!(f, "price above no-arbitrage maximum"),
+            Self::NotConverged => write!(f, "implied volatility did not converge"),
+            Self::Arithmetic(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl From<ArithmeticError> for ImpliedVolError {
+    fn from(err: ArithmeticError) -> Self {
+        Self::Arithmetic(err)
+    }
+}
+
+/// Solves for Black-Scholes implied volatility.
+///
+/// `params.volatility` is ignored. `tolerance` applies to the price error
+/// (default 1e-12) and `max_iter` bounds Newton and Brent iterations
+/// together (default [`DEFAULT_MAX_ITER`]).
+///
+/// Returns the volatility as `root` with iteration diagnostics, or an
+/// [`ImpliedVolError`] if the price violates no-arbitrage bounds or the
+/// budget runs out first. A returned result has always converged.
+pub fn solve_implied_volatility(
+    market_price: Decimal,
+    params: &OptionParams,
+    is_call: bool,
+    tolerance: Option<Decimal>,
+    max_iter: Option<u32>,
+) -> Result<SolverResult, ImpliedVolError> {
+    if params.spot <= Decimal::ZERO || params.strike <= Decimal::ZERO {
+        return Err(ArithmeticError::LogOfNegative.into());
+    }
+    if params.time <= Decimal::ZERO {
+        return Err(ArithmeticError::NegativeSqrt.into());
+    }
+    let tol = tolerance.unwrap_or_else(default_tolerance);
+    let max = max_iter.unwrap_or(DEFAULT_MAX_ITER);
+
+    let strike_value = params
+        .strike
+        .try_mul((-params.rate.try_mul(params.time)?).try_exp()?)?;
+    let (floor, ceiling) = if is_call {
+        (params.spot.try_sub(strike_value)?, params.spot)
+    } else {
+        (strike_value.try_sub(params.spot)?, strike_value)
+    };
+    if market_price <= floor.max(Decimal::ZERO) {
+        return Err(ImpliedVolError::BelowIntrinsic);
+    }
+    if market_price >= ceiling {
+        return Err(ImpliedVolError::AboveMaximum);
+    }
+
+    let error_at = |volatility: Decimal| -> Result<Decimal, ArithmeticError> {
+        let trial = OptionParams {
+            volatility,
+            ..*params
+        };
+        let price = if is_call {
+            black_scholes_call(&trial)?
+        } else {
+            black_scholes_put(&trial)?
+        };
+        price.try_sub(market_price)
+    };
+
+    // Bracket: model price is increasing in volatility
+    let mut low = Decimal::new(1, 8);
+    let low_error = error_at(low)?;
+    if !low_error.is_negative() {
+        if low_error.abs() >= tol {
+            return Err(ImpliedVolError::NotConverged);
+        }
+        return Ok(SolverResult {
+            root: low,
+            iterations: 0,
+            residual: low_error,
+            converged: true,
+        });
+    }
+    // The guess only steers Newton, so fall back to a unit volatility when
+    // its asymptotic maps leave the representable range
+    let seed = jaeckel_guess(market_price, params, is_call, strike_value).unwrap_or(Decimal::ONE);
+    let mut high = seed.max(Decimal::ONE);
+    let mut iterations = 0;
+    while error_at(high)?.is_negative() {
+        iterations += 1;
+        high = high.try_mul(Decimal::from(2i64))?;
+        if iterations >= max {
+            return Err(ImpliedVolError::NotConverged);
+        }
+    }
+
+    // Safeguarded Newton
+    let mut sigma = if seed > low && seed < high {
+        seed
+    } else {
+        low.try_add(high)?.try_div(Decimal::from(2i64))?
+    };
+    let mut previous = Decimal::MAX;
+    while iterations < max {
+        iterations += 1;
+        let error = error_at(sigma)?;
+        if error.abs() < tol {
+            return Ok(SolverResult {
+                root: sigma,
+                iterations,
+                residual: error,
+                converged: true,
+            });
+        }
+        if error.is_negative() {
+            low = sigma;
+        } else {
+            high = sigma;
+        }
+
+        let vega = vega(params, sigma)?;
+        if vega.is_zero() || error.abs() > previous.try_div(Decimal::from(2i64))? {
+            break;
+        }
+        let next = sigma.try_sub(error.try_div(vega)?)?;
+        if next <= low || next >= high {
+            break;
+        }
+        previous = error.abs();
+        sigma = next;
+    }
+
+    // Newton used the whole budget without converging
+    if iterations >= max {
+        return Err(ImpliedVolError::NotConverged);
+    }
+    let mut result = brent(error_at, low, high, Some(tol), Some(max - iterations))?;
+    if !result.converged {
+        return Err(ImpliedVolError::NotConverged);
+    }
+    result.iterations += iterations;
+    Ok(result)
+}
+
+/// Jäckel's initial guess from "Let's Be Rational" (2015).
+///
+/// Works on the normalised out-of-the-money call
+/// b(x, s) = e^(x/2) N(x/s + s/2) − e^(-x/2) N(x/s − s/2) with
+/// x = ln(S/X) ≤ 0, X = K e^(-rT) and s = σ√T. The price axis is split at
+/// the inflection point s_c = √(2|x|) and at the points s_l, s_h where its
+/// tangent meets b = 0 and b = e^(x/2). The two middle segments interpolate
+/// s directly; the outer ones interpolate the asymptotic maps
+///
+/// - f_l(s) = 2π|x| / √27 × N(−|x| / (√3 s))³ as s → 0
+/// - f_h(s) = N(−s/2) as s → ∞
+///
+/// and invert them. Every segment uses a rational cubic, which keeps the
+/// guess monotone and within a few percent of the root.
+fn jaeckel_guess(
+    market_price: Decimal,
+    params: &OptionParams,
+    is_call: bool,
+    strike_value: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let two = Decimal::from(2i64);
+    // Put-call parity gives the matching call price
+    let call = if is_call {
+        market_price
+    } else {
+        market_price.try_add(params.spot.try_sub(strike_value)?)?
+    };
+    let mut x = params.spot.try_div(strike_value)?.try_ln()?;
+    let mut beta = call.try_div(params.spot.try_mul(strike_value)?.try_sqrt()?)?;
+    if x.is_positive() {
+        // In the money: b(x) − b(−x) = e^(x/2) − e^(-x/2)
+        let half = x.try_div(two)?;
+        beta = beta.try_sub(half.try_exp()?.try_sub((-half).try_exp()?)?)?;
+        x = -x;
+    }
+    let sqrt_t = params.time.try_sqrt()?;
+    if x.is_zero() {
+        // At the money b = 2 N(s/2) − 1
+        let tail = Decimal::ONE.try_sub(beta)?.try_div(two)?;
+        return (-two.try_mul(inverse_normal_cdf(tail)?)?).try_div(sqrt_t);
+    }
+
+    let b_max = x.try_div(two)?.try_exp()?;
+    let s_c = two.try_mul(x.abs())?.try_sqrt()?;
+    let b_c = normalised_call(x, s_c)?;
+    let v_c = normalised_vega(x, s_c)?;
+
+    let s = if beta < b_c {
+        let s_l = s_c.try_sub(b_c.try_div(v_c)?)?;
+        let b_l = normalised_call(x, s_l)?;
+        if beta < b_l {
+            let (f_l, d_l, dd_l) = lower_map(x, s_l)?;
+            let r = control_parameter(
+                Decimal::ZERO,
+                b_l,
+                Decimal::ZERO,
+                f_l,
+                Decimal::ONE,
+                d_l,
+                dd_l,
+                Side::Right,
+                true,
+            )?;
+            let f = rational_cubic(
+                beta,
+                Decimal::ZERO,
+                b_l,
+                Decimal::ZERO,
+                f_l,
+                Decimal::ONE,
+                d_l,
+                r,
+            )?;
+            let f = if f.is_positive() {
+                f
+            } else {
+                // Roundoff far below b_l: quadratic with f(0) = 0, f'(0) = 1
+                let t = beta.try_div(b_l)?;
+                f_l.try_mul(t)?
+                    .try_add(b_l.try_mul(Decimal::ONE.try_sub(t)?)?)?
+                    .try_mul(t)?
+            };
+            inverse_lower_map(x, f)?
+        } else {
+            let d_l = Decimal::ONE.try_div(normalised_vega(x, s_l)?)?;
+            let d_c = Decimal::ONE.try_div(v_c)?;
+            let r = control_parameter(
+                b_l,
+                b_c,
+                s_l,
+                s_c,
+                d_l,
+                d_c,
+                Decimal::ZERO,
+                Side::Right,
+                false,
+            )?;
+            rational_cubic(beta, b_l, b_c, s_l, s_c, d_l, d_c, r)?
+        }
+    } else {
+        let s_h = s_c.try_add(b_max.try_sub(b_c)?.try_div(v_c)?)?;
+        let b_h = normalised_call(x, s_h)?;
+        if beta <= b_h {
+            let d_c = Decimal::ONE.try_div(v_c)?;
+            let d_h = Decimal::ONE.try_div(normalised_vega(x, s_h)?)?;
+            let r = control_parameter(
+                b_c,
+                b_h,
+                s_c,
+                s_h,
+                d_c,
+                d_h,
+                Decimal::ZERO,
+                Side::Left,
+                false,
+            )?;
+            rational_cubic(beta, b_c, b_h, s_c, s_h, d_c, d_h, r)?
+        } else {
+            let (f_h, d_h, dd_h) = upper_map(x, s_h)?;
+            let d_max = Decimal::new(-5, 1);
+            let r = control_parameter(
+                b_h,
+                b_max,
+                f_h,
+                Decimal::ZERO,
+                d_h,
+                d_max,
+                dd_h,
+                Side::Left,
+                true,
+            )?;
+            let f = rational_cubic(beta, b_h, b_max, f_h, Decimal::ZERO, d_h, d_max, r)?;
+            let f = if f.is_positive() {
+                f
+            } else {
+                // Roundoff next to b_max: quadratic with f(b_max) = 0, f'(b_max) = −1/2
+                let h = b_max.try_sub(b_h)?;
+                let omt = b_max.try_sub(beta)?.try_div(h)?;
+                f_h.try_mul(omt)?
+                    .try_add(
+                        h.try_mul(Decimal::ONE.try_sub(omt)?)?
+                            .try_div(Decimal::from(2i64))?,
+                    )?
+                    .try_mul(omt)?
+            };
+            -two.try_mul(inverse_normal_cdf(f)?)?
+        }
+    };
+    s.try_div(sqrt_t)
+}
+
+/// Normalised call price b(x, s).
+fn normalised_call(x: Decimal, s: Decimal) -> Result<Decimal, ArithmeticError> {
+    let two = Decimal::from(2i64);
+    let half = x.try_div(two)?;
+    let z = x.try_div(s)?;
+    let h = s.try_div(two)?;
+    half.try_exp()?
+        .try_mul(normal_cdf(z.try_add(h)?)?)?
+        .try_sub((-half).try_exp()?.try_mul(normal_cdf(z.try_sub(h)?)?)?)
+}
+
+/// ∂b/∂s = exp(−(x²/s² + s²/4) / 2) / √(2π)
+fn normalised_vega(x: Decimal, s: Decimal) -> Result<Decimal, ArithmeticError> {
+    let z = x.try_div(s)?;
+    let h = s.try_div(Decimal::from(2i64))?;
+    normal_pdf(z.try_mul(z)?.try_add(h.try_mul(h)?)?.try_sqrt()?)
+}
+
+/// f_l and its first two derivatives with respect to b at s.
+fn lower_map(x: Decimal, s: Decimal) -> Result<(Decimal, Decimal, Decimal), ArithmeticError> {
+    let two_pi = Decimal::from(2i64).try_mul(Decimal::pi())?;
+    let sqrt_three = Decimal::from(3i64).try_sqrt()?;
+    let z = x.abs().try_div(sqrt_three.try_mul(s)?)?;
+    let y = z.try_mul(z)?;
+    let s2 = s.try_mul(s)?;
+    let cdf = normal_cdf(-z)?;
+    let cdf2 = cdf.try_mul(cdf)?;
+
+    let f = two_pi
+        .try_div(Decimal::from(27i64).try_sqrt()?)?
+        .try_mul(x.abs())?
+        .try_mul(cdf2.try_mul(cdf)?)?;
+    let d = two_pi
+        .try_mul(y)?
+        .try_mul(cdf2)?
+        .try_mul(y.try_add(s2.try_div(Decimal::from(8i64))?)?.try_exp()?)?;
+    // π/6 y/s³ N (8√3 s|x| + (3s²(s² − 8) − 8x²) N/n) exp(2y + s²/4)
+    let bracket = Decimal::from(8i64)
+        .try_mul(sqrt_three)?
+        .try_mul(s)?
+        .try_mul(x.abs())?
+        .try_add(
+            Decimal::from(3i64)
+                .try_mul(s2)?
+                .try_mul(s2.try_sub(Decimal::from(8i64))?)?
+                .try_sub(Decimal::from(8i64).try_mul(x.try_mul(x)?)?)?
+                .try_mul(cdf.try_div(normal_pdf(z)?)?)?,
+        )?;
+    let dd = Decimal::pi()
+        .try_div(Decimal::from(6i64))?
+        .try_mul(y)?
+        .try_div(s2.try_mul(s)?)?
+        .try_mul(cdf)?
+        .try_mul(bracket)?
+        .try_mul(
+            Decimal::from(2i64)
+                .try_mul(y)?
+                .try_add(s2.try_div(Decimal::from(4i64))?)?
+                .try_exp()?,
+        )?;
+    Ok((f, d, dd))
+}
+
+/// s such that f_l(s) = f.
+fn inverse_lower_map(x: Decimal, f: Decimal) -> Result<Decimal, ArithmeticError> {
+    let scale = Decimal::from(2i64)
+        .try_mul(Decimal::pi())?
+        .try_div(Decimal::from(27i64).try_sqrt()?)?
+        .try_mul(x.abs())?;
+    let cube_root = f
+        .try_div(scale)?
+        .try_ln()?
+        .try_div(Decimal::from(3i64))?
+        .try_exp()?;
+    x.abs().try_div(
+        Decimal::from(3i64)
+            .try_sqrt()?
+            .try_mul(inverse_normal_cdf(cube_root)?.abs())?,
+    )
+}
+
+/// f_h and its first two derivatives with respect to b at s.
+fn upper_map(x: Decimal, s: Decimal) -> Result<(Decimal, Decimal, Decimal), ArithmeticError> {
+    let two = Decimal::from(2i64);
+    let w = x.try_div(s)?.try_mul(x.try_div(s)?)?;
+    let f = normal_cdf(-s.try_div(two)?)?;
+    let d = Decimal::new(-5, 1).try_mul(w.try_div(two)?.try_exp()?)?;
+    let dd = Decimal::pi()
+        .try_div(two)?
+        .try_sqrt()?
+        .try_mul(
+            w.try_add(s.try_mul(s)?.try_div(Decimal::from(8i64))?)?
+                .try_exp()?,
+        )?
+        .try_mul(w)?
+        .try_div(s)?;
+    Ok((f, d, dd))
+}
+
+/// End of a rational cubic segment whose second derivative is matched.
+#[derive(Clone, Copy)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// Control parameters at or above this reduce the rational cubic to a line.
+fn linear_control() -> Decimal {
+    Decimal::from(1_000_000_000_000_000_000i64)
+}
+
+/// Delbourgo-Gregory rational cubic through (x_l, y_l) and (x_r, y_r) with
+/// end slopes d_l and d_r; r = 3 gives the Hermite cubic.
+#[allow(clippy::too_many_arguments)]
+fn rational_cubic(
+    x: Decimal,
+    x_l: Decimal,
+    x_r: Decimal,
+    y_l: Decimal,
+    y_r: Decimal,
+    d_l: Decimal,
+    d_r: Decimal,
+    r: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    let h = x_r.try_sub(x_l)?;
+    let t = x.try_sub(x_l)?.try_div(h)?;
+    let omt = Decimal::ONE.try_sub(t)?;
+    if r >= linear_control() {
+        return y_r.try_mul(t)?.try_add(y_l.try_mul(omt)?);
+    }
+    let t2 = t.try_mul(t)?;
+    let omt2 = omt.try_mul(omt)?;
+    let numerator = y_r
+        .try_mul(t2.try_mul(t)?)?
+        .try_add(
+            r.try_mul(y_r)?
+                .try_sub(h.try_mul(d_r)?)?
+                .try_mul(t2.try_mul(omt)?)?,
+        )?
+        .try_add(
+            r.try_mul(y_l)?
+                .try_add(h.try_mul(d_l)?)?
+                .try_mul(t.try_mul(omt2)?)?,
+        )?
+        .try_add(y_l.try_mul(omt2.try_mul(omt)?)?)?;
+    let denominator =
+        Decimal::ONE.try_add(r.try_sub(Decimal::from(3i64))?.try_mul(t.try_mul(omt)?)?)?;
+    numerator.try_div(denominator)
+}
+
+/// Control parameter matching the second derivative at one end, raised to
+/// the minimum that keeps the segment monotone and convex or concave.
+#[allow(clippy::too_many_arguments)]
+fn control_parameter(
+    x_l: Decimal,
+    x_r: Decimal,
+    y_l: Decimal,
+    y_r: Decimal,
+    d_l: Decimal,
+    d_r: Decimal,
+    second_derivative: Decimal,
+    side: Side,
+    prefer_shape: bool,
+) -> Result<Decimal, ArithmeticError> {
+    let h = x_r.try_sub(x_l)?;
+    let slope = y_r.try_sub(y_l)?.try_div(h)?;
+    let numerator = h
+        .try_mul(second_derivative)?
+        .try_div(Decimal::from(2i64))?
+        .try_add(d_r.try_sub(d_l)?)?;
+    let denominator = match side {
+        Side::Left => slope.try_sub(d_l)?,
+        Side::Right => d_r.try_sub(slope)?,
+    };
+    let fitted = if numerator.is_zero() {
+        Decimal::ZERO
+    } else if denominator.is_zero() {
+        if numerator.is_positive() {
+            linear_control()
+        } else {
+            minimum_control()
+        }
+    } else {
+        numerator.try_div(denominator)?
+    };
+    Ok(fitted.max(shape_control(d_l, d_r, slope, prefer_shape)?))
+}
+
+fn minimum_control() -> Decimal {
+    Decimal::new(-99999999, 8)
+}
+
+/// Smallest control parameter preserving monotonicity and convexity.
+fn shape_control(
+    d_l: Decimal,
+    d_r: Decimal,
+    slope: Decimal,
+    prefer_shape: bool,
+) -> Result<Decimal, ArithmeticError> {
+    let monotonic = !d_l.try_mul(slope)?.is_negative() && !d_r.try_mul(slope)?.is_negative();
+    let convex = d_l <= slope && slope <= d_r;
+    let concave = d_l >= slope && slope >= d_r;
+    if !monotonic && !convex && !concave {
+        return Ok(minimum_control());
+    }
+    let mut r1 = minimum_control();
+    let mut r2 = minimum_control();
+    if monotonic {
+        if !slope.is_zero() {
+            r1 = d_r.try_add(d_l)?.try_div(slope)?;
+        } else if prefer_shape {
+            r1 = linear_control();
+        }
+    }
+    if convex || concave {
+        let right = d_r.try_sub(slope)?;
+        let left = slope.try_sub(d_l)?;
+        if !right.is_zero() && !left.is_zero() {
+            let spread = d_r.try_sub(d_l)?;
+            r2 = spread
+                .try_div(right)?
+                .abs()
+                .max(spread.try_div(left)?.abs());
+        } else if prefer_shape {
+            r2 = linear_control();
+        }
+    } else if monotonic && prefer_shape {
+        r2 = linear_control();
+    }
+    Ok(r1.max(r2).max(minimum_control()))
+}
+
+fn vega(params: &OptionParams, volatility: Decimal) -> Result<Decimal, ArithmeticError> {
+    let trial = OptionParams {
+        volatility,
+        ..*params
+    };
+    let (d1, _) = calculate_d1_d2(&trial)?;
+    params
+        .spot
+        .try_mul(params.time.try_sqrt()?)?
+        .try_mul(normal_pdf(d1)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn params(strike: &str, time: &str, volatility: &str) -> OptionParams {
+        OptionParams {
+            spot: decimal("100"),
+            strike: decimal(strike),
+            rate: decimal("0.05"),
+            time: decimal(time),
+            volatility: decimal(volatility),
+        }
+    }
+
+    #[test]
+    fn test_round_trips_across_regimes() {
+        let cases = [
+            ("100", "0.5", "0.2"),
+            ("60", "1", "0.3"),
+            ("150", "0.02", "0.8"),
+            ("130", "0.01", "0.3"),
+            ("100", "2", "3"),
+            ("80", "0.1", "0.05"),
+        ];
+        for (strike, time, volatility) in cases {
+            let params = params(strike, time, volatility);
+            for is_call in [true, false] {
+                let price = if is_call {
+                    black_scholes_call(&params).unwrap()
+                } else {
+                    black_scholes_put(&params).unwrap()
+                };
+                let floor = if is_call {
+                    params.spot - params.strike * (-params.rate * params.time).exp().unwrap()
+                } else {
+                    params.strike * (-params.rate * params.time).exp().unwrap() - params.spot
+                };
+                if price <= floor.max(Decimal::ZERO) + decimal("0.000000001") {
+                    continue;
+                }
+                let result = solve_implied_volatility(price, &params, is_call, None, None).unwrap();
+                assert!(result.converged);
+                assert!(result.residual.abs() < decimal("0.000000000001"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_deep_otm_short_dated_recovers_volatility() {
+        let params = params("130", "0.02", "0.9");
+        let price = black_scholes_call(&params).unwrap();
+        let result = solve_implied_volatility(price, &params, true, None, None).unwrap();
+        assert!(result.converged);
+        assert!((result.root - params.volatility).abs() < decimal("0.0001"));
+    }
+
+    #[test]
+    fn test_jaeckel_guess_is_close() {
+        // Exact at the money forward up to the accuracy of normal_cdf, and
+        // within a few percent elsewhere
+        let atm = OptionParams {
+            strike: decimal("100") * (decimal("0.05") * decimal("0.5")).exp().unwrap(),
+            ..params("100", "0.5", "0.2")
+        };
+        let strike_value = atm.strike * (-atm.rate * atm.time).exp().unwrap();
+        let price = black_scholes_call(&atm).unwrap();
+        let guess = jaeckel_guess(price, &atm, true, strike_value).unwrap();
+        assert!((guess - atm.volatility).abs() < decimal("0.00001"));
+
+        for (strike, time, volatility) in [
+            ("60", "1", "0.3"),
+            ("150", "0.02", "0.8"),
+            ("120", "0.25", "0.15"),
+            ("100", "2", "3"),
+            ("90", "0.5", "0.1"),
+        ] {
+            let params = params(strike, time, volatility);
+            let strike_value = params.strike * (-params.rate * params.time).exp().unwrap();
+            for is_call in [true, false] {
+                let price = if is_call {
+                    black_scholes_call(&params).unwrap()
+                } else {
+                    black_scholes_put(&params).unwrap()
+                };
+                let guess = jaeckel_guess(price, &params, is_call, strike_value).unwrap();
+                assert!(
+                    (guess / params.volatility - Decimal::ONE).abs() < decimal("0.1"),
+                    "{:?} {:?}",
+                    guess,
+                    params.volatility
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_exhausted_budget_is_not_converged() {
+        let params = params("150", "0.02", "0.8");
+        let price = black_scholes_call(&params).unwrap();
+        assert_eq!(
+            solve_implied_volatility(price, &params, true, None, Some(1)).unwrap_err(),
+            ImpliedVolError::NotConverged
+        );
+        let result = solve_implied_volatility(price, &params, true, None, None).unwrap();
+        assert!(result.converged);
+    }
+
+    #[test]
+    fn test_no_arbitrage_bounds() {
+        let params = params("90", "0.5", "0.2");
+        assert_eq!(
+            solve_implied_volatility(decimal("5"), &params, true, None, None).unwrap_err(),
+            ImpliedVolError::BelowIntrinsic
+        );
+        assert_eq!(
+            solve_implied_volatility(decimal("100"), &params, true, None, None).unwrap_err(),
+            ImpliedVolError::AboveMaximum
+        );
+        assert_eq!(
+            solve_implied_volatility(decimal("90"), &params, false, None, None).unwrap_err(),
+            ImpliedVolError::AboveMaximum
+        );
+        assert_eq!(
+            solve_implied_volatility(Decimal::ZERO, &params, false, None, None).unwrap_err(),
+            ImpliedVolError::BelowIntrinsic
+        );
+
+        let expired = OptionParams {
+            time: Decimal::ZERO,
+            ..params
+        };
+        assert_eq!(
+            solve_implied_volatility(decimal("12"), &expired, true, None, None).unwrap_err(),
+            ImpliedVolError::Arithmetic(ArithmeticError::NegativeSqrt)
+        );
+    }
+}
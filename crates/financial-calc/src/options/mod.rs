@@ -21,9 +21,53 @@
 //!
 //! let call_price = black_scholes_call(&params).unwrap();
 //! ```
+//!
+//! Dividend and FX (Garman-Kohlhagen) options use [`GeneralizedParams`],
+//! options on futures and perps use [`Black76Params`], and rates or spreads
+//! that can go negative use the Bachelier model with [`BachelierParams`].
+//! American exercise is priced on a binomial or trinomial [`Lattice`] or
+//! with the [`barone_adesi_whaley`] approximation. Digital, barrier and
+//! Asian payoffs are priced in closed form; see [`barrier_price`] and
+//! [`arithmetic_asian_price`]. [`solve_implied_volatility`] inverts
+//! Black-Scholes prices with guaranteed convergence, and
+//! [`OptionPortfolio`] aggregates first- and higher-order Greeks.
+//! Strike-dependent volatility comes from [`Svi`] and [`Sabr`] smiles
+//! stacked into a [`VolatilitySurface`]. [`OptionStrategy`] builds
+//! spreads, straddles, condors and collars and analyses their payoff.
 
 use precision_core::{ArithmeticError, Decimal};
 
+mod american;
+mod exotic;
+mod greeks;
+mod implied;
+mod models;
+mod smile;
+mod strategy;
+
+pub use american::{
+    barone_adesi_whaley, ExerciseStyle, Lattice, LatticeMethod, MAX_LATTICE_STEPS,
+};
+pub use exotic::{
+    arithmetic_asian_price, asset_or_nothing_price, barrier_price, cash_or_nothing_price,
+    finite_difference_greeks, geometric_asian_price, Barrier, BarrierKind, RealizedAverage,
+};
+pub use greeks::{
+    higher_order_greeks, GreekUnits, HigherOrderGreeks, OptionLeg, OptionPortfolio,
+    PortfolioGreeks, ShiftUnit, TimeUnit, MAX_PORTFOLIO_LEGS,
+};
+pub use implied::{solve_implied_volatility, ImpliedVolError};
+pub use models::{
+    bachelier_greeks, bachelier_implied_volatility, bachelier_price, black_76_greeks,
+    black_76_implied_volatility, black_76_price, generalized_greeks, generalized_implied_volatility,
+    generalized_price, BachelierParams, Black76Params, GeneralizedParams,
+};
+pub use smile::{
+    butterfly_arbitrage, calendar_arbitrage, NaturalSvi, Sabr, Smile, SmileQuote, SmileSlice, Svi,
+    VolatilitySurface, MAX_FIT_QUOTES, MAX_SMILE_SLICES,
+};
+pub use strategy::OptionStrategy;
+
 /// Parameters for Black-Scholes option pricing.
 #[derive(Debug, Clone, Copy)]
 pub struct OptionParams {
@@ -40,7 +84,7 @@ pub struct OptionParams {
 }
 
 /// Greeks for an option position.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct Greeks {
     /// Rate of change of option price with respect to underlying price.
     pub delta: Decimal,
@@ -341,19 +385,23 @@ pub fn put_greeks(params: &OptionParams) -> Result<Greeks, ArithmeticError> {
     })
 }
 
-/// Calculates implied volatility using Newton-Raphson iteration.
+/// Calculates implied volatility.
 ///
 /// # Arguments
 ///
 /// * `market_price` - The observed market price of the option
-/// * `params` - Option parameters (volatility field is ignored as initial guess)
+/// * `params` - Option parameters (volatility field is ignored)
 /// * `is_call` - True for call option, false for put
 /// * `max_iterations` - Maximum number of iterations (default: 100)
-/// * `tolerance` - Convergence tolerance (default: 0.0001)
+/// * `tolerance` - Convergence tolerance on price (default: 0.0001)
 ///
 /// # Returns
 ///
-/// The implied volatility as a decimal (e.g., 0.20 for 20%).
+/// The implied volatility as a decimal (e.g., 0.20 for 20%). Prices at or
+/// below intrinsic value return `Underflow`, prices at or above the
+/// no-arbitrage maximum return `Overflow` and running out of iterations
+/// returns `DivisionByZero`; use [`solve_implied_volatility`] for a typed
+/// error and convergence diagnostics.
 pub fn implied_volatility(
     market_price: Decimal,
     params: &OptionParams,
@@ -361,58 +409,14 @@ pub fn implied_volatility(
     max_iterations: Option<u32>,
     tolerance: Option<Decimal>,
 ) -> Result<Decimal, ArithmeticError> {
-    let max_iter = max_iterations.unwrap_or(100);
     let tol = tolerance.unwrap_or_else(|| parse_const("0.0001"));
-
-    // Initial guess using Brenner-Subrahmanyam approximation
-    // σ ≈ √(2π/T) * (C/S)
-    let two_pi = Decimal::pi().try_mul(Decimal::from(2i64))?;
-    let sqrt_two_pi_over_t = two_pi.try_div(params.time)?.try_sqrt()?;
-    let mut sigma = sqrt_two_pi_over_t.try_mul(market_price.try_div(params.spot)?)?;
-
-    // Clamp initial guess to reasonable range
-    let min_vol = parse_const("0.01");
-    let max_vol = parse_const("5.0");
-    sigma = sigma.max(min_vol).min(max_vol);
-
-    // Newton-Raphson iteration
-    for _ in 0..max_iter {
-        let mut iter_params = *params;
-        iter_params.volatility = sigma;
-
-        let price = if is_call {
-            black_scholes_call(&iter_params)?
-        } else {
-            black_scholes_put(&iter_params)?
-        };
-
-        let diff = price.try_sub(market_price)?;
-
-        // Check convergence
-        if diff.abs() < tol {
-            return Ok(sigma);
-        }
-
-        // Vega = ∂C/∂σ = S * √T * N'(d1)
-        let (d1, _) = calculate_d1_d2(&iter_params)?;
-        let sqrt_t = params.time.try_sqrt()?;
-        let n_prime_d1 = normal_pdf(d1)?;
-        let vega = params.spot.try_mul(sqrt_t)?.try_mul(n_prime_d1)?;
-
-        // Avoid division by zero
-        if vega.abs() < parse_const("0.00000001") {
-            break;
-        }
-
-        // Newton-Raphson update: σ_new = σ - (C(σ) - C_market) / vega
-        let adjustment = diff.try_div(vega)?;
-        sigma = sigma.try_sub(adjustment)?;
-
-        // Keep sigma in valid range
-        sigma = sigma.max(min_vol).min(max_vol);
+    match solve_implied_volatility(market_price, params, is_call, Some(tol), max_iterations) {
+        Ok(result) => Ok(result.root),
+        Err(ImpliedVolError::BelowIntrinsic) => Err(ArithmeticError::Underflow),
+        Err(ImpliedVolError::AboveMaximum) => Err(ArithmeticError::Overflow),
+        Err(ImpliedVolError::NotConverged) => Err(ArithmeticError::DivisionByZero),
+        Err(ImpliedVolError::Arithmetic(err)) => Err(err),
     }
-
-    Ok(sigma)
 }
 
 fn validate_params(params: &OptionParams) -> Result<(), ArithmeticError> {
@@ -567,5 +571,18 @@ mod tests {
         let iv = implied_volatility(price, &params, true, None, None).unwrap();
 
         assert!((iv - true_vol).abs() < decimal("0.001"));
+
+        // A budget too small to converge is an error, not a stale estimate
+        let deep = OptionParams {
+            strike: Decimal::from(150i64),
+            time: decimal("0.02"),
+            volatility: decimal("0.8"),
+            ..params
+        };
+        let price = black_scholes_call(&deep).unwrap();
+        assert_eq!(
+            implied_volatility(price, &deep, true, Some(1), Some(decimal("0.000000000001"))),
+            Err(ArithmeticError::DivisionByZero)
+        );
     }
 }
@@ -0,0 +1,666 @@
+//! Cost-of-carry, futures and normal option pricing models.
+//!
+//! - [`GeneralizedParams`] - Generalised Black-Scholes with a continuous
+//!   yield (dividend yield, foreign rate for Garman-Kohlhagen FX options,
+//!   or borrow cost), cost of carry b = r − q
+//! - [`Black76Params`] - Black-76 on a forward, futures or perp mark price
+//! - [`BachelierParams`] - Normal model for rates and spreads that can be
+//!   negative; volatility is absolute (e.g., 0.01 for 100bp)
+//!
+//! Each model has a price, analytical Greeks (theta per day, vega and rho
+//! per 1% move, as in [`call_greeks`](super::call_greeks)) and implied
+//! volatility.
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::options::{black_76_price, black_76_implied_volatility, Black76Params};
+//! use precision_core::Decimal;
+//!
+//! // Call on a perp marked at 2000
+//! let params = Black76Params {
+//!     forward: Decimal::from(2000i64),
+//!     strike: Decimal::from(2100i64),
+//!     rate: Decimal::new(5, 2),
+//!     time: Decimal::new(25, 2),
+//!     volatility: Decimal::new(6, 1),
+//! };
+//!
+//! let price = black_76_price(&params, true).unwrap();
+//! let vol = black_76_implied_volatility(price, &params, true).unwrap();
+//! assert!((vol - params.volatility).abs() < Decimal::new(1, 6));
+//! ```
+
+use super::{normal_cdf, normal_pdf, parse_const, Greeks, OptionParams};
+use crate::solver::brent;
+use precision_core::{ArithmeticError, Decimal};
+
+/// Parameters for the cost-of-carry generalised Black-Scholes model.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GeneralizedParams {
+    /// Current price of the underlying asset.
+    pub spot: Decimal,
+    /// Strike price of the option.
+    pub strike: Decimal,
+    /// Risk-free interest rate (annualized, continuous).
+    pub rate: Decimal,
+    /// Continuous yield earned by holding the underlying: dividend yield,
+    /// foreign interest rate, or minus the borrow cost.
+    pub yield_rate: Decimal,
+    /// Time to expiration in years.
+    pub time: Decimal,
+    /// Volatility (annualized).
+    pub volatility: Decimal,
+}
+
+impl GeneralizedParams {
+    /// Adds a continuous dividend (or other) yield to Black-Scholes parameters.
+    pub fn with_yield(params: &OptionParams, yield_rate: Decimal) -> Self {
+        Self {
+            spot: params.spot,
+            strike: params.strike,
+            rate: params.rate,
+            yield_rate,
+            time: params.time,
+            volatility: params.volatility,
+        }
+    }
+
+    /// Garman-Kohlhagen FX option parameters: spot is the exchange rate in
+    /// domestic per foreign units and `rate` the domestic rate.
+    pub fn garman_kohlhagen(params: &OptionParams, foreign_rate: Decimal) -> Self {
+        Self::with_yield(params, foreign_rate)
+    }
+
+    /// Cost of carry b = rate − yield.
+    pub fn cost_of_carry(&self) -> Result<Decimal, ArithmeticError> {
+        self.rate.try_sub(self.yield_rate)
+    }
+}
+
+/// Parameters for Black-76 options on a forward, future or perp.
+#[derive(Debug, Clone, Copy)]
+pub struct Black76Params {
+    /// Forward, futures or perp mark price.
+    pub forward: Decimal,
+    /// Strike price of the option.
+    pub strike: Decimal,
+    /// Discount rate (annualized, continuous).
+    pub rate: Decimal,
+    /// Time to expiration in years.
+    pub time: Decimal,
+    /// Lognormal volatility (annualized).
+    pub volatility: Decimal,
+}
+
+/// Parameters for the Bachelier (normal) model.
+#[derive(Debug, Clone, Copy)]
+pub struct BachelierParams {
+    /// Forward value; may be zero or negative.
+    pub forward: Decimal,
+    /// Strike; may be zero or negative.
+    pub strike: Decimal,
+    /// Discount rate (annualized, continuous).
+    pub rate: Decimal,
+    /// Time to expiration in years.
+    pub time: Decimal,
+    /// Normal volatility in absolute units per year (e.g., 0.01 for 100bp).
+    pub volatility: Decimal,
+}
+
+/// Generalised Black-Scholes price.
+///
+/// C = S e^(-qT) N(d1) - K e^(-rT) N(d2)
+/// P = K e^(-rT) N(-d2) - S e^(-qT) N(-d1)
+pub fn generalized_price(
+    params: &GeneralizedParams,
+    is_call: bool,
+) -> Result<Decimal, ArithmeticError> {
+    Ok(generalized(params, is_call)?.0)
+}
+
+/// Generalised Black-Scholes Greeks. Rho is sensitivity to the rate with
+/// the yield held fixed.
+pub fn generalized_greeks(
+    params: &GeneralizedParams,
+    is_call: bool,
+) -> Result<Greeks, ArithmeticError> {
+    Ok(generalized(params, is_call)?.1)
+}
+
+/// Implied volatility under the generalised Black-Scholes model.
+///
+/// Returns error if the price is outside the model's arbitrage bounds or
+/// the solver does not converge.
+pub fn generalized_implied_volatility(
+    market_price: Decimal,
+    params: &GeneralizedParams,
+    is_call: bool,
+) -> Result<Decimal, ArithmeticError> {
+    implied_from_price(
+        market_price,
+        min_volatility(),
+        max_volatility(),
+        IMPLIED_MAX_ITER,
+        |volatility| {
+            generalized_price(
+                &GeneralizedParams {
+                    volatility,
+                    ..*params
+                },
+                is_call,
+            )
+        },
+    )
+}
+
+/// Black-76 price.
+///
+/// C = e^(-rT) (F N(d1) - K N(d2))
+/// P = e^(-rT) (K N(-d2) - F N(-d1))
+pub fn black_76_price(params: &Black76Params, is_call: bool) -> Result<Decimal, ArithmeticError> {
+    generalized_price(&black_76_as_generalized(params), is_call)
+}
+
+/// Black-76 Greeks. Delta and gamma are with respect to the forward; rho
+/// holds the forward fixed.
+pub fn black_76_greeks(params: &Black76Params, is_call: bool) -> Result<Greeks, ArithmeticError> {
+    let (price, mut greeks) = generalized(&black_76_as_generalized(params), is_call)?;
+    // Only discounting depends on the rate: dV/dr = -T V
+    greeks.rho = -params.time.try_mul(price)?.try_div(Decimal::from(100i64))?;
+    Ok(greeks)
+}
+
+/// Implied volatility under Black-76.
+///
+/// Returns error if the price is outside the model's arbitrage bounds or
+/// the solver does not converge.
+pub fn black_76_implied_volatility(
+    market_price: Decimal,
+    params: &Black76Params,
+    is_call: bool,
+) -> Result<Decimal, ArithmeticError> {
+    implied_from_price(
+        market_price,
+        min_volatility(),
+        max_volatility(),
+        IMPLIED_MAX_ITER,
+        |volatility| {
+            black_76_price(
+                &Black76Params {
+                    volatility,
+                    ..*params
+                },
+                is_call,
+            )
+        },
+    )
+}
+
+/// Bachelier (normal model) price.
+///
+/// C = e^(-rT) ((F - K) N(d) + σ√T n(d)), d = (F - K) / (σ√T)
+/// P = e^(-rT) ((K - F) N(-d) + σ√T n(d))
+pub fn bachelier_price(
+    params: &BachelierParams,
+    is_call: bool,
+) -> Result<Decimal, ArithmeticError> {
+    Ok(bachelier(params, is_call)?.0)
+}
+
+/// Bachelier Greeks. Vega is per 0.01 of normal volatility and rho holds
+/// the forward fixed.
+pub fn bachelier_greeks(
+    params: &BachelierParams,
+    is_call: bool,
+) -> Result<Greeks, ArithmeticError> {
+    Ok(bachelier(params, is_call)?.1)
+}
+
+/// Implied normal volatility under the Bachelier model.
+///
+/// Returns error if the price is at or below discounted intrinsic value or
+/// the solver does not converge.
+pub fn bachelier_implied_volatility(
+    market_price: Decimal,
+    params: &BachelierParams,
+    is_call: bool,
+) -> Result<Decimal, ArithmeticError> {
+    if params.time <= Decimal::ZERO {
+        return Err(ArithmeticError::NegativeSqrt);
+    }
+    // At this vol |d| <= 1/4, so the time value alone exceeds the price
+    let growth = params.rate.try_mul(params.time)?.try_exp()?;
+    let upper = market_price
+        .abs()
+        .try_mul(growth)?
+        .try_add(params.forward.try_sub(params.strike)?.abs())?
+        .try_mul(Decimal::from(4i64))?
+        .try_div(params.time.try_sqrt()?)?
+        .max(parse_const("0.000001"));
+
+    implied_from_price(
+        market_price,
+        parse_const("0.0000000001"),
+        upper,
+        IMPLIED_MAX_ITER,
+        |volatility| {
+            bachelier_price(
+                &BachelierParams {
+                    volatility,
+                    ..*params
+                },
+                is_call,
+            )
+        },
+    )
+}
+
+fn generalized(
+    params: &GeneralizedParams,
+    is_call: bool,
+) -> Result<(Decimal, Greeks), ArithmeticError> {
+    validate(params.spot, params.strike, params.time, params.volatility)?;
+
+    let two = Decimal::from(2i64);
+    let hundred = Decimal::from(100i64);
+    let sqrt_t = params.time.try_sqrt()?;
+    let vol_sqrt_t = params.volatility.try_mul(sqrt_t)?;
+
+    // d1 = (ln(S/K) + (b + σ²/2)T) / (σ√T)
+    let half_var = params.volatility.try_mul(params.volatility)?.try_div(two)?;
+    let drift = params
+        .cost_of_carry()?
+        .try_add(half_var)?
+        .try_mul(params.time)?;
+    let d1 = params
+        .spot
+        .try_div(params.strike)?
+        .try_ln()?
+        .try_add(drift)?
+        .try_div(vol_sqrt_t)?;
+    let d2 = d1.try_sub(vol_sqrt_t)?;
+
+    let discount = (-params.rate.try_mul(params.time)?).try_exp()?;
+    let carry_discount = (-params.yield_rate.try_mul(params.time)?).try_exp()?;
+    let forward_value = params.spot.try_mul(carry_discount)?;
+    let strike_value = params.strike.try_mul(discount)?;
+    let pdf = normal_pdf(d1)?;
+
+    let gamma = carry_discount
+        .try_mul(pdf)?
+        .try_div(params.spot.try_mul(vol_sqrt_t)?)?;
+    let vega = forward_value
+        .try_mul(pdf)?
+        .try_mul(sqrt_t)?
+        .try_div(hundred)?;
+    let decay = forward_value
+        .try_mul(pdf)?
+        .try_mul(params.volatility)?
+        .try_div(two.try_mul(sqrt_t)?)?;
+
+    let (price, delta, theta, rho) = if is_call {
+        let n_d1 = normal_cdf(d1)?;
+        let n_d2 = normal_cdf(d2)?;
+        let asset = forward_value.try_mul(n_d1)?;
+        let cash = strike_value.try_mul(n_d2)?;
+        // Θ = -decay + q S e^(-qT) N(d1) - r K e^(-rT) N(d2)
+        let theta = params
+            .yield_rate
+            .try_mul(asset)?
+            .try_sub(params.rate.try_mul(cash)?)?
+            .try_sub(decay)?;
+        (
+            asset.try_sub(cash)?,
+            carry_discount.try_mul(n_d1)?,
+            theta,
+            params.time.try_mul(cash)?,
+        )
+    } else {
+        let n_d1 = normal_cdf(-d1)?;
+        let n_d2 = normal_cdf(-d2)?;
+        let asset = forward_value.try_mul(n_d1)?;
+        let cash = strike_value.try_mul(n_d2)?;
+        // Θ = -decay - q S e^(-qT) N(-d1) + r K e^(-rT) N(-d2)
+        let theta = params
+            .rate
+            .try_mul(cash)?
+            .try_sub(params.yield_rate.try_mul(asset)?)?
+            .try_sub(decay)?;
+        (
+            cash.try_sub(asset)?,
+            -carry_discount.try_mul(n_d1)?,
+            theta,
+            -params.time.try_mul(cash)?,
+        )
+    };
+
+    Ok((
+        price,
+        Greeks {
+            delta,
+            gamma,
+            theta: theta.try_div(Decimal::from(365i64))?,
+            vega,
+            rho: rho.try_div(hundred)?,
+        },
+    ))
+}
+
+fn bachelier(
+    params: &BachelierParams,
+    is_call: bool,
+) -> Result<(Decimal, Greeks), ArithmeticError> {
+    if params.time <= Decimal::ZERO {
+        return Err(ArithmeticError::NegativeSqrt);
+    }
+    if params.volatility <= Decimal::ZERO {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+
+    let hundred = Decimal::from(100i64);
+    let sqrt_t = params.time.try_sqrt()?;
+    let std_dev = params.volatility.try_mul(sqrt_t)?;
+    let moneyness = params.forward.try_sub(params.strike)?;
+    let d = moneyness.try_div(std_dev)?;
+
+    let discount = (-params.rate.try_mul(params.time)?).try_exp()?;
+    let pdf = normal_pdf(d)?;
+    let time_value = std_dev.try_mul(pdf)?;
+
+    let (intrinsic, delta) = if is_call {
+        let n_d = normal_cdf(d)?;
+        (moneyness.try_mul(n_d)?, discount.try_mul(n_d)?)
+    } else {
+        let n_d = normal_cdf(-d)?;
+        (-moneyness.try_mul(n_d)?, -discount.try_mul(n_d)?)
+    };
+    let price = discount.try_mul(intrinsic.try_add(time_value)?)?;
+
+    let gamma = discount.try_mul(pdf)?.try_div(std_dev)?;
+    let vega = discount.try_mul(sqrt_t)?.try_mul(pdf)?.try_div(hundred)?;
+    // Θ = r V - e^(-rT) σ n(d) / (2√T)
+    let decay = discount
+        .try_mul(params.volatility)?
+        .try_mul(pdf)?
+        .try_div(Decimal::from(2i64).try_mul(sqrt_t)?)?;
+    let theta = params.rate.try_mul(price)?.try_sub(decay)?;
+    let rho = -params.time.try_mul(price)?;
+
+    Ok((
+        price,
+        Greeks {
+            delta,
+            gamma,
+            theta: theta.try_div(Decimal::from(365i64))?,
+            vega,
+            rho: rho.try_div(hundred)?,
+        },
+    ))
+}
+
+fn black_76_as_generalized(params: &Black76Params) -> GeneralizedParams {
+    // Zero cost of carry: the forward earns the discount rate as its yield
+    GeneralizedParams {
+        spot: params.forward,
+        strike: params.strike,
+        rate: params.rate,
+        yield_rate: params.rate,
+        time: params.time,
+        volatility: params.volatility,
+    }
+}
+
+/// Iteration cap for the implied volatility solvers.
+const IMPLIED_MAX_ITER: u32 = 200;
+
+/// Solves `price(volatility) = market_price` on [lower, upper].
+///
+/// Returns `DivisionByZero` if the price is not bracketed or the solver
+/// does not converge within `max_iter` iterations.
+fn implied_from_price<F>(
+    market_price: Decimal,
+    lower: Decimal,
+    upper: Decimal,
+    max_iter: u32,
+    price: F,
+) -> Result<Decimal, ArithmeticError>
+where
+    F: Fn(Decimal) -> Result<Decimal, ArithmeticError>,
+{
+    let result = brent(
+        |volatility| price(volatility)?.try_sub(market_price),
+        lower,
+        upper,
+        Some(parse_const("0.000000000001")),
+        Some(max_iter),
+    )?;
+    if !result.converged {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    Ok(result.root)
+}
+
+fn validate(
+    spot: Decimal,
+    strike: Decimal,
+    time: Decimal,
+    volatility: Decimal,
+) -> Result<(), ArithmeticError> {
+    if spot <= Decimal::ZERO || strike <= Decimal::ZERO || volatility <= Decimal::ZERO {
+        return Err(ArithmeticError::LogOfNegative);
+    }
+    if time <= Decimal::ZERO {
+        return Err(ArithmeticError::NegativeSqrt);
+    }
+    Ok(())
+}
+
+fn min_volatility() -> Decimal {
+    parse_const("0.0001")
+}
+
+fn max_volatility() -> Decimal {
+    parse_const("10")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{black_scholes_call, call_greeks};
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn close(a: Decimal, b: Decimal, tolerance: &str) -> bool {
+        (a - b).abs() < decimal(tolerance)
+    }
+
+    fn option_params() -> OptionParams {
+        OptionParams {
+            spot: decimal("100"),
+            strike: decimal("95"),
+            rate: decimal("0.05"),
+            time: decimal("0.5"),
+            volatility: decimal("0.25"),
+        }
+    }
+
+    #[test]
+    fn test_generalized_reduces_to_black_scholes() {
+        let params = GeneralizedParams::with_yield(&option_params(), Decimal::ZERO);
+        let price = generalized_price(&params, true).unwrap();
+        assert!(close(
+            price,
+            black_scholes_call(&option_params()).unwrap(),
+            "0.0000001"
+        ));
+
+        let greeks = generalized_greeks(&params, true).unwrap();
+        let expected = call_greeks(&option_params()).unwrap();
+        assert!(close(greeks.delta, expected.delta, "0.0000001"));
+        assert!(close(greeks.theta, expected.theta, "0.0000001"));
+        assert!(close(greeks.rho, expected.rho, "0.0000001"));
+    }
+
+    #[test]
+    fn test_garman_kohlhagen_parity() {
+        // C - P = S e^(-r_f T) - K e^(-r_d T)
+        let fx = OptionParams {
+            spot: decimal("1.10"),
+            strike: decimal("1.12"),
+            rate: decimal("0.04"),
+            time: decimal("0.75"),
+            volatility: decimal("0.08"),
+        };
+        let params = GeneralizedParams::garman_kohlhagen(&fx, decimal("0.02"));
+        let call = generalized_price(&params, true).unwrap();
+        let put = generalized_price(&params, false).unwrap();
+
+        let forward = fx.spot * (decimal("-0.015")).exp().unwrap();
+        let strike = fx.strike * (decimal("-0.03")).exp().unwrap();
+        assert!(close(call - put, forward - strike, "0.0000001"));
+    }
+
+    #[test]
+    fn test_generalized_delta_matches_finite_difference() {
+        let params = GeneralizedParams::with_yield(&option_params(), decimal("0.03"));
+        let h = decimal("0.001");
+        let up = GeneralizedParams {
+            spot: params.spot + h,
+            ..params
+        };
+        let down = GeneralizedParams {
+            spot: params.spot - h,
+            ..params
+        };
+        for is_call in [true, false] {
+            let numeric = (generalized_price(&up, is_call).unwrap()
+                - generalized_price(&down, is_call).unwrap())
+                / (h + h);
+            let delta = generalized_greeks(&params, is_call).unwrap().delta;
+            assert!(close(numeric, delta, "0.00001"));
+        }
+    }
+
+    #[test]
+    fn test_black_76_atm() {
+        let params = Black76Params {
+            forward: decimal("100"),
+            strike: decimal("100"),
+            rate: decimal("0.05"),
+            time: decimal("1"),
+            volatility: decimal("0.2"),
+        };
+        // e^(-0.05) × 100 × (N(0.1) - N(-0.1)) ≈ 7.577
+        let call = black_76_price(&params, true).unwrap();
+        assert!(close(call, decimal("7.577"), "0.001"));
+        assert!(close(
+            call,
+            black_76_price(&params, false).unwrap(),
+            "0.0000001"
+        ));
+
+        let greeks = black_76_greeks(&params, true).unwrap();
+        assert!(close(greeks.rho, -call / decimal("100"), "0.0000001"));
+    }
+
+    #[test]
+    fn test_bachelier_negative_rates() {
+        // ATM: e^(-rT) σ √T / √(2π)
+        let atm = BachelierParams {
+            forward: decimal("0.03"),
+            strike: decimal("0.03"),
+            rate: Decimal::ZERO,
+            time: decimal("1"),
+            volatility: decimal("0.01"),
+        };
+        assert!(close(
+            bachelier_price(&atm, true).unwrap(),
+            decimal("0.0039894228"),
+            "0.0000001"
+        ));
+
+        // Negative forward still prices, and parity holds: C - P = e^(-rT)(F - K)
+        let negative = BachelierParams {
+            forward: decimal("-0.005"),
+            strike: Decimal::ZERO,
+            rate: decimal("0.01"),
+            time: decimal("2"),
+            volatility: decimal("0.007"),
+        };
+        let call = bachelier_price(&negative, true).unwrap();
+        let put = bachelier_price(&negative, false).unwrap();
+        assert!(call.is_positive());
+        let parity = (decimal("-0.02")).exp().unwrap() * decimal("-0.005");
+        assert!(close(call - put, parity, "0.0000001"));
+
+        let h = decimal("0.00001");
+        let numeric = (bachelier_price(
+            &BachelierParams {
+                forward: negative.forward + h,
+                ..negative
+            },
+            true,
+        )
+        .unwrap()
+            - bachelier_price(
+                &BachelierParams {
+                    forward: negative.forward - h,
+                    ..negative
+                },
+                true,
+            )
+            .unwrap())
+            / (h + h);
+        let delta = bachelier_greeks(&negative, true).unwrap().delta;
+        assert!(close(numeric, delta, "0.00001"));
+    }
+
+    #[test]
+    fn test_implied_volatility_round_trips() {
+        let generalized = GeneralizedParams::with_yield(&option_params(), decimal("0.02"));
+        let price = generalized_price(&generalized, false).unwrap();
+        let vol = generalized_implied_volatility(price, &generalized, false).unwrap();
+        assert!(close(vol, generalized.volatility, "0.000001"));
+
+        let normal = BachelierParams {
+            forward: decimal("-0.002"),
+            strike: decimal("0.001"),
+            rate: decimal("0.02"),
+            time: decimal("0.5"),
+            volatility: decimal("0.008"),
+        };
+        let price = bachelier_price(&normal, true).unwrap();
+        let vol = bachelier_implied_volatility(price, &normal, true).unwrap();
+        assert!(close(vol, normal.volatility, "0.0000001"));
+    }
+
+    #[test]
+    fn test_implied_volatility_requires_convergence() {
+        let params = GeneralizedParams::with_yield(&option_params(), decimal("0.02"));
+        let market_price = generalized_price(&params, true).unwrap();
+        let price = |volatility| {
+            generalized_price(
+                &GeneralizedParams {
+                    volatility,
+                    ..params
+                },
+                true,
+            )
+        };
+        let solve = |max_iter| {
+            implied_from_price(
+                market_price,
+                min_volatility(),
+                max_volatility(),
+                max_iter,
+                price,
+            )
+        };
+        assert!(solve(2).is_err());
+        assert!(close(solve(IMPLIED_MAX_ITER).unwrap(), params.volatility, "0.000001"));
+    }
+}
@@ -0,0 +1,1078 @@
+//! Volatility smile models and an arbitrage-checked volatility surface.
+//!
+//! Each expiry slice is described by a [`Smile`]: total implied variance
+//! w(k) = σ²T as a function of log-moneyness k = ln(K/F).
+//!
+//! - [`Svi`] - Gatheral's raw SVI, w(k) = a + b(ρ(k − m) + √((k − m)² + σ²)),
+//!   also constructible from the natural parametrisation [`NaturalSvi`]
+//! - [`Sabr`] - Hagan's lognormal SABR expansion with fixed β
+//!
+//! Both calibrate to observed implied vols by least squares
+//! (see [`levenberg_marquardt`]) and can be checked for static arbitrage:
+//! [`butterfly_arbitrage`] tests Gatheral's density condition g(k) ≥ 0 and
+//! [`calendar_arbitrage`] tests that total variance increases with expiry.
+//!
+//! A [`VolatilitySurface`] stacks slices by expiry, interpolating total
+//! variance linearly in time at fixed log-moneyness, and feeds the
+//! per-strike volatility into [`black_scholes_call`] and
+//! [`black_scholes_put`].
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::options::{Smile, SmileQuote, Svi, VolatilitySurface};
+//! use precision_core::Decimal;
+//!
+//! let forward = Decimal::from(2000i64);
+//! let expiry = Decimal::new(25, 2);
+//! let quote = |strike: i64, vol: i64| {
+//!     SmileQuote::new(Decimal::from(strike), Decimal::new(vol, 2))
+//! };
+//! let quotes = [
+//!     quote(1600, 78),
+//!     quote(1800, 68),
+//!     quote(2000, 62),
+//!     quote(2200, 60),
+//!     quote(2400, 61),
+//!     quote(2600, 63),
+//! ];
+//!
+//! let fit = Svi::fit(forward, expiry, &quotes, None).unwrap();
+//! assert!(fit.converged);
+//!
+//! let mut surface = VolatilitySurface::new();
+//! surface.add_slice(fit.curve.into()).unwrap();
+//! let put = surface
+//!     .price(forward, Decimal::from(1700i64), Decimal::ZERO, expiry, false)
+//!     .unwrap();
+//! assert!(put.is_positive());
+//! ```
+
+use super::{black_scholes_call, black_scholes_put, OptionParams};
+use crate::solver::levenberg_marquardt;
+use crate::storage::{self, Buffer, Fixed, Storage};
+use crate::surface::{interpolate_total_variance, SurfaceInterpolator};
+use crate::term_structure::CalibrationResult;
+use precision_core::{ArithmeticError, Decimal};
+
+/// Default number of expiry slices in a surface (for no_std fixed allocation).
+pub const MAX_SMILE_SLICES: usize = 16;
+
+/// An implied volatility smile for one expiry.
+pub trait Smile {
+    /// Expiry in years.
+    fn expiry(&self) -> Decimal;
+
+    /// Total implied variance w(k) = σ²T at log-moneyness k = ln(K/F).
+    fn total_variance(&self, log_moneyness: Decimal) -> Result<Decimal, ArithmeticError>;
+
+    /// Implied volatility at log-moneyness k.
+    fn volatility(&self, log_moneyness: Decimal) -> Result<Decimal, ArithmeticError> {
+        self.total_variance(log_moneyness)?
+            .try_div(self.expiry())?
+            .try_sqrt()
+    }
+
+    /// Gatheral's butterfly density function
+    ///
+    /// g(k) = (1 − k w′/(2w))² − (w′²/4)(1/w + 1/4) + w″/2
+    ///
+    /// which must be non-negative for the implied risk-neutral density to
+    /// be non-negative. Derivatives are taken by central differences.
+    fn butterfly_density(&self, log_moneyness: Decimal) -> Result<Decimal, ArithmeticError> {
+        let h = Decimal::new(1, 4);
+        let w = self.total_variance(log_moneyness)?;
+        let up = self.total_variance(log_moneyness.try_add(h)?)?;
+        let down = self.total_variance(log_moneyness.try_sub(h)?)?;
+        let slope = up.try_sub(down)?.try_div(h.try_add(h)?)?;
+        let curvature = up
+            .try_sub(w.try_add(w)?)?
+            .try_add(down)?
+            .try_div(h.try_mul(h)?)?;
+        density(log_moneyness, w, slope, curvature)
+    }
+}
+
+/// An observed implied volatility at a strike.
+#[derive(Debug, Clone, Copy)]
+pub struct SmileQuote {
+    /// Strike price.
+    pub strike: Decimal,
+    /// Implied volatility (annualized).
+    pub volatility: Decimal,
+}
+
+impl SmileQuote {
+    /// Creates a quote.
+    pub fn new(strike: Decimal, volatility: Decimal) -> Self {
+        Self { strike, volatility }
+    }
+}
+
+/// Raw SVI smile for one expiry.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Svi {
+    a: Decimal,
+    b: Decimal,
+    rho: Decimal,
+    m: Decimal,
+    sigma: Decimal,
+    expiry: Decimal,
+}
+
+/// Natural SVI parameters.
+///
+/// w(k) = Δ + ω/2 (1 + ζρ(k − μ) + √((ζ(k − μ) + ρ)² + (1 − ρ²)))
+#[derive(Debug, Clone, Copy)]
+pub struct NaturalSvi {
+    /// Variance level shift Δ.
+    pub delta: Decimal,
+    /// Log-moneyness shift μ.
+    pub mu: Decimal,
+    /// Skew ρ, in (−1, 1).
+    pub rho: Decimal,
+    /// ATM total variance level ω.
+    pub omega: Decimal,
+    /// Curvature ζ.
+    pub zeta: Decimal,
+}
+
+impl Svi {
+    /// Creates a raw SVI slice.
+    ///
+    /// Returns error unless b ≥ 0, |ρ| < 1, σ > 0, the expiry is positive
+    /// and the minimum variance a + bσ√(1 − ρ²) is non-negative.
+    pub fn new(
+        a: Decimal,
+        b: Decimal,
+        rho: Decimal,
+        m: Decimal,
+        sigma: Decimal,
+        expiry: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        if b.is_negative() || rho.abs() >= Decimal::ONE || !sigma.is_positive() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        if !expiry.is_positive() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let spread = Decimal::ONE.try_sub(rho.try_mul(rho)?)?.try_sqrt()?;
+        if a.try_add(b.try_mul(sigma)?.try_mul(spread)?)?.is_negative() {
+            return Err(ArithmeticError::NegativeSqrt);
+        }
+        Ok(Self {
+            a,
+            b,
+            rho,
+            m,
+            sigma,
+            expiry,
+        })
+    }
+
+    /// Creates a slice from natural SVI parameters.
+    ///
+    /// a = Δ + ω/2 (1 − ρ²), b = ωζ/2, m = μ − ρ/ζ, σ = √(1 − ρ²)/ζ
+    pub fn from_natural(params: &NaturalSvi, expiry: Decimal) -> Result<Self, ArithmeticError> {
+        let two = Decimal::from(2i64);
+        let one_minus_rho_sq = Decimal::ONE.try_sub(params.rho.try_mul(params.rho)?)?;
+        let half_omega = params.omega.try_div(two)?;
+        Self::new(
+            params
+                .delta
+                .try_add(half_omega.try_mul(one_minus_rho_sq)?)?,
+            half_omega.try_mul(params.zeta)?,
+            params.rho,
+            params.mu.try_sub(params.rho.try_div(params.zeta)?)?,
+            one_minus_rho_sq.try_sqrt()?.try_div(params.zeta)?,
+            expiry,
+        )
+    }
+
+    /// Natural SVI parameters of the slice.
+    pub fn natural(&self) -> Result<NaturalSvi, ArithmeticError> {
+        let one_minus_rho_sq = Decimal::ONE.try_sub(self.rho.try_mul(self.rho)?)?;
+        let zeta = one_minus_rho_sq.try_sqrt()?.try_div(self.sigma)?;
+        let omega = Decimal::from(2i64).try_mul(self.b)?.try_div(zeta)?;
+        Ok(NaturalSvi {
+            delta: self.a.try_sub(
+                omega
+                    .try_div(Decimal::from(2i64))?
+                    .try_mul(one_minus_rho_sq)?,
+            )?,
+            mu: self.m.try_add(self.rho.try_div(zeta)?)?,
+            rho: self.rho,
+            omega,
+            zeta,
+        })
+    }
+
+    /// Calibrates raw SVI to implied vol quotes by least squares on total
+    /// variance.
+    ///
+    /// If `initial` is `None` a starting point is derived from the quotes.
+    /// Requires at least five quotes and returns `Overflow` for more than
+    /// [`MAX_FIT_QUOTES`].
+    pub fn fit(
+        forward: Decimal,
+        expiry: Decimal,
+        quotes: &[SmileQuote],
+        initial: Option<Self>,
+    ) -> Result<CalibrationResult<Self>, ArithmeticError> {
+        let (ks, ws) = observations::<Fixed<MAX_FIT_QUOTES>>(forward, expiry, quotes)?;
+        let (ks, ws) = (ks.as_slice(), ws.as_slice());
+        let start = match initial {
+            Some(svi) => svi,
+            None => {
+                // Vertex at the lowest quoted variance with a mild smile
+                let (low, _) = ws
+                    .iter()
+                    .enumerate()
+                    .min_by(|x, y| x.1.cmp(y.1))
+                    .ok_or(ArithmeticError::DivisionByZero)?;
+                let b = Decimal::new(1, 1);
+                let sigma = Decimal::new(1, 1);
+                let a = ws[low].try_sub(b.try_mul(sigma)?)?.max(Decimal::ZERO);
+                Self::new(a, b, Decimal::ZERO, ks[low], sigma, expiry)?
+            }
+        };
+        calibrate(
+            ks.len(),
+            [start.a, start.b, start.rho, start.m, start.sigma],
+            |p: &[Decimal; 5]| Self::new(p[0], p[1], p[2], p[3], p[4], expiry),
+            |svi, i| svi.total_variance(ks[i])?.try_sub(ws[i]),
+        )
+    }
+
+    /// Variance level a.
+    pub fn a(&self) -> Decimal {
+        self.a
+    }
+
+    /// Wing slope b.
+    pub fn b(&self) -> Decimal {
+        self.b
+    }
+
+    /// Skew ρ.
+    pub fn rho(&self) -> Decimal {
+        self.rho
+    }
+
+    /// Horizontal shift m.
+    pub fn m(&self) -> Decimal {
+        self.m
+    }
+
+    /// ATM curvature σ.
+    pub fn sigma(&self) -> Decimal {
+        self.sigma
+    }
+
+    /// First and second derivatives of total variance in log-moneyness.
+    fn derivatives(&self, k: Decimal) -> Result<(Decimal, Decimal), ArithmeticError> {
+        let x = k.try_sub(self.m)?;
+        let sigma_sq = self.sigma.try_mul(self.sigma)?;
+        let radius_sq = x.try_mul(x)?.try_add(sigma_sq)?;
+        let radius = radius_sq.try_sqrt()?;
+        let slope = self.b.try_mul(self.rho.try_add(x.try_div(radius)?)?)?;
+        let curvature = self
+            .b
+            .try_mul(sigma_sq)?
+            .try_div(radius_sq.try_mul(radius)?)?;
+        Ok((slope, curvature))
+    }
+}
+
+impl Smile for Svi {
+    fn expiry(&self) -> Decimal {
+        self.expiry
+    }
+
+    fn total_variance(&self, log_moneyness: Decimal) -> Result<Decimal, ArithmeticError> {
+        let x = log_moneyness.try_sub(self.m)?;
+        let radius = x
+            .try_mul(x)?
+            .try_add(self.sigma.try_mul(self.sigma)?)?
+            .try_sqrt()?;
+        self.a
+            .try_add(self.b.try_mul(self.rho.try_mul(x)?.try_add(radius)?)?)
+    }
+
+    fn butterfly_density(&self, log_moneyness: Decimal) -> Result<Decimal, ArithmeticError> {
+        let w = self.total_variance(log_moneyness)?;
+        let (slope, curvature) = self.derivatives(log_moneyness)?;
+        density(log_moneyness, w, slope, curvature)
+    }
+}
+
+/// Hagan lognormal SABR smile for one expiry.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sabr {
+    alpha: Decimal,
+    beta: Decimal,
+    rho: Decimal,
+    nu: Decimal,
+    forward: Decimal,
+    expiry: Decimal,
+}
+
+impl Sabr {
+    /// Creates a SABR slice.
+    ///
+    /// Returns error unless α > 0, 0 ≤ β ≤ 1, |ρ| < 1, ν ≥ 0 and the
+    /// forward and expiry are positive.
+    pub fn new(
+        alpha: Decimal,
+        beta: Decimal,
+        rho: Decimal,
+        nu: Decimal,
+        forward: Decimal,
+        expiry: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        if !alpha.is_positive()
+            || beta.is_negative()
+            || beta > Decimal::ONE
+            || rho.abs() >= Decimal::ONE
+            || nu.is_negative()
+            || !forward.is_positive()
+            || !expiry.is_positive()
+        {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(Self {
+            alpha,
+            beta,
+            rho,
+            nu,
+            forward,
+            expiry,
+        })
+    }
+
+    /// Calibrates α, ρ and ν to implied vol quotes with β held fixed.
+    ///
+    /// If `initial` is `None` α is seeded from the quote nearest the
+    /// forward. Requires at least three quotes.
+    pub fn fit(
+        forward: Decimal,
+        expiry: Decimal,
+        beta: Decimal,
+        quotes: &[SmileQuote],
+        initial: Option<Self>,
+    ) -> Result<CalibrationResult<Self>, ArithmeticError> {
+        let start = match initial {
+            Some(sabr) => sabr,
+            None => {
+                let atm = quotes
+                    .iter()
+                    .min_by_key(|q| q.strike.try_sub(forward).map(|d| d.abs()).ok())
+                    .ok_or(ArithmeticError::DivisionByZero)?;
+                // σ_ATM ≈ α / F^(1−β)
+                let scale = power(forward, Decimal::ONE.try_sub(beta)?)?;
+                let alpha = atm.volatility.try_mul(scale)?;
+                Self::new(
+                    alpha,
+                    beta,
+                    Decimal::ZERO,
+                    Decimal::new(5, 1),
+                    forward,
+                    expiry,
+                )?
+            }
+        };
+        calibrate(
+            quotes.len(),
+            [start.alpha, start.rho, start.nu],
+            |p: &[Decimal; 3]| Self::new(p[0], beta, p[1], p[2], forward, expiry),
+            |sabr, i| {
+                sabr.implied_volatility(quotes[i].strike)?
+                    .try_sub(quotes[i].volatility)
+            },
+        )
+    }
+
+    /// Hagan's lognormal implied volatility at a strike.
+    ///
+    /// σ = α / ((FK)^((1−β)/2) (1 + (1−β)²/24 ln²(F/K) + (1−β)⁴/1920 ln⁴(F/K)))
+    ///     × z/x(z) × (1 + ((1−β)²α²/(24(FK)^(1−β)) + ρβνα/(4(FK)^((1−β)/2))
+    ///     + (2 − 3ρ²)ν²/24) T)
+    ///
+    /// with z = ν/α (FK)^((1−β)/2) ln(F/K) and
+    /// x(z) = ln((√(1 − 2ρz + z²) + z − ρ)/(1 − ρ)).
+    pub fn implied_volatility(&self, strike: Decimal) -> Result<Decimal, ArithmeticError> {
+        if !strike.is_positive() {
+            return Err(ArithmeticError::LogOfNegative);
+        }
+        let one = Decimal::ONE;
+        let two = Decimal::from(2i64);
+        let one_minus_beta = one.try_sub(self.beta)?;
+        let log_fk = self.forward.try_div(strike)?.try_ln()?;
+        let fk = self.forward.try_mul(strike)?;
+        let fk_half = power(fk, one_minus_beta.try_div(two)?)?;
+
+        let omb_sq = one_minus_beta.try_mul(one_minus_beta)?;
+        let log_sq = log_fk.try_mul(log_fk)?;
+        let denominator = fk_half.try_mul(
+            one.try_add(omb_sq.try_mul(log_sq)?.try_div(Decimal::from(24i64))?)?
+                .try_add(
+                    omb_sq
+                        .try_mul(omb_sq)?
+                        .try_mul(log_sq)?
+                        .try_mul(log_sq)?
+                        .try_div(Decimal::from(1920i64))?,
+                )?,
+        )?;
+
+        let z = self
+            .nu
+            .try_div(self.alpha)?
+            .try_mul(fk_half)?
+            .try_mul(log_fk)?;
+        let ratio = if z.abs() < Decimal::new(1, 12) {
+            one
+        } else {
+            let root = one
+                .try_sub(two.try_mul(self.rho)?.try_mul(z)?)?
+                .try_add(z.try_mul(z)?)?
+                .try_sqrt()?;
+            let x = root
+                .try_add(z)?
+                .try_sub(self.rho)?
+                .try_div(one.try_sub(self.rho)?)?
+                .try_ln()?;
+            z.try_div(x)?
+        };
+
+        let correction = omb_sq
+            .try_mul(self.alpha)?
+            .try_mul(self.alpha)?
+            .try_div(Decimal::from(24i64).try_mul(fk_half)?.try_mul(fk_half)?)?
+            .try_add(
+                self.rho
+                    .try_mul(self.beta)?
+                    .try_mul(self.nu)?
+                    .try_mul(self.alpha)?
+                    .try_div(Decimal::from(4i64).try_mul(fk_half)?)?,
+            )?
+            .try_add(
+                two.try_sub(Decimal::from(3i64).try_mul(self.rho)?.try_mul(self.rho)?)?
+                    .try_mul(self.nu)?
+                    .try_mul(self.nu)?
+                    .try_div(Decimal::from(24i64))?,
+            )?;
+
+        self.alpha
+            .try_div(denominator)?
+            .try_mul(ratio)?
+            .try_mul(one.try_add(correction.try_mul(self.expiry)?)?)
+    }
+
+    /// Volatility level α.
+    pub fn alpha(&self) -> Decimal {
+        self.alpha
+    }
+
+    /// CEV exponent β.
+    pub fn beta(&self) -> Decimal {
+        self.beta
+    }
+
+    /// Spot-vol correlation ρ.
+    pub fn rho(&self) -> Decimal {
+        self.rho
+    }
+
+    /// Volatility of volatility ν.
+    pub fn nu(&self) -> Decimal {
+        self.nu
+    }
+
+    /// Forward the slice was built for.
+    pub fn forward(&self) -> Decimal {
+        self.forward
+    }
+}
+
+impl Smile for Sabr {
+    fn expiry(&self) -> Decimal {
+        self.expiry
+    }
+
+    fn total_variance(&self, log_moneyness: Decimal) -> Result<Decimal, ArithmeticError> {
+        let strike = self.forward.try_mul(log_moneyness.try_exp()?)?;
+        let vol = self.implied_volatility(strike)?;
+        vol.try_mul(vol)?.try_mul(self.expiry)
+    }
+}
+
+/// A smile slice stored in a [`VolatilitySurface`].
+#[derive(Debug, Clone, Copy)]
+pub enum SmileSlice {
+    /// Raw SVI slice.
+    Svi(Svi),
+    /// SABR slice.
+    Sabr(Sabr),
+}
+
+impl Default for SmileSlice {
+    fn default() -> Self {
+        Self::Svi(Svi::default())
+    }
+}
+
+impl From<Svi> for SmileSlice {
+    fn from(svi: Svi) -> Self {
+        Self::Svi(svi)
+    }
+}
+
+impl From<Sabr> for SmileSlice {
+    fn from(sabr: Sabr) -> Self {
+        Self::Sabr(sabr)
+    }
+}
+
+impl Smile for SmileSlice {
+    fn expiry(&self) -> Decimal {
+        match self {
+            Self::Svi(s) => s.expiry(),
+            Self::Sabr(s) => s.expiry(),
+        }
+    }
+
+    fn total_variance(&self, log_moneyness: Decimal) -> Result<Decimal, ArithmeticError> {
+        match self {
+            Self::Svi(s) => s.total_variance(log_moneyness),
+            Self::Sabr(s) => s.total_variance(log_moneyness),
+        }
+    }
+
+    fn butterfly_density(&self, log_moneyness: Decimal) -> Result<Decimal, ArithmeticError> {
+        match self {
+            Self::Svi(s) => s.butterfly_density(log_moneyness),
+            Self::Sabr(s) => s.butterfly_density(log_moneyness),
+        }
+    }
+}
+
+/// Finds butterfly arbitrage in a smile.
+///
+/// Returns the first log-moneyness in `grid` where Gatheral's density
+/// g(k) is negative, or `None` if the smile is arbitrage-free there.
+pub fn butterfly_arbitrage<M: Smile + ?Sized>(
+    smile: &M,
+    grid: &[Decimal],
+) -> Result<Option<Decimal>, ArithmeticError> {
+    for &k in grid {
+        if smile.butterfly_density(k)?.is_negative() {
+            return Ok(Some(k));
+        }
+    }
+    Ok(None)
+}
+
+/// Finds calendar arbitrage between two smiles.
+///
+/// Returns the first log-moneyness in `grid` where the later smile has
+/// less total variance than the earlier one, or `None` if total variance
+/// is non-decreasing in expiry there.
+pub fn calendar_arbitrage<A: Smile + ?Sized, B: Smile + ?Sized>(
+    earlier: &A,
+    later: &B,
+    grid: &[Decimal],
+) -> Result<Option<Decimal>, ArithmeticError> {
+    for &k in grid {
+        if later.total_variance(k)? < earlier.total_variance(k)? {
+            return Ok(Some(k));
+        }
+    }
+    Ok(None)
+}
+
+/// Implied volatility surface built from smile slices.
+///
+/// Slices are kept sorted by expiry. Queries use log-moneyness against the
+/// forward F = S e^(rT) implied by spot and rate. Between expiries total
+/// variance is interpolated linearly in time at fixed log-moneyness;
+/// outside them the nearest slice's volatility is held flat, as in
+/// [`VolSurface`](crate::surface::VolSurface). As a [`SurfaceInterpolator`]
+/// it takes log-moneyness and expiry.
+#[derive(Debug, Clone)]
+pub struct VolatilitySurface<S: Storage = Fixed<MAX_SMILE_SLICES>> {
+    slices: S::Buffer<SmileSlice>,
+}
+
+impl VolatilitySurface {
+    /// Creates an empty surface with default fixed storage.
+    pub fn new() -> Self {
+        Self::with_storage()
+    }
+}
+
+impl Default for VolatilitySurface {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Storage> VolatilitySurface<S> {
+    /// Creates an empty surface with the chosen storage.
+    pub fn with_storage() -> Self {
+        Self {
+            slices: Default::default(),
+        }
+    }
+
+    /// Adds a slice, keeping slices sorted by expiry.
+    ///
+    /// Returns `DivisionByZero` if a slice with the same expiry exists and
+    /// `Overflow` if the storage is full.
+    pub fn add_slice(&mut self, slice: SmileSlice) -> Result<(), ArithmeticError> {
+        let expiry = slice.expiry();
+        if self.slices.as_slice().iter().any(|s| s.expiry() == expiry) {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        storage::insert_sorted(&mut self.slices, slice, |s| s.expiry())
+    }
+
+    /// Slices in expiry order.
+    pub fn slices(&self) -> &[SmileSlice] {
+        self.slices.as_slice()
+    }
+
+    /// Total implied variance at log-moneyness k and expiry.
+    ///
+    /// Returns `DivisionByZero` if the surface is empty.
+    pub fn total_variance(
+        &self,
+        log_moneyness: Decimal,
+        expiry: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        let slices = self.slices.as_slice();
+        if slices.is_empty() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        interpolate_total_variance(
+            slices,
+            |s| s.expiry(),
+            |j| slices[j].total_variance(log_moneyness),
+            expiry,
+        )
+    }
+
+    /// Implied volatility for a strike and expiry given spot and rate.
+    pub fn volatility(
+        &self,
+        spot: Decimal,
+        strike: Decimal,
+        rate: Decimal,
+        time: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        if !time.is_positive() {
+            return Err(ArithmeticError::NegativeSqrt);
+        }
+        let forward = spot.try_mul(rate.try_mul(time)?.try_exp()?)?;
+        self.interpolate(strike.try_div(forward)?.try_ln()?, time)
+    }
+
+    /// Builds Black-Scholes parameters using the smile volatility at the
+    /// option's strike and time to expiry.
+    pub fn option_params(
+        &self,
+        spot: Decimal,
+        strike: Decimal,
+        rate: Decimal,
+        time: Decimal,
+    ) -> Result<OptionParams, ArithmeticError> {
+        Ok(OptionParams {
+            spot,
+            strike,
+            rate,
+            time,
+            volatility: self.volatility(spot, strike, rate, time)?,
+        })
+    }
+
+    /// Black-Scholes price at the smile volatility for the strike.
+    pub fn price(
+        &self,
+        spot: Decimal,
+        strike: Decimal,
+        rate: Decimal,
+        time: Decimal,
+        is_call: bool,
+    ) -> Result<Decimal, ArithmeticError> {
+        let params = self.option_params(spot, strike, rate, time)?;
+        if is_call {
+            black_scholes_call(&params)
+        } else {
+            black_scholes_put(&params)
+        }
+    }
+
+    /// Checks every slice for butterfly arbitrage and every pair of
+    /// adjacent slices for calendar arbitrage on a log-moneyness grid.
+    ///
+    /// Returns true if no arbitrage is found.
+    pub fn is_arbitrage_free(&self, grid: &[Decimal]) -> Result<bool, ArithmeticError> {
+        let slices = self.slices.as_slice();
+        for slice in slices {
+            if butterfly_arbitrage(slice, grid)?.is_some() {
+                return Ok(false);
+            }
+        }
+        for pair in slices.windows(2) {
+            if calendar_arbitrage(&pair[0], &pair[1], grid)?.is_some() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Interpolates implied volatility at x = log-moneyness ln(K/F) and
+/// y = expiry in years.
+impl<S: Storage> SurfaceInterpolator for VolatilitySurface<S> {
+    fn interpolate(&self, x: Decimal, y: Decimal) -> Result<Decimal, ArithmeticError> {
+        if !y.is_positive() {
+            return Err(ArithmeticError::NegativeSqrt);
+        }
+        self.total_variance(x, y)?.try_div(y)?.try_sqrt()
+    }
+}
+
+/// Maximum quotes in a single SVI calibration (for no_std fixed allocation).
+pub const MAX_FIT_QUOTES: usize = 64;
+
+type Observations<S> = (
+    <S as Storage>::Buffer<Decimal>,
+    <S as Storage>::Buffer<Decimal>,
+);
+
+/// Converts quotes to log-moneyness and total variance.
+fn observations<S: Storage>(
+    forward: Decimal,
+    expiry: Decimal,
+    quotes: &[SmileQuote],
+) -> Result<Observations<S>, ArithmeticError> {
+    if !forward.is_positive() || !expiry.is_positive() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let mut ks = S::Buffer::<Decimal>::default();
+    let mut ws = S::Buffer::<Decimal>::default();
+    for quote in quotes {
+        ks.push(quote.strike.try_div(forward)?.try_ln()?)?;
+        ws.push(
+            quote
+                .volatility
+                .try_mul(quote.volatility)?
+                .try_mul(expiry)?,
+        )?;
+    }
+    Ok((ks, ws))
+}
+
+/// Runs a least-squares smile fit.
+fn calibrate<C, B, R, const N: usize>(
+    count: usize,
+    start: [Decimal; N],
+    build: B,
+    residual: R,
+) -> Result<CalibrationResult<C>, ArithmeticError>
+where
+    B: Fn(&[Decimal; N]) -> Result<C, ArithmeticError>,
+    R: Fn(&C, usize) -> Result<Decimal, ArithmeticError>,
+{
+    if count < N {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let fit = levenberg_marquardt(
+        |i, p: &[Decimal; N]| residual(&build(p)?, i),
+        count,
+        start,
+        Some(Decimal::new(1, 14)),
+        Some(200),
+    )?;
+    Ok(CalibrationResult {
+        curve: build(&fit.params)?,
+        iterations: fit.iterations,
+        residual: fit.residual,
+        converged: fit.converged,
+    })
+}
+
+/// g(k) = (1 − k w′/(2w))² − (w′²/4)(1/w + 1/4) + w″/2
+fn density(
+    k: Decimal,
+    w: Decimal,
+    slope: Decimal,
+    curvature: Decimal,
+) -> Result<Decimal, ArithmeticError> {
+    if !w.is_positive() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    let two = Decimal::from(2i64);
+    let four = Decimal::from(4i64);
+    let skew = Decimal::ONE.try_sub(k.try_mul(slope)?.try_div(two.try_mul(w)?)?)?;
+    let wings = slope
+        .try_mul(slope)?
+        .try_div(four)?
+        .try_mul(Decimal::ONE.try_div(w)?.try_add(Decimal::new(25, 2))?)?;
+    skew.try_mul(skew)?
+        .try_sub(wings)?
+        .try_add(curvature.try_div(two)?)
+}
+
+/// x^y for positive x.
+fn power(x: Decimal, y: Decimal) -> Result<Decimal, ArithmeticError> {
+    if y.is_zero() {
+        return Ok(Decimal::ONE);
+    }
+    y.try_mul(x.try_ln()?)?.try_exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn grid() -> [Decimal; 31] {
+        let mut ks = [Decimal::ZERO; 31];
+        for (i, k) in ks.iter_mut().enumerate() {
+            *k = Decimal::new(i as i64 - 15, 1);
+        }
+        ks
+    }
+
+    fn svi(expiry: &str) -> Svi {
+        Svi::new(
+            decimal("0.02"),
+            decimal("0.1"),
+            decimal("-0.4"),
+            decimal("0.05"),
+            decimal("0.2"),
+            decimal(expiry),
+        )
+        .unwrap()
+    }
+
+    fn quotes_from<M: Smile>(smile: &M, forward: Decimal) -> [SmileQuote; 9] {
+        let mut quotes = [SmileQuote::new(Decimal::ZERO, Decimal::ZERO); 9];
+        for (i, quote) in quotes.iter_mut().enumerate() {
+            let k = Decimal::new(i as i64 - 4, 1);
+            *quote = SmileQuote::new(forward * k.exp().unwrap(), smile.volatility(k).unwrap());
+        }
+        quotes
+    }
+
+    #[test]
+    fn test_svi_natural_round_trip() {
+        let raw = svi("0.5");
+        let natural = raw.natural().unwrap();
+        let back = Svi::from_natural(&natural, decimal("0.5")).unwrap();
+        for k in grid() {
+            let diff = back.total_variance(k).unwrap() - raw.total_variance(k).unwrap();
+            assert!(diff.abs() < decimal("0.0000000001"));
+        }
+    }
+
+    #[test]
+    fn test_svi_validation() {
+        let t = decimal("1");
+        let z = Decimal::ZERO;
+        assert!(Svi::new(z, decimal("-0.1"), z, z, decimal("0.1"), t).is_err());
+        assert!(Svi::new(z, decimal("0.1"), decimal("1"), z, decimal("0.1"), t).is_err());
+        assert_eq!(
+            Svi::new(decimal("-1"), decimal("0.1"), z, z, decimal("0.1"), t).unwrap_err(),
+            ArithmeticError::NegativeSqrt
+        );
+    }
+
+    #[test]
+    fn test_svi_fit_recovers_smile() {
+        let forward = decimal("100");
+        let target = svi("0.5");
+        let quotes = quotes_from(&target, forward);
+
+        let fit = Svi::fit(forward, decimal("0.5"), &quotes, None).unwrap();
+        assert!(fit.converged);
+        for quote in quotes {
+            let k = (quote.strike / forward).ln().unwrap();
+            let vol = fit.curve.volatility(k).unwrap();
+            assert!((vol - quote.volatility).abs() < decimal("0.0001"));
+        }
+    }
+
+    #[test]
+    fn test_sabr_lognormal_limit_and_atm_continuity() {
+        // β = 1 and ν = 0 is Black-Scholes with volatility α
+        let flat = Sabr::new(
+            decimal("0.3"),
+            Decimal::ONE,
+            Decimal::ZERO,
+            Decimal::ZERO,
+            decimal("100"),
+            Decimal::ONE,
+        )
+        .unwrap();
+        assert_eq!(
+            flat.implied_volatility(decimal("80")).unwrap(),
+            decimal("0.3")
+        );
+
+        let sabr = Sabr::new(
+            decimal("0.5"),
+            decimal("0.7"),
+            decimal("-0.3"),
+            decimal("0.8"),
+            decimal("100"),
+            Decimal::ONE,
+        )
+        .unwrap();
+        let atm = sabr.implied_volatility(decimal("100")).unwrap();
+        let near = sabr.implied_volatility(decimal("100.000001")).unwrap();
+        assert!((atm - near).abs() < decimal("0.000001"));
+        // Negative correlation skews vol towards low strikes
+        assert!(
+            sabr.implied_volatility(decimal("80")).unwrap()
+                > sabr.implied_volatility(decimal("120")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_sabr_fit_recovers_smile() {
+        let forward = decimal("100");
+        let target = Sabr::new(
+            decimal("0.5"),
+            decimal("0.7"),
+            decimal("-0.3"),
+            decimal("0.8"),
+            forward,
+            Decimal::ONE,
+        )
+        .unwrap();
+        let quotes = quotes_from(&target, forward);
+
+        let fit = Sabr::fit(forward, Decimal::ONE, decimal("0.7"), &quotes, None).unwrap();
+        assert!(fit.converged);
+        assert!((fit.curve.rho() - target.rho()).abs() < decimal("0.001"));
+        assert!((fit.curve.nu() - target.nu()).abs() < decimal("0.001"));
+    }
+
+    #[test]
+    fn test_butterfly_arbitrage() {
+        assert_eq!(butterfly_arbitrage(&svi("1"), &grid()).unwrap(), None);
+
+        // Axel Vogt's example: arbitrage in the right wing
+        let vogt = Svi::new(
+            decimal("-0.041"),
+            decimal("0.1331"),
+            decimal("0.306"),
+            decimal("0.3586"),
+            decimal("0.4153"),
+            Decimal::ONE,
+        )
+        .unwrap();
+        assert!(butterfly_arbitrage(&vogt, &grid()).unwrap().is_some());
+
+        // Finite differences agree with the analytical SVI density
+        let slice = SmileSlice::from(svi("1"));
+        let k = decimal("0.3");
+        let analytical = slice.butterfly_density(k).unwrap();
+        let w = |x: Decimal| svi("1").total_variance(x).unwrap();
+        let h = decimal("0.0001");
+        let slope = (w(k + h) - w(k - h)) / (h + h);
+        let curvature = (w(k + h) - w(k) - w(k) + w(k - h)) / (h * h);
+        let fd = density(k, w(k), slope, curvature).unwrap();
+        assert!((analytical - fd).abs() < decimal("0.000001"));
+    }
+
+    #[test]
+    fn test_calendar_arbitrage() {
+        let near = svi("0.25");
+        let far = Svi::new(
+            decimal("0.04"),
+            decimal("0.1"),
+            decimal("-0.4"),
+            decimal("0.05"),
+            decimal("0.2"),
+            decimal("1"),
+        )
+        .unwrap();
+        assert_eq!(calendar_arbitrage(&near, &far, &grid()).unwrap(), None);
+        assert!(calendar_arbitrage(&far, &near, &grid()).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_surface_prices_with_smile() {
+        let spot = decimal("100");
+        let rate = decimal("0.05");
+        let mut surface = VolatilitySurface::new();
+        surface.add_slice(svi("0.25").into()).unwrap();
+        surface
+            .add_slice(
+                Svi::new(
+                    decimal("0.04"),
+                    decimal("0.1"),
+                    decimal("-0.4"),
+                    decimal("0.05"),
+                    decimal("0.2"),
+                    decimal("1"),
+                )
+                .unwrap()
+                .into(),
+            )
+            .unwrap();
+        assert!(surface.add_slice(svi("1").into()).is_err());
+        assert!(surface.is_arbitrage_free(&grid()).unwrap());
+
+        // Downside skew: low strikes carry more vol
+        let low = surface
+            .volatility(spot, decimal("80"), rate, decimal("0.5"))
+            .unwrap();
+        let high = surface
+            .volatility(spot, decimal("120"), rate, decimal("0.5"))
+            .unwrap();
+        assert!(low > high);
+
+        // Prices use the strike's own volatility
+        let params = surface
+            .option_params(spot, decimal("90"), rate, decimal("0.5"))
+            .unwrap();
+        assert_eq!(
+            surface
+                .price(spot, decimal("90"), rate, decimal("0.5"), false)
+                .unwrap(),
+            black_scholes_put(&params).unwrap()
+        );
+
+        // Total variance interpolates linearly between slices
+        let k = decimal("0.1");
+        let w = surface.total_variance(k, decimal("0.625")).unwrap();
+        let w0 = surface.slices()[0].total_variance(k).unwrap();
+        let w1 = surface.slices()[1].total_variance(k).unwrap();
+        assert_eq!(w, w0 + (w1 - w0) * decimal("0.5"));
+
+        // The interpolator view takes log-moneyness against the forward
+        let forward = spot * (rate * decimal("0.5")).exp().unwrap();
+        let k = (decimal("90") / forward).ln().unwrap();
+        assert_eq!(
+            surface.interpolate(k, decimal("0.5")).unwrap(),
+            params.volatility
+        );
+    }
+
+    #[test]
+    fn test_svi_fit_quote_capacity() {
+        let quotes = [SmileQuote::new(decimal("100"), decimal("0.2")); MAX_FIT_QUOTES + 1];
+        assert_eq!(
+            Svi::fit(decimal("100"), decimal("0.5"), &quotes, None).unwrap_err(),
+            ArithmeticError::Overflow
+        );
+    }
+}
@@ -0,0 +1,578 @@
+//! Multi-leg option strategies and payoff analysis.
+//!
+//! An [`OptionStrategy`] combines calls, puts and the underlying on one
+//! market (spot, rate, yield, volatility and expiry) and reports:
+//!
+//! - net premium and aggregated Greeks
+//! - payoff and profit at expiry, for a single price or over a grid
+//! - breakeven prices, maximum profit and maximum loss
+//! - probability of profit under the risk-neutral lognormal distribution
+//!
+//! Profit is payoff at expiry less the premium and underlying cost paid
+//! today, without interest on the premium. The expiry payoff is piecewise
+//! linear with kinks at the strikes, so breakevens and extremes are exact.
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::options::{GeneralizedParams, OptionStrategy};
+//! use financial_calc::storage::Buffer;
+//! use precision_core::Decimal;
+//!
+//! let market = GeneralizedParams {
+//!     spot: Decimal::from(2000i64),
+//!     rate: Decimal::new(5, 2),
+//!     time: Decimal::new(25, 2),
+//!     volatility: Decimal::new(6, 1),
+//!     ..Default::default()
+//! };
+//!
+//! let strike = Decimal::from(2000i64);
+//! let straddle = OptionStrategy::straddle(market, strike, Decimal::ONE).unwrap();
+//! let breakevens = straddle.breakevens().unwrap();
+//! assert_eq!(breakevens.as_slice().len(), 2);
+//! assert_eq!(straddle.max_profit().unwrap(), None); // unlimited upside
+//! ```
+
+use super::{
+    generalized_price, normal_cdf, GeneralizedParams, OptionLeg, OptionPortfolio, PortfolioGreeks,
+    MAX_PORTFOLIO_LEGS,
+};
+use crate::storage::{self, Buffer, Fixed, Storage};
+use precision_core::{ArithmeticError, Decimal};
+
+/// A multi-leg position in options and the underlying on one market.
+#[derive(Debug, Clone)]
+pub struct OptionStrategy<S: Storage = Fixed<MAX_PORTFOLIO_LEGS>> {
+    market: GeneralizedParams,
+    portfolio: OptionPortfolio<S>,
+}
+
+impl OptionStrategy {
+    /// Creates an empty strategy with default fixed storage.
+    ///
+    /// `market.strike` is ignored; each leg sets its own.
+    pub fn new(market: GeneralizedParams) -> Self {
+        Self::with_storage(market)
+    }
+
+    /// Long `long_strike`, short `short_strike`, both calls or both puts.
+    ///
+    /// A bull call spread buys the lower strike call; a bear put spread
+    /// buys the higher strike put.
+    pub fn vertical_spread(
+        market: GeneralizedParams,
+        long_strike: Decimal,
+        short_strike: Decimal,
+        is_call: bool,
+        quantity: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        let mut strategy = Self::new(market);
+        strategy.add_option(long_strike, is_call, quantity)?;
+        strategy.add_option(short_strike, is_call, -quantity)?;
+        Ok(strategy)
+    }
+
+    /// Long a call and a put at the same strike.
+    pub fn straddle(
+        market: GeneralizedParams,
+        strike: Decimal,
+        quantity: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        Self::strangle(market, strike, strike, quantity)
+    }
+
+    /// Long a put and a call at or above the put strike.
+    pub fn strangle(
+        market: GeneralizedParams,
+        put_strike: Decimal,
+        call_strike: Decimal,
+        quantity: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        if call_strike < put_strike {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let mut strategy = Self::new(market);
+        strategy.add_option(put_strike, false, quantity)?;
+        strategy.add_option(call_strike, true, quantity)?;
+        Ok(strategy)
+    }
+
+    /// Long the wings, short the body: +1 `low`, −2 `middle`, +1 `high`.
+    pub fn butterfly(
+        market: GeneralizedParams,
+        low: Decimal,
+        middle: Decimal,
+        high: Decimal,
+        is_call: bool,
+        quantity: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        if !(low < middle && middle < high) {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let mut strategy = Self::new(market);
+        strategy.add_option(low, is_call, quantity)?;
+        strategy.add_option(middle, is_call, quantity.try_mul(Decimal::from(-2i64))?)?;
+        strategy.add_option(high, is_call, quantity)?;
+        Ok(strategy)
+    }
+
+    /// Short put spread plus short call spread, collecting a credit.
+    ///
+    /// Strikes are in increasing order: long put, short put, short call,
+    /// long call.
+    pub fn iron_condor(
+        market: GeneralizedParams,
+        strikes: [Decimal; 4],
+        quantity: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        if strikes.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let mut strategy = Self::new(market);
+        strategy.add_option(strikes[0], false, quantity)?;
+        strategy.add_option(strikes[1], false, -quantity)?;
+        strategy.add_option(strikes[2], true, -quantity)?;
+        strategy.add_option(strikes[3], true, quantity)?;
+        Ok(strategy)
+    }
+
+    /// Long the underlying and short a call against it.
+    pub fn covered_call(
+        market: GeneralizedParams,
+        strike: Decimal,
+        quantity: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        let mut strategy = Self::new(market);
+        strategy.add_underlying(quantity)?;
+        strategy.add_option(strike, true, -quantity)?;
+        Ok(strategy)
+    }
+
+    /// Long the underlying, long a protective put and short a call above it.
+    pub fn collar(
+        market: GeneralizedParams,
+        put_strike: Decimal,
+        call_strike: Decimal,
+        quantity: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        if call_strike <= put_strike {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let mut strategy = Self::new(market);
+        strategy.add_underlying(quantity)?;
+        strategy.add_option(put_strike, false, quantity)?;
+        strategy.add_option(call_strike, true, -quantity)?;
+        Ok(strategy)
+    }
+}
+
+impl<S: Storage> OptionStrategy<S> {
+    /// Creates an empty strategy with the chosen storage.
+    pub fn with_storage(market: GeneralizedParams) -> Self {
+        Self {
+            market,
+            portfolio: OptionPortfolio::with_storage(),
+        }
+    }
+
+    /// Adds a signed quantity of calls or puts at `strike`.
+    ///
+    /// Returns `Overflow` if the storage is full.
+    pub fn add_option(
+        &mut self,
+        strike: Decimal,
+        is_call: bool,
+        quantity: Decimal,
+    ) -> Result<(), ArithmeticError> {
+        if !strike.is_positive() {
+            return Err(ArithmeticError::LogOfNegative);
+        }
+        let params = GeneralizedParams {
+            strike,
+            ..self.market
+        };
+        self.portfolio.add_option(params, is_call, quantity)
+    }
+
+    /// Adds a signed quantity of the underlying, bought at the market spot.
+    pub fn add_underlying(&mut self, quantity: Decimal) -> Result<(), ArithmeticError> {
+        self.portfolio.add_underlying(quantity)
+    }
+
+    /// Market the strategy is priced on.
+    pub fn market(&self) -> &GeneralizedParams {
+        &self.market
+    }
+
+    /// Option legs.
+    pub fn legs(&self) -> &[OptionLeg] {
+        self.portfolio.legs()
+    }
+
+    /// Net units of the underlying held.
+    pub fn underlying(&self) -> Decimal {
+        self.portfolio.underlying()
+    }
+
+    /// Net option premium: positive for a net debit, negative for a credit.
+    pub fn net_premium(&self) -> Result<Decimal, ArithmeticError> {
+        let mut total = Decimal::ZERO;
+        for leg in self.legs() {
+            let price = generalized_price(&leg.params, leg.is_call)?;
+            total = total.try_add(leg.quantity.try_mul(price)?)?;
+        }
+        Ok(total)
+    }
+
+    /// Net cost to open: premium plus underlying bought at spot.
+    pub fn cost(&self) -> Result<Decimal, ArithmeticError> {
+        self.net_premium()?
+            .try_add(self.underlying().try_mul(self.market.spot)?)
+    }
+
+    /// Sum of quantity-weighted Greeks, including the underlying's delta.
+    pub fn greeks(&self) -> Result<PortfolioGreeks, ArithmeticError> {
+        self.portfolio.greeks()
+    }
+
+    /// Value of the position at expiry for an underlying price.
+    pub fn payoff(&self, price: Decimal) -> Result<Decimal, ArithmeticError> {
+        let mut total = self.underlying().try_mul(price)?;
+        for leg in self.legs() {
+            let intrinsic = if leg.is_call {
+                price.try_sub(leg.params.strike)?
+            } else {
+                leg.params.strike.try_sub(price)?
+            };
+            let value = leg.quantity.try_mul(intrinsic.max(Decimal::ZERO))?;
+            total = total.try_add(value)?;
+        }
+        Ok(total)
+    }
+
+    /// Profit at expiry: payoff less the cost to open.
+    pub fn profit(&self, price: Decimal) -> Result<Decimal, ArithmeticError> {
+        self.payoff(price)?.try_sub(self.cost()?)
+    }
+
+    /// Profit at expiry for each price in `prices`, written to `profits`.
+    ///
+    /// Returns `DivisionByZero` if the slices differ in length.
+    pub fn profit_profile(
+        &self,
+        prices: &[Decimal],
+        profits: &mut [Decimal],
+    ) -> Result<(), ArithmeticError> {
+        if prices.len() != profits.len() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let cost = self.cost()?;
+        for (price, profit) in prices.iter().zip(profits.iter_mut()) {
+            *profit = self.payoff(*price)?.try_sub(cost)?;
+        }
+        Ok(())
+    }
+
+    /// Prices at expiry where the position moves between profit and loss,
+    /// in increasing order.
+    pub fn breakevens(&self) -> Result<S::Buffer<Decimal>, ArithmeticError> {
+        let kinks = self.kinks()?;
+        let kinks = kinks.as_slice();
+        let cost = self.cost()?;
+        let mut roots = S::Buffer::<Decimal>::default();
+
+        let mut x0 = Decimal::ZERO;
+        let mut p0 = self.payoff(x0)?.try_sub(cost)?;
+        for &x1 in kinks {
+            let p1 = self.payoff(x1)?.try_sub(cost)?;
+            if p0.is_positive() != p1.is_positive() {
+                // Linear between kinks
+                let root = x0.try_add(x1.try_sub(x0)?.try_mul(p0.try_div(p0.try_sub(p1)?)?)?)?;
+                push_unique(&mut roots, root)?;
+            }
+            x0 = x1;
+            p0 = p1;
+        }
+
+        let slope = self.terminal_slope()?;
+        if !slope.is_zero() && p0.is_positive() != slope.is_positive() {
+            push_unique(&mut roots, x0.try_sub(p0.try_div(slope)?)?)?;
+        }
+        Ok(roots)
+    }
+
+    /// Highest profit at expiry, or `None` if unlimited.
+    pub fn max_profit(&self) -> Result<Option<Decimal>, ArithmeticError> {
+        if self.terminal_slope()?.is_positive() {
+            return Ok(None);
+        }
+        let (_, high) = self.profit_range()?;
+        Ok(Some(high))
+    }
+
+    /// Largest loss at expiry as a positive amount, or `None` if unlimited.
+    ///
+    /// Zero if the position cannot lose.
+    pub fn max_loss(&self) -> Result<Option<Decimal>, ArithmeticError> {
+        if self.terminal_slope()?.is_negative() {
+            return Ok(None);
+        }
+        let (low, _) = self.profit_range()?;
+        Ok(Some((-low).max(Decimal::ZERO)))
+    }
+
+    /// Probability that profit at expiry is positive when the underlying
+    /// follows the risk-neutral lognormal distribution of the market.
+    pub fn probability_of_profit(&self) -> Result<Decimal, ArithmeticError> {
+        let roots = self.breakevens()?;
+        let roots = roots.as_slice();
+        let mut total = Decimal::ZERO;
+        let mut low = Decimal::ZERO;
+        for i in 0..=roots.len() {
+            let high = roots.get(i).copied();
+            let probe = match high {
+                Some(high) => low.try_add(high)?.try_div(Decimal::from(2i64))?,
+                None => low.try_mul(Decimal::from(2i64))?.try_add(Decimal::ONE)?,
+            };
+            if self.profit(probe)?.is_positive() {
+                let upper = match high {
+                    Some(high) => self.terminal_cdf(high)?,
+                    None => Decimal::ONE,
+                };
+                total = total.try_add(upper.try_sub(self.terminal_cdf(low)?)?)?;
+            }
+            if let Some(high) = high {
+                low = high;
+            }
+        }
+        Ok(total.max(Decimal::ZERO).min(Decimal::ONE))
+    }
+
+    /// P(S_T ≤ x) under the risk-neutral lognormal distribution.
+    fn terminal_cdf(&self, x: Decimal) -> Result<Decimal, ArithmeticError> {
+        if !x.is_positive() {
+            return Ok(Decimal::ZERO);
+        }
+        let m = &self.market;
+        let std_dev = m.volatility.try_mul(m.time.try_sqrt()?)?;
+        let log_ratio = x.try_div(m.spot)?.try_ln()?;
+        let half_var = m
+            .volatility
+            .try_mul(m.volatility)?
+            .try_div(Decimal::from(2i64))?;
+        let drift = m.cost_of_carry()?.try_sub(half_var)?.try_mul(m.time)?;
+        if std_dev.is_zero() {
+            return Ok(if log_ratio >= drift {
+                Decimal::ONE
+            } else {
+                Decimal::ZERO
+            });
+        }
+        normal_cdf(log_ratio.try_sub(drift)?.try_div(std_dev)?)
+    }
+
+    /// Distinct positive strikes in increasing order.
+    fn kinks(&self) -> Result<S::Buffer<Decimal>, ArithmeticError> {
+        let mut kinks = S::Buffer::<Decimal>::default();
+        for leg in self.legs() {
+            let strike = leg.params.strike;
+            if !kinks.as_slice().contains(&strike) {
+                storage::insert_sorted(&mut kinks, strike, |k| *k)?;
+            }
+        }
+        Ok(kinks)
+    }
+
+    /// Slope of the payoff above the highest strike.
+    fn terminal_slope(&self) -> Result<Decimal, ArithmeticError> {
+        self.legs()
+            .iter()
+            .filter(|leg| leg.is_call)
+            .try_fold(self.underlying(), |acc, leg| acc.try_add(leg.quantity))
+    }
+
+    /// Lowest and highest profit over zero and the strikes.
+    fn profit_range(&self) -> Result<(Decimal, Decimal), ArithmeticError> {
+        let cost = self.cost()?;
+        let start = self.payoff(Decimal::ZERO)?.try_sub(cost)?;
+        let mut range = (start, start);
+        for &x in self.kinks()?.as_slice() {
+            let p = self.payoff(x)?.try_sub(cost)?;
+            range = (range.0.min(p), range.1.max(p));
+        }
+        Ok(range)
+    }
+}
+
+fn push_unique<B: Buffer<Decimal>>(buffer: &mut B, value: Decimal) -> Result<(), ArithmeticError> {
+    if buffer.as_slice().last() == Some(&value) {
+        return Ok(());
+    }
+    buffer.push(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn market() -> GeneralizedParams {
+        GeneralizedParams {
+            spot: decimal("100"),
+            strike: Decimal::ZERO,
+            rate: decimal("0.05"),
+            yield_rate: Decimal::ZERO,
+            time: decimal("0.5"),
+            volatility: decimal("0.3"),
+        }
+    }
+
+    fn close(a: Decimal, b: Decimal) -> bool {
+        (a - b).abs() < decimal("0.0000001")
+    }
+
+    #[test]
+    fn test_bull_call_spread() {
+        let spread = OptionStrategy::vertical_spread(
+            market(),
+            decimal("95"),
+            decimal("110"),
+            true,
+            Decimal::ONE,
+        )
+        .unwrap();
+        let debit = spread.net_premium().unwrap();
+        assert!(debit.is_positive() && debit < decimal("15"));
+
+        assert!(close(
+            spread.max_profit().unwrap().unwrap(),
+            decimal("15") - debit
+        ));
+        assert!(close(spread.max_loss().unwrap().unwrap(), debit));
+        let breakevens = spread.breakevens().unwrap();
+        assert_eq!(breakevens.as_slice().len(), 1);
+        assert!(close(breakevens.as_slice()[0], decimal("95") + debit));
+        assert!(close(
+            spread.profit(breakevens.as_slice()[0]).unwrap(),
+            Decimal::ZERO
+        ));
+
+        let greeks = spread.greeks().unwrap().greeks;
+        assert!(greeks.delta.is_positive() && greeks.delta < Decimal::ONE);
+    }
+
+    #[test]
+    fn test_straddle_and_strangle() {
+        let straddle = OptionStrategy::straddle(market(), decimal("100"), Decimal::ONE).unwrap();
+        let premium = straddle.net_premium().unwrap();
+        let roots = straddle.breakevens().unwrap();
+        assert_eq!(roots.as_slice().len(), 2);
+        assert!(close(roots.as_slice()[0], decimal("100") - premium));
+        assert!(close(roots.as_slice()[1], decimal("100") + premium));
+        assert_eq!(straddle.max_profit().unwrap(), None);
+        assert!(close(straddle.max_loss().unwrap().unwrap(), premium));
+
+        // Short strangle: limited profit, unlimited loss
+        let short =
+            OptionStrategy::strangle(market(), decimal("90"), decimal("110"), -Decimal::ONE)
+                .unwrap();
+        assert!(short.net_premium().unwrap().is_negative());
+        assert_eq!(short.max_loss().unwrap(), None);
+        assert!(close(
+            short.max_profit().unwrap().unwrap(),
+            -short.net_premium().unwrap()
+        ));
+        assert!(
+            OptionStrategy::strangle(market(), decimal("110"), decimal("90"), Decimal::ONE)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_iron_condor_and_butterfly() {
+        let strikes = [decimal("80"), decimal("90"), decimal("110"), decimal("120")];
+        let condor = OptionStrategy::iron_condor(market(), strikes, Decimal::ONE).unwrap();
+        let credit = -condor.net_premium().unwrap();
+        assert!(credit.is_positive());
+        assert!(close(condor.max_profit().unwrap().unwrap(), credit));
+        assert!(close(
+            condor.max_loss().unwrap().unwrap(),
+            decimal("10") - credit
+        ));
+        let roots = condor.breakevens().unwrap();
+        assert_eq!(roots.as_slice().len(), 2);
+        assert!(close(roots.as_slice()[0], decimal("90") - credit));
+        assert!(close(roots.as_slice()[1], decimal("110") + credit));
+
+        let mut prices = [Decimal::ZERO; 5];
+        for (i, p) in prices.iter_mut().enumerate() {
+            *p = Decimal::from(70 + 15 * i as i64);
+        }
+        let mut profits = [Decimal::ZERO; 5];
+        condor.profit_profile(&prices, &mut profits).unwrap();
+        assert!(close(profits[2], credit));
+        assert_eq!(profits[0], profits[4]);
+        assert!(condor.profit_profile(&prices, &mut profits[..4]).is_err());
+
+        let fly = OptionStrategy::butterfly(
+            market(),
+            decimal("90"),
+            decimal("100"),
+            decimal("110"),
+            true,
+            Decimal::ONE,
+        )
+        .unwrap();
+        assert!(close(fly.payoff(decimal("100")).unwrap(), decimal("10")));
+        assert_eq!(fly.payoff(decimal("120")).unwrap(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_covered_call_and_collar() {
+        let covered = OptionStrategy::covered_call(market(), decimal("110"), Decimal::ONE).unwrap();
+        assert!(covered.max_profit().unwrap().is_some());
+        // Loss bounded by the stock cost less the call premium
+        let loss = covered.max_loss().unwrap().unwrap();
+        assert!(close(loss, covered.cost().unwrap()));
+        let delta = covered.greeks().unwrap().greeks.delta;
+        assert!(delta.is_positive() && delta < Decimal::ONE);
+
+        let collar =
+            OptionStrategy::collar(market(), decimal("90"), decimal("110"), Decimal::ONE).unwrap();
+        let cost = collar.cost().unwrap();
+        assert!(close(
+            collar.max_loss().unwrap().unwrap(),
+            cost - decimal("90")
+        ));
+        assert!(close(
+            collar.max_profit().unwrap().unwrap(),
+            decimal("110") - cost
+        ));
+    }
+
+    #[test]
+    fn test_probability_of_profit() {
+        // A long call profits when S_T > K + premium
+        let mut call = OptionStrategy::new(market());
+        call.add_option(decimal("100"), true, Decimal::ONE).unwrap();
+        let breakeven = decimal("100") + call.net_premium().unwrap();
+        let expected = Decimal::ONE - call.terminal_cdf(breakeven).unwrap();
+        assert!(close(call.probability_of_profit().unwrap(), expected));
+
+        // Long and short straddles partition the outcomes
+        let long = OptionStrategy::straddle(market(), decimal("100"), Decimal::ONE).unwrap();
+        let short = OptionStrategy::straddle(market(), decimal("100"), -Decimal::ONE).unwrap();
+        let total = long.probability_of_profit().unwrap() + short.probability_of_profit().unwrap();
+        assert!(close(total, Decimal::ONE));
+
+        // The terminal distribution has the forward as its mean, so its
+        // median sits below the forward
+        let forward = decimal("100") * decimal("0.025").exp().unwrap();
+        assert!(call.terminal_cdf(forward).unwrap() > decimal("0.5"));
+    }
+}
@@ -8,6 +8,7 @@
 //! - [`newton_raphson`]: Fast convergence with derivative, best for smooth functions
 //! - [`brent`]: Guaranteed convergence without derivatives, robust fallback
 //! - [`bisection`]: Simple bracketing method, always converges
+//! - [`levenberg_marquardt`]: Non-linear least squares for curve calibration
 
 use precision_core::{ArithmeticError, Decimal};
 
@@ -467,6 +468,288 @@ where
     })
 }
 
+/// Result of a least-squares fit.
+#[derive(Debug, Clone)]
+pub struct LeastSquaresResult<const N: usize> {
+    /// The fitted parameters.
+    pub params: [Decimal; N],
+    /// Number of accepted iterations used.
+    pub iterations: u32,
+    /// Sum of squared residuals at the fitted parameters.
+    pub residual: Decimal,
+    /// Whether convergence was achieved.
+    pub converged: bool,
+}
+
+/// Levenberg-Marquardt method for non-linear least squares.
+///
+/// Minimises `sum(r_i(p)^2)` over `i in 0..count`, where `residual(i, p)`
+/// returns the i-th residual for parameter vector `p`. The Jacobian is
+/// approximated with forward differences, so no derivatives are required.
+///
+/// Residuals are evaluated one at a time, which keeps the solver heap-free
+/// regardless of the number of observations. If a residual evaluation fails
+/// at a trial point (for example because a parameter left its valid domain),
+/// the step is rejected and the damping increased.
+///
+/// # Arguments
+/// * `residual` - Function returning the i-th residual for a parameter vector
+/// * `count` - Number of residuals (observations)
+/// * `x0` - Initial parameter guess
+/// * `tolerance` - Convergence tolerance on the relative reduction of the
+///   sum of squares, the relative step size and the gradient
+/// * `max_iter` - Maximum number of iterations
+///
+/// The reduction and step size tests only count for steps taken with no
+/// more damping than at the start, since heavy damping makes any step
+/// small. If no damped step reduces the sum of squares before a test is
+/// met, the fit has stalled and is reported with `converged: false`.
+///
+/// # Example
+///
+/// ```
+/// use financial_calc::solver::levenberg_marquardt;
+/// use precision_core::Decimal;
+///
+/// // Fit y = a + b*x to points lying exactly on y = 1 + 2x
+/// let xs = [Decimal::ZERO, Decimal::ONE, Decimal::from(2i64)];
+/// let ys = [Decimal::ONE, Decimal::from(3i64), Decimal::from(5i64)];
+/// let r = |i: usize, p: &[Decimal; 2]| p[0].try_add(p[1].try_mul(xs[i])?)?.try_sub(ys[i]);
+///
+/// let result = levenberg_marquardt(r, 3, [Decimal::ZERO, Decimal::ZERO], None, None).unwrap();
+/// assert!(result.converged);
+/// ```
+pub fn levenberg_marquardt<F, const N: usize>(
+    residual: F,
+    count: usize,
+    x0: [Decimal; N],
+    tolerance: Option<Decimal>,
+    max_iter: Option<u32>,
+) -> Result<LeastSquaresResult<N>, ArithmeticError>
+where
+    F: Fn(usize, &[Decimal; N]) -> Result<Decimal, ArithmeticError>,
+{
+    let tol = tolerance.unwrap_or_else(default_tolerance);
+    let max = max_iter.unwrap_or(DEFAULT_MAX_ITER);
+
+    if count == 0 || N == 0 {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+
+    let initial_lambda = Decimal::new(1, 3);
+    let max_lambda = Decimal::from(10_000_000_000i64);
+    let ten = Decimal::TEN;
+
+    let mut x = x0;
+    let mut cost = sum_of_squares(&residual, count, &x)?;
+    let mut lambda = initial_lambda;
+    let mut iterations = 0;
+
+    while iterations < max {
+        if cost.is_zero() {
+            return Ok(LeastSquaresResult {
+                params: x,
+                iterations,
+                residual: cost,
+                converged: true,
+            });
+        }
+
+        let (jtj, jtr) = normal_equations(&residual, count, &x)?;
+
+        let gradient = jtr.iter().fold(Decimal::ZERO, |acc, g| acc.max(g.abs()));
+        if gradient < tol {
+            return Ok(LeastSquaresResult {
+                params: x,
+                iterations,
+                residual: cost,
+                converged: true,
+            });
+        }
+
+        // Damped step: (JᵀJ + λ·diag(JᵀJ)) δ = -Jᵀr
+        let mut accepted = false;
+        while lambda <= max_lambda {
+            let mut a = jtj;
+            for (i, row) in a.iter_mut().enumerate() {
+                let diag = jtj[i][i].max(Decimal::new(1, 12));
+                row[i] = row[i].try_add(lambda.try_mul(diag)?)?;
+            }
+            let mut rhs = [Decimal::ZERO; N];
+            for (r, g) in rhs.iter_mut().zip(jtr.iter()) {
+                *r = -*g;
+            }
+
+            let delta = match solve_linear_system(a, rhs) {
+                Ok(delta) => delta,
+                Err(_) => {
+                    lambda = lambda.try_mul(ten)?;
+                    continue;
+                }
+            };
+
+            let mut trial = x;
+            for (t, d) in trial.iter_mut().zip(delta.iter()) {
+                *t = t.try_add(*d)?;
+            }
+
+            match sum_of_squares(&residual, count, &trial) {
+                Ok(trial_cost) if trial_cost < cost => {
+                    let reduction = cost.try_sub(trial_cost)?;
+                    let mut small_step = true;
+                    for (d, t) in delta.iter().zip(trial.iter()) {
+                        small_step &= d.abs() <= tol.try_mul(t.abs().try_add(tol)?)?;
+                    }
+                    let converged = lambda <= initial_lambda
+                        && (small_step || reduction <= tol.try_mul(cost)?);
+                    x = trial;
+                    cost = trial_cost;
+                    lambda = lambda.try_div(ten)?.max(Decimal::new(1, 12));
+                    iterations += 1;
+                    accepted = true;
+
+                    if converged {
+                        return Ok(LeastSquaresResult {
+                            params: x,
+                            iterations,
+                            residual: cost,
+                            converged: true,
+                        });
+                    }
+                    break;
+                }
+                _ => {
+                    lambda = lambda.try_mul(ten)?;
+                }
+            }
+        }
+
+        if !accepted {
+            // Every damped step failed while the gradient is still above
+            // tolerance, so the fit has stalled rather than converged.
+            return Ok(LeastSquaresResult {
+                params: x,
+                iterations,
+                residual: cost,
+                converged: false,
+            });
+        }
+    }
+
+    Ok(LeastSquaresResult {
+        params: x,
+        iterations,
+        residual: cost,
+        converged: false,
+    })
+}
+
+fn sum_of_squares<F, const N: usize>(
+    residual: &F,
+    count: usize,
+    x: &[Decimal; N],
+) -> Result<Decimal, ArithmeticError>
+where
+    F: Fn(usize, &[Decimal; N]) -> Result<Decimal, ArithmeticError>,
+{
+    let mut sum = Decimal::ZERO;
+    for i in 0..count {
+        let r = residual(i, x)?;
+        sum = sum.try_add(r.try_mul(r)?)?;
+    }
+    Ok(sum)
+}
+
+/// Accumulates JᵀJ and Jᵀr using a forward-difference Jacobian.
+#[allow(clippy::type_complexity)]
+fn normal_equations<F, const N: usize>(
+    residual: &F,
+    count: usize,
+    x: &[Decimal; N],
+) -> Result<([[Decimal; N]; N], [Decimal; N]), ArithmeticError>
+where
+    F: Fn(usize, &[Decimal; N]) -> Result<Decimal, ArithmeticError>,
+{
+    let mut steps = [Decimal::ZERO; N];
+    for (h, xj) in steps.iter_mut().zip(x.iter()) {
+        *h = xj
+            .abs()
+            .try_mul(Decimal::new(1, 7))?
+            .max(Decimal::new(1, 9));
+    }
+
+    let mut jtj = [[Decimal::ZERO; N]; N];
+    let mut jtr = [Decimal::ZERO; N];
+
+    for i in 0..count {
+        let r = residual(i, x)?;
+
+        let mut row = [Decimal::ZERO; N];
+        for j in 0..N {
+            let mut bumped = *x;
+            bumped[j] = bumped[j].try_add(steps[j])?;
+            let r_bumped = residual(i, &bumped)?;
+            row[j] = r_bumped.try_sub(r)?.try_div(steps[j])?;
+        }
+
+        for j in 0..N {
+            jtr[j] = jtr[j].try_add(row[j].try_mul(r)?)?;
+            for k in 0..N {
+                jtj[j][k] = jtj[j][k].try_add(row[j].try_mul(row[k])?)?;
+            }
+        }
+    }
+
+    Ok((jtj, jtr))
+}
+
+/// Solves the dense linear system `a * x = b` by Gaussian elimination with
+/// partial pivoting.
+///
+/// Returns `DivisionByZero` if the matrix is singular to working precision.
+pub(crate) fn solve_linear_system<const N: usize>(
+    mut a: [[Decimal; N]; N],
+    mut b: [Decimal; N],
+) -> Result<[Decimal; N], ArithmeticError> {
+    for col in 0..N {
+        // Partial pivoting
+        let mut pivot = col;
+        for row in col + 1..N {
+            if a[row][col].abs() > a[pivot][col].abs() {
+                pivot = row;
+            }
+        }
+        if a[pivot][col].abs() < Decimal::new(1, 24) {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        for row in col + 1..N {
+            let factor = a[row][col].try_div(a[col][col])?;
+            if factor.is_zero() {
+                continue;
+            }
+            let pivot_row = a[col];
+            for (target, source) in a[row].iter_mut().zip(pivot_row.iter()).skip(col) {
+                *target = target.try_sub(factor.try_mul(*source)?)?;
+            }
+            b[row] = b[row].try_sub(factor.try_mul(b[col])?)?;
+        }
+    }
+
+    let mut x = [Decimal::ZERO; N];
+    for row in (0..N).rev() {
+        let mut sum = b[row];
+        for k in row + 1..N {
+            sum = sum.try_sub(a[row][k].try_mul(x[k])?)?;
+        }
+        x[row] = sum.try_div(a[row][row])?;
+    }
+
+    Ok(x)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -584,4 +867,82 @@ mod tests {
         let diff = (result.root - expected).abs();
         assert!(diff < Decimal::new(1, 3)); // Within 0.001
     }
+
+    #[test]
+    fn test_solve_linear_system() {
+        // 2x + y = 5, x + 3y = 10 -> x = 1, y = 3
+        let a = [
+            [Decimal::from(2i64), Decimal::ONE],
+            [Decimal::ONE, Decimal::from(3i64)],
+        ];
+        let b = [Decimal::from(5i64), Decimal::from(10i64)];
+
+        let x = solve_linear_system(a, b).unwrap();
+
+        assert!((x[0] - Decimal::ONE).abs() < Decimal::new(1, 20));
+        assert!((x[1] - Decimal::from(3i64)).abs() < Decimal::new(1, 20));
+    }
+
+    #[test]
+    fn test_levenberg_marquardt_exponential_fit() {
+        // Fit y = a * exp(-b * x) to exact data with a = 2, b = 0.5
+        let xs = [0i64, 1, 2, 3, 4, 5];
+        let r = |i: usize, p: &[Decimal; 2]| {
+            let x = Decimal::from(xs[i]);
+            let y = Decimal::from(2i64).try_mul((-(Decimal::new(5, 1) * x)).try_exp()?)?;
+            let model = p[0].try_mul((-(p[1].try_mul(x)?)).try_exp()?)?;
+            model.try_sub(y)
+        };
+
+        let result =
+            levenberg_marquardt(r, xs.len(), [Decimal::ONE, Decimal::ONE], None, None).unwrap();
+
+        assert!(result.converged);
+        assert!((result.params[0] - Decimal::from(2i64)).abs() < Decimal::new(1, 6));
+        assert!((result.params[1] - Decimal::new(5, 1)).abs() < Decimal::new(1, 6));
+    }
+
+    #[test]
+    fn test_levenberg_marquardt_stall_is_not_converged() {
+        // Minimum at 5, but the residual is only defined at the start point
+        // and its Jacobian bump, so every damped step is rejected
+        let start = Decimal::ONE;
+        let bumped = start + Decimal::new(1, 7);
+        let r = |_: usize, p: &[Decimal; 1]| {
+            if p[0] == start || p[0] == bumped {
+                p[0].try_sub(Decimal::from(5i64))
+            } else {
+                Err(ArithmeticError::Overflow)
+            }
+        };
+
+        let result = levenberg_marquardt(r, 1, [start], None, None).unwrap();
+
+        assert!(!result.converged);
+        assert_eq!(result.iterations, 0);
+        assert_eq!(result.params[0], start);
+    }
+
+    #[test]
+    fn test_levenberg_marquardt_damped_steps_are_not_converged() {
+        // Minimum at 5, but trial points are only valid just past the start
+        // (plus the Jacobian bumps), so accepted steps need heavy damping
+        // and barely reduce the cost
+        let wall = Decimal::ONE + Decimal::new(1, 9);
+        let bumps = (Decimal::ONE + Decimal::new(9, 8), Decimal::ONE + Decimal::new(2, 7));
+        let r = |_: usize, p: &[Decimal; 1]| {
+            if p[0] <= wall || (p[0] >= bumps.0 && p[0] <= bumps.1) {
+                p[0].try_sub(Decimal::from(5i64))
+            } else {
+                Err(ArithmeticError::Overflow)
+            }
+        };
+
+        let result =
+            levenberg_marquardt(r, 1, [Decimal::ONE], Some(Decimal::new(1, 6)), None).unwrap();
+
+        assert!(!result.converged);
+        assert!(result.iterations > 0);
+        assert!(result.params[0] <= wall);
+    }
 }
@@ -0,0 +1,337 @@
+//! Storage backends for curves, interpolators and other node collections.
+//!
+//! Curve and interpolator types are generic over a [`Storage`] strategy so
+//! the same code can run with fixed-size buffers inside contracts and with
+//! growable heap buffers in backend services.
+//!
+//! # Available Backends
+//!
+//! - [`Fixed`]: Inline, heap-free buffer holding at most `N` elements
+//! - [`Heap`]: Unbounded `alloc::vec::Vec` storage (requires the `alloc` feature)
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::interpolation::{DataPoint, Interpolator, Linear};
+//! use financial_calc::storage::Fixed;
+//! use precision_core::Decimal;
+//!
+//! // A linear interpolator with room for 256 points, no allocation required
+//! let mut points = [DataPoint::default(); 200];
+//! for (i, p) in points.iter_mut().enumerate() {
+//!     *p = DataPoint::new(Decimal::from(i as i64), Decimal::from(2 * i as i64));
+//! }
+//! let interp = Linear::<Fixed<256>>::from_sorted(&points).unwrap();
+//! assert_eq!(interp.interpolate(Decimal::new(15, 1)).unwrap(), Decimal::from(3i64));
+//! ```
+
+use core::fmt::Debug;
+use precision_core::ArithmeticError;
+
+/// A contiguous, ordered buffer of elements.
+pub trait Buffer<T>: Default {
+    /// Returns the stored elements as a slice.
+    fn as_slice(&self) -> &[T];
+
+    /// Returns the stored elements as a mutable slice.
+    fn as_mut_slice(&mut self) -> &mut [T];
+
+    /// Inserts an element at `index`, shifting later elements right.
+    ///
+    /// Returns `Overflow` if the buffer is full.
+    fn insert(&mut self, index: usize, value: T) -> Result<(), ArithmeticError>;
+
+    /// Removes and returns the element at `index`, shifting later elements left.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    fn remove(&mut self, index: usize) -> Option<T>;
+
+    /// Removes all elements.
+    fn clear(&mut self);
+
+    /// Maximum number of elements, or `None` if unbounded.
+    fn capacity(&self) -> Option<usize>;
+
+    /// Appends an element to the end of the buffer.
+    ///
+    /// Returns `Overflow` if the buffer is full.
+    fn push(&mut self, value: T) -> Result<(), ArithmeticError> {
+        let len = self.len();
+        self.insert(len, value)
+    }
+
+    /// Number of stored elements.
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Returns true if no elements are stored.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A storage strategy, selecting the buffer type used for every element type.
+pub trait Storage {
+    /// Buffer type used to hold elements of type `T`.
+    type Buffer<T: Copy + Default + Debug>: Buffer<T> + Clone + Debug;
+}
+
+/// Fixed-capacity inline storage holding at most `N` elements per buffer.
+///
+/// Suitable for `no_std` contracts where the maximum size is known at
+/// compile time. Inserting beyond `N` elements returns `Overflow`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fixed<const N: usize>;
+
+impl<const N: usize> Storage for Fixed<N> {
+    type Buffer<T: Copy + Default + Debug> = ArrayBuffer<T, N>;
+}
+
+/// Unbounded heap storage backed by `Vec`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Heap;
+
+#[cfg(feature = "alloc")]
+impl Storage for Heap {
+    type Buffer<T: Copy + Default + Debug> = alloc::vec::Vec<T>;
+}
+
+/// An inline array buffer with a runtime length, in the style of `heapless::Vec`.
+#[derive(Debug, Clone, Copy)]
+pub struct ArrayBuffer<T, const N: usize> {
+    items: [T; N],
+    len: usize,
+}
+
+impl<T: Copy + Default, const N: usize> Default for ArrayBuffer<T, N> {
+    fn default() -> Self {
+        Self {
+            items: [T::default(); N],
+            len: 0,
+        }
+    }
+}
+
+impl<T: Copy + Default, const N: usize> Buffer<T> for ArrayBuffer<T, N> {
+    fn as_slice(&self) -> &[T] {
+        &self.items[..self.len]
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.items[..self.len]
+    }
+
+    fn insert(&mut self, index: usize, value: T) -> Result<(), ArithmeticError> {
+        if self.len >= N || index > self.len {
+            return Err(ArithmeticError::Overflow);
+        }
+        self.items.copy_within(index..self.len, index + 1);
+        self.items[index] = value;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        let value = self.items[index];
+        self.items.copy_within(index + 1..self.len, index);
+        self.len -= 1;
+        Some(value)
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    fn capacity(&self) -> Option<usize> {
+        Some(N)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Buffer<T> for alloc::vec::Vec<T> {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
+    fn insert(&mut self, index: usize, value: T) -> Result<(), ArithmeticError> {
+        if index > self.len() {
+            return Err(ArithmeticError::Overflow);
+        }
+        alloc::vec::Vec::insert(self, index, value);
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        (index < self.len()).then(|| alloc::vec::Vec::remove(self, index))
+    }
+
+    fn clear(&mut self) {
+        alloc::vec::Vec::clear(self);
+    }
+
+    fn capacity(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Creates a buffer holding `len` copies of `value`.
+pub(crate) fn filled<T: Copy, B: Buffer<T>>(len: usize, value: T) -> Result<B, ArithmeticError> {
+    let mut buffer = B::default();
+    for _ in 0..len {
+        buffer.push(value)?;
+    }
+    Ok(buffer)
+}
+
+/// Inserts `value` keeping the buffer sorted by `key`.
+///
+/// Equal keys are inserted after existing ones. Uses binary search, so only
+/// the element shift is linear.
+pub(crate) fn insert_sorted<T, B, K, F>(
+    buffer: &mut B,
+    value: T,
+    key: F,
+) -> Result<(), ArithmeticError>
+where
+    B: Buffer<T>,
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    let k = key(&value);
+    let index = buffer.as_slice().partition_point(|item| key(item) <= k);
+    buffer.insert(index, value)
+}
+
+/// Copies strictly increasing elements into a new buffer.
+///
+/// Returns `DivisionByZero` if keys are not strictly increasing (a zero or
+/// negative interval between consecutive nodes) and `Overflow` if the
+/// storage capacity is exceeded.
+pub(crate) fn from_sorted<T, B, K, F>(items: &[T], key: F) -> Result<B, ArithmeticError>
+where
+    T: Copy,
+    B: Buffer<T>,
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    if items.windows(2).any(|w| key(&w[0]) >= key(&w[1])) {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+    if let Some(cap) = B::default().capacity() {
+        if items.len() > cap {
+            return Err(ArithmeticError::Overflow);
+        }
+    }
+    let mut buffer = B::default();
+    for item in items {
+        buffer.push(*item)?;
+    }
+    Ok(buffer)
+}
+
+/// Finds the elements bracketing `x` in a slice sorted by `key`.
+///
+/// Returns the last element with key <= x and the first element with
+/// key >= x. Both are the same element on an exact match; either is `None`
+/// outside the data range.
+pub(crate) fn bracket<T, K, F>(items: &[T], x: K, key: F) -> (Option<&T>, Option<&T>)
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    let upper_idx = items.partition_point(|item| key(item) < x);
+    let lower_end = upper_idx + items[upper_idx..].partition_point(|item| key(item) <= x);
+    let lower = lower_end.checked_sub(1).map(|i| &items[i]);
+    (lower, items.get(upper_idx))
+}
+
+/// Index of the segment [i, i+1] containing `x` in a slice sorted by `key`.
+///
+/// Clamps to the first or last segment outside the data range. Requires at
+/// least two elements.
+pub(crate) fn segment_index<T, K, F>(items: &[T], x: K, key: F) -> usize
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    let idx = items.partition_point(|item| key(item) <= x);
+    idx.saturating_sub(1).min(items.len().saturating_sub(2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_array_buffer_insert_and_overflow() {
+        let mut buffer = ArrayBuffer::<i32, 3>::default();
+        buffer.push(1).unwrap();
+        buffer.push(3).unwrap();
+        buffer.insert(1, 2).unwrap();
+
+        assert_eq!(buffer.as_slice(), &[1, 2, 3]);
+        assert_eq!(buffer.push(4), Err(ArithmeticError::Overflow));
+
+        assert_eq!(buffer.remove(0), Some(1));
+        assert_eq!(buffer.remove(5), None);
+        assert_eq!(buffer.as_slice(), &[2, 3]);
+    }
+
+    #[test]
+    fn test_insert_sorted_keeps_order() {
+        let mut buffer = ArrayBuffer::<i32, 8>::default();
+        for v in [5, 1, 4, 2, 3] {
+            insert_sorted(&mut buffer, v, |x| *x).unwrap();
+        }
+        assert_eq!(buffer.as_slice(), &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_from_sorted_rejects_unsorted() {
+        let result: Result<ArrayBuffer<i32, 8>, _> = from_sorted(&[1, 3, 2], |x| *x);
+        assert!(result.is_err());
+
+        let result: Result<ArrayBuffer<i32, 2>, _> = from_sorted(&[1, 2, 3], |x| *x);
+        assert_eq!(result.unwrap_err(), ArithmeticError::Overflow);
+    }
+
+    #[test]
+    fn test_bracket() {
+        let items = [10, 20, 30];
+
+        assert_eq!(bracket(&items, 20, |x| *x), (Some(&20), Some(&20)));
+        assert_eq!(bracket(&items, 25, |x| *x), (Some(&20), Some(&30)));
+        assert_eq!(bracket(&items, 5, |x| *x), (None, Some(&10)));
+        assert_eq!(bracket(&items, 35, |x| *x), (Some(&30), None));
+    }
+
+    #[test]
+    fn test_segment_index() {
+        let items = [10, 20, 30, 40];
+
+        assert_eq!(segment_index(&items, 5, |x| *x), 0);
+        assert_eq!(segment_index(&items, 25, |x| *x), 1);
+        assert_eq!(segment_index(&items, 30, |x| *x), 2);
+        assert_eq!(segment_index(&items, 45, |x| *x), 2);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_heap_buffer_unbounded() {
+        let mut buffer = <Heap as Storage>::Buffer::<crate::Decimal>::default();
+        for i in 0..1000 {
+            Buffer::push(&mut buffer, crate::Decimal::from(i as i64)).unwrap();
+        }
+        assert_eq!(Buffer::len(&buffer), 1000);
+        assert_eq!(Buffer::capacity(&buffer), None);
+    }
+}
@@ -0,0 +1,483 @@
+//! Two-dimensional interpolation over rectangular grids.
+//!
+//! Surfaces such as implied volatility by strike and expiry, or rates by
+//! tenor and utilization, are stored as a [`Grid`] of values and queried
+//! through the [`SurfaceInterpolator`] trait.
+//!
+//! # Available Methods
+//!
+//! - [`Bilinear`]: Linear in each axis; exact for bilinear data
+//! - [`Bicubic`]: Cubic Hermite in each axis with finite-difference slopes
+//! - [`VolSurface`]: Linear in strike, then linear in total variance across
+//!   expiries, so calendar spreads stay consistent
+//!
+//! Queries outside the grid are clamped to its edges (flat extrapolation),
+//! matching the one-dimensional interpolators. Grids are generic over a
+//! [`Storage`] backend; with fixed storage the capacity must cover every
+//! value in the grid (rows times columns).
+//!
+//! # Example
+//!
+//! ```
+//! use financial_calc::options::black_scholes_call;
+//! use financial_calc::surface::VolSurface;
+//! use precision_core::Decimal;
+//!
+//! let strikes = [Decimal::from(90i64), Decimal::from(100i64), Decimal::from(110i64)];
+//! let expiries = [Decimal::new(25, 2), Decimal::ONE];
+//! // One row of vols per expiry
+//! let vols = [
+//!     Decimal::new(24, 2), Decimal::new(20, 2), Decimal::new(22, 2),
+//!     Decimal::new(22, 2), Decimal::new(19, 2), Decimal::new(20, 2),
+//! ];
+//! let surface: VolSurface = VolSurface::new(&strikes, &expiries, &vols).unwrap();
+//!
+//! let params = surface
+//!     .option_params(Decimal::from(100i64), Decimal::from(95i64), Decimal::new(5, 2), Decimal::new(5, 1))
+//!     .unwrap();
+//! let price = black_scholes_call(&params).unwrap();
+//! assert!(price.is_positive());
+//! ```
+
+use crate::options::OptionParams;
+use crate::storage::{self, Buffer, Fixed, Storage};
+use precision_core::{ArithmeticError, Decimal};
+
+/// Default number of grid values (for no_std fixed allocation).
+pub const MAX_GRID_VALUES: usize = 256;
+
+/// Trait for two-dimensional interpolation methods.
+pub trait SurfaceInterpolator {
+    /// Interpolates a value at (x, y).
+    fn interpolate(&self, x: Decimal, y: Decimal) -> Result<Decimal, ArithmeticError>;
+}
+
+/// A rectangular grid of values.
+///
+/// Values are stored row by row: one row per y coordinate, with one entry
+/// per x coordinate, so `values[j * xs.len() + i]` is the value at
+/// `(xs[i], ys[j])`.
+#[derive(Debug, Clone)]
+pub struct Grid<S: Storage = Fixed<MAX_GRID_VALUES>> {
+    xs: S::Buffer<Decimal>,
+    ys: S::Buffer<Decimal>,
+    values: S::Buffer<Decimal>,
+}
+
+impl<S: Storage> Grid<S> {
+    /// Creates a grid from strictly increasing axes and row-major values.
+    ///
+    /// Returns error if an axis is empty or not strictly increasing, if the
+    /// number of values is not `xs.len() * ys.len()`, or if the storage
+    /// capacity is exceeded.
+    pub fn new(
+        xs: &[Decimal],
+        ys: &[Decimal],
+        values: &[Decimal],
+    ) -> Result<Self, ArithmeticError> {
+        if xs.is_empty() || ys.is_empty() || values.len() != xs.len() * ys.len() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+
+        let mut buffer = S::Buffer::<Decimal>::default();
+        for value in values {
+            buffer.push(*value)?;
+        }
+        Ok(Self {
+            xs: storage::from_sorted(xs, |x| *x)?,
+            ys: storage::from_sorted(ys, |y| *y)?,
+            values: buffer,
+        })
+    }
+
+    /// Returns the x coordinates.
+    pub fn xs(&self) -> &[Decimal] {
+        self.xs.as_slice()
+    }
+
+    /// Returns the y coordinates.
+    pub fn ys(&self) -> &[Decimal] {
+        self.ys.as_slice()
+    }
+
+    /// Returns the value at `(xs[i], ys[j])`.
+    pub fn value(&self, i: usize, j: usize) -> Decimal {
+        self.values.as_slice()[j * self.xs.len() + i]
+    }
+}
+
+/// Locates v on an axis as (segment index, fraction in [0, 1]), clamping
+/// to the axis range. A single-point axis gives (0, 0).
+fn cell(axis: &[Decimal], v: Decimal) -> Result<(usize, Decimal), ArithmeticError> {
+    let n = axis.len();
+    if n == 1 || v <= axis[0] {
+        return Ok((0, Decimal::ZERO));
+    }
+    if v >= axis[n - 1] {
+        return Ok((n - 2, Decimal::ONE));
+    }
+    let i = storage::segment_index(axis, v, |a| *a);
+    let t = v.try_sub(axis[i])?.try_div(axis[i + 1].try_sub(axis[i])?)?;
+    Ok((i, t))
+}
+
+/// Total variance at `expiry` across slices sorted by expiry.
+///
+/// Interpolates linearly in time between the bracketing slices and holds
+/// the nearest slice's volatility flat outside them. `variance` gives the
+/// total variance of slice j; `slices` must not be empty.
+pub(crate) fn interpolate_total_variance<T, E, W>(
+    slices: &[T],
+    expiry_of: E,
+    variance: W,
+    expiry: Decimal,
+) -> Result<Decimal, ArithmeticError>
+where
+    E: Fn(&T) -> Decimal,
+    W: Fn(usize) -> Result<Decimal, ArithmeticError>,
+{
+    let n = slices.len();
+    let edge = if n == 1 || expiry <= expiry_of(&slices[0]) {
+        Some(0)
+    } else if expiry >= expiry_of(&slices[n - 1]) {
+        Some(n - 1)
+    } else {
+        None
+    };
+    if let Some(j) = edge {
+        return variance(j)?.try_mul(expiry)?.try_div(expiry_of(&slices[j]));
+    }
+
+    let j = storage::segment_index(slices, expiry, &expiry_of);
+    let (t0, t1) = (expiry_of(&slices[j]), expiry_of(&slices[j + 1]));
+    let frac = expiry.try_sub(t0)?.try_div(t1.try_sub(t0)?)?;
+    lerp(variance(j)?, variance(j + 1)?, frac)
+}
+
+/// Linear interpolation between a and b at fraction t.
+fn lerp(a: Decimal, b: Decimal, t: Decimal) -> Result<Decimal, ArithmeticError> {
+    a.try_add(b.try_sub(a)?.try_mul(t)?)
+}
+
+/// Bilinear interpolation on a grid.
+#[derive(Debug, Clone)]
+pub struct Bilinear<S: Storage = Fixed<MAX_GRID_VALUES>> {
+    grid: Grid<S>,
+}
+
+impl<S: Storage> Bilinear<S> {
+    /// Creates a bilinear interpolator over a grid.
+    pub fn new(grid: Grid<S>) -> Self {
+        Self { grid }
+    }
+
+    /// Returns the underlying grid.
+    pub fn grid(&self) -> &Grid<S> {
+        &self.grid
+    }
+}
+
+impl<S: Storage> SurfaceInterpolator for Bilinear<S> {
+    fn interpolate(&self, x: Decimal, y: Decimal) -> Result<Decimal, ArithmeticError> {
+        let grid = &self.grid;
+        let (i, tx) = cell(grid.xs(), x)?;
+        let (j, ty) = cell(grid.ys(), y)?;
+        let i1 = (i + 1).min(grid.xs().len() - 1);
+        let j1 = (j + 1).min(grid.ys().len() - 1);
+
+        let lower = lerp(grid.value(i, j), grid.value(i1, j), tx)?;
+        let upper = lerp(grid.value(i, j1), grid.value(i1, j1), tx)?;
+        lerp(lower, upper, ty)
+    }
+}
+
+/// Bicubic interpolation on a grid.
+///
+/// Interpolates with cubic Hermite segments along x, then along y. Slopes
+/// at grid points are central finite differences (one-sided at the edges),
+/// giving a C1 surface that reproduces linear data exactly.
+#[derive(Debug, Clone)]
+pub struct Bicubic<S: Storage = Fixed<MAX_GRID_VALUES>> {
+    grid: Grid<S>,
+}
+
+impl<S: Storage> Bicubic<S> {
+    /// Creates a bicubic interpolator over a grid.
+    pub fn new(grid: Grid<S>) -> Self {
+        Self { grid }
+    }
+
+    /// Returns the underlying grid.
+    pub fn grid(&self) -> &Grid<S> {
+        &self.grid
+    }
+}
+
+impl<S: Storage> SurfaceInterpolator for Bicubic<S> {
+    fn interpolate(&self, x: Decimal, y: Decimal) -> Result<Decimal, ArithmeticError> {
+        let grid = &self.grid;
+        let (i, tx) = cell(grid.xs(), x)?;
+        let (j, ty) = cell(grid.ys(), y)?;
+
+        let row = |jj: usize| hermite_1d(grid.xs(), |ii| Ok(grid.value(ii, jj)), i, tx);
+        hermite_1d(grid.ys(), row, j, ty)
+    }
+}
+
+/// Cubic Hermite interpolation on segment i of an axis at fraction t, with
+/// finite-difference slopes from the values `f`.
+fn hermite_1d<F>(axis: &[Decimal], f: F, i: usize, t: Decimal) -> Result<Decimal, ArithmeticError>
+where
+    F: Fn(usize) -> Result<Decimal, ArithmeticError>,
+{
+    let n = axis.len();
+    if n == 1 {
+        return f(0);
+    }
+
+    let slope = |k: usize| -> Result<Decimal, ArithmeticError> {
+        let (lo, hi) = (k.saturating_sub(1), (k + 1).min(n - 1));
+        f(hi)?.try_sub(f(lo)?)?.try_div(axis[hi].try_sub(axis[lo])?)
+    };
+
+    let h = axis[i + 1].try_sub(axis[i])?;
+    let (f0, f1) = (f(i)?, f(i + 1)?);
+    let (m0, m1) = (slope(i)?.try_mul(h)?, slope(i + 1)?.try_mul(h)?);
+
+    let two = Decimal::from(2i64);
+    let three = Decimal::from(3i64);
+    let t2 = t.try_mul(t)?;
+    let t3 = t2.try_mul(t)?;
+
+    // h00 = 2t^3 - 3t^2 + 1, h10 = t^3 - 2t^2 + t, h01 = 3t^2 - 2t^3, h11 = t^3 - t^2
+    let h00 = two
+        .try_mul(t3)?
+        .try_sub(three.try_mul(t2)?)?
+        .try_add(Decimal::ONE)?;
+    let h10 = t3.try_sub(two.try_mul(t2)?)?.try_add(t)?;
+    let h01 = three.try_mul(t2)?.try_sub(two.try_mul(t3)?)?;
+    let h11 = t3.try_sub(t2)?;
+
+    h00.try_mul(f0)?
+        .try_add(h10.try_mul(m0)?)?
+        .try_add(h01.try_mul(f1)?)?
+        .try_add(h11.try_mul(m1)?)
+}
+
+/// An implied volatility surface by strike and expiry.
+///
+/// Each expiry slice is interpolated linearly in strike. Between expiries
+/// the total implied variance σ²T is interpolated linearly in time, which
+/// keeps total variance increasing whenever the quoted slices do (no
+/// calendar arbitrage at a fixed strike). Before the first and after the
+/// last expiry the slice volatility is held flat.
+#[derive(Debug, Clone)]
+pub struct VolSurface<S: Storage = Fixed<MAX_GRID_VALUES>> {
+    grid: Grid<S>,
+}
+
+impl<S: Storage> VolSurface<S> {
+    /// Creates a surface from strikes, expiries (in years) and one row of
+    /// volatilities per expiry.
+    ///
+    /// Returns error if an expiry is not positive, a volatility is negative,
+    /// or the grid is invalid.
+    pub fn new(
+        strikes: &[Decimal],
+        expiries: &[Decimal],
+        vols: &[Decimal],
+    ) -> Result<Self, ArithmeticError> {
+        if expiries.iter().any(|t| !t.is_positive()) || vols.iter().any(|v| v.is_negative()) {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(Self {
+            grid: Grid::new(strikes, expiries, vols)?,
+        })
+    }
+
+    /// Returns the underlying grid (x = strike, y = expiry).
+    pub fn grid(&self) -> &Grid<S> {
+        &self.grid
+    }
+
+    /// Implied volatility at a strike and expiry (in years).
+    pub fn volatility(&self, strike: Decimal, expiry: Decimal) -> Result<Decimal, ArithmeticError> {
+        // No variance has accrued yet; the first slice's volatility applies
+        if !expiry.is_positive() {
+            return self.slice_vol(strike, 0);
+        }
+        self.total_variance(strike, expiry)?
+            .try_div(expiry)?
+            .try_sqrt()
+    }
+
+    /// Total implied variance σ²T at a strike and expiry.
+    pub fn total_variance(
+        &self,
+        strike: Decimal,
+        expiry: Decimal,
+    ) -> Result<Decimal, ArithmeticError> {
+        interpolate_total_variance(
+            self.grid.ys(),
+            |t| *t,
+            |j| self.slice_variance(strike, j),
+            expiry,
+        )
+    }
+
+    /// Builds Black-Scholes parameters using the surface volatility at the
+    /// option's strike and time to expiry.
+    pub fn option_params(
+        &self,
+        spot: Decimal,
+        strike: Decimal,
+        rate: Decimal,
+        time: Decimal,
+    ) -> Result<OptionParams, ArithmeticError> {
+        Ok(OptionParams {
+            spot,
+            strike,
+            rate,
+            time,
+            volatility: self.volatility(strike, time)?,
+        })
+    }
+
+    /// Volatility of expiry slice j at a strike.
+    fn slice_vol(&self, strike: Decimal, j: usize) -> Result<Decimal, ArithmeticError> {
+        let grid = &self.grid;
+        let (i, t) = cell(grid.xs(), strike)?;
+        let i1 = (i + 1).min(grid.xs().len() - 1);
+        lerp(grid.value(i, j), grid.value(i1, j), t)
+    }
+
+    /// Total variance of expiry slice j at a strike.
+    fn slice_variance(&self, strike: Decimal, j: usize) -> Result<Decimal, ArithmeticError> {
+        let vol = self.slice_vol(strike, j)?;
+        vol.try_mul(vol)?.try_mul(self.grid.ys()[j])
+    }
+}
+
+impl<S: Storage> SurfaceInterpolator for VolSurface<S> {
+    fn interpolate(&self, x: Decimal, y: Decimal) -> Result<Decimal, ArithmeticError> {
+        self.volatility(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(v: i64) -> Decimal {
+        Decimal::from(v)
+    }
+
+    /// f(x, y) = 2x + 3y + xy on an uneven grid.
+    fn bilinear_grid() -> Grid<Fixed<16>> {
+        let xs = [d(0), d(1), d(3)];
+        let ys = [d(0), d(2), d(5)];
+        let mut values = [Decimal::ZERO; 9];
+        for (j, y) in ys.iter().enumerate() {
+            for (i, x) in xs.iter().enumerate() {
+                values[j * 3 + i] = d(2) * *x + d(3) * *y + *x * *y;
+            }
+        }
+        Grid::new(&xs, &ys, &values).unwrap()
+    }
+
+    #[test]
+    fn test_grid_validation() {
+        let axis = [d(0), d(1)];
+        assert!(Grid::<Fixed<8>>::new(&axis, &axis, &[d(1); 3]).is_err());
+        assert!(Grid::<Fixed<8>>::new(&[d(1), d(0)], &axis, &[d(1); 4]).is_err());
+        assert_eq!(
+            Grid::<Fixed<3>>::new(&axis, &axis, &[d(1); 4]).unwrap_err(),
+            ArithmeticError::Overflow
+        );
+    }
+
+    #[test]
+    fn test_bilinear_exact_on_bilinear_data() {
+        let surface = Bilinear::new(bilinear_grid());
+        let (x, y) = (Decimal::new(15, 1), Decimal::new(35, 1));
+        let expected = d(2) * x + d(3) * y + x * y;
+        assert_eq!(surface.interpolate(x, y).unwrap(), expected);
+
+        // Clamped outside the grid
+        let corner = surface.interpolate(d(10), d(10)).unwrap();
+        assert_eq!(corner, bilinear_grid().value(2, 2));
+    }
+
+    #[test]
+    fn test_bicubic_exact_on_linear_data() {
+        let xs = [d(0), d(1), d(3), d(4)];
+        let ys = [d(0), d(2), d(5)];
+        let mut values = [Decimal::ZERO; 12];
+        for (j, y) in ys.iter().enumerate() {
+            for (i, x) in xs.iter().enumerate() {
+                values[j * 4 + i] = d(2) * *x - *y + d(1);
+            }
+        }
+        let surface = Bicubic::new(Grid::<Fixed<16>>::new(&xs, &ys, &values).unwrap());
+
+        let (x, y) = (Decimal::new(25, 1), Decimal::new(12, 1));
+        let expected = d(2) * x - y + d(1);
+        assert!((surface.interpolate(x, y).unwrap() - expected).abs() < Decimal::new(1, 20));
+    }
+
+    #[test]
+    fn test_bicubic_matches_nodes() {
+        let grid = bilinear_grid();
+        let surface = Bicubic::new(grid.clone());
+        assert_eq!(surface.interpolate(d(1), d(2)).unwrap(), grid.value(1, 1));
+        assert_eq!(surface.interpolate(d(3), d(5)).unwrap(), grid.value(2, 2));
+    }
+
+    #[test]
+    fn test_vol_surface_total_variance_interpolation() {
+        let strikes = [d(90), d(110)];
+        let expiries = [Decimal::new(25, 2), Decimal::ONE];
+        let vols = [
+            Decimal::new(30, 2),
+            Decimal::new(30, 2),
+            Decimal::new(20, 2),
+            Decimal::new(20, 2),
+        ];
+        let surface = VolSurface::<Fixed<8>>::new(&strikes, &expiries, &vols).unwrap();
+
+        // w(0.25) = 0.0225, w(1) = 0.04; at T = 0.5, w = 0.0225 + 0.0175/3
+        let w = surface.total_variance(d(100), Decimal::new(5, 1)).unwrap();
+        let expected = Decimal::new(225, 4) + Decimal::new(175, 4) / d(3);
+        assert!((w - expected).abs() < Decimal::new(1, 18));
+
+        // Flat vol beyond the last expiry
+        assert_eq!(
+            surface.volatility(d(100), d(2)).unwrap(),
+            Decimal::new(20, 2)
+        );
+    }
+
+    #[test]
+    fn test_vol_surface_strike_interpolation_and_params() {
+        let strikes = [d(90), d(100), d(110)];
+        let expiries = [Decimal::ONE];
+        let vols = [
+            Decimal::new(25, 2),
+            Decimal::new(20, 2),
+            Decimal::new(22, 2),
+        ];
+        let surface = VolSurface::<Fixed<8>>::new(&strikes, &expiries, &vols).unwrap();
+
+        assert_eq!(
+            surface.volatility(d(95), Decimal::ONE).unwrap(),
+            Decimal::new(225, 3)
+        );
+
+        let params = surface
+            .option_params(d(100), d(105), Decimal::new(5, 2), Decimal::new(5, 1))
+            .unwrap();
+        assert_eq!(params.volatility, Decimal::new(21, 2));
+
+        assert!(VolSurface::<Fixed<8>>::new(&strikes, &[Decimal::ZERO], &vols).is_err());
+    }
+}
@@ -0,0 +1,777 @@
+//! Term Structure (Yield Curve) primitives for fixed income calculations.
+//!
+//! A yield curve relates time to risk-free rates, discount factors, and forward rates.
+//! This module provides the foundational traits and implementations for building
+//! and querying yield curves in a `no_std` environment.
+//!
+//! # Overview
+//!
+//! - [`TermStructure`] trait: Core interface for all yield curve implementations
+//! - [`FlatTermStructure`]: Constant rate across all maturities
+//! - [`PiecewiseTermStructure`]: Multiple rate points with a chosen
+//!   [`CurveInterpolation`] and [`Extrapolation`]
+//! - [`NelsonSiegel`] / [`Svensson`]: Smooth parametric curves with calibration
+//! - [`ParallelShift`], [`KeyRateShift`], [`SpreadCurve`], [`ForwardCurve`]:
+//!   Composable perturbations of any curve, with [`dv01`] and [`key_rate_dv01`]
+
+mod monotone_convex;
+mod parametric;
+mod scenario;
+
+pub use parametric::{BondQuote, CalibrationResult, NelsonSiegel, Svensson};
+pub use scenario::{dv01, key_rate_dv01, ForwardCurve, KeyRateShift, ParallelShift, SpreadCurve};
+
+use crate::day_count::YearFraction;
+use crate::interpolation::{self, DataPoint};
+use crate::storage::{self, Buffer, Fixed, Storage};
+use precision_core::{ArithmeticError, Decimal};
+
+/// Core trait for term structure (yield curve) implementations.
+///
+/// All yield curves must implement these fundamental methods that relate
+/// time to discount factors, zero rates, and forward rates.
+pub trait TermStructure {
+    /// Returns the discount factor for a given time.
+    ///
+    /// The discount factor D(t) is the present value of $1 to be received at time t.
+    /// D(t) = exp(-r(t) * t) where r(t) is the continuously compounded zero rate.
+    ///
+    /// # Arguments
+    /// * `t` - Time to maturity as a year fraction
+    ///
+    /// # Returns
+    /// Discount factor in (0, 1] range
+    fn discount_factor(&self, t: YearFraction) -> Result<Decimal, ArithmeticError>;
+
+    /// Returns the continuously compounded zero rate for a given time.
+    ///
+    /// The zero rate r(t) is the rate that, when continuously compounded,
+    /// gives the discount factor: D(t) = exp(-r(t) * t)
+    ///
+    /// # Arguments
+    /// * `t` - Time to maturity as a year fraction
+    ///
+    /// # Returns
+    /// Zero rate as a decimal (e.g., 0.05 for 5%)
+    fn zero_rate(&self, t: YearFraction) -> Result<Decimal, ArithmeticError>;
+
+    /// Returns the forward rate between two times.
+    ///
+    /// The forward rate f(t1, t2) is the rate applicable from time t1 to t2,
+    /// implied by the zero rates at t1 and t2.
+    ///
+    /// Formula: f(t1,t2) = (r(t2)*t2 - r(t1)*t1) / (t2 - t1)
+    ///
+    /// # Arguments
+    /// * `t1` - Start time as year fraction
+    /// * `t2` - End time as year fraction (must be > t1)
+    ///
+    /// # Returns
+    /// Forward rate as a decimal
+    fn forward_rate(&self, t1: YearFraction, t2: YearFraction) -> Result<Decimal, ArithmeticError> {
+        if t2 <= t1 {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+
+        let r1 = self.zero_rate(t1)?;
+        let r2 = self.zero_rate(t2)?;
+
+        let r1_t1 = r1.try_mul(t1)?;
+        let r2_t2 = r2.try_mul(t2)?;
+        let delta_t = t2.try_sub(t1)?;
+
+        r2_t2.try_sub(r1_t1)?.try_div(delta_t)
+    }
+
+    /// Returns the instantaneous forward rate at time t.
+    ///
+    /// This is the limit of forward_rate(t, t+dt) as dt -> 0.
+    /// Approximated using a small time step.
+    fn instantaneous_forward(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let dt = Decimal::new(1, 4); // 0.0001 years (~0.9 hours)
+        let t2 = t.try_add(dt)?;
+        self.forward_rate(t, t2)
+    }
+}
+
+/// A flat (constant) term structure where the rate is the same for all maturities.
+///
+/// This is the simplest yield curve implementation, useful for testing
+/// and as a baseline comparison.
+#[derive(Debug, Clone)]
+pub struct FlatTermStructure {
+    /// The constant continuously compounded rate
+    rate: Decimal,
+}
+
+impl FlatTermStructure {
+    /// Creates a new flat term structure with the given rate.
+    ///
+    /// # Arguments
+    /// * `rate` - Continuously compounded rate (e.g., 0.05 for 5%)
+    pub fn new(rate: Decimal) -> Self {
+        Self { rate }
+    }
+
+    /// Returns the underlying constant rate.
+    pub fn rate(&self) -> Decimal {
+        self.rate
+    }
+}
+
+impl TermStructure for FlatTermStructure {
+    fn discount_factor(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        // D(t) = exp(-r * t)
+        let rt = self.rate.try_mul(t)?;
+        exp_approx(-rt)
+    }
+
+    fn zero_rate(&self, _t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        Ok(self.rate)
+    }
+
+    fn forward_rate(
+        &self,
+        _t1: YearFraction,
+        _t2: YearFraction,
+    ) -> Result<Decimal, ArithmeticError> {
+        // For flat curve, forward rate equals spot rate
+        Ok(self.rate)
+    }
+}
+
+/// A node in a piecewise yield curve, representing a rate at a specific time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CurveNode {
+    /// Time to maturity as year fraction
+    pub time: YearFraction,
+    /// Zero rate at this time
+    pub rate: Decimal,
+}
+
+impl CurveNode {
+    /// Creates a new curve node.
+    pub fn new(time: YearFraction, rate: Decimal) -> Self {
+        Self { time, rate }
+    }
+}
+
+/// Default number of nodes in a piecewise curve (for no_std fixed allocation).
+pub const MAX_CURVE_NODES: usize = 32;
+
+/// Interpolation scheme used between the nodes of a piecewise curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CurveInterpolation {
+    /// Linear in zero rates. Simple, but forwards jump at every node.
+    #[default]
+    LinearZero,
+    /// Linear in log discount factors, i.e. linear in r(t) * t.
+    /// Also known as raw interpolation: forwards are piecewise flat.
+    LogLinearDiscount,
+    /// Hagan-West monotone convex interpolation of forwards.
+    /// Forwards are continuous and stay positive for positive discrete forwards.
+    MonotoneConvex,
+    /// Natural cubic spline through the zero rates.
+    NaturalCubic,
+}
+
+/// Extrapolation of zero rates beyond the first and last nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Extrapolation {
+    /// Hold the end node's zero rate constant.
+    #[default]
+    Flat,
+    /// Extend the zero rate along the slope of the end segment.
+    Linear,
+}
+
+/// A piecewise term structure built from discrete rate points.
+///
+/// Rates between nodes are interpolated with the chosen
+/// [`CurveInterpolation`] (linear in zero rates by default) and extended
+/// beyond the nodes with the chosen [`Extrapolation`] (flat by default).
+/// This is the foundation for bootstrapped yield curves.
+///
+/// [`CurveInterpolation::MonotoneConvex`] anchors the curve at t = 0, so
+/// times before the first node are interpolated rather than extrapolated.
+///
+/// Nodes are held in the chosen [`Storage`]: the default
+/// [`Fixed<MAX_CURVE_NODES>`] suits contracts, while larger fixed buffers or
+/// `Heap` storage (with the `alloc` feature) hold curves with hundreds of
+/// points. Bracketing uses binary search.
+///
+/// # Example
+///
+/// ```
+/// use financial_calc::storage::Fixed;
+/// use financial_calc::term_structure::{
+///     CurveInterpolation, CurveNode, Extrapolation, PiecewiseTermStructure, TermStructure,
+/// };
+/// use precision_core::Decimal;
+///
+/// let nodes = [
+///     CurveNode::new(Decimal::ONE, Decimal::new(3, 2)),
+///     CurveNode::new(Decimal::from(2i64), Decimal::new(4, 2)),
+/// ];
+/// let curve = PiecewiseTermStructure::<Fixed<64>>::from_sorted(&nodes).unwrap();
+/// assert_eq!(curve.zero_rate(Decimal::new(15, 1)).unwrap(), Decimal::new(35, 3));
+///
+/// // Piecewise flat forwards, with zero rates extended linearly past 2Y
+/// let curve = curve
+///     .with_interpolation(CurveInterpolation::LogLinearDiscount)
+///     .unwrap()
+///     .with_extrapolation(Extrapolation::Linear);
+/// assert_eq!(curve.zero_rate(Decimal::from(3i64)).unwrap(), Decimal::new(5, 2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PiecewiseTermStructure<S: Storage = Fixed<MAX_CURVE_NODES>> {
+    /// Curve nodes sorted by time
+    nodes: S::Buffer<CurveNode>,
+    interpolation: CurveInterpolation,
+    extrapolation: Extrapolation,
+    /// Spline second derivatives, only populated for natural cubic
+    second_derivs: S::Buffer<Decimal>,
+}
+
+impl PiecewiseTermStructure {
+    /// Creates an empty piecewise term structure with default fixed storage.
+    pub fn new() -> Self {
+        Self::with_storage()
+    }
+}
+
+impl<S: Storage> PiecewiseTermStructure<S> {
+    /// Creates an empty piecewise term structure with the chosen storage.
+    pub fn with_storage() -> Self {
+        Self {
+            nodes: Default::default(),
+            interpolation: CurveInterpolation::default(),
+            extrapolation: Extrapolation::default(),
+            second_derivs: Default::default(),
+        }
+    }
+
+    /// Builds a curve from nodes sorted by strictly increasing time.
+    ///
+    /// Returns error if the nodes are not strictly increasing or exceed the
+    /// storage capacity.
+    pub fn from_sorted(nodes: &[CurveNode]) -> Result<Self, ArithmeticError> {
+        let mut curve = Self::with_storage();
+        curve.nodes = storage::from_sorted(nodes, |n| n.time)?;
+        Ok(curve)
+    }
+
+    /// Sets the interpolation scheme.
+    ///
+    /// Returns error if the scheme cannot be fitted to the current nodes.
+    pub fn with_interpolation(
+        mut self,
+        interpolation: CurveInterpolation,
+    ) -> Result<Self, ArithmeticError> {
+        self.interpolation = interpolation;
+        self.refit()?;
+        Ok(self)
+    }
+
+    /// Sets the extrapolation scheme.
+    pub fn with_extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+
+    /// Returns the interpolation scheme.
+    pub fn interpolation(&self) -> CurveInterpolation {
+        self.interpolation
+    }
+
+    /// Returns the extrapolation scheme.
+    pub fn extrapolation(&self) -> Extrapolation {
+        self.extrapolation
+    }
+
+    /// Adds a node to the curve.
+    ///
+    /// Nodes are kept sorted by time. Returns error if curve is full or the
+    /// interpolation scheme cannot be fitted with the new node (a natural
+    /// cubic spline needs distinct times), in which case the curve is left
+    /// unchanged.
+    pub fn add_node(&mut self, node: CurveNode) -> Result<(), ArithmeticError> {
+        storage::insert_sorted(&mut self.nodes, node, |n| n.time)?;
+        let refit = self.refit();
+        if refit.is_err() {
+            // insert_sorted places the node after any equal times
+            let index = self.nodes().partition_point(|n| n.time <= node.time) - 1;
+            self.nodes.remove(index);
+        }
+        refit
+    }
+
+    /// Returns the number of nodes in the curve.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the curve nodes sorted by time.
+    pub fn nodes(&self) -> &[CurveNode] {
+        self.nodes.as_slice()
+    }
+
+    /// Finds the bracketing nodes for a given time.
+    fn find_bracket(&self, t: YearFraction) -> (Option<&CurveNode>, Option<&CurveNode>) {
+        storage::bracket(self.nodes(), t, |n| n.time)
+    }
+
+    /// Recomputes cached spline coefficients after the nodes or scheme change.
+    fn refit(&mut self) -> Result<(), ArithmeticError> {
+        self.second_derivs = if self.interpolation == CurveInterpolation::NaturalCubic {
+            interpolation::natural_spline_second_derivs::<S, _, _, _>(
+                self.nodes.as_slice(),
+                |n| n.time,
+                |n| n.rate,
+            )?
+        } else {
+            Default::default()
+        };
+        Ok(())
+    }
+
+    /// Returns true if t lies outside the interpolated range.
+    fn is_extrapolated(&self, t: YearFraction) -> bool {
+        let nodes = self.nodes();
+        let before_first = self.interpolation != CurveInterpolation::MonotoneConvex
+            && t < nodes[0].time;
+        before_first || t > nodes[nodes.len() - 1].time
+    }
+
+    /// Zero rate beyond the end nodes.
+    fn extrapolate(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let nodes = self.nodes();
+        let n = nodes.len();
+        let (end, inner) = if t > nodes[n - 1].time {
+            (&nodes[n - 1], n.checked_sub(2).map(|i| &nodes[i]))
+        } else {
+            (&nodes[0], nodes.get(1))
+        };
+
+        match (self.extrapolation, inner) {
+            (Extrapolation::Linear, Some(inner)) => {
+                let slope = end
+                    .rate
+                    .try_sub(inner.rate)?
+                    .try_div(end.time.try_sub(inner.time)?)?;
+                end.rate.try_add(slope.try_mul(t.try_sub(end.time)?)?)
+            }
+            _ => Ok(end.rate),
+        }
+    }
+
+    /// Zero rate within the node range for the bracketed schemes.
+    fn interpolate_bracket(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let (lower, upper) = self.find_bracket(t);
+
+        match (lower, upper) {
+            (Some(l), Some(u)) if l.time == u.time => {
+                // Exact match
+                Ok(l.rate)
+            }
+            (Some(l), Some(u)) => {
+                let t_range = u.time.try_sub(l.time)?;
+                let t_offset = t.try_sub(l.time)?;
+                match self.interpolation {
+                    CurveInterpolation::LogLinearDiscount => {
+                        // Linear in ln D(t) = -r(t) t
+                        let rt_l = l.rate.try_mul(l.time)?;
+                        let rt_u = u.rate.try_mul(u.time)?;
+                        let slope = rt_u.try_sub(rt_l)?.try_div(t_range)?;
+                        rt_l.try_add(slope.try_mul(t_offset)?)?.try_div(t)
+                    }
+                    _ => {
+                        // Linear interpolation
+                        let r_range = u.rate.try_sub(l.rate)?;
+                        let slope = r_range.try_div(t_range)?;
+                        l.rate.try_add(slope.try_mul(t_offset)?)
+                    }
+                }
+            }
+            _ => self.extrapolate(t),
+        }
+    }
+
+    /// Zero rate within the node range on the natural cubic spline.
+    fn interpolate_cubic(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let nodes = self.nodes();
+        if nodes.len() == 1 {
+            return Ok(nodes[0].rate);
+        }
+
+        let i = storage::segment_index(nodes, t, |n| n.time);
+        let m = self.second_derivs.as_slice();
+        let point = |n: &CurveNode| DataPoint::new(n.time, n.rate);
+        interpolation::spline_segment(point(&nodes[i]), point(&nodes[i + 1]), m[i], m[i + 1], t)
+    }
+}
+
+impl Default for PiecewiseTermStructure {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Storage> TermStructure for PiecewiseTermStructure<S> {
+    fn discount_factor(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let rate = self.zero_rate(t)?;
+        let rt = rate.try_mul(t)?;
+        exp_approx(-rt)
+    }
+
+    fn zero_rate(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        if self.nodes.is_empty() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        if self.is_extrapolated(t) {
+            return self.extrapolate(t);
+        }
+
+        match self.interpolation {
+            CurveInterpolation::LinearZero | CurveInterpolation::LogLinearDiscount => {
+                self.interpolate_bracket(t)
+            }
+            CurveInterpolation::MonotoneConvex => monotone_convex::zero_rate(self.nodes(), t),
+            CurveInterpolation::NaturalCubic => self.interpolate_cubic(t),
+        }
+    }
+
+    fn instantaneous_forward(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        if self.interpolation == CurveInterpolation::MonotoneConvex
+            && !self.nodes.is_empty()
+            && !self.is_extrapolated(t)
+        {
+            return monotone_convex::instantaneous_forward(self.nodes(), t);
+        }
+
+        let dt = Decimal::new(1, 4);
+        self.forward_rate(t, t.try_add(dt)?)
+    }
+}
+
+/// Approximates exp(x) using Taylor series.
+///
+/// This is a no_std compatible implementation for small to moderate values of x.
+/// For |x| < 2, uses 12 terms for good precision.
+fn exp_approx(x: Decimal) -> Result<Decimal, ArithmeticError> {
+    // For very small x, return 1 + x
+    if x.abs() < Decimal::new(1, 10) {
+        return Decimal::ONE.try_add(x);
+    }
+
+    // Taylor series: exp(x) = 1 + x + x^2/2! + x^3/3! + ...
+    let mut sum = Decimal::ONE;
+    let mut term = Decimal::ONE;
+
+    for n in 1..=16 {
+        term = term.try_mul(x)?.try_div(Decimal::from(n as i64))?;
+        sum = sum.try_add(term)?;
+
+        // Early termination if term is negligible
+        if term.abs() < Decimal::new(1, 20) {
+            break;
+        }
+    }
+
+    Ok(sum)
+}
+
+/// Approximates ln(x) using series expansion.
+///
+/// Uses the identity: ln(x) = 2 * arctanh((x-1)/(x+1)) for x > 0
+#[allow(dead_code)]
+fn ln_approx(x: Decimal) -> Result<Decimal, ArithmeticError> {
+    if x <= Decimal::ZERO {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+
+    // For x close to 1, use ln(1+y) series where y = x - 1
+    let y = x.try_sub(Decimal::ONE)?;
+    if y.abs() < Decimal::new(5, 1) {
+        // |x - 1| < 0.5, use arctanh formula
+        let num = x.try_sub(Decimal::ONE)?;
+        let den = x.try_add(Decimal::ONE)?;
+        let z = num.try_div(den)?;
+
+        // arctanh(z) = z + z^3/3 + z^5/5 + ...
+        let mut sum = z;
+        let mut z_pow = z;
+        let z_sq = z.try_mul(z)?;
+
+        for n in (3..=15).step_by(2) {
+            z_pow = z_pow.try_mul(z_sq)?;
+            let term = z_pow.try_div(Decimal::from(n as i64))?;
+            sum = sum.try_add(term)?;
+        }
+
+        // ln(x) = 2 * arctanh((x-1)/(x+1))
+        sum.try_mul(Decimal::from(2i64))
+    } else {
+        // For larger values, use reduction: ln(x) = ln(x/e) + 1
+        // This is simplified; in production, use range reduction
+        let e_approx = Decimal::new(2718281828, 9); // ~e
+        let reduced = x.try_div(e_approx)?;
+        let ln_reduced = ln_approx(reduced)?;
+        ln_reduced.try_add(Decimal::ONE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use precision_core::RoundingMode;
+
+    #[test]
+    fn test_flat_structure_discount() {
+        let curve = FlatTermStructure::new(Decimal::new(5, 2)); // 5%
+        let t = Decimal::ONE; // 1 year
+
+        let df = curve.discount_factor(t).unwrap();
+        // exp(-0.05) ≈ 0.9512
+        let rounded = df.round(4, RoundingMode::HalfEven);
+        assert!(rounded > Decimal::new(95, 2));
+        assert!(rounded < Decimal::new(96, 2));
+    }
+
+    #[test]
+    fn test_flat_structure_zero_rate() {
+        let rate = Decimal::new(5, 2);
+        let curve = FlatTermStructure::new(rate);
+
+        assert_eq!(curve.zero_rate(Decimal::ONE).unwrap(), rate);
+        assert_eq!(curve.zero_rate(Decimal::from(5i64)).unwrap(), rate);
+    }
+
+    #[test]
+    fn test_flat_structure_forward_rate() {
+        let rate = Decimal::new(5, 2);
+        let curve = FlatTermStructure::new(rate);
+
+        let fwd = curve
+            .forward_rate(Decimal::ONE, Decimal::from(2i64))
+            .unwrap();
+        assert_eq!(fwd, rate);
+    }
+
+    #[test]
+    fn test_piecewise_interpolation() {
+        let mut curve = PiecewiseTermStructure::new();
+        curve
+            .add_node(CurveNode::new(Decimal::ONE, Decimal::new(3, 2)))
+            .unwrap(); // 3% at 1Y
+        curve
+            .add_node(CurveNode::new(Decimal::from(2i64), Decimal::new(4, 2)))
+            .unwrap(); // 4% at 2Y
+
+        // At 1.5Y, should interpolate to 3.5%
+        let rate = curve.zero_rate(Decimal::new(15, 1)).unwrap();
+        let rounded = rate.round(4, RoundingMode::HalfEven);
+        assert_eq!(rounded, Decimal::new(35, 3));
+    }
+
+    #[test]
+    fn test_piecewise_extrapolation() {
+        let mut curve = PiecewiseTermStructure::new();
+        curve
+            .add_node(CurveNode::new(Decimal::ONE, Decimal::new(3, 2)))
+            .unwrap();
+        curve
+            .add_node(CurveNode::new(Decimal::from(2i64), Decimal::new(4, 2)))
+            .unwrap();
+
+        // Beyond 2Y, should extrapolate flat at 4%
+        let rate = curve.zero_rate(Decimal::from(5i64)).unwrap();
+        assert_eq!(rate, Decimal::new(4, 2));
+
+        // Before 1Y, should extrapolate flat at 3%
+        let rate = curve.zero_rate(Decimal::new(5, 1)).unwrap();
+        assert_eq!(rate, Decimal::new(3, 2));
+    }
+
+    #[test]
+    fn test_exp_approx() {
+        // exp(0) = 1
+        let e0 = exp_approx(Decimal::ZERO).unwrap();
+        assert_eq!(e0, Decimal::ONE);
+
+        // exp(1) ≈ 2.718
+        let e1 = exp_approx(Decimal::ONE).unwrap();
+        let rounded = e1.round(3, RoundingMode::HalfEven);
+        assert_eq!(rounded, Decimal::new(2718, 3));
+
+        // exp(-1) ≈ 0.368
+        let e_neg1 = exp_approx(-Decimal::ONE).unwrap();
+        let rounded = e_neg1.round(3, RoundingMode::HalfEven);
+        assert_eq!(rounded, Decimal::new(368, 3));
+    }
+
+    #[test]
+    fn test_forward_rate_calculation() {
+        let mut curve = PiecewiseTermStructure::new();
+        curve
+            .add_node(CurveNode::new(Decimal::ONE, Decimal::new(3, 2)))
+            .unwrap(); // 3% at 1Y
+        curve
+            .add_node(CurveNode::new(Decimal::from(2i64), Decimal::new(4, 2)))
+            .unwrap(); // 4% at 2Y
+
+        // Forward rate from 1Y to 2Y
+        // f(1,2) = (r2*t2 - r1*t1) / (t2 - t1) = (0.04*2 - 0.03*1) / 1 = 0.05
+        let fwd = curve
+            .forward_rate(Decimal::ONE, Decimal::from(2i64))
+            .unwrap();
+        let rounded = fwd.round(4, RoundingMode::HalfEven);
+        assert_eq!(rounded, Decimal::new(5, 2));
+    }
+
+    #[test]
+    fn test_piecewise_from_sorted_large_curve() {
+        let mut nodes = [CurveNode::default(); 300];
+        for (i, node) in nodes.iter_mut().enumerate() {
+            // Monthly pillars with rates rising 1bp per node
+            let t = Decimal::from(i as i64 + 1).try_div(Decimal::from(12i64)).unwrap();
+            *node = CurveNode::new(t, Decimal::new(200 + i as i64, 4));
+        }
+
+        let curve = PiecewiseTermStructure::<Fixed<512>>::from_sorted(&nodes).unwrap();
+        assert_eq!(curve.node_count(), 300);
+
+        // Exactly on the 121st pillar (10.0833Y)
+        let rate = curve.zero_rate(nodes[120].time).unwrap();
+        assert_eq!(rate, Decimal::new(320, 4));
+
+        // Default fixed storage cannot hold the curve
+        assert_eq!(
+            PiecewiseTermStructure::<Fixed<MAX_CURVE_NODES>>::from_sorted(&nodes).unwrap_err(),
+            ArithmeticError::Overflow
+        );
+    }
+
+    #[test]
+    fn test_piecewise_full_curve_overflows() {
+        let mut curve = PiecewiseTermStructure::<Fixed<2>>::with_storage();
+        curve.add_node(CurveNode::new(Decimal::ONE, Decimal::new(3, 2))).unwrap();
+        curve.add_node(CurveNode::new(Decimal::from(2i64), Decimal::new(4, 2))).unwrap();
+
+        let result = curve.add_node(CurveNode::new(Decimal::from(3i64), Decimal::new(5, 2)));
+        assert_eq!(result, Err(ArithmeticError::Overflow));
+    }
+
+    fn sample_nodes() -> [CurveNode; 4] {
+        [
+            CurveNode::new(Decimal::ONE, Decimal::new(2, 2)),
+            CurveNode::new(Decimal::from(2i64), Decimal::new(3, 2)),
+            CurveNode::new(Decimal::from(5i64), Decimal::new(35, 3)),
+            CurveNode::new(Decimal::from(10i64), Decimal::new(33, 3)),
+        ]
+    }
+
+    #[test]
+    fn test_log_linear_discount_flat_forwards() {
+        let curve = PiecewiseTermStructure::<Fixed<8>>::from_sorted(&sample_nodes())
+            .unwrap()
+            .with_interpolation(CurveInterpolation::LogLinearDiscount)
+            .unwrap();
+
+        // Forwards are constant within (2, 5]: (0.035*5 - 0.03*2) / 3
+        let expected = Decimal::new(115, 3).try_div(Decimal::from(3i64)).unwrap();
+        for t in ["2.5", "3", "4.5"] {
+            let t: Decimal = t.parse().unwrap();
+            let fwd = curve.forward_rate(t, t + Decimal::new(25, 2)).unwrap();
+            assert!((fwd - expected).abs() < Decimal::new(1, 20));
+        }
+
+        // Node rates are reproduced exactly
+        assert_eq!(curve.zero_rate(Decimal::from(5i64)).unwrap(), Decimal::new(35, 3));
+    }
+
+    #[test]
+    fn test_linear_extrapolation() {
+        let curve = PiecewiseTermStructure::<Fixed<8>>::from_sorted(&sample_nodes())
+            .unwrap()
+            .with_extrapolation(Extrapolation::Linear);
+
+        // Slope over (5, 10] is -0.0004 per year
+        let rate = curve.zero_rate(Decimal::from(15i64)).unwrap();
+        assert_eq!(rate, Decimal::new(31, 3));
+
+        // Slope over (1, 2] is 0.01 per year
+        let rate = curve.zero_rate(Decimal::new(5, 1)).unwrap();
+        assert_eq!(rate, Decimal::new(15, 3));
+    }
+
+    #[test]
+    fn test_natural_cubic_matches_spline() {
+        use crate::interpolation::{CubicSpline, Interpolator};
+
+        let nodes = sample_nodes();
+        let mut spline = CubicSpline::new();
+        for node in &nodes {
+            spline.add_point(DataPoint::new(node.time, node.rate)).unwrap();
+        }
+        spline.compute().unwrap();
+
+        let mut curve = PiecewiseTermStructure::new()
+            .with_interpolation(CurveInterpolation::NaturalCubic)
+            .unwrap();
+        for node in &nodes {
+            curve.add_node(*node).unwrap();
+        }
+
+        for t in ["1.5", "3", "7.25", "9.9"] {
+            let t: Decimal = t.parse().unwrap();
+            assert_eq!(curve.zero_rate(t).unwrap(), spline.interpolate(t).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_failed_add_node_leaves_curve_usable() {
+        let nodes = sample_nodes();
+        let mut curve = PiecewiseTermStructure::new()
+            .with_interpolation(CurveInterpolation::NaturalCubic)
+            .unwrap();
+        for node in &nodes {
+            curve.add_node(*node).unwrap();
+        }
+        let t = Decimal::new(3, 0);
+        let before = curve.zero_rate(t).unwrap();
+
+        // A repeated time cannot be fitted by the spline
+        let duplicate = CurveNode::new(nodes[1].time, Decimal::new(5, 2));
+        assert!(curve.add_node(duplicate).is_err());
+        assert_eq!(curve.node_count(), nodes.len());
+        assert_eq!(curve.nodes()[1].rate, nodes[1].rate);
+        assert_eq!(curve.zero_rate(t).unwrap(), before);
+        assert!(curve.zero_rate(nodes[nodes.len() - 1].time).is_ok());
+    }
+
+    #[test]
+    fn test_monotone_convex_continuous_forwards() {
+        let curve = PiecewiseTermStructure::<Fixed<8>>::from_sorted(&sample_nodes())
+            .unwrap()
+            .with_interpolation(CurveInterpolation::MonotoneConvex)
+            .unwrap();
+
+        // Node rates are reproduced
+        for node in &sample_nodes() {
+            let r = curve.zero_rate(node.time).unwrap();
+            assert!((r - node.rate).abs() < Decimal::new(1, 20));
+        }
+
+        // Forwards are continuous across the 2Y and 5Y nodes
+        let eps = Decimal::new(1, 6);
+        for t in [Decimal::from(2i64), Decimal::from(5i64)] {
+            let left = curve.instantaneous_forward(t - eps).unwrap();
+            let right = curve.instantaneous_forward(t + eps).unwrap();
+            assert!((left - right).abs() < Decimal::new(1, 4));
+        }
+
+        // Short end is interpolated from the origin, not held flat
+        assert_ne!(curve.zero_rate(Decimal::new(5, 1)).unwrap(), Decimal::new(2, 2));
+    }
+}
@@ -0,0 +1,314 @@
+//! Monotone convex interpolation (Hagan & West, 2006).
+//!
+//! Interpolates instantaneous forwards so that each interval reproduces the
+//! discrete forward implied by the curve nodes exactly. Forwards are
+//! continuous, do not overshoot between nodes and, when the discrete forwards
+//! are non-negative, stay non-negative.
+//!
+//! The curve is anchored at the origin (t = 0). A node at time zero is used
+//! as the origin directly; otherwise one is implied.
+
+use super::CurveNode;
+use crate::day_count::YearFraction;
+use precision_core::{ArithmeticError, Decimal};
+
+/// Knots of the curve: the origin followed by the nodes.
+struct Knots<'a> {
+    nodes: &'a [CurveNode],
+    /// 1 if the origin is implied, 0 if the first node sits at t = 0
+    offset: usize,
+}
+
+impl<'a> Knots<'a> {
+    fn new(nodes: &'a [CurveNode]) -> Self {
+        let offset = usize::from(!nodes[0].time.is_zero());
+        Self { nodes, offset }
+    }
+
+    /// Index of the last knot.
+    fn last(&self) -> usize {
+        self.nodes.len() - 1 + self.offset
+    }
+
+    fn time(&self, k: usize) -> YearFraction {
+        if k < self.offset {
+            Decimal::ZERO
+        } else {
+            self.nodes[k - self.offset].time
+        }
+    }
+
+    /// Integrated forward r(t_k) * t_k at knot k.
+    fn rate_time(&self, k: usize) -> Result<Decimal, ArithmeticError> {
+        if k < self.offset {
+            Ok(Decimal::ZERO)
+        } else {
+            let node = &self.nodes[k - self.offset];
+            node.rate.try_mul(node.time)
+        }
+    }
+
+    /// Discrete forward over interval (t_{i-1}, t_i].
+    fn discrete_forward(&self, i: usize) -> Result<Decimal, ArithmeticError> {
+        let dt = self.time(i).try_sub(self.time(i - 1))?;
+        self.rate_time(i)?
+            .try_sub(self.rate_time(i - 1)?)?
+            .try_div(dt)
+    }
+
+    /// Instantaneous forward at knot k.
+    fn node_forward(&self, k: usize) -> Result<Decimal, ArithmeticError> {
+        let last = self.last();
+        if last == 1 {
+            return self.discrete_forward(1);
+        }
+
+        if k == 0 || k == last {
+            // Boundary: f_0 = fd_1 - (f_1 - fd_1) / 2, similarly at the end
+            let (edge, inner) = if k == 0 { (1, 1) } else { (last, last - 1) };
+            let fd = self.discrete_forward(edge)?;
+            let f_inner = self.node_forward(inner)?;
+            let f = fd.try_sub(f_inner.try_sub(fd)?.try_div(Decimal::from(2i64))?)?;
+            return Ok(collar(f, fd, fd));
+        }
+
+        // Interior: interval-weighted average of adjacent discrete forwards
+        let (t_prev, t, t_next) = (self.time(k - 1), self.time(k), self.time(k + 1));
+        let fd_left = self.discrete_forward(k)?;
+        let fd_right = self.discrete_forward(k + 1)?;
+        let w = t.try_sub(t_prev)?.try_div(t_next.try_sub(t_prev)?)?;
+        let f = w
+            .try_mul(fd_right)?
+            .try_add(Decimal::ONE.try_sub(w)?.try_mul(fd_left)?)?;
+        Ok(collar(f, fd_left, fd_right))
+    }
+
+    /// Locates the interval (t_{i-1}, t_i] containing t and returns
+    /// (i, x, g0, g1), with x the position within the interval in [0, 1].
+    fn interval(
+        &self,
+        t: YearFraction,
+    ) -> Result<(usize, Decimal, Decimal, Decimal), ArithmeticError> {
+        let j = self.nodes.partition_point(|n| n.time < t);
+        let i = (j + self.offset).clamp(1, self.last());
+
+        let t0 = self.time(i - 1);
+        let x = t.try_sub(t0)?.try_div(self.time(i).try_sub(t0)?)?;
+        let fd = self.discrete_forward(i)?;
+        let g0 = self.node_forward(i - 1)?.try_sub(fd)?;
+        let g1 = self.node_forward(i)?.try_sub(fd)?;
+        Ok((i, x, g0, g1))
+    }
+}
+
+/// Bounds a node forward to [0, 2 * min(fd_left, fd_right)] so the
+/// interpolated forward stays non-negative when the discrete forwards are.
+fn collar(f: Decimal, fd_left: Decimal, fd_right: Decimal) -> Decimal {
+    if fd_left.is_negative() || fd_right.is_negative() {
+        return f;
+    }
+    let upper = fd_left.min(fd_right).saturating_mul(Decimal::from(2i64));
+    f.max(Decimal::ZERO).min(upper)
+}
+
+/// Zero rate at t, for t within (0, last node time].
+///
+/// At t <= 0 the limit r(0) = f(0) is returned.
+pub(super) fn zero_rate(nodes: &[CurveNode], t: YearFraction) -> Result<Decimal, ArithmeticError> {
+    let knots = Knots::new(nodes);
+    if knots.last() == 0 {
+        return Ok(nodes[0].rate);
+    }
+    if !t.is_positive() {
+        return knots.node_forward(0);
+    }
+
+    let (i, x, g0, g1) = knots.interval(t)?;
+    let (_, big_g) = forward_adjustment(g0, g1, x)?;
+    let t0 = knots.time(i - 1);
+    let dt = knots.time(i).try_sub(t0)?;
+    let fd = knots.discrete_forward(i)?;
+
+    // r(t) t = r(t_{i-1}) t_{i-1} + dt * (fd * x + G(x))
+    let integral = fd.try_mul(x)?.try_add(big_g)?.try_mul(dt)?;
+    knots.rate_time(i - 1)?.try_add(integral)?.try_div(t)
+}
+
+/// Instantaneous forward at t, for t within (0, last node time].
+pub(super) fn instantaneous_forward(
+    nodes: &[CurveNode],
+    t: YearFraction,
+) -> Result<Decimal, ArithmeticError> {
+    let knots = Knots::new(nodes);
+    if knots.last() == 0 {
+        return Ok(nodes[0].rate);
+    }
+    if !t.is_positive() {
+        return knots.node_forward(0);
+    }
+
+    let (i, x, g0, g1) = knots.interval(t)?;
+    let (g, _) = forward_adjustment(g0, g1, x)?;
+    knots.discrete_forward(i)?.try_add(g)
+}
+
+/// Hagan-West forward adjustment g(x) and its integral G(x) = ∫_0^x g.
+///
+/// g0 and g1 are the node forwards less the interval's discrete forward, at
+/// the left and right ends. The shape is chosen by which sector (g0, g1)
+/// falls in, keeping g monotone between the endpoints.
+fn forward_adjustment(
+    g0: Decimal,
+    g1: Decimal,
+    x: Decimal,
+) -> Result<(Decimal, Decimal), ArithmeticError> {
+    let zero = Decimal::ZERO;
+    let two = Decimal::from(2i64);
+    let three = Decimal::from(3i64);
+
+    if g0.is_zero() && g1.is_zero() {
+        return Ok((zero, zero));
+    }
+
+    let half_neg_g0 = -g0.try_div(two)?;
+    let neg_two_g0 = -g0.try_mul(two)?;
+    let x2 = x.try_mul(x)?;
+    let x3 = x2.try_mul(x)?;
+
+    // Sector (i): quadratic, no overshoot
+    if (g0 < zero && half_neg_g0 <= g1 && g1 <= neg_two_g0)
+        || (g0 > zero && half_neg_g0 >= g1 && g1 >= neg_two_g0)
+    {
+        // g = g0 (1 - 4x + 3x^2) + g1 (-2x + 3x^2)
+        let g = g0
+            .try_mul(
+                Decimal::ONE
+                    .try_sub(x.try_mul(Decimal::from(4i64))?)?
+                    .try_add(three.try_mul(x2)?)?,
+            )?
+            .try_add(g1.try_mul(three.try_mul(x2)?.try_sub(two.try_mul(x)?)?)?)?;
+        // G = g0 (x - 2x^2 + x^3) + g1 (x^3 - x^2)
+        let big_g = g0
+            .try_mul(x.try_sub(two.try_mul(x2)?)?.try_add(x3)?)?
+            .try_add(g1.try_mul(x3.try_sub(x2)?)?)?;
+        return Ok((g, big_g));
+    }
+
+    // Sector (ii): flat at g0, then quadratic up to g1
+    if (g0 < zero && g1 > neg_two_g0) || (g0 > zero && g1 < neg_two_g0) {
+        let eta = g1.try_add(two.try_mul(g0)?)?.try_div(g1.try_sub(g0)?)?;
+        let (tail_g, tail_int) = right_branch(g1.try_sub(g0)?, eta, x)?;
+        return Ok((g0.try_add(tail_g)?, g0.try_mul(x)?.try_add(tail_int)?));
+    }
+
+    // Sector (iii): quadratic from g0 down to g1, then flat
+    if (g0 > zero && g1 < zero && g1 > half_neg_g0) || (g0 < zero && g1 > zero && g1 < half_neg_g0)
+    {
+        let eta = three.try_mul(g1)?.try_div(g1.try_sub(g0)?)?;
+        let (head_g, head_int) = left_branch(g0.try_sub(g1)?, eta, x)?;
+        return Ok((g1.try_add(head_g)?, g1.try_mul(x)?.try_add(head_int)?));
+    }
+
+    // Sector (iv): g0 and g1 share a sign; both branches meet at A
+    let eta = g1.try_div(g1.try_add(g0)?)?;
+    let a = -g0.try_mul(g1)?.try_div(g0.try_add(g1)?)?;
+    let (head_g, head_int) = left_branch(g0.try_sub(a)?, eta, x)?;
+    let (tail_g, tail_int) = right_branch(g1.try_sub(a)?, eta, x)?;
+    let g = a.try_add(head_g)?.try_add(tail_g)?;
+    let big_g = a.try_mul(x)?.try_add(head_int)?.try_add(tail_int)?;
+    Ok((g, big_g))
+}
+
+/// Decaying term scale * ((eta - x) / eta)^2 for x < eta, and its integral.
+fn left_branch(
+    scale: Decimal,
+    eta: Decimal,
+    x: Decimal,
+) -> Result<(Decimal, Decimal), ArithmeticError> {
+    if !eta.is_positive() {
+        return Ok((Decimal::ZERO, Decimal::ZERO));
+    }
+    let u = eta.try_sub(x.min(eta))?.try_div(eta)?;
+    let u2 = u.try_mul(u)?;
+    let g = scale.try_mul(u2)?;
+    // ∫_0^x ((eta - s) / eta)^2 ds = eta / 3 * (1 - u^3)
+    let integral = scale
+        .try_mul(eta.try_div(Decimal::from(3i64))?)?
+        .try_mul(Decimal::ONE.try_sub(u2.try_mul(u)?)?)?;
+    Ok((g, integral))
+}
+
+/// Growing term scale * ((x - eta) / (1 - eta))^2 for x > eta, and its integral.
+fn right_branch(
+    scale: Decimal,
+    eta: Decimal,
+    x: Decimal,
+) -> Result<(Decimal, Decimal), ArithmeticError> {
+    if x <= eta || eta >= Decimal::ONE {
+        return Ok((Decimal::ZERO, Decimal::ZERO));
+    }
+    let width = Decimal::ONE.try_sub(eta)?;
+    let v = x.try_sub(eta)?.try_div(width)?;
+    let v2 = v.try_mul(v)?;
+    let g = scale.try_mul(v2)?;
+    // ∫_eta^x ((s - eta) / (1 - eta))^2 ds = (1 - eta) / 3 * v^3
+    let integral = scale
+        .try_mul(width.try_div(Decimal::from(3i64))?)?
+        .try_mul(v2.try_mul(v)?)?;
+    Ok((g, integral))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve() -> [CurveNode; 4] {
+        [
+            CurveNode::new(Decimal::ONE, Decimal::new(2, 2)),
+            CurveNode::new(Decimal::from(2i64), Decimal::new(3, 2)),
+            CurveNode::new(Decimal::from(5i64), Decimal::new(35, 3)),
+            CurveNode::new(Decimal::from(10i64), Decimal::new(33, 3)),
+        ]
+    }
+
+    #[test]
+    fn test_reproduces_node_rates() {
+        let nodes = curve();
+        for node in &nodes {
+            let r = zero_rate(&nodes, node.time).unwrap();
+            assert!((r - node.rate).abs() < Decimal::new(1, 20));
+        }
+    }
+
+    #[test]
+    fn test_forward_integrates_to_discrete_forward() {
+        // Midpoint rule over (2, 5]: average instantaneous forward should
+        // match the discrete forward (0.035*5 - 0.03*2) / 3
+        let nodes = curve();
+        let steps = 300i64;
+        let dt = Decimal::from(3i64).try_div(Decimal::from(steps)).unwrap();
+        let mut sum = Decimal::ZERO;
+        for k in 0..steps {
+            let t = Decimal::from(2i64) + dt * (Decimal::from(k) + Decimal::new(5, 1));
+            sum = sum + instantaneous_forward(&nodes, t).unwrap() * dt;
+        }
+        let expected = Decimal::new(115, 3);
+        assert!((sum - expected).abs() < Decimal::new(1, 6));
+    }
+
+    #[test]
+    fn test_forwards_non_negative() {
+        // Steeply inverted curve: linear-in-rate forwards would go negative
+        let nodes = [
+            CurveNode::new(Decimal::ONE, Decimal::new(8, 2)),
+            CurveNode::new(Decimal::from(2i64), Decimal::new(45, 3)),
+            CurveNode::new(Decimal::from(3i64), Decimal::new(31, 3)),
+        ];
+        for k in 1..=60 {
+            let t = Decimal::from(k as i64)
+                .try_div(Decimal::from(20i64))
+                .unwrap();
+            assert!(!instantaneous_forward(&nodes, t).unwrap().is_negative());
+        }
+    }
+}
@@ -0,0 +1,708 @@
+//! Parametric yield curves.
+//!
+//! Parametric curves describe the whole zero curve with a handful of
+//! parameters, so they are smooth by construction and free of the kinks that
+//! node-based interpolation introduces between pillars.
+//!
+//! - [`NelsonSiegel`]: level, slope and curvature factors with one decay
+//! - [`Svensson`]: Nelson-Siegel plus a second curvature hump
+//!
+//! Both curves can be calibrated to observed zero rates or bond prices by
+//! least squares (see [`levenberg_marquardt`]).
+
+use super::{CurveNode, TermStructure};
+use crate::day_count::YearFraction;
+use crate::solver::{levenberg_marquardt, solve_linear_system};
+use precision_core::{ArithmeticError, Decimal};
+
+/// Outcome of calibrating a parametric curve.
+#[derive(Debug, Clone)]
+pub struct CalibrationResult<C> {
+    /// The fitted curve.
+    pub curve: C,
+    /// Number of solver iterations used.
+    pub iterations: u32,
+    /// Sum of squared residuals (rate or price errors) at the fit.
+    pub residual: Decimal,
+    /// Whether the solver converged.
+    pub converged: bool,
+}
+
+/// An observed coupon bond price used for curve calibration.
+///
+/// Coupons are paid `frequency` times a year, counting back from maturity.
+#[derive(Debug, Clone, Copy)]
+pub struct BondQuote {
+    /// Time to maturity as year fraction
+    pub maturity: YearFraction,
+    /// Annual coupon rate (e.g., 0.05 for 5%), zero for a discount bond
+    pub coupon_rate: Decimal,
+    /// Coupon payments per year, zero only for a discount bond
+    pub frequency: u32,
+    /// Observed dirty price per unit of face value
+    pub price: Decimal,
+}
+
+impl BondQuote {
+    /// Creates a new coupon bond quote.
+    pub fn new(
+        maturity: YearFraction,
+        coupon_rate: Decimal,
+        frequency: u32,
+        price: Decimal,
+    ) -> Self {
+        Self {
+            maturity,
+            coupon_rate,
+            frequency,
+            price,
+        }
+    }
+
+    /// Creates a zero-coupon bond quote.
+    pub fn zero_coupon(maturity: YearFraction, price: Decimal) -> Self {
+        Self::new(maturity, Decimal::ZERO, 0, price)
+    }
+
+    /// Prices the bond off a term structure.
+    ///
+    /// Returns the present value per unit face of all remaining coupons plus
+    /// the redemption at maturity.
+    ///
+    /// Returns error if the bond pays a coupon with a frequency of zero.
+    pub fn model_price<T: TermStructure + ?Sized>(
+        &self,
+        curve: &T,
+    ) -> Result<Decimal, ArithmeticError> {
+        let mut price = curve.discount_factor(self.maturity)?;
+
+        if self.is_discount()? {
+            return Ok(price);
+        }
+
+        let period = Decimal::ONE.try_div(Decimal::from(self.frequency))?;
+        let coupon = self.coupon_rate.try_mul(period)?;
+
+        let mut t = self.maturity;
+        while t.is_positive() {
+            let df = curve.discount_factor(t)?;
+            price = price.try_add(coupon.try_mul(df)?)?;
+            t = t.try_sub(period)?;
+        }
+
+        Ok(price)
+    }
+
+    /// Rough yield used to seed calibration: continuously compounded yield
+    /// for discount bonds, current yield plus pull-to-par otherwise.
+    fn approximate_yield(&self) -> Result<Decimal, ArithmeticError> {
+        if self.is_discount()? {
+            let ln_p = self.price.try_ln()?;
+            return (-ln_p).try_div(self.maturity);
+        }
+        let pull_to_par = Decimal::ONE.try_sub(self.price)?.try_div(self.maturity)?;
+        self.coupon_rate.try_add(pull_to_par)
+    }
+
+    /// Whether the bond pays no coupons. A coupon with no payment
+    /// frequency is an error rather than a discount bond.
+    fn is_discount(&self) -> Result<bool, ArithmeticError> {
+        match (self.coupon_rate.is_zero(), self.frequency) {
+            (true, _) => Ok(true),
+            (false, 0) => Err(ArithmeticError::DivisionByZero),
+            (false, _) => Ok(false),
+        }
+    }
+}
+
+/// Nelson-Siegel parametric zero curve.
+///
+/// The continuously compounded zero rate is
+///
+/// r(t) = β₀ + β₁ · (1 - e^(-t/τ)) / (t/τ) + β₂ · ((1 - e^(-t/τ)) / (t/τ) - e^(-t/τ))
+///
+/// where β₀ is the long-run level, β₀ + β₁ the short rate, β₂ the size of
+/// the medium-term hump and τ its location.
+#[derive(Debug, Clone, Copy)]
+pub struct NelsonSiegel {
+    beta0: Decimal,
+    beta1: Decimal,
+    beta2: Decimal,
+    tau: Decimal,
+}
+
+impl NelsonSiegel {
+    /// Creates a Nelson-Siegel curve.
+    ///
+    /// Returns error if the decay `tau` is not positive.
+    pub fn new(
+        beta0: Decimal,
+        beta1: Decimal,
+        beta2: Decimal,
+        tau: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        if !tau.is_positive() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(Self {
+            beta0,
+            beta1,
+            beta2,
+            tau,
+        })
+    }
+
+    /// Long-term level β₀.
+    pub fn beta0(&self) -> Decimal {
+        self.beta0
+    }
+
+    /// Slope factor β₁.
+    pub fn beta1(&self) -> Decimal {
+        self.beta1
+    }
+
+    /// Curvature factor β₂.
+    pub fn beta2(&self) -> Decimal {
+        self.beta2
+    }
+
+    /// Decay parameter τ (in years).
+    pub fn tau(&self) -> Decimal {
+        self.tau
+    }
+
+    /// Calibrates the curve to observed zero rates.
+    ///
+    /// If `initial` is `None` a starting point is derived from the quotes.
+    /// Requires at least four quotes.
+    pub fn fit_zero_rates(
+        quotes: &[CurveNode],
+        initial: Option<Self>,
+    ) -> Result<CalibrationResult<Self>, ArithmeticError> {
+        let start = match initial {
+            Some(curve) => curve,
+            None => Self::grid_start(quotes.len(), |i| Ok((quotes[i].time, quotes[i].rate)))?,
+        };
+        calibrate(
+            quotes.len(),
+            start.params(),
+            Self::from_params,
+            |curve, i| {
+                let q = &quotes[i];
+                curve.zero_rate(q.time)?.try_sub(q.rate)
+            },
+        )
+    }
+
+    /// Calibrates the curve to observed bond prices.
+    ///
+    /// If `initial` is `None` a starting point is derived from approximate
+    /// bond yields. Requires at least four quotes.
+    pub fn fit_bond_prices(
+        quotes: &[BondQuote],
+        initial: Option<Self>,
+    ) -> Result<CalibrationResult<Self>, ArithmeticError> {
+        let start = match initial {
+            Some(curve) => curve,
+            None => Self::grid_start(quotes.len(), |i| {
+                Ok((quotes[i].maturity, quotes[i].approximate_yield()?))
+            })?,
+        };
+        calibrate(
+            quotes.len(),
+            start.params(),
+            Self::from_params,
+            |curve, i| {
+                let q = &quotes[i];
+                q.model_price(curve)?.try_sub(q.price)
+            },
+        )
+    }
+
+    /// Profiles the decay over a grid, fitting the betas linearly at each
+    /// point, and returns the best candidate as a calibration start.
+    fn grid_start<F>(count: usize, node: F) -> Result<Self, ArithmeticError>
+    where
+        F: Fn(usize) -> Result<(YearFraction, Decimal), ArithmeticError>,
+    {
+        let mut best: Option<(Decimal, Self)> = None;
+        for tau in decay_grid() {
+            let fit = linear_fit(count, |i| {
+                let (t, rate) = node(i)?;
+                let (slope, curvature) = factor_loadings(t, tau)?;
+                Ok(([Decimal::ONE, slope, curvature], rate))
+            });
+            if let Ok((b, sse)) = fit {
+                if best.as_ref().map_or(true, |(s, _)| sse < *s) {
+                    best = Some((sse, Self::new(b[0], b[1], b[2], tau)?));
+                }
+            }
+        }
+        best.map(|(_, curve)| curve)
+            .ok_or(ArithmeticError::DivisionByZero)
+    }
+
+    fn params(&self) -> [Decimal; 4] {
+        [self.beta0, self.beta1, self.beta2, self.tau]
+    }
+
+    fn from_params(p: &[Decimal; 4]) -> Result<Self, ArithmeticError> {
+        Self::new(p[0], p[1], p[2], p[3])
+    }
+}
+
+impl TermStructure for NelsonSiegel {
+    fn discount_factor(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let rate = self.zero_rate(t)?;
+        (-rate.try_mul(t)?).try_exp()
+    }
+
+    fn zero_rate(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let (slope, curvature) = factor_loadings(t, self.tau)?;
+        self.beta0
+            .try_add(self.beta1.try_mul(slope)?)?
+            .try_add(self.beta2.try_mul(curvature)?)
+    }
+
+    fn instantaneous_forward(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        // f(t) = β₀ + β₁e^(-t/τ) + β₂(t/τ)e^(-t/τ)
+        let x = t.max(Decimal::ZERO).try_div(self.tau)?;
+        let e = (-x).try_exp()?;
+        self.beta0
+            .try_add(self.beta1.try_mul(e)?)?
+            .try_add(self.beta2.try_mul(x)?.try_mul(e)?)
+    }
+}
+
+/// Svensson (Nelson-Siegel-Svensson) parametric zero curve.
+///
+/// Extends [`NelsonSiegel`] with a second curvature term β₃ with its own
+/// decay τ₂, which lets the curve fit a second hump at the long end:
+///
+/// r(t) = NS(t; β₀, β₁, β₂, τ₁) + β₃ · ((1 - e^(-t/τ₂)) / (t/τ₂) - e^(-t/τ₂))
+#[derive(Debug, Clone, Copy)]
+pub struct Svensson {
+    beta0: Decimal,
+    beta1: Decimal,
+    beta2: Decimal,
+    beta3: Decimal,
+    tau1: Decimal,
+    tau2: Decimal,
+}
+
+impl Svensson {
+    /// Creates a Svensson curve.
+    ///
+    /// Returns error if either decay parameter is not positive.
+    pub fn new(
+        beta0: Decimal,
+        beta1: Decimal,
+        beta2: Decimal,
+        beta3: Decimal,
+        tau1: Decimal,
+        tau2: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        if !tau1.is_positive() || !tau2.is_positive() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(Self {
+            beta0,
+            beta1,
+            beta2,
+            beta3,
+            tau1,
+            tau2,
+        })
+    }
+
+    /// Long-term level β₀.
+    pub fn beta0(&self) -> Decimal {
+        self.beta0
+    }
+
+    /// Slope factor β₁.
+    pub fn beta1(&self) -> Decimal {
+        self.beta1
+    }
+
+    /// First curvature factor β₂.
+    pub fn beta2(&self) -> Decimal {
+        self.beta2
+    }
+
+    /// Second curvature factor β₃.
+    pub fn beta3(&self) -> Decimal {
+        self.beta3
+    }
+
+    /// First decay parameter τ₁ (in years).
+    pub fn tau1(&self) -> Decimal {
+        self.tau1
+    }
+
+    /// Second decay parameter τ₂ (in years).
+    pub fn tau2(&self) -> Decimal {
+        self.tau2
+    }
+
+    /// Calibrates the curve to observed zero rates.
+    ///
+    /// If `initial` is `None` a starting point is derived from the quotes.
+    /// Requires at least six quotes.
+    pub fn fit_zero_rates(
+        quotes: &[CurveNode],
+        initial: Option<Self>,
+    ) -> Result<CalibrationResult<Self>, ArithmeticError> {
+        let start = match initial {
+            Some(curve) => curve,
+            None => Self::grid_start(quotes.len(), |i| Ok((quotes[i].time, quotes[i].rate)))?,
+        };
+        calibrate(
+            quotes.len(),
+            start.params(),
+            Self::from_params,
+            |curve, i| {
+                let q = &quotes[i];
+                curve.zero_rate(q.time)?.try_sub(q.rate)
+            },
+        )
+    }
+
+    /// Calibrates the curve to observed bond prices.
+    ///
+    /// If `initial` is `None` a starting point is derived from approximate
+    /// bond yields. Requires at least six quotes.
+    pub fn fit_bond_prices(
+        quotes: &[BondQuote],
+        initial: Option<Self>,
+    ) -> Result<CalibrationResult<Self>, ArithmeticError> {
+        let start = match initial {
+            Some(curve) => curve,
+            None => Self::grid_start(quotes.len(), |i| {
+                Ok((quotes[i].maturity, quotes[i].approximate_yield()?))
+            })?,
+        };
+        calibrate(
+            quotes.len(),
+            start.params(),
+            Self::from_params,
+            |curve, i| {
+                let q = &quotes[i];
+                q.model_price(curve)?.try_sub(q.price)
+            },
+        )
+    }
+
+    /// Profiles both decays over a grid (with τ₁ < τ₂), fitting the betas
+    /// linearly at each point, and returns the best calibration start.
+    fn grid_start<F>(count: usize, node: F) -> Result<Self, ArithmeticError>
+    where
+        F: Fn(usize) -> Result<(YearFraction, Decimal), ArithmeticError>,
+    {
+        let grid = decay_grid();
+        let mut best: Option<(Decimal, Self)> = None;
+        for (k, &tau1) in grid.iter().enumerate() {
+            for &tau2 in &grid[k + 1..] {
+                let fit = linear_fit(count, |i| {
+                    let (t, rate) = node(i)?;
+                    let (slope, curvature1) = factor_loadings(t, tau1)?;
+                    let (_, curvature2) = factor_loadings(t, tau2)?;
+                    Ok(([Decimal::ONE, slope, curvature1, curvature2], rate))
+                });
+                if let Ok((b, sse)) = fit {
+                    if best.as_ref().map_or(true, |(s, _)| sse < *s) {
+                        best = Some((sse, Self::new(b[0], b[1], b[2], b[3], tau1, tau2)?));
+                    }
+                }
+            }
+        }
+        best.map(|(_, curve)| curve)
+            .ok_or(ArithmeticError::DivisionByZero)
+    }
+
+    fn params(&self) -> [Decimal; 6] {
+        [
+            self.beta0, self.beta1, self.beta2, self.beta3, self.tau1, self.tau2,
+        ]
+    }
+
+    fn from_params(p: &[Decimal; 6]) -> Result<Self, ArithmeticError> {
+        Self::new(p[0], p[1], p[2], p[3], p[4], p[5])
+    }
+}
+
+impl TermStructure for Svensson {
+    fn discount_factor(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let rate = self.zero_rate(t)?;
+        (-rate.try_mul(t)?).try_exp()
+    }
+
+    fn zero_rate(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let (slope, curvature1) = factor_loadings(t, self.tau1)?;
+        let (_, curvature2) = factor_loadings(t, self.tau2)?;
+        self.beta0
+            .try_add(self.beta1.try_mul(slope)?)?
+            .try_add(self.beta2.try_mul(curvature1)?)?
+            .try_add(self.beta3.try_mul(curvature2)?)
+    }
+
+    fn instantaneous_forward(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let t = t.max(Decimal::ZERO);
+        let x1 = t.try_div(self.tau1)?;
+        let x2 = t.try_div(self.tau2)?;
+        let e1 = (-x1).try_exp()?;
+        let e2 = (-x2).try_exp()?;
+        self.beta0
+            .try_add(self.beta1.try_mul(e1)?)?
+            .try_add(self.beta2.try_mul(x1)?.try_mul(e1)?)?
+            .try_add(self.beta3.try_mul(x2)?.try_mul(e2)?)
+    }
+}
+
+/// Returns the Nelson-Siegel slope and curvature loadings at time t.
+///
+/// slope = (1 - e^(-x)) / x, curvature = slope - e^(-x), with x = t/τ.
+/// At t = 0 the loadings take their limits (1, 0).
+fn factor_loadings(t: YearFraction, tau: Decimal) -> Result<(Decimal, Decimal), ArithmeticError> {
+    let x = t.try_div(tau)?;
+    if x.abs() < Decimal::new(1, 12) {
+        return Ok((Decimal::ONE, Decimal::ZERO));
+    }
+    let e = (-x).try_exp()?;
+    let slope = Decimal::ONE.try_sub(e)?.try_div(x)?;
+    Ok((slope, slope.try_sub(e)?))
+}
+
+/// Runs the least-squares fit shared by all parametric curves.
+fn calibrate<C, B, R, const N: usize>(
+    count: usize,
+    start: [Decimal; N],
+    build: B,
+    residual: R,
+) -> Result<CalibrationResult<C>, ArithmeticError>
+where
+    B: Fn(&[Decimal; N]) -> Result<C, ArithmeticError>,
+    R: Fn(&C, usize) -> Result<Decimal, ArithmeticError>,
+{
+    if count < N {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+
+    let fit = levenberg_marquardt(
+        |i, p: &[Decimal; N]| residual(&build(p)?, i),
+        count,
+        start,
+        Some(Decimal::new(1, 14)),
+        Some(200),
+    )?;
+
+    Ok(CalibrationResult {
+        curve: build(&fit.params)?,
+        iterations: fit.iterations,
+        residual: fit.residual,
+        converged: fit.converged,
+    })
+}
+
+/// Candidate decay parameters (in years) used to seed calibration.
+fn decay_grid() -> [Decimal; 8] {
+    [
+        Decimal::new(25, 2),
+        Decimal::new(5, 1),
+        Decimal::ONE,
+        Decimal::from(2i64),
+        Decimal::from(3i64),
+        Decimal::from(5i64),
+        Decimal::from(7i64),
+        Decimal::from(10i64),
+    ]
+}
+
+/// Ordinary least squares for a model linear in its K coefficients.
+///
+/// `row(i)` returns the regressors and observed value of the i-th
+/// observation. Returns the coefficients and the sum of squared errors.
+fn linear_fit<F, const K: usize>(
+    count: usize,
+    row: F,
+) -> Result<([Decimal; K], Decimal), ArithmeticError>
+where
+    F: Fn(usize) -> Result<([Decimal; K], Decimal), ArithmeticError>,
+{
+    let mut xtx = [[Decimal::ZERO; K]; K];
+    let mut xty = [Decimal::ZERO; K];
+    for i in 0..count {
+        let (x, y) = row(i)?;
+        for j in 0..K {
+            xty[j] = xty[j].try_add(x[j].try_mul(y)?)?;
+            for k in 0..K {
+                xtx[j][k] = xtx[j][k].try_add(x[j].try_mul(x[k])?)?;
+            }
+        }
+    }
+
+    let beta = solve_linear_system(xtx, xty)?;
+
+    let mut sse = Decimal::ZERO;
+    for i in 0..count {
+        let (x, y) = row(i)?;
+        let mut fitted = Decimal::ZERO;
+        for j in 0..K {
+            fitted = fitted.try_add(x[j].try_mul(beta[j])?)?;
+        }
+        let err = fitted.try_sub(y)?;
+        sse = sse.try_add(err.try_mul(err)?)?;
+    }
+
+    Ok((beta, sse))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn decimal(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn sample_ns() -> NelsonSiegel {
+        NelsonSiegel::new(
+            decimal("0.05"),
+            decimal("-0.02"),
+            decimal("0.01"),
+            decimal("2"),
+        )
+        .unwrap()
+    }
+
+    const MATURITIES: [&str; 8] = ["0.25", "0.5", "1", "2", "3", "5", "7", "10"];
+
+    #[test]
+    fn test_nelson_siegel_limits() {
+        let curve = sample_ns();
+
+        // Short end tends to β₀ + β₁
+        let r0 = curve.zero_rate(Decimal::ZERO).unwrap();
+        assert_eq!(r0, decimal("0.03"));
+
+        // Long end tends to β₀
+        let r_long = curve.zero_rate(Decimal::from(1000i64)).unwrap();
+        assert!((r_long - decimal("0.05")).abs() < decimal("0.0001"));
+    }
+
+    #[test]
+    fn test_nelson_siegel_rejects_non_positive_tau() {
+        let result =
+            NelsonSiegel::new(decimal("0.05"), Decimal::ZERO, Decimal::ZERO, Decimal::ZERO);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_nelson_siegel_forward_matches_numerical() {
+        let curve = sample_ns();
+        let t = decimal("3");
+
+        let analytic = curve.instantaneous_forward(t).unwrap();
+        let numerical = curve.forward_rate(t, t + decimal("0.0001")).unwrap();
+        assert!((analytic - numerical).abs() < decimal("0.00001"));
+    }
+
+    #[test]
+    fn test_svensson_reduces_to_nelson_siegel() {
+        let ns = sample_ns();
+        let sv = Svensson::new(
+            decimal("0.05"),
+            decimal("-0.02"),
+            decimal("0.01"),
+            Decimal::ZERO,
+            decimal("2"),
+            decimal("5"),
+        )
+        .unwrap();
+
+        for m in MATURITIES {
+            let t = decimal(m);
+            assert_eq!(ns.zero_rate(t).unwrap(), sv.zero_rate(t).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_fit_nelson_siegel_to_zero_rates() {
+        let target = sample_ns();
+        let mut quotes = [CurveNode::new(Decimal::ZERO, Decimal::ZERO); 8];
+        for (q, m) in quotes.iter_mut().zip(MATURITIES) {
+            let t = decimal(m);
+            *q = CurveNode::new(t, target.zero_rate(t).unwrap());
+        }
+
+        let fit = NelsonSiegel::fit_zero_rates(&quotes, None).unwrap();
+
+        assert!(fit.converged);
+        for q in &quotes {
+            let err = fit.curve.zero_rate(q.time).unwrap() - q.rate;
+            assert!(err.abs() < decimal("0.000001"));
+        }
+    }
+
+    #[test]
+    fn test_fit_svensson_to_bond_prices() {
+        let target = Svensson::new(
+            decimal("0.045"),
+            decimal("-0.015"),
+            decimal("0.02"),
+            decimal("-0.01"),
+            decimal("1.5"),
+            decimal("6"),
+        )
+        .unwrap();
+
+        let mut quotes = [BondQuote::zero_coupon(Decimal::ONE, Decimal::ONE); 8];
+        for (q, m) in quotes.iter_mut().zip(MATURITIES) {
+            let t = decimal(m);
+            let coupon = if t > Decimal::ONE {
+                decimal("0.04")
+            } else {
+                Decimal::ZERO
+            };
+            let mut quote = BondQuote::new(t, coupon, 2, Decimal::ZERO);
+            quote.price = quote.model_price(&target).unwrap();
+            *q = quote;
+        }
+
+        let fit = Svensson::fit_bond_prices(&quotes, None).unwrap();
+        assert!(fit.converged);
+
+        for q in &quotes {
+            let err = q.model_price(&fit.curve).unwrap() - q.price;
+            assert!(err.abs() < decimal("0.0001"), "pricing error {}", err);
+        }
+    }
+
+    #[test]
+    fn test_bond_quote_rejects_coupon_without_frequency() {
+        let curve = NelsonSiegel::new(
+            decimal("0.04"),
+            decimal("-0.01"),
+            decimal("0.01"),
+            decimal("2"),
+        )
+        .unwrap();
+        let quote = BondQuote::new(decimal("5"), decimal("0.04"), 0, Decimal::ONE);
+        assert!(quote.model_price(&curve).is_err());
+        assert!(NelsonSiegel::fit_bond_prices(&[quote; 4], None).is_err());
+
+        let discount = BondQuote::zero_coupon(decimal("5"), Decimal::ONE);
+        assert_eq!(
+            discount.model_price(&curve).unwrap(),
+            curve.discount_factor(decimal("5")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_fit_requires_enough_quotes() {
+        let quotes = [CurveNode::new(Decimal::ONE, decimal("0.03"))];
+        assert!(NelsonSiegel::fit_zero_rates(&quotes, None).is_err());
+    }
+}
@@ -0,0 +1,391 @@
+//! Curve perturbations for risk and scenario analysis.
+//!
+//! Each wrapper takes a base curve (owned, or borrowed through the `&T`
+//! implementation of [`TermStructure`]) and is itself a [`TermStructure`],
+//! so perturbations compose: a spread over a parallel-shifted curve, a
+//! forward curve of a key-rate bumped curve, and so on.
+
+use super::TermStructure;
+use crate::day_count::YearFraction;
+use precision_core::{ArithmeticError, Decimal};
+
+/// One basis point (0.0001).
+const ONE_BP: Decimal = Decimal::from_parts(1, 0, 0, false, 4);
+
+impl<T: TermStructure + ?Sized> TermStructure for &T {
+    fn discount_factor(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        (**self).discount_factor(t)
+    }
+
+    fn zero_rate(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        (**self).zero_rate(t)
+    }
+
+    fn forward_rate(&self, t1: YearFraction, t2: YearFraction) -> Result<Decimal, ArithmeticError> {
+        (**self).forward_rate(t1, t2)
+    }
+
+    fn instantaneous_forward(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        (**self).instantaneous_forward(t)
+    }
+}
+
+/// A base curve with every zero rate shifted by a constant amount.
+///
+/// D'(t) = D(t) * exp(-shift * t)
+#[derive(Debug, Clone)]
+pub struct ParallelShift<T> {
+    base: T,
+    shift: Decimal,
+}
+
+impl<T: TermStructure> ParallelShift<T> {
+    /// Creates a curve with zero rates shifted by `shift` (e.g. 0.0001 for +1bp).
+    pub fn new(base: T, shift: Decimal) -> Self {
+        Self { base, shift }
+    }
+
+    /// Returns the shift applied to zero rates.
+    pub fn shift(&self) -> Decimal {
+        self.shift
+    }
+}
+
+impl<T: TermStructure> TermStructure for ParallelShift<T> {
+    fn discount_factor(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let bump = (-self.shift.try_mul(t)?).try_exp()?;
+        self.base.discount_factor(t)?.try_mul(bump)
+    }
+
+    fn zero_rate(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        self.base.zero_rate(t)?.try_add(self.shift)
+    }
+
+    fn forward_rate(&self, t1: YearFraction, t2: YearFraction) -> Result<Decimal, ArithmeticError> {
+        self.base.forward_rate(t1, t2)?.try_add(self.shift)
+    }
+
+    fn instantaneous_forward(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        self.base.instantaneous_forward(t)?.try_add(self.shift)
+    }
+}
+
+/// A base curve with a triangular bump around one key-rate tenor.
+///
+/// The bump is `shift` at the key tenor and falls linearly to zero at the
+/// neighbouring tenors. The first and last key rates extend flat to the
+/// short and long ends, so bumping every key rate by the same amount is
+/// equivalent to a parallel shift.
+#[derive(Debug, Clone)]
+pub struct KeyRateShift<T> {
+    base: T,
+    lower: Option<YearFraction>,
+    tenor: YearFraction,
+    upper: Option<YearFraction>,
+    shift: Decimal,
+}
+
+impl<T: TermStructure> KeyRateShift<T> {
+    /// Creates a curve bumped by `shift` at `tenors[index]`.
+    ///
+    /// Returns error if the tenors are not strictly increasing or the index
+    /// is out of range.
+    pub fn new(
+        base: T,
+        tenors: &[YearFraction],
+        index: usize,
+        shift: Decimal,
+    ) -> Result<Self, ArithmeticError> {
+        if index >= tenors.len() || tenors.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(Self {
+            base,
+            lower: index.checked_sub(1).map(|i| tenors[i]),
+            tenor: tenors[index],
+            upper: tenors.get(index + 1).copied(),
+            shift,
+        })
+    }
+
+    /// Returns the key tenor being bumped.
+    pub fn tenor(&self) -> YearFraction {
+        self.tenor
+    }
+
+    /// Bump applied to the zero rate at time t.
+    fn bump(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let neighbour = if t <= self.tenor {
+            self.lower
+        } else {
+            self.upper
+        };
+        let Some(edge) = neighbour else {
+            return Ok(self.shift);
+        };
+
+        let width = self.tenor.try_sub(edge)?.abs();
+        let weight = Decimal::ONE.try_sub(t.try_sub(self.tenor)?.abs().try_div(width)?)?;
+        self.shift.try_mul(weight.max(Decimal::ZERO))
+    }
+}
+
+impl<T: TermStructure> TermStructure for KeyRateShift<T> {
+    fn discount_factor(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let bump = (-self.bump(t)?.try_mul(t)?).try_exp()?;
+        self.base.discount_factor(t)?.try_mul(bump)
+    }
+
+    fn zero_rate(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        self.base.zero_rate(t)?.try_add(self.bump(t)?)
+    }
+}
+
+/// A base curve plus an additive spread curve.
+///
+/// Zero rates add, so discount factors multiply: D'(t) = D(t) * D_spread(t).
+/// Typical uses are credit or basis spreads over a risk-free curve.
+#[derive(Debug, Clone)]
+pub struct SpreadCurve<B, S> {
+    base: B,
+    spread: S,
+}
+
+impl<B: TermStructure, S: TermStructure> SpreadCurve<B, S> {
+    /// Creates a curve whose zero rates are `base` plus `spread`.
+    pub fn new(base: B, spread: S) -> Self {
+        Self { base, spread }
+    }
+
+    /// Returns the base curve.
+    pub fn base(&self) -> &B {
+        &self.base
+    }
+
+    /// Returns the spread curve.
+    pub fn spread(&self) -> &S {
+        &self.spread
+    }
+}
+
+impl<B: TermStructure, S: TermStructure> TermStructure for SpreadCurve<B, S> {
+    fn discount_factor(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        self.base
+            .discount_factor(t)?
+            .try_mul(self.spread.discount_factor(t)?)
+    }
+
+    fn zero_rate(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        self.base.zero_rate(t)?.try_add(self.spread.zero_rate(t)?)
+    }
+
+    fn forward_rate(&self, t1: YearFraction, t2: YearFraction) -> Result<Decimal, ArithmeticError> {
+        self.base
+            .forward_rate(t1, t2)?
+            .try_add(self.spread.forward_rate(t1, t2)?)
+    }
+
+    fn instantaneous_forward(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        self.base
+            .instantaneous_forward(t)?
+            .try_add(self.spread.instantaneous_forward(t)?)
+    }
+}
+
+/// A base curve seen from a later reference date.
+///
+/// Times are measured from `start` (in years after the base curve's
+/// reference date): D'(t) = D(start + t) / D(start). The zero rate at t is
+/// the base forward rate between `start` and `start + t`.
+#[derive(Debug, Clone)]
+pub struct ForwardCurve<T> {
+    base: T,
+    start: YearFraction,
+}
+
+impl<T: TermStructure> ForwardCurve<T> {
+    /// Creates a curve with its reference date moved forward by `start` years.
+    ///
+    /// Returns error if `start` is negative.
+    pub fn new(base: T, start: YearFraction) -> Result<Self, ArithmeticError> {
+        if start.is_negative() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(Self { base, start })
+    }
+
+    /// Returns the offset of the new reference date.
+    pub fn start(&self) -> YearFraction {
+        self.start
+    }
+}
+
+impl<T: TermStructure> TermStructure for ForwardCurve<T> {
+    fn discount_factor(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        let end = self.start.try_add(t)?;
+        self.base
+            .discount_factor(end)?
+            .try_div(self.base.discount_factor(self.start)?)
+    }
+
+    fn zero_rate(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        if !t.is_positive() {
+            return self.base.instantaneous_forward(self.start);
+        }
+        self.base.forward_rate(self.start, self.start.try_add(t)?)
+    }
+
+    fn forward_rate(&self, t1: YearFraction, t2: YearFraction) -> Result<Decimal, ArithmeticError> {
+        self.base
+            .forward_rate(self.start.try_add(t1)?, self.start.try_add(t2)?)
+    }
+
+    fn instantaneous_forward(&self, t: YearFraction) -> Result<Decimal, ArithmeticError> {
+        self.base.instantaneous_forward(self.start.try_add(t)?)
+    }
+}
+
+/// Parallel DV01: the price change for a 1bp fall in all zero rates.
+///
+/// Uses a central difference of ±1bp parallel shifts, so a long bond
+/// position has a positive DV01.
+pub fn dv01<T, F>(curve: &T, price: F) -> Result<Decimal, ArithmeticError>
+where
+    T: TermStructure + ?Sized,
+    F: Fn(&dyn TermStructure) -> Result<Decimal, ArithmeticError>,
+{
+    let down = price(&ParallelShift::new(curve, -ONE_BP))?;
+    let up = price(&ParallelShift::new(curve, ONE_BP))?;
+    down.try_sub(up)?.try_div(Decimal::from(2i64))
+}
+
+/// Key-rate DV01s: the price change for a 1bp fall at each key tenor.
+///
+/// Each entry bumps one tenor with a triangular [`KeyRateShift`] and takes
+/// a central difference of ±1bp. The key-rate DV01s sum to the parallel
+/// [`dv01`] for prices linear in the bumps.
+///
+/// # Example
+///
+/// ```
+/// use financial_calc::term_structure::{key_rate_dv01, BondQuote, FlatTermStructure};
+/// use precision_core::Decimal;
+///
+/// let curve = FlatTermStructure::new(Decimal::new(4, 2));
+/// let bond = BondQuote::new(Decimal::from(5i64), Decimal::new(5, 2), 2, Decimal::ONE);
+/// let tenors = [Decimal::from(2i64), Decimal::from(5i64), Decimal::from(10i64)];
+///
+/// let krd = key_rate_dv01(&curve, &tenors, |c| bond.model_price(c)).unwrap();
+/// // A 5Y bond has no exposure beyond its maturity
+/// assert!(krd[1] > krd[0]);
+/// assert_eq!(krd[2], Decimal::ZERO);
+/// ```
+pub fn key_rate_dv01<T, F, const N: usize>(
+    curve: &T,
+    tenors: &[YearFraction; N],
+    price: F,
+) -> Result<[Decimal; N], ArithmeticError>
+where
+    T: TermStructure + ?Sized,
+    F: Fn(&dyn TermStructure) -> Result<Decimal, ArithmeticError>,
+{
+    let mut result = [Decimal::ZERO; N];
+    for (i, dv01) in result.iter_mut().enumerate() {
+        let down = price(&KeyRateShift::new(curve, tenors, i, -ONE_BP)?)?;
+        let up = price(&KeyRateShift::new(curve, tenors, i, ONE_BP)?)?;
+        *dv01 = down.try_sub(up)?.try_div(Decimal::from(2i64))?;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::term_structure::{BondQuote, FlatTermStructure};
+
+    fn flat(rate: i64) -> FlatTermStructure {
+        FlatTermStructure::new(Decimal::new(rate, 3))
+    }
+
+    #[test]
+    fn test_parallel_shift_matches_flat_curve() {
+        let shifted = ParallelShift::new(flat(40), Decimal::new(1, 2));
+        let target = flat(50);
+
+        let t = Decimal::from(3i64);
+        assert_eq!(shifted.zero_rate(t).unwrap(), Decimal::new(5, 2));
+        let diff = shifted.discount_factor(t).unwrap() - target.discount_factor(t).unwrap();
+        assert!(diff.abs() < Decimal::new(1, 12));
+    }
+
+    #[test]
+    fn test_key_rate_bump_shape() {
+        let tenors = [
+            Decimal::from(2i64),
+            Decimal::from(5i64),
+            Decimal::from(10i64),
+        ];
+        let bump = Decimal::new(1, 2);
+        let curve = KeyRateShift::new(flat(30), &tenors, 1, bump).unwrap();
+        let base = Decimal::new(3, 2);
+
+        let rate = |t: i64| curve.zero_rate(Decimal::from(t)).unwrap() - base;
+        assert_eq!(rate(1), Decimal::ZERO);
+        assert_eq!(rate(2), Decimal::ZERO);
+        assert_eq!(rate(5), bump);
+        assert_eq!(rate(8), Decimal::new(4, 3));
+        assert_eq!(rate(10), Decimal::ZERO);
+        assert_eq!(rate(20), Decimal::ZERO);
+
+        // End key rates extend flat
+        let long = KeyRateShift::new(flat(30), &tenors, 2, bump).unwrap();
+        assert_eq!(long.zero_rate(Decimal::from(30i64)).unwrap() - base, bump);
+    }
+
+    #[test]
+    fn test_spread_curve_adds_rates() {
+        let curve = SpreadCurve::new(flat(30), flat(15));
+        let t = Decimal::from(4i64);
+
+        assert_eq!(curve.zero_rate(t).unwrap(), Decimal::new(45, 3));
+        let diff = curve.discount_factor(t).unwrap() - flat(45).discount_factor(t).unwrap();
+        assert!(diff.abs() < Decimal::new(1, 12));
+    }
+
+    #[test]
+    fn test_forward_curve_discount_ratio() {
+        let base = ParallelShift::new(flat(30), Decimal::ZERO);
+        let fwd = ForwardCurve::new(&base, Decimal::from(2i64)).unwrap();
+
+        let df = fwd.discount_factor(Decimal::from(3i64)).unwrap();
+        let expected = base.discount_factor(Decimal::from(5i64)).unwrap()
+            / base.discount_factor(Decimal::from(2i64)).unwrap();
+        assert_eq!(df, expected);
+        assert_eq!(
+            fwd.zero_rate(Decimal::from(3i64)).unwrap(),
+            Decimal::new(3, 2)
+        );
+
+        assert!(ForwardCurve::new(&base, -Decimal::ONE).is_err());
+    }
+
+    #[test]
+    fn test_key_rate_dv01_sums_to_parallel() {
+        let curve = flat(40);
+        let bond = BondQuote::new(Decimal::from(7i64), Decimal::new(5, 2), 2, Decimal::ONE);
+        let tenors = [
+            Decimal::ONE,
+            Decimal::from(3i64),
+            Decimal::from(5i64),
+            Decimal::from(10i64),
+        ];
+
+        let krd = key_rate_dv01(&curve, &tenors, |c| bond.model_price(c)).unwrap();
+        let total = krd.iter().fold(Decimal::ZERO, |acc, d| acc + *d);
+        let parallel = dv01(&curve, |c| bond.model_price(c)).unwrap();
+
+        assert!(parallel.is_positive());
+        assert!((total - parallel).abs() < Decimal::new(1, 8));
+        assert!(krd[2] > krd[0]); // Most exposure near maturity
+    }
+}
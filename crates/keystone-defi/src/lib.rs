@@ -93,9 +93,22 @@ pub mod amm {
         calculate_amounts_from_liquidity, calculate_impermanent_loss, calculate_liquidity_burn,
         calculate_liquidity_from_amounts, calculate_liquidity_mint, calculate_position_value,
         calculate_price_impact, calculate_spot_price, calculate_swap_input, calculate_swap_output,
-        sqrt_price_to_tick, tick_spacing_to_fee_bps, tick_to_sqrt_price, ConcentratedPosition,
-        MAX_TICK, MIN_TICK, TICK_SPACING_HIGH, TICK_SPACING_LOW, TICK_SPACING_MEDIUM,
+        cryptoswap_d, cryptoswap_y, find_best_route, optimize_split, quote_route,
+        sqrt_price_to_tick, stable_bpt_in_given_exact_tokens_out,
+        stable_bpt_out_given_exact_tokens_in, stable_token_in_given_exact_bpt_out,
+        stable_token_out_given_exact_bpt_in, stableswap_d, stableswap_y, stableswap_y_d,
+        tick_spacing_to_fee_bps, tick_to_sqrt_price, weighted_bpt_in_given_exact_tokens_out,
+        weighted_bpt_out_given_exact_tokens_in, weighted_in_given_out, weighted_invariant,
+        weighted_out_given_in, weighted_spot_price, weighted_token_in_given_exact_bpt_out,
+        weighted_token_out_given_exact_bpt_in, AmplificationRamp, ComposableStablePool,
+        ConcentratedPool, ConcentratedPosition, Hop, Route, RoutePool, RouteQuote, SplitQuote,
+        StableSwapPool,
+        SwapResult, TickInfo, WeightedPool, MAX_AMPLIFICATION, MAX_AMPLIFICATION_CHANGE,
+        MAX_INITIALIZED_TICKS, MAX_ROUTE_HOPS, MAX_ROUTE_SPLITS, MAX_STABLE_COINS, MAX_TICK,
+        MAX_WEIGHTED_TOKENS, MIN_RAMP_TIME, MIN_TICK, TICK_SPACING_HIGH, TICK_SPACING_LOW,
+        TICK_SPACING_MEDIUM,
     };
+    pub use financial_calc::amm::q96;
 }
 
 /// Vault and yield calculations.
@@ -169,19 +182,36 @@ pub mod vault {
 /// Derivatives and perpetual futures calculations.
 pub mod derivatives {
     pub use financial_calc::derivatives::{
-        calculate_average_entry_price, calculate_breakeven_price, calculate_effective_leverage,
-        calculate_funding_payment, calculate_funding_rate, calculate_liquidation_distance,
-        calculate_liquidation_price, calculate_margin_ratio, calculate_max_position_size,
-        calculate_pnl, calculate_pnl_percentage, calculate_required_collateral, calculate_roe,
-        FundingParams, PerpPosition,
+        calculate_adl_score, calculate_average_entry_price, calculate_breakeven_price,
+        calculate_effective_leverage, calculate_funding_payment, calculate_funding_rate,
+        calculate_liquidation_distance, calculate_liquidation_price,
+        calculate_liquidation_price_tiered, calculate_margin_ratio, calculate_max_position_size,
+        calculate_max_position_size_tiered, calculate_pnl, calculate_pnl_percentage,
+        calculate_position_after_fill, calculate_required_collateral,
+        calculate_required_collateral_tiered, calculate_roe, AccountOutcome, AccountStatus,
+        AdlReport, AdlSimulator, CrossMarginAccount, CrossPosition, CrossTrade, Fill, FillOutcome,
+        FundingIndex, FundingParams, MarginRates, OrderExecution, PerpPosition, PositionFunding,
+        PositionLedger, PriceImpactParams, RiskTier, RiskTierTable, SkewFunding, SkewParams,
+        TradeImpact, VelocityFunding, MAX_ADL_ACCOUNTS, MAX_CROSS_POSITIONS, MAX_RISK_TIERS,
     };
 }
 
 /// Options pricing and Greeks.
 pub mod options {
     pub use financial_calc::options::{
-        black_scholes_call, black_scholes_put, call_greeks, implied_volatility, normal_cdf,
-        normal_pdf, put_greeks, Greeks, OptionParams,
+        arithmetic_asian_price, asset_or_nothing_price, bachelier_greeks,
+        bachelier_implied_volatility, bachelier_price, barone_adesi_whaley, barrier_price,
+        black_76_greeks, black_76_implied_volatility, black_76_price, black_scholes_call,
+        black_scholes_put, butterfly_arbitrage, calendar_arbitrage, call_greeks,
+        cash_or_nothing_price, finite_difference_greeks, generalized_greeks,
+        generalized_implied_volatility, generalized_price, geometric_asian_price,
+        higher_order_greeks, implied_volatility, normal_cdf, normal_pdf, put_greeks,
+        solve_implied_volatility, BachelierParams, Barrier, BarrierKind, Black76Params,
+        ExerciseStyle, GeneralizedParams, GreekUnits, Greeks, HigherOrderGreeks,
+        ImpliedVolError, Lattice, LatticeMethod, NaturalSvi, OptionLeg, OptionParams,
+        OptionPortfolio, OptionStrategy, PortfolioGreeks, RealizedAverage, Sabr, ShiftUnit,
+        Smile, SmileQuote, SmileSlice, Svi, TimeUnit, VolatilitySurface, MAX_FIT_QUOTES,
+        MAX_LATTICE_STEPS, MAX_PORTFOLIO_LEGS, MAX_SMILE_SLICES,
     };
 }
 
@@ -201,25 +231,52 @@ pub mod day_count {
 /// Yield curve and term structure.
 pub mod term_structure {
     pub use financial_calc::term_structure::{
-        CurveNode, FlatTermStructure, PiecewiseTermStructure, TermStructure, MAX_CURVE_NODES,
+        dv01, key_rate_dv01, BondQuote, CalibrationResult, CurveInterpolation, CurveNode,
+        Extrapolation, FlatTermStructure, ForwardCurve, KeyRateShift, NelsonSiegel,
+        ParallelShift, PiecewiseTermStructure, SpreadCurve, Svensson, TermStructure,
+        MAX_CURVE_NODES,
     };
 }
 
 /// Numerical solvers.
 pub mod solver {
     pub use financial_calc::solver::{
-        bisection, brent, default_tolerance, newton_raphson, newton_raphson_numerical, secant,
-        SolverResult, DEFAULT_MAX_ITER,
+        bisection, brent, default_tolerance, levenberg_marquardt, newton_raphson,
+        newton_raphson_numerical, secant, LeastSquaresResult, SolverResult, DEFAULT_MAX_ITER,
     };
 }
 
 /// Interpolation methods.
 pub mod interpolation {
     pub use financial_calc::interpolation::{
-        CubicSpline, DataPoint, Interpolator, Linear, LogLinear,
+        Akima, AkimaSlopes, CubicSpline, DataPoint, FritschCarlson, Hermite, Interpolator,
+        Linear, LogLinear, Pchip, ShapePreserving, SlopeMethod, Steffen, SteffenSlopes,
+        MAX_INTERP_POINTS,
     };
 }
 
+/// Two-dimensional surface interpolation.
+pub mod surface {
+    pub use financial_calc::surface::{
+        Bicubic, Bilinear, Grid, SurfaceInterpolator, VolSurface, MAX_GRID_VALUES,
+    };
+}
+
+/// Deterministic Monte Carlo simulation.
+pub mod monte_carlo {
+    pub use financial_calc::monte_carlo::{
+        inverse_normal_cdf, Antithetic, Estimate, Gbm, Heston, JumpDiffusion, MonteCarlo,
+        PathModel, RandomSource, Xoshiro256StarStar, MAX_PATH_STEPS,
+    };
+}
+
+/// Storage backends for curves and interpolators.
+pub mod storage {
+    pub use financial_calc::storage::{ArrayBuffer, Buffer, Fixed, Storage};
+    #[cfg(feature = "std")]
+    pub use financial_calc::storage::Heap;
+}
+
 /// Commonly used imports for DeFi calculations.
 pub mod prelude {
     pub use crate::precision::{ArithmeticError, Decimal, RoundingMode};
@@ -334,9 +334,8 @@ impl Decimal {
     /// ```
     #[must_use]
     pub fn exp(self) -> Option<Self> {
-        // rust_decimal's exp() can overflow, so we need to catch panics
-        // or check bounds. For safety, we use checked_exp if available.
-        // Since rust_decimal 1.x exp() returns Decimal directly, we wrap in Option.
+        // rust_decimal's exp() panics on overflow and underflow, so we use
+        // checked_exp and map underflow to zero.
 
         // Check for extreme values that would overflow
         // e^710 is approximately the max for f64, our Decimal has similar limits
@@ -347,7 +346,12 @@ impl Decimal {
             return Some(Self::ZERO); // Underflows to effectively zero
         }
 
-        Some(Self(self.0.exp()))
+        match self.0.checked_exp() {
+            Some(value) => Some(Self(value)),
+            // Results below the smallest representable value underflow to zero
+            None if self.is_negative() => Some(Self::ZERO),
+            None => None,
+        }
     }
 
     /// Computes e^self, returning an error on overflow.
@@ -798,6 +802,10 @@ mod tests {
         // Very negative exponent should return zero (underflow to zero)
         let result = Decimal::from(-200i64).exp();
         assert_eq!(result, Some(Decimal::ZERO));
+
+        // Inside the guard bounds but beyond the representable range
+        assert_eq!(Decimal::from(80i64).exp(), None);
+        assert_eq!(Decimal::from(-80i64).exp(), Some(Decimal::ZERO));
     }
 
     #[test]
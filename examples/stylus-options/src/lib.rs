@@ -3,11 +3,12 @@
 extern crate alloc;
 
 use alloc::{vec, vec::Vec};
-use alloy_primitives::U256;
+use alloy_primitives::{I256, U256};
 use financial_calc::options::{
     black_scholes_call, black_scholes_put, call_greeks, implied_volatility, put_greeks,
-    OptionParams,
+    GeneralizedParams, OptionParams, OptionStrategy,
 };
+use financial_calc::storage::Buffer;
 use precision_core::{Decimal, RoundingMode};
 use stylus_sdk::prelude::*;
 
@@ -22,6 +23,24 @@ sol_storage! {
 const SCALE: u64 = 1_000_000_000_000_000_000;
 const BPS_DIVISOR: u64 = 10_000;
 
+// Strategy kinds for the strategy_* entry points and the strikes each expects
+/// Vertical call spread: long strikes[0] call, short strikes[1] call.
+pub const STRATEGY_CALL_SPREAD: u8 = 0;
+/// Vertical put spread: long strikes[0] put, short strikes[1] put.
+pub const STRATEGY_PUT_SPREAD: u8 = 1;
+/// Long call and put at strikes[0].
+pub const STRATEGY_STRADDLE: u8 = 2;
+/// Long put at strikes[0], long call at strikes[1].
+pub const STRATEGY_STRANGLE: u8 = 3;
+/// Call butterfly over increasing strikes[0..3].
+pub const STRATEGY_BUTTERFLY: u8 = 4;
+/// Iron condor over increasing strikes[0..4].
+pub const STRATEGY_IRON_CONDOR: u8 = 5;
+/// Long underlying, short call at strikes[0].
+pub const STRATEGY_COVERED_CALL: u8 = 6;
+/// Long underlying, long put at strikes[0], short call at strikes[1].
+pub const STRATEGY_COLLAR: u8 = 7;
+
 fn u256_to_decimal(value: U256) -> Decimal {
     let lo: u128 = value.as_limbs()[0] as u128 | ((value.as_limbs()[1] as u128) << 64);
     let raw = Decimal::from(lo);
@@ -38,6 +57,22 @@ fn decimal_to_u256(value: Decimal) -> U256 {
     U256::from(mantissa.unsigned_abs())
 }
 
+fn decimal_to_i256(value: Decimal) -> I256 {
+    let magnitude = I256::from_raw(decimal_to_u256(value.abs()));
+    if value.is_negative() {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+fn strike_at(strikes: &[U256], index: usize) -> Result<Decimal, Vec<u8>> {
+    strikes
+        .get(index)
+        .map(|&strike| u256_to_decimal(strike))
+        .ok_or_else(|| b"missing strike".to_vec())
+}
+
 fn build_params(
     spot: U256,
     strike: U256,
@@ -217,6 +252,143 @@ impl OptionsEngine {
         Ok(decimal_to_u256(diff))
     }
 
+    /// Net premium of a strategy (see the STRATEGY_* kinds).
+    ///
+    /// strikes: strike prices for the kind (1e18 scaled)
+    /// quantity: number of units of the strategy (1e18 scaled)
+    ///
+    /// Returns: premium paid, negative for a net credit (1e18 scaled)
+    pub fn strategy_net_premium(
+        &self,
+        kind: u8,
+        spot: U256,
+        strikes: Vec<U256>,
+        volatility: U256,
+        time_to_expiry: U256,
+        quantity: U256,
+    ) -> Result<I256, Vec<u8>> {
+        let strategy =
+            self.build_strategy(kind, spot, &strikes, volatility, time_to_expiry, quantity)?;
+        let premium = strategy
+            .net_premium()
+            .map_err(|_| b"premium calc error".to_vec())?;
+        Ok(decimal_to_i256(premium))
+    }
+
+    /// Value and profit of a strategy at expiry for an underlying price.
+    ///
+    /// Profit is the payoff less the premium and underlying bought today.
+    ///
+    /// Returns: (payoff, profit) both scaled by 1e18
+    #[allow(clippy::too_many_arguments)]
+    pub fn strategy_payoff(
+        &self,
+        kind: u8,
+        spot: U256,
+        strikes: Vec<U256>,
+        volatility: U256,
+        time_to_expiry: U256,
+        quantity: U256,
+        price_at_expiry: U256,
+    ) -> Result<(I256, I256), Vec<u8>> {
+        let strategy =
+            self.build_strategy(kind, spot, &strikes, volatility, time_to_expiry, quantity)?;
+        let price = u256_to_decimal(price_at_expiry);
+        let payoff = strategy
+            .payoff(price)
+            .map_err(|_| b"payoff calc error".to_vec())?;
+        let profit = strategy
+            .profit(price)
+            .map_err(|_| b"payoff calc error".to_vec())?;
+        Ok((decimal_to_i256(payoff), decimal_to_i256(profit)))
+    }
+
+    /// Underlying prices at expiry where a strategy breaks even.
+    ///
+    /// Returns: breakeven prices in increasing order (1e18 scaled)
+    pub fn strategy_breakevens(
+        &self,
+        kind: u8,
+        spot: U256,
+        strikes: Vec<U256>,
+        volatility: U256,
+        time_to_expiry: U256,
+        quantity: U256,
+    ) -> Result<Vec<U256>, Vec<u8>> {
+        let strategy =
+            self.build_strategy(kind, spot, &strikes, volatility, time_to_expiry, quantity)?;
+        let breakevens = strategy
+            .breakevens()
+            .map_err(|_| b"breakeven calc error".to_vec())?;
+        Ok(breakevens
+            .as_slice()
+            .iter()
+            .map(|&price| decimal_to_u256(price))
+            .collect())
+    }
+
+    /// Aggregated Greeks of a strategy, signed by position.
+    ///
+    /// Returns: (delta, gamma, theta, vega, rho) all scaled by 1e18
+    pub fn strategy_greeks(
+        &self,
+        kind: u8,
+        spot: U256,
+        strikes: Vec<U256>,
+        volatility: U256,
+        time_to_expiry: U256,
+        quantity: U256,
+    ) -> Result<(I256, I256, I256, I256, I256), Vec<u8>> {
+        let strategy =
+            self.build_strategy(kind, spot, &strikes, volatility, time_to_expiry, quantity)?;
+        let greeks = strategy
+            .greeks()
+            .map_err(|_| b"greeks calc error".to_vec())?
+            .greeks;
+
+        Ok((
+            decimal_to_i256(greeks.delta),
+            decimal_to_i256(greeks.gamma),
+            decimal_to_i256(greeks.theta),
+            decimal_to_i256(greeks.vega),
+            decimal_to_i256(greeks.rho),
+        ))
+    }
+
+    /// Risk profile of a strategy at expiry.
+    ///
+    /// Unlimited profit is reported as I256::MAX and unlimited loss as
+    /// U256::MAX. The probability of profit assumes a lognormal underlying.
+    ///
+    /// Returns: (max_profit, max_loss, probability_of_profit) all scaled by 1e18
+    pub fn strategy_risk(
+        &self,
+        kind: u8,
+        spot: U256,
+        strikes: Vec<U256>,
+        volatility: U256,
+        time_to_expiry: U256,
+        quantity: U256,
+    ) -> Result<(I256, U256, U256), Vec<u8>> {
+        let strategy =
+            self.build_strategy(kind, spot, &strikes, volatility, time_to_expiry, quantity)?;
+        let max_profit = strategy
+            .max_profit()
+            .map_err(|_| b"risk calc error".to_vec())?;
+        let max_loss = strategy
+            .max_loss()
+            .map_err(|_| b"risk calc error".to_vec())?;
+        let probability = strategy
+            .probability_of_profit()
+            .map_err(|_| b"risk calc error".to_vec())?;
+
+        Ok((
+            max_profit.map_or(I256::MAX, decimal_to_i256),
+            max_loss.map_or(U256::MAX, decimal_to_u256),
+            decimal_to_u256(probability),
+        ))
+    }
+
     /// Set the risk-free rate (admin only in production).
     pub fn set_risk_free_rate(&mut self, rate_bps: U256) {
         self.risk_free_rate_bps.set(rate_bps);
@@ -230,6 +402,47 @@ impl OptionsEngine {
             .checked_div(Decimal::from(BPS_DIVISOR))
             .ok_or_else(|| b"rate error".to_vec())
     }
+
+    fn build_strategy(
+        &self,
+        kind: u8,
+        spot: U256,
+        strikes: &[U256],
+        volatility: U256,
+        time_to_expiry: U256,
+        quantity: U256,
+    ) -> Result<OptionStrategy, Vec<u8>> {
+        let market = GeneralizedParams {
+            spot: u256_to_decimal(spot),
+            rate: self.get_rate()?,
+            time: u256_to_decimal(time_to_expiry),
+            volatility: u256_to_decimal(volatility),
+            ..Default::default()
+        };
+        let quantity = u256_to_decimal(quantity);
+        let k = |index| strike_at(strikes, index);
+
+        let strategy = match kind {
+            STRATEGY_CALL_SPREAD => {
+                OptionStrategy::vertical_spread(market, k(0)?, k(1)?, true, quantity)
+            }
+            STRATEGY_PUT_SPREAD => {
+                OptionStrategy::vertical_spread(market, k(0)?, k(1)?, false, quantity)
+            }
+            STRATEGY_STRADDLE => OptionStrategy::straddle(market, k(0)?, quantity),
+            STRATEGY_STRANGLE => OptionStrategy::strangle(market, k(0)?, k(1)?, quantity),
+            STRATEGY_BUTTERFLY => {
+                OptionStrategy::butterfly(market, k(0)?, k(1)?, k(2)?, true, quantity)
+            }
+            STRATEGY_IRON_CONDOR => {
+                OptionStrategy::iron_condor(market, [k(0)?, k(1)?, k(2)?, k(3)?], quantity)
+            }
+            STRATEGY_COVERED_CALL => OptionStrategy::covered_call(market, k(0)?, quantity),
+            STRATEGY_COLLAR => OptionStrategy::collar(market, k(0)?, k(1)?, quantity),
+            _ => return Err(b"unknown strategy".to_vec()),
+        };
+        strategy.map_err(|_| b"strategy error".to_vec())
+    }
 }
 
 #[cfg(test)]
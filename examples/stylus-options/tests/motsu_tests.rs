@@ -1,6 +1,6 @@
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, I256, U256};
 use motsu::prelude::*;
-use stylus_options_example::OptionsEngine;
+use stylus_options_example::{OptionsEngine, STRATEGY_IRON_CONDOR, STRATEGY_STRADDLE};
 
 const ONE_ETH: u128 = 1_000_000_000_000_000_000;
 
@@ -176,3 +176,88 @@ fn test_higher_vol_means_higher_price(contract: Contract<OptionsEngine>) {
 
     assert!(high_price > low_price, "higher vol should mean higher price");
 }
+
+#[motsu::test]
+fn test_straddle_premium_and_breakevens(contract: Contract<OptionsEngine>) {
+    let alice = Address::random();
+
+    contract.sender(alice).set_risk_free_rate(U256::from(500u64));
+
+    let spot = scaled(100);
+    let strike = scaled(100);
+    let vol = scaled_fraction(2, 10);
+    let time = scaled_fraction(1, 4);
+    let one = scaled(1);
+
+    let call = contract
+        .sender(alice)
+        .price_call(spot, strike, vol, time)
+        .expect("call price");
+    let put = contract
+        .sender(alice)
+        .price_put(spot, strike, vol, time)
+        .expect("put price");
+
+    let premium = contract
+        .sender(alice)
+        .strategy_net_premium(STRATEGY_STRADDLE, spot, vec![strike], vol, time, one)
+        .expect("should price straddle");
+
+    // Straddle premium is the call plus the put, within rounding
+    let diff = premium.into_raw().abs_diff(call + put);
+    assert!(diff < U256::from(10u64), "straddle premium mismatch");
+
+    let breakevens = contract
+        .sender(alice)
+        .strategy_breakevens(STRATEGY_STRADDLE, spot, vec![strike], vol, time, one)
+        .expect("should find breakevens");
+
+    // Breakevens sit one premium either side of the strike
+    assert_eq!(breakevens.len(), 2);
+    assert!(breakevens[0] < strike && breakevens[1] > strike);
+
+    let (payoff, profit) = contract
+        .sender(alice)
+        .strategy_payoff(STRATEGY_STRADDLE, spot, vec![strike], vol, time, one, strike)
+        .expect("should value payoff");
+    assert_eq!(payoff, I256::ZERO);
+    assert!(profit < I256::ZERO, "straddle loses its premium at the strike");
+}
+
+#[motsu::test]
+fn test_iron_condor_risk(contract: Contract<OptionsEngine>) {
+    let alice = Address::random();
+
+    contract.sender(alice).set_risk_free_rate(U256::from(500u64));
+
+    let strikes = vec![scaled(80), scaled(90), scaled(110), scaled(120)];
+    let spot = scaled(100);
+    let vol = scaled_fraction(2, 10);
+    let time = scaled_fraction(1, 4);
+    let one = scaled(1);
+
+    let premium = contract
+        .sender(alice)
+        .strategy_net_premium(STRATEGY_IRON_CONDOR, spot, strikes.clone(), vol, time, one)
+        .expect("should price condor");
+    assert!(premium < I256::ZERO, "iron condor collects a credit");
+
+    let (max_profit, max_loss, probability) = contract
+        .sender(alice)
+        .strategy_risk(STRATEGY_IRON_CONDOR, spot, strikes.clone(), vol, time, one)
+        .expect("should compute risk");
+
+    // Profit capped at the credit, loss at the wing width less the credit
+    assert_eq!(max_profit, -premium);
+    assert!(max_loss < scaled(10), "loss limited by the wings");
+    assert!(probability > U256::ZERO && probability < U256::from(ONE_ETH));
+
+    let (delta, _, _, vega, _) = contract
+        .sender(alice)
+        .strategy_greeks(STRATEGY_IRON_CONDOR, spot, strikes, vol, time, one)
+        .expect("should aggregate greeks");
+
+    // Short volatility, close to delta neutral
+    assert!(vega < I256::ZERO, "condor is short vega");
+    assert!(delta.unsigned_abs() < U256::from(ONE_ETH / 10));
+}